@@ -17,6 +17,8 @@ pub use utils::{
     pretty::{
         print_rows, print_src_files_table, print_validate_evidence, print_validate_headline,
         print_validate_report, print_validate_tables, print_validate_tables_verbose,
+        print_wpl_fmt_report, print_wpl_learn_report, print_wpl_profile_report,
+        print_wpl_test_report,
     },
     types::*,
 };
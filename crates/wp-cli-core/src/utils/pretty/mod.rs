@@ -2,6 +2,10 @@ pub mod helpers;
 pub mod sinks;
 pub mod sources;
 pub mod validate;
+pub mod wpl_fmt;
+pub mod wpl_learn;
+pub mod wpl_profile;
+pub mod wpl_test;
 
 pub use sinks::print_rows;
 pub use sources::print_src_files_table;
@@ -9,3 +13,7 @@ pub use validate::{
     print_validate_evidence, print_validate_headline, print_validate_report, print_validate_tables,
     print_validate_tables_verbose,
 };
+pub use wpl_fmt::print_wpl_fmt_report;
+pub use wpl_learn::print_wpl_learn_report;
+pub use wpl_profile::print_wpl_profile_report;
+pub use wpl_test::print_wpl_test_report;
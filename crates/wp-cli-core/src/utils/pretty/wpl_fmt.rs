@@ -0,0 +1,72 @@
+use super::super::types::WplFmtReport;
+use super::helpers::{bg_fail, bg_pass, bg_warn, color_err, color_warn};
+
+/// Print WPL format results: one line per file; in check mode changed files are reported as unformatted,
+/// files the formatter does not support are skipped and left untouched (a failure in check mode).
+pub fn print_wpl_fmt_report(rep: &WplFmtReport) {
+    for file in &rep.files {
+        let tag = match (file.status.as_str(), rep.check) {
+            ("ok", _) => bg_pass("  OK  "),
+            ("changed", true) => bg_warn(" DIFF "),
+            ("changed", false) => bg_pass(" FMT  "),
+            ("unsupported", true) => bg_fail(" SKIP "),
+            ("unsupported", false) => bg_warn(" SKIP "),
+            _ => bg_fail(" FAIL "),
+        };
+        println!("{} {}", tag, file.file);
+        if let Some(note) = &file.note {
+            println!("    {}", color_warn(note));
+        }
+        if let Some(err) = &file.error {
+            println!("    {}", color_err(err));
+        }
+    }
+    let verb = if rep.check {
+        "unformatted"
+    } else {
+        "formatted"
+    };
+    println!(
+        "\n{} {}, {} skipped, {} error, {} total",
+        rep.changed(),
+        verb,
+        rep.unsupported(),
+        rep.errors(),
+        rep.files.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::types::WplFmtFile;
+    use super::*;
+
+    #[test]
+    fn report_counts() {
+        let ok = WplFmtFile {
+            file: "a.wpl".into(),
+            status: "ok".into(),
+            error: None,
+            note: None,
+        };
+        let mut changed = ok.clone();
+        changed.file = "b.wpl".into();
+        changed.status = "changed".into();
+        let mut bad = ok.clone();
+        bad.file = "c.wpl".into();
+        bad.status = "error".into();
+        bad.error = Some("expect rule end".into());
+        let mut skip = ok.clone();
+        skip.file = "d.wpl".into();
+        skip.status = "unsupported".into();
+        skip.note = Some("type/macro definitions".into());
+        let rep = WplFmtReport {
+            check: true,
+            files: vec![ok, changed, bad, skip],
+        };
+        assert_eq!(rep.changed(), 1);
+        assert_eq!(rep.errors(), 1);
+        assert_eq!(rep.unsupported(), 1);
+        print_wpl_fmt_report(&rep);
+    }
+}
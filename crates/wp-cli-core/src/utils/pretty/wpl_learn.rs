@@ -0,0 +1,64 @@
+use super::super::types::WplLearnReport;
+use super::helpers::{bg_fail, bg_pass, bg_warn, color_err, fmt_f};
+
+/// Print an inferred WPL rule: proposed rule text, column table, then per-sample replay status.
+pub fn print_wpl_learn_report(rep: &WplLearnReport) {
+    println!("{}\n", rep.rule);
+    for (i, col) in rep.columns.iter().enumerate() {
+        println!(
+            "  #{:<3} {:<8} {:<28} {}",
+            i, col.kind, col.field, col.example
+        );
+    }
+    println!();
+    for sample in &rep.samples {
+        let tag = match sample.status.as_str() {
+            "full" => bg_pass(" FULL "),
+            "partial" => bg_warn(" PART "),
+            _ => bg_fail(" FAIL "),
+        };
+        println!("{} {}", tag, sample.line);
+        if sample.status != "full" && !sample.residue.is_empty() {
+            println!("    {}", color_err(&sample.residue));
+        }
+    }
+    println!(
+        "\nseparator '{}', coverage {}% ({}/{})",
+        rep.separator,
+        fmt_f(rep.coverage() * 100.0),
+        rep.matched(),
+        rep.samples.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::types::{WplLearnColumn, WplLearnSample};
+    use super::*;
+
+    #[test]
+    fn report_coverage() {
+        let full = WplLearnSample {
+            line: "1.2.3.4 200".into(),
+            status: "full".into(),
+            residue: String::new(),
+            aligned: true,
+        };
+        let mut part = full.clone();
+        part.status = "partial".into();
+        part.residue = " x".into();
+        let rep = WplLearnReport {
+            rule: "rule r {\n    (ip:ip_0, digit:digit_1)\n}".into(),
+            separator: " ".into(),
+            columns: vec![WplLearnColumn {
+                field: "ip:ip_0".into(),
+                kind: "ip".into(),
+                example: "1.2.3.4".into(),
+            }],
+            samples: vec![full, part],
+        };
+        assert_eq!(rep.matched(), 1);
+        assert_eq!(rep.coverage(), 0.5);
+        print_wpl_learn_report(&rep);
+    }
+}
@@ -0,0 +1,112 @@
+use super::super::types::WplProfileReport;
+use super::helpers::fmt_f;
+use comfy_table::{
+    Cell, CellAlignment, ContentArrangement, Row as CRow, Table, presets::ASCII_MARKDOWN,
+};
+
+fn us(nanos: u64) -> String {
+    fmt_f(nanos as f64 / 1000.0)
+}
+
+fn profile_row(
+    name: String,
+    hit: u64,
+    miss: u64,
+    avg_hit: u64,
+    avg_miss: Option<u64>,
+    total: u64,
+) -> CRow {
+    let mut row = CRow::new();
+    row.add_cell(Cell::new(name).set_alignment(CellAlignment::Left));
+    row.add_cell(Cell::new(hit).set_alignment(CellAlignment::Right));
+    row.add_cell(Cell::new(miss).set_alignment(CellAlignment::Right));
+    row.add_cell(Cell::new(us(avg_hit)).set_alignment(CellAlignment::Right));
+    row.add_cell(
+        Cell::new(avg_miss.map(us).unwrap_or_else(|| "-".to_string()))
+            .set_alignment(CellAlignment::Right),
+    );
+    row.add_cell(Cell::new(us(total)).set_alignment(CellAlignment::Right));
+    row
+}
+
+/// Print WPL parse profile in table form, rules ordered by total cost with their fields indented below.
+/// Columns: Rule / Field | Hit | Miss | Avg Hit(us) | Avg Miss(us) | Total(us)
+/// Field rows count successful calls as hits and report one average over all calls.
+pub fn print_wpl_profile_report(rep: &WplProfileReport, json: bool) {
+    if json {
+        println!("{}", serde_json::to_string_pretty(rep).unwrap_or_default());
+        return;
+    }
+    let mut t = Table::new();
+    t.load_preset(ASCII_MARKDOWN);
+    t.set_content_arrangement(ContentArrangement::Dynamic);
+    t.set_header(vec![
+        "Rule / Field",
+        "Hit",
+        "Miss",
+        "Avg Hit(us)",
+        "Avg Miss(us)",
+        "Total(us)",
+    ]);
+    for rule in &rep.rules {
+        t.add_row(profile_row(
+            rule.rule.clone(),
+            rule.hits,
+            rule.misses,
+            rule.avg_hit_ns,
+            Some(rule.avg_miss_ns),
+            rule.total_ns,
+        ));
+        for field in &rule.fields {
+            t.add_row(profile_row(
+                format!("  {}", field.field),
+                field.calls - field.fails,
+                field.fails,
+                field.avg_ns,
+                None,
+                field.total_ns,
+            ));
+        }
+    }
+    println!("{}", t);
+    println!(
+        "\nsampled 1/{}, {} rules",
+        rep.sample_every,
+        rep.rules.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::types::{WplProfileField, WplProfileRule};
+    use super::*;
+
+    #[test]
+    fn report_table_and_json() {
+        let rep = WplProfileReport {
+            sample_every: 10,
+            rules: vec![WplProfileRule {
+                rule: "demo/kv".into(),
+                hits: 2,
+                misses: 1,
+                total_ns: 4500,
+                miss_ns: 500,
+                avg_hit_ns: 2000,
+                avg_miss_ns: 500,
+                fields: vec![WplProfileField {
+                    field: "ip:sip".into(),
+                    calls: 3,
+                    fails: 1,
+                    total_ns: 1500,
+                    avg_ns: 500,
+                }],
+            }],
+        };
+        assert_eq!(us(1500), "1.5");
+        print_wpl_profile_report(&rep, false);
+        print_wpl_profile_report(&rep, true);
+        let json = serde_json::to_string(&rep).unwrap();
+        assert!(json.contains("\"rule\":\"demo/kv\""));
+        assert!(json.contains("\"sample_every\":10"));
+    }
+}
@@ -0,0 +1,65 @@
+use super::super::types::WplTestReport;
+use super::helpers::{bg_fail, bg_pass, color_err};
+
+/// Print WPL inline test results: one line per case, followed by field diffs of failed cases.
+pub fn print_wpl_test_report(rep: &WplTestReport) {
+    for case in &rep.cases {
+        let target = case.rule.as_deref().unwrap_or("*");
+        let title = format!("{} {}#{} [{}]", case.package, target, case.index, case.file);
+        if case.passed() {
+            println!("{} {}", bg_pass(" PASS "), title);
+            continue;
+        }
+        println!("{} {}", bg_fail(" FAIL "), title);
+        if let Some(err) = &case.error {
+            println!("    {}", color_err(err));
+        }
+        for diff in &case.diffs {
+            let actual = diff.actual.as_deref().unwrap_or("<missing>");
+            println!(
+                "    {}: expect {}, actual {}",
+                diff.field,
+                diff.expect,
+                color_err(actual)
+            );
+        }
+    }
+    println!(
+        "\n{} passed, {} failed, {} total",
+        rep.passed(),
+        rep.failed(),
+        rep.cases.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::super::types::{WplCaseResult, WplFieldDiff};
+    use super::*;
+
+    #[test]
+    fn report_counts_failed_cases() {
+        let ok = WplCaseResult {
+            file: "parse.wpl".into(),
+            package: "nginx".into(),
+            rule: Some("access".into()),
+            index: 1,
+            error: None,
+            diffs: vec![],
+        };
+        let mut bad = ok.clone();
+        bad.index = 2;
+        bad.diffs.push(WplFieldDiff {
+            field: "sip".into(),
+            expect: "ip(1.2.3.4)".into(),
+            actual: None,
+        });
+        let rep = WplTestReport {
+            cases: vec![ok, bad],
+        };
+        assert_eq!(rep.passed(), 1);
+        assert_eq!(rep.failed(), 1);
+        assert!(!rep.is_ok());
+        print_wpl_test_report(&rep);
+    }
+}
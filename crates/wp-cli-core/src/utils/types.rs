@@ -128,3 +128,146 @@ pub struct ValidateItem {
     pub msg: String,
     pub severity: Severity,
 }
+
+/// WPL 用例中单个字段的差异；`actual` 为 `None` 表示未解析出该字段
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct WplFieldDiff {
+    pub field: String,
+    pub expect: String,
+    pub actual: Option<String>,
+}
+
+/// 单个 WPL 用例的执行结果
+#[derive(Debug, Serialize, Clone)]
+pub struct WplCaseResult {
+    pub file: String,
+    pub package: String,
+    pub rule: Option<String>,
+    /// 用例在包内的序号（从 1 开始）
+    pub index: usize,
+    pub error: Option<String>,
+    pub diffs: Vec<WplFieldDiff>,
+}
+
+impl WplCaseResult {
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.diffs.is_empty()
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct WplTestReport {
+    pub cases: Vec<WplCaseResult>,
+}
+
+impl WplTestReport {
+    pub fn failed(&self) -> usize {
+        self.cases.iter().filter(|c| !c.passed()).count()
+    }
+    pub fn passed(&self) -> usize {
+        self.cases.len() - self.failed()
+    }
+    pub fn is_ok(&self) -> bool {
+        self.failed() == 0
+    }
+}
+
+/// 规则推断得到的一列
+#[derive(Debug, Serialize, Clone)]
+pub struct WplLearnColumn {
+    pub field: String,
+    pub kind: String,
+    pub example: String,
+}
+
+/// 样本回放结果；`status` 取值 full / partial / failed
+#[derive(Debug, Serialize, Clone)]
+pub struct WplLearnSample {
+    pub line: String,
+    pub status: String,
+    pub residue: String,
+    pub aligned: bool,
+}
+
+/// `wpl learn` 报告：推断出的规则及其在样本上的覆盖率
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct WplLearnReport {
+    pub rule: String,
+    pub separator: String,
+    pub columns: Vec<WplLearnColumn>,
+    pub samples: Vec<WplLearnSample>,
+}
+
+impl WplLearnReport {
+    pub fn matched(&self) -> usize {
+        self.samples.iter().filter(|s| s.status == "full").count()
+    }
+    pub fn coverage(&self) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        self.matched() as f64 / self.samples.len() as f64
+    }
+}
+
+/// 单个 WPL 文件的格式化结果；`status` 取值 ok / changed / unsupported / error
+#[derive(Debug, Serialize, Clone)]
+pub struct WplFmtFile {
+    pub file: String,
+    pub status: String,
+    pub error: Option<String>,
+    /// 跳过格式化的原因（unsupported）
+    pub note: Option<String>,
+}
+
+/// `wpl fmt` 报告；`check` 模式下不改写文件
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct WplFmtReport {
+    pub check: bool,
+    pub files: Vec<WplFmtFile>,
+}
+
+impl WplFmtReport {
+    pub fn changed(&self) -> usize {
+        self.files.iter().filter(|f| f.status == "changed").count()
+    }
+    pub fn errors(&self) -> usize {
+        self.files.iter().filter(|f| f.status == "error").count()
+    }
+    pub fn unsupported(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|f| f.status == "unsupported")
+            .count()
+    }
+}
+
+/// 单个字段的解析剖析统计；耗时单位为纳秒
+#[derive(Debug, Serialize, Clone)]
+pub struct WplProfileField {
+    pub field: String,
+    pub calls: u64,
+    pub fails: u64,
+    pub total_ns: u64,
+    pub avg_ns: u64,
+}
+
+/// 单条规则的解析剖析统计；耗时单位为纳秒
+#[derive(Debug, Serialize, Clone)]
+pub struct WplProfileRule {
+    pub rule: String,
+    pub hits: u64,
+    pub misses: u64,
+    pub total_ns: u64,
+    pub miss_ns: u64,
+    pub avg_hit_ns: u64,
+    pub avg_miss_ns: u64,
+    pub fields: Vec<WplProfileField>,
+}
+
+/// `wpl profile` 报告：每条规则每 `sample_every` 次解析采样一次
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct WplProfileReport {
+    pub sample_every: u64,
+    pub rules: Vec<WplProfileRule>,
+}
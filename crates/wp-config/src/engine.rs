@@ -71,16 +71,54 @@ pub struct PerformanceConf {
     pub rate_limit_rps: usize,
     #[serde(default = "default_parse_workers")]
     pub parse_workers: usize,
+    /// 解析剖析：每个规则每 N 次解析采样一次，未设置时关闭
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile_every: Option<u64>,
 }
 impl Default for PerformanceConf {
     fn default() -> Self {
         Self {
             rate_limit_rps: 10000,
             parse_workers: 2,
+            profile_every: None,
         }
     }
 }
 
+/// `[[plugins.wasm]]`：WASM 插件声明；未设置的限制使用宿主默认值
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
+pub struct WasmPluginConf {
+    pub name: String,
+    pub path: String,
+    /// 每次调用的燃料配额
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fuel: Option<u64>,
+    /// 线性内存上限（MiB）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_mb: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone, Default)]
+pub struct PluginsConf {
+    #[serde(default)]
+    pub wasm: Vec<WasmPluginConf>,
+}
+
+impl PluginsConf {
+    pub fn is_empty(&self) -> bool {
+        self.wasm.is_empty()
+    }
+}
+
+impl EnvEvaluable<PluginsConf> for PluginsConf {
+    fn env_eval(mut self, dict: &orion_variate::EnvDict) -> PluginsConf {
+        for plugin in self.wasm.iter_mut() {
+            plugin.path = plugin.path.clone().env_eval(dict);
+        }
+        self
+    }
+}
+
 #[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
 pub struct EngineConfig {
     #[serde(default = "default_version")]
@@ -106,6 +144,8 @@ pub struct EngineConfig {
     /// 是否跳过 SINK 阶段（不启动 sink/infra 任务；若未进一步配置为黑洞，将阻塞在下发边界）
     #[serde(default)]
     skip_sink: bool,
+    #[serde(default, skip_serializing_if = "PluginsConf::is_empty")]
+    plugins: PluginsConf,
 }
 
 impl EnvEvaluable<EngineConfig> for EngineConfig {
@@ -113,6 +153,7 @@ impl EnvEvaluable<EngineConfig> for EngineConfig {
         self.models = self.models.env_eval(dict);
         self.topology = self.topology.env_eval(dict);
         self.rescue = self.rescue.env_eval(dict);
+        self.plugins = self.plugins.env_eval(dict);
         self
     }
 }
@@ -177,6 +218,7 @@ impl Default for EngineConfig {
             robust: RobustnessMode::Normal,
             skip_parse: false,
             skip_sink: false,
+            plugins: PluginsConf::default(),
         }
     }
 }
@@ -200,12 +242,14 @@ impl EngineConfig {
             performance: PerformanceConf {
                 rate_limit_rps: 10000,
                 parse_workers: 2,
+                profile_every: None,
             },
             log_conf: LogConf::default(),
             stat_conf: StatConf::default(),
             robust: RobustnessMode::Normal,
             skip_parse: false,
             skip_sink: false,
+            plugins: PluginsConf::default(),
         }
     }
 
@@ -242,6 +286,10 @@ impl EngineConfig {
         self.performance.rate_limit_rps
     }
 
+    pub fn profile_every(&self) -> Option<u64> {
+        self.performance.profile_every
+    }
+
     pub fn stat_conf(&self) -> &StatConf {
         &self.stat_conf
     }
@@ -267,6 +315,10 @@ impl EngineConfig {
         self.skip_sink
     }
 
+    pub fn plugins(&self) -> &PluginsConf {
+        &self.plugins
+    }
+
     pub fn src_conf_of(&self, file_name: &str) -> String {
         format!("{}/{}", self.src_root(), file_name)
     }
@@ -278,6 +330,9 @@ impl EngineConfig {
         self.topology.sources = resolve_engine_path(self.topology.sources.as_str(), abs_work_root);
         self.topology.sinks = resolve_engine_path(self.topology.sinks.as_str(), abs_work_root);
         self.rescue.path = resolve_engine_path(self.rescue.path.as_str(), abs_work_root);
+        for plugin in self.plugins.wasm.iter_mut() {
+            plugin.path = resolve_engine_path(plugin.path.as_str(), abs_work_root);
+        }
         self
     }
 
@@ -405,6 +460,46 @@ mod tests {
         assert_eq!(result, "/work/topology/sinks");
     }
 
+    #[test]
+    fn test_wasm_plugins_conf() {
+        let conf: EngineConfig = toml::from_str(
+            r#"
+[[plugins.wasm]]
+name = "mask_ip"
+path = "./plugins/mask_ip.wasm"
+fuel = 500000
+
+[[plugins.wasm]]
+name = "upper"
+path = "/opt/wp/upper.wasm"
+memory_mb = 4
+"#,
+        )
+        .unwrap();
+        let conf = conf.conf_absolutize(Path::new("/work"));
+        let wasm = &conf.plugins().wasm;
+        assert_eq!(wasm.len(), 2);
+        assert_eq!(wasm[0].path, "/work/plugins/mask_ip.wasm");
+        assert_eq!(wasm[0].fuel, Some(500000));
+        assert_eq!(wasm[0].memory_mb, None);
+        assert_eq!(wasm[1].path, "/opt/wp/upper.wasm");
+
+        // 未声明插件时不写出 [plugins]
+        let text = toml::to_string(&EngineConfig::default()).unwrap();
+        assert!(!text.contains("plugins"));
+    }
+
+    #[test]
+    fn test_profile_every_conf() {
+        let conf: EngineConfig =
+            toml::from_str("[performance]\nparse_workers = 4\nprofile_every = 100\n").unwrap();
+        assert_eq!(conf.profile_every(), Some(100));
+        assert_eq!(conf.parallel(), 4);
+        assert_eq!(EngineConfig::default().profile_every(), None);
+        let text = toml::to_string(&EngineConfig::default()).unwrap();
+        assert!(!text.contains("profile_every"));
+    }
+
     #[test]
     fn test_resolve_engine_path_absolute() {
         let work_root = Path::new("/work");
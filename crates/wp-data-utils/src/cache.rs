@@ -35,6 +35,7 @@ impl FieldQueryCache {
         }
     }
 }
+/// 缓存键：各参数值的索引序列，超过 6 个参数时使用 `IdxN`
 #[derive(PartialEq, Eq, Hash, Debug, Clone)]
 pub enum EnumSizeIndex {
     Idx1(usize),
@@ -43,149 +44,59 @@ pub enum EnumSizeIndex {
     Idx4(usize, usize, usize, usize),
     Idx5(usize, usize, usize, usize, usize),
     Idx6(usize, usize, usize, usize, usize, usize),
-}
-pub trait CacheAble<P, T, const N: usize> {
-    fn save(&mut self, params: &[P; N], result: T);
-    fn fetch(&self, params: &[P; N]) -> Option<&T>;
-}
-
-impl CacheAble<DataField, Vec<DataField>, 1> for FieldQueryCache {
-    fn save(&mut self, params: &[DataField; 1], result: Vec<DataField>) {
-        if let Some(i0) = self.try_up_idx(&params[0]) {
-            let idxs = EnumSizeIndex::Idx1(i0);
-            self.cache_data.put(idxs, result);
-        }
-    }
-    fn fetch(&self, params: &[DataField; 1]) -> Option<&Vec<DataField>> {
-        if let Some(idx1) = self.get_idx(&params[0]) {
-            let idxs = EnumSizeIndex::Idx1(idx1);
-            return self.cache_data.peek(&idxs);
-        }
-        None
-    }
-}
-
-impl CacheAble<DataField, Vec<DataField>, 2> for FieldQueryCache {
-    fn save(&mut self, params: &[DataField; 2], result: Vec<DataField>) {
-        if let (Some(i0), Some(i1)) = (self.try_up_idx(&params[0]), self.try_up_idx(&params[1])) {
-            let idxs = EnumSizeIndex::Idx2(i0, i1);
-            self.cache_data.put(idxs, result);
-        }
-    }
-    fn fetch(&self, params: &[DataField; 2]) -> Option<&Vec<DataField>> {
-        if let (Some(idx1), Some(idx2)) = (self.get_idx(&params[0]), self.get_idx(&params[1])) {
-            let idxs = EnumSizeIndex::Idx2(idx1, idx2);
-            return self.cache_data.peek(&idxs);
-        }
-        None
-    }
+    IdxN(Vec<usize>),
 }
 
-impl CacheAble<DataField, Vec<DataField>, 3> for FieldQueryCache {
-    fn save(&mut self, params: &[DataField; 3], result: Vec<DataField>) {
-        if let (Some(i0), Some(i1), Some(i2)) = (
-            self.try_up_idx(&params[0]),
-            self.try_up_idx(&params[1]),
-            self.try_up_idx(&params[2]),
-        ) {
-            let idxs = EnumSizeIndex::Idx3(i0, i1, i2);
-            self.cache_data.put(idxs, result);
-        }
-    }
-    fn fetch(&self, params: &[DataField; 3]) -> Option<&Vec<DataField>> {
-        if let (Some(idx1), Some(idx2), Some(idx3)) = (
-            self.get_idx(&params[0]),
-            self.get_idx(&params[1]),
-            self.get_idx(&params[2]),
-        ) {
-            let idxs = EnumSizeIndex::Idx3(idx1, idx2, idx3);
-            return self.cache_data.peek(&idxs);
-        }
-        None
+impl EnumSizeIndex {
+    fn build(idx: &[usize]) -> Option<Self> {
+        let key = match *idx {
+            [] => return None,
+            [i0] => Self::Idx1(i0),
+            [i0, i1] => Self::Idx2(i0, i1),
+            [i0, i1, i2] => Self::Idx3(i0, i1, i2),
+            [i0, i1, i2, i3] => Self::Idx4(i0, i1, i2, i3),
+            [i0, i1, i2, i3, i4] => Self::Idx5(i0, i1, i2, i3, i4),
+            [i0, i1, i2, i3, i4, i5] => Self::Idx6(i0, i1, i2, i3, i4, i5),
+            _ => Self::IdxN(idx.to_vec()),
+        };
+        Some(key)
     }
 }
 
-impl CacheAble<DataField, Vec<DataField>, 4> for FieldQueryCache {
-    fn save(&mut self, params: &[DataField; 4], result: Vec<DataField>) {
-        if let (Some(i0), Some(i1), Some(i2), Some(i3)) = (
-            self.try_up_idx(&params[0]),
-            self.try_up_idx(&params[1]),
-            self.try_up_idx(&params[2]),
-            self.try_up_idx(&params[3]),
-        ) {
-            let idxs = EnumSizeIndex::Idx4(i0, i1, i2, i3);
-            self.cache_data.put(idxs, result);
-        }
-    }
-    fn fetch(&self, params: &[DataField; 4]) -> Option<&Vec<DataField>> {
-        if let (Some(idx1), Some(idx2), Some(idx3), Some(idx4)) = (
-            self.get_idx(&params[0]),
-            self.get_idx(&params[1]),
-            self.get_idx(&params[2]),
-            self.get_idx(&params[3]),
-        ) {
-            let idxs = EnumSizeIndex::Idx4(idx1, idx2, idx3, idx4);
-            return self.cache_data.peek(&idxs);
-        }
-        None
-    }
+/// 按参数序列缓存查询结果，参数个数不限
+pub trait CacheAble<P, T> {
+    fn save(&mut self, params: &[P], result: T);
+    fn fetch(&self, params: &[P]) -> Option<&T>;
 }
 
-impl CacheAble<DataField, Vec<DataField>, 5> for FieldQueryCache {
-    fn save(&mut self, params: &[DataField; 5], result: Vec<DataField>) {
-        if let (Some(i0), Some(i1), Some(i2), Some(i3), Some(i4)) = (
-            self.try_up_idx(&params[0]),
-            self.try_up_idx(&params[1]),
-            self.try_up_idx(&params[2]),
-            self.try_up_idx(&params[3]),
-            self.try_up_idx(&params[4]),
-        ) {
-            let idxs = EnumSizeIndex::Idx5(i0, i1, i2, i3, i4);
+impl CacheAble<DataField, Vec<DataField>> for FieldQueryCache {
+    fn save(&mut self, params: &[DataField], result: Vec<DataField>) {
+        if let Some(idxs) = self.up_key(params) {
             self.cache_data.put(idxs, result);
         }
     }
-    fn fetch(&self, params: &[DataField; 5]) -> Option<&Vec<DataField>> {
-        if let (Some(idx1), Some(idx2), Some(idx3), Some(idx4), Some(idx5)) = (
-            self.get_idx(&params[0]),
-            self.get_idx(&params[1]),
-            self.get_idx(&params[2]),
-            self.get_idx(&params[3]),
-            self.get_idx(&params[4]),
-        ) {
-            let idxs = EnumSizeIndex::Idx5(idx1, idx2, idx3, idx4, idx5);
-            return self.cache_data.peek(&idxs);
-        }
-        None
+    fn fetch(&self, params: &[DataField]) -> Option<&Vec<DataField>> {
+        self.key(params)
+            .and_then(|idxs| self.cache_data.peek(&idxs))
     }
 }
 
-impl CacheAble<DataField, Vec<DataField>, 6> for FieldQueryCache {
-    fn save(&mut self, params: &[DataField; 6], result: Vec<DataField>) {
-        if let (Some(i0), Some(i1), Some(i2), Some(i3), Some(i4), Some(i5)) = (
-            self.try_up_idx(&params[0]),
-            self.try_up_idx(&params[1]),
-            self.try_up_idx(&params[2]),
-            self.try_up_idx(&params[3]),
-            self.try_up_idx(&params[4]),
-            self.try_up_idx(&params[5]),
-        ) {
-            let idxs = EnumSizeIndex::Idx6(i0, i1, i2, i3, i4, i5);
-            self.cache_data.put(idxs, result);
-        }
-    }
-    fn fetch(&self, params: &[DataField; 6]) -> Option<&Vec<DataField>> {
-        if let (Some(idx1), Some(idx2), Some(idx3), Some(idx4), Some(idx5), Some(idx6)) = (
-            self.get_idx(&params[0]),
-            self.get_idx(&params[1]),
-            self.get_idx(&params[2]),
-            self.get_idx(&params[3]),
-            self.get_idx(&params[4]),
-            self.get_idx(&params[5]),
-        ) {
-            let idxs = EnumSizeIndex::Idx6(idx1, idx2, idx3, idx4, idx5, idx6);
-            return self.cache_data.peek(&idxs);
-        }
-        None
+impl FieldQueryCache {
+    // 含不支持索引的值（如 float、bool）时不缓存
+    fn key(&self, params: &[DataField]) -> Option<EnumSizeIndex> {
+        let idx = params
+            .iter()
+            .map(|x| self.get_idx(x))
+            .collect::<Option<Vec<_>>>()?;
+        EnumSizeIndex::build(&idx)
+    }
+
+    fn up_key(&mut self, params: &[DataField]) -> Option<EnumSizeIndex> {
+        let idx = params
+            .iter()
+            .map(|x| self.try_up_idx(x))
+            .collect::<Option<Vec<_>>>()?;
+        EnumSizeIndex::build(&idx)
     }
 }
 
@@ -289,4 +200,26 @@ mod test {
         let cache_ret = cache.fetch(&data2);
         assert_eq!(cache_ret, Some(&out));
     }
+
+    #[test]
+    fn test_cache_many_params() {
+        let data: Vec<DataField> = (0..9)
+            .map(|i| DataField::from_digit(format!("p{}", i), i))
+            .collect();
+        let out = vec![DataField::from_chars("A1", "chars-11")];
+
+        let mut cache = FieldQueryCache::with_capacity(3);
+        assert!(cache.fetch(&data).is_none());
+        cache.save(&data, out.clone());
+        assert_eq!(cache.fetch(&data), Some(&out));
+        assert!(cache.fetch(&data[..8]).is_none());
+
+        // 不支持索引的值不缓存
+        let data = [
+            DataField::from_digit("a", 1),
+            DataField::from_float("b", 1.5),
+        ];
+        cache.save(&data, out.clone());
+        assert!(cache.fetch(&data).is_none());
+    }
 }
@@ -6,9 +6,9 @@ use wp_model_core::model::DataField;
 use crate::mem::RowData;
 
 /// Generic cache wrapper: fetch from cache by `c_params`, otherwise run `query_fn` and save.
-pub fn cache_query_impl<const N: usize>(
-    c_params: &[DataField; N],
-    cache: &mut impl CacheAble<DataField, RowData, N>,
+pub fn cache_query_impl(
+    c_params: &[DataField],
+    cache: &mut impl CacheAble<DataField, RowData>,
     query_fn: impl FnOnce() -> KnowledgeResult<RowData>,
 ) -> RowData {
     if let Some(hit) = cache.fetch(c_params) {
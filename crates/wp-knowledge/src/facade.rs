@@ -134,11 +134,11 @@ pub fn query_cipher(table: &str) -> KnowledgeResult<Vec<String>> {
 /// - `c_params` 用于上层缓存键（通常为 `[md5, :k1, :k2, ...]`）
 /// - `named_params` 为 SQLite 命名参数切片（可由 `SqlNamedParam` 数组通过 `to_params()` 生成）
 ///   命中缓存直接返回；未命中则通过全局 provider 查询并回填缓存。
-pub fn cache_query<const N: usize>(
+pub fn cache_query(
     sql: &str,
-    c_params: &[DataField; N],
+    c_params: &[DataField],
     named_params: &[(&str, &dyn ToSql)],
-    cache: &mut impl CacheAble<DataField, RowData, N>,
+    cache: &mut impl CacheAble<DataField, RowData>,
 ) -> RowData {
     crate::cache_util::cache_query_impl(c_params, cache, || {
         if named_params.is_empty() {
@@ -65,12 +65,12 @@ impl MDBEnum {
     }
 }
 
-pub fn cache_query<const N: usize, P: Params>(
+pub fn cache_query<P: Params>(
     db: &MDBEnum,
     sql: &str,
-    c_params: &[DataField; N],
+    c_params: &[DataField],
     q_params: P,
-    cache: &mut impl CacheAble<DataField, RowData, N>,
+    cache: &mut impl CacheAble<DataField, RowData>,
 ) -> RowData {
     crate::cache_util::cache_query_impl(c_params, cache, || db.query_row_params(sql, q_params))
 }
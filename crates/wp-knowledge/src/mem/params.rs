@@ -31,3 +31,12 @@ impl_to_params!(7);
 impl_to_params!(8);
 impl_to_params!(9);
 impl_to_params!(10);
+
+// 参数个数在运行期确定（如 OML SQL 展开 IN 列表）
+impl<'a> ToSqlParams<'a, Vec<(&'a str, &'a dyn ToSql)>> for [SqlNamedParam] {
+    fn to_params(&'a self) -> Vec<(&'a str, &'a dyn ToSql)> {
+        self.iter()
+            .map(|param| (param.0.get_name(), param as &dyn ToSql))
+            .collect()
+    }
+}
@@ -0,0 +1,110 @@
+//! 分隔符扫描基准：同一组输入（nginx、64 列宽 CSV）上对比 `WplSep::read_until_sep`
+//! 的 memchr 快路径与旧的 winnow 逐字节扫描（仅保留在本基准内），报告中
+//! `sep_scan/legacy/*` 与 `sep_scan/memchr/*` 即新旧实现的对比；`sep_rule/*`
+//! 给出整条规则（含多字节分隔符）的引擎耗时。
+//!
+//!   cargo bench -p wp-lang --bench sep_scan
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use orion_error::TestAssert;
+use wp_parse_api::RawData;
+use wp_parser::Parser;
+use wpl::{WplEvaluator, WplSep, wpl_express};
+
+/// 旧的分隔符扫描：引号整体读取，否则 `take_until` 读到分隔符或末尾
+mod legacy {
+    use winnow::combinator::alt;
+    use winnow::token::take_until;
+    use wp_parser::Parser;
+    use wp_parser::WResult;
+    use wpl::parser::utils::{quot_r_str, quot_str, take_to_end};
+
+    pub fn read_until_sep(sep: &str, data: &mut &str) -> WResult<String> {
+        let buf =
+            alt((quot_r_str, quot_str, take_until(0.., sep), take_to_end)).parse_next(data)?;
+        Ok(buf.to_string())
+    }
+}
+
+const NGINX_RULE: &str =
+    r#"(ip:sip,_^2,time<[,]>,http/request",http/status,digit,chars",http/agent",_")"#;
+const NGINX_LINE: &str = r#"222.133.52.20 - - [06/Aug/2019:12:12:19 +0800] "GET /nginx-logo.png HTTP/1.1" 200 368 "http://119.122.1.4/" "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_14_5) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/75.0.3770.142 Safari/537.36" "-""#;
+
+// 宽 CSV：cols 列，交替 chars/digit，每列值较长以突出分隔符扫描
+fn wide_csv(cols: usize) -> (String, String) {
+    let rule = format!("({})\\,", vec!["chars"; cols].join(","));
+    let line = (0..cols)
+        .map(|i| format!("col{:02}_{}", i, "x".repeat(48)))
+        .collect::<Vec<_>>()
+        .join(",");
+    (rule, line)
+}
+
+// 逐字段切分：memchr 走 WplSep::read_until_sep（引擎实际使用的路径），legacy 走旧实现
+fn split_fields(sep: &WplSep, legacy: bool, mut data: &str) -> usize {
+    let mut cnt = 0;
+    loop {
+        let field = if legacy {
+            legacy::read_until_sep(sep.sep_str(), &mut data)
+        } else {
+            sep.read_until_sep(&mut data)
+        };
+        black_box(field.assert());
+        cnt += 1;
+        match data.strip_prefix(sep.sep_str()) {
+            Some(rest) => data = rest,
+            None => return cnt,
+        }
+    }
+}
+
+fn bench_scan(c: &mut Criterion) {
+    let (_, csv) = wide_csv(64);
+    let cases = [
+        ("nginx", WplSep::field_sep(" "), NGINX_LINE.to_string()),
+        ("wide_csv_64", WplSep::field_sep(","), csv),
+    ];
+    for (path, legacy) in [("legacy", true), ("memchr", false)] {
+        let mut group = c.benchmark_group(format!("sep_scan/{}", path));
+        for (name, sep, line) in &cases {
+            // 两种实现须切出相同的字段数
+            assert_eq!(
+                split_fields(sep, true, line),
+                split_fields(sep, false, line)
+            );
+            group.throughput(Throughput::Bytes(line.len() as u64));
+            group.bench_function(*name, |b| {
+                b.iter(|| split_fields(sep, legacy, black_box(line)))
+            });
+        }
+        group.finish();
+    }
+}
+
+fn bench_rule(c: &mut Criterion, name: &str, rule: &str, line: &str) {
+    let express = wpl_express.parse(rule).assert();
+    let evaluator = WplEvaluator::from(&express, None).assert();
+    let raw = RawData::from_string(line.to_string());
+    assert!(
+        evaluator.proc(raw.clone(), 0).is_ok(),
+        "{} sample not match",
+        name
+    );
+    let mut group = c.benchmark_group("sep_rule");
+    group.throughput(Throughput::Bytes(line.len() as u64));
+    group.bench_function(name, |b| {
+        b.iter(|| black_box(evaluator.proc(raw.clone(), 0)))
+    });
+    group.finish();
+}
+
+fn bench_rules(c: &mut Criterion) {
+    bench_rule(c, "nginx", NGINX_RULE, NGINX_LINE);
+    let (rule, line) = wide_csv(64);
+    bench_rule(c, "wide_csv_64", &rule, &line);
+    let rule = format!("({})", ["chars\\|\\|"; 32].join(","));
+    let line = ["abcdefghijklmnopqrstuvwxyz0123456789"; 32].join("||");
+    bench_rule(c, "multi_byte_sep_32", &rule, &line);
+}
+
+criterion_group!(benches, bench_scan, bench_rules);
+criterion_main!(benches);
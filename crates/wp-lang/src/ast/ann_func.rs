@@ -1,4 +1,4 @@
-use crate::ast::AnnFun;
+use crate::ast::{AnnFun, FingerprintAlgo, FingerprintConf};
 use crate::{WparseError, WparseReason};
 use orion_error::{ToStructError, UvsDataFrom};
 use smol_str::SmolStr;
@@ -74,10 +74,92 @@ impl AnnotationFunc for RawCopy {
     }
 }
 
+/// 将 SourceEvent 携带的标签（如 access_source）按前缀写入记录
+#[derive(Clone, Debug)]
+pub struct TagsCopy {
+    prefix: SmolStr,
+}
+
+impl AnnotationFunc for TagsCopy {
+    fn proc(&self, src: &SourceEvent, data: &mut DataRecord) -> Result<(), WparseError> {
+        for (key, val) in src.tags.iter() {
+            data.append(DataField::from_chars(
+                format!("{}{}", self.prefix, key),
+                val.to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct IngestTime {
+    name: SmolStr,
+}
+
+impl AnnotationFunc for IngestTime {
+    fn proc(&self, _src: &SourceEvent, data: &mut DataRecord) -> Result<(), WparseError> {
+        data.append(DataField::from_time(
+            self.name.to_string(),
+            chrono::Local::now().naive_local(),
+        ));
+        Ok(())
+    }
+}
+
+/// 对选定字段的值计算事件指纹，缺失字段按空值参与计算
+#[derive(Clone, Debug)]
+pub struct Fingerprint {
+    conf: FingerprintConf,
+}
+
+impl Fingerprint {
+    // 字段间以 0x1f 分隔，避免 "ab"+"c" 与 "a"+"bc" 冲突
+    fn digest_input(&self, data: &DataRecord) -> Vec<u8> {
+        let mut buf = Vec::new();
+        if self.conf.fields.is_empty() {
+            for field in data.items.iter() {
+                if field.get_name() == self.conf.name.as_str() {
+                    continue;
+                }
+                buf.extend_from_slice(field.get_value().to_string().as_bytes());
+                buf.push(0x1f);
+            }
+        } else {
+            for name in &self.conf.fields {
+                if let Some(field) = data.field(name.as_str()) {
+                    buf.extend_from_slice(field.get_value().to_string().as_bytes());
+                }
+                buf.push(0x1f);
+            }
+        }
+        buf
+    }
+
+    pub fn digest(&self, data: &DataRecord) -> String {
+        let buf = self.digest_input(data);
+        match self.conf.algo {
+            FingerprintAlgo::Xxh64 => format!("{:016x}", xxhash_rust::xxh64::xxh64(&buf, 0)),
+            FingerprintAlgo::Md5 => format!("{:x}", md5::compute(&buf)),
+        }
+    }
+}
+
+impl AnnotationFunc for Fingerprint {
+    fn proc(&self, _src: &SourceEvent, data: &mut DataRecord) -> Result<(), WparseError> {
+        let fp = self.digest(data);
+        data.append(DataField::from_chars(self.conf.name.to_string(), fp));
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum AnnotationType {
     Tag(TagAnnotation),
     Copy(RawCopy),
+    CopyTags(TagsCopy),
+    IngestTime(IngestTime),
+    Fingerprint(Fingerprint),
     Null(NoopAnnotation),
 }
 
@@ -87,6 +169,9 @@ impl AnnotationFunc for AnnotationType {
             AnnotationType::Tag(func) => func.proc(src, data),
             AnnotationType::Null(func) => func.proc(src, data),
             AnnotationType::Copy(func) => func.proc(src, data),
+            AnnotationType::CopyTags(func) => func.proc(src, data),
+            AnnotationType::IngestTime(func) => func.proc(src, data),
+            AnnotationType::Fingerprint(func) => func.proc(src, data),
         }
     }
 }
@@ -108,6 +193,26 @@ impl AnnotationType {
                     vec.push(AnnotationType::Null(NoopAnnotation {}))
                 }
             }
+            if let Some(prefix) = &ann.copy_tags {
+                vec.push(AnnotationType::CopyTags(TagsCopy {
+                    prefix: prefix.clone(),
+                }));
+            }
+            if let Some(name) = &ann.ingest_time {
+                vec.push(AnnotationType::IngestTime(IngestTime {
+                    name: name.clone(),
+                }));
+            }
+            // 未指定字段时指纹最先计算，只覆盖解析出的字段（不含 ingest_time 等注解字段）；
+            // 指定字段时放在最后，可引用注解追加的字段
+            if let Some(conf) = &ann.fingerprint {
+                let fp = AnnotationType::Fingerprint(Fingerprint { conf: conf.clone() });
+                if conf.fields.is_empty() {
+                    vec.insert(0, fp);
+                } else {
+                    vec.push(fp);
+                }
+            }
         } else {
             vec.push(AnnotationType::Null(NoopAnnotation {}))
         }
@@ -130,6 +235,7 @@ mod tests {
         let ann = AnnFun {
             tags: BTreeMap::from([("tag_1".into(), "x".into())]),
             copy_raw: None,
+            ..Default::default()
         };
         let tag = AnnotationType::convert(&Some(ann));
         let mut data = DataRecord::test_value();
@@ -151,6 +257,7 @@ mod tests {
         let ann = AnnFun {
             tags: Default::default(),
             copy_raw: Some(("name".into(), "raw".into())),
+            ..Default::default()
         };
         let tag = AnnotationType::convert(&Some(ann));
         let mut data = DataRecord::test_value();
@@ -166,4 +273,109 @@ mod tests {
             Some(&DataField::from_chars("raw", "test"))
         );
     }
+
+    #[test]
+    fn test_source_annotations() {
+        let ann = AnnFun {
+            copy_tags: Some("src_".into()),
+            ingest_time: Some("recv_ts".into()),
+            fingerprint: Some(FingerprintConf {
+                name: "fp".into(),
+                fields: vec!["src_access_source".into(), "missing".into()],
+                algo: FingerprintAlgo::Xxh64,
+            }),
+            ..Default::default()
+        };
+        let funcs = AnnotationType::convert(&Some(ann));
+        assert_eq!(funcs.len(), 3);
+
+        let mut tags = Tags::new();
+        tags.set("access_source", "/var/log/a.log".to_string());
+        let src = SourceEvent::new(
+            1,
+            DEFAULT_KEY.to_string(),
+            RawData::String("test".to_string()),
+            tags.into(),
+        );
+        let mut data = DataRecord::test_value();
+        for func in &funcs {
+            func.proc(&src, &mut data).assert();
+        }
+        assert_eq!(
+            data.field("src_access_source"),
+            Some(&DataField::from_chars(
+                "src_access_source",
+                "/var/log/a.log"
+            ))
+        );
+        assert!(data.field("recv_ts").is_some());
+        let fp = data.field("fp").unwrap().get_value().to_string();
+        assert_eq!(fp.len(), 16);
+
+        // 同样的字段值得到同样的指纹，字段边界参与计算
+        let same = Fingerprint {
+            conf: FingerprintConf {
+                name: "fp".into(),
+                fields: vec!["src_access_source".into(), "missing".into()],
+                algo: FingerprintAlgo::Xxh64,
+            },
+        };
+        assert_eq!(same.digest(&data), fp);
+        let md5 = Fingerprint {
+            conf: FingerprintConf {
+                name: "fp".into(),
+                fields: vec!["src_access_source".into()],
+                algo: FingerprintAlgo::Md5,
+            },
+        };
+        assert_eq!(md5.digest(&data).len(), 32);
+        assert_ne!(md5.digest(&data), fp);
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_annotation_fields() {
+        let ann = AnnFun {
+            tags: BTreeMap::from([("env".into(), "prod".into())]),
+            ingest_time: Some("ingest_time".into()),
+            fingerprint: Some(FingerprintConf {
+                name: "fp".into(),
+                fields: vec![],
+                algo: FingerprintAlgo::Xxh64,
+            }),
+            ..Default::default()
+        };
+        let funcs = AnnotationType::convert(&Some(ann));
+        let src = SourceEvent::new(
+            1,
+            DEFAULT_KEY.to_string(),
+            RawData::String("same line".to_string()),
+            Tags::new().into(),
+        );
+        let annotate = || {
+            let mut data = DataRecord::test_value();
+            for func in &funcs {
+                func.proc(&src, &mut data).assert();
+            }
+            data
+        };
+        let first = annotate();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        let second = annotate();
+        assert_ne!(first.field("ingest_time"), second.field("ingest_time"));
+        assert_eq!(first.field("fp"), second.field("fp"));
+        assert!(first.field("env").is_some());
+
+        // 指纹只与解析出的字段有关
+        let plain = Fingerprint {
+            conf: FingerprintConf {
+                name: "fp".into(),
+                fields: vec![],
+                algo: FingerprintAlgo::Xxh64,
+            },
+        };
+        assert_eq!(
+            first.field("fp").unwrap().get_value().to_string(),
+            plain.digest(&DataRecord::test_value())
+        );
+    }
 }
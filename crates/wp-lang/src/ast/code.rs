@@ -40,8 +40,9 @@ impl TryFrom<&str> for WplCode {
 
 impl WplCode {
     pub fn build(path: PathBuf, code: &str) -> WplCodeResult<Self> {
+        // 注释以空白遮蔽而非删除，保留源文件的偏移与行号（报错行号据此计算）
         let mut in_code = code;
-        let pure_code = CommentParser::ignore_comment(&mut in_code).map_err(|e| {
+        let pure_code = CommentParser::mask_comment(&mut in_code).map_err(|e| {
             WplCodeError::from(WplCodeReason::Syntax(format!("comment proc error {} ", e)))
         })?;
 
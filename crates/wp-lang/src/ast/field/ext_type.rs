@@ -0,0 +1,73 @@
+use wp_model_core::model::DataType;
+
+/// WPL 扩展类型
+///
+/// wp-model-core 的 `DataType` 之外、仅在 WPL 层实现的字段类型。
+/// 字段配置以 `meta_name` 区分扩展类型，`meta_type` 取输出数据最接近的承载类型，
+/// 使命名、模式优先解析等既有行为与承载类型保持一致。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WplExtType {
+    /// `xml`：元素/属性按 `/` 展开为字段路径，保留命名空间前缀
+    Xml,
+    /// `xml/local`：同 `xml`，去除命名空间前缀与 `xmlns` 声明
+    XmlLocal,
+    /// `time/bsd`：BSD syslog 时间 `Mmm dd hh:mm:ss`，推断年份
+    TimeBsd,
+    /// `time/epoch(unit)`：显式单位（s/ms/us/ns）的 unix 时间戳
+    TimeEpoch,
+    /// `wasm(plugin)`：由已加载的 WASM 插件解析，输出插件给出的类型化字段
+    Wasm,
+}
+
+impl WplExtType {
+    pub const ALL: [WplExtType; 5] = [
+        Self::Xml,
+        Self::XmlLocal,
+        Self::TimeBsd,
+        Self::TimeEpoch,
+        Self::Wasm,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "xml" => Some(Self::Xml),
+            "xml/local" => Some(Self::XmlLocal),
+            "time/bsd" => Some(Self::TimeBsd),
+            "time/epoch" => Some(Self::TimeEpoch),
+            "wasm" => Some(Self::Wasm),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Xml => "xml",
+            Self::XmlLocal => "xml/local",
+            Self::TimeBsd => "time/bsd",
+            Self::TimeEpoch => "time/epoch",
+            Self::Wasm => "wasm",
+        }
+    }
+
+    /// 承载类型：决定字段命名与解析方式（如 json 一样按路径展开、模式优先）
+    pub fn carrier(&self) -> DataType {
+        match self {
+            Self::Xml | Self::XmlLocal | Self::Wasm => DataType::Json,
+            Self::TimeBsd | Self::TimeEpoch => DataType::Time,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ext_type_name_roundtrip() {
+        for ext in WplExtType::ALL {
+            assert_eq!(WplExtType::from_name(ext.name()), Some(ext));
+        }
+        assert_eq!(WplExtType::from_name("json"), None);
+        assert_eq!(WplExtType::TimeEpoch.carrier(), DataType::Time);
+    }
+}
@@ -1,2 +1,3 @@
+pub mod ext_type;
 pub mod format;
 pub mod types;
@@ -1,5 +1,6 @@
 use crate::ast::WplPipe;
 use crate::ast::debug::DebugFormat;
+use crate::ast::field::ext_type::WplExtType;
 use crate::ast::fld_fmt::WplFieldFmt;
 use crate::ast::syntax::wpl_sep::WplSep;
 use crate::parser::wpl_field::wpl_field;
@@ -50,6 +51,10 @@ impl WplField {
     pub fn run_key_str(&self, key: &str) -> FNameStr {
         self.name().clone().unwrap_or_else(|| FNameStr::from(key))
     }
+    /// WPL 扩展类型（如 `xml`），由 `meta_name` 决定
+    pub fn ext_type(&self) -> Option<WplExtType> {
+        WplExtType::from_name(self.meta_name.as_str())
+    }
     /*
     pub fn use_sep(&mut self, sep: PrioSep) {
         self.fmt_conf.use_sep(sep.clone());
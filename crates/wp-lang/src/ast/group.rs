@@ -1,4 +1,5 @@
 use orion_overload::new::New1;
+use smol_str::SmolStr;
 use std::fmt::{Display, Formatter};
 use std::io::Write;
 use std::str::FromStr;
@@ -13,6 +14,7 @@ pub enum WplGroupType {
     Seq(GroupSeq),
     Alt(GroupAlt),
     SomeOf(GroupSomeOf),
+    Repeat(GroupRepeat),
 }
 impl Default for WplGroupType {
     fn default() -> Self {
@@ -31,6 +33,29 @@ pub struct GroupAlt;
 #[derive(Default, Debug, PartialEq, Clone)]
 pub struct GroupSomeOf;
 
+/// `repeat(min..max)`：组内字段序列重复匹配 min~max 次（max 缺省不设上限）。
+/// 输出字段名追加 `[i]` 序号；指定 `collect` 时收集为同名数组字段。
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct GroupRepeat {
+    pub min: usize,
+    pub max: Option<usize>,
+    pub collect: Option<SmolStr>,
+}
+
+impl Display for GroupRepeat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.max {
+            Some(max) if max == self.min => write!(f, "repeat({}", max)?,
+            Some(max) => write!(f, "repeat({}..{}", self.min, max)?,
+            None => write!(f, "repeat({}..", self.min)?,
+        }
+        if let Some(name) = &self.collect {
+            write!(f, ", {}", name)?;
+        }
+        write!(f, ")")
+    }
+}
+
 impl Display for WplGroupType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -46,6 +71,9 @@ impl Display for WplGroupType {
             WplGroupType::SomeOf(_) => {
                 write!(f, "some_of")?;
             }
+            WplGroupType::Repeat(x) => {
+                write!(f, "{}", x)?;
+            }
         }
         Ok(())
     }
@@ -14,15 +14,22 @@ pub struct WplFmt<T>(pub T);
 pub struct GenFmt<T>(pub T);
 
 pub use code::WplCode;
+pub use field::ext_type::WplExtType;
 pub use field::types::WplField;
 pub use field::types::{DEFAULT_FIELD_KEY, DEFAULT_META_NAME, WplFieldSet};
 pub use fld_fmt::WplFieldFmt;
 pub use package::WplPackage;
 pub use package::WplPkgMeta;
+pub use package::{WplExpectField, WplTestCase};
 pub use processor::WplFun;
 pub use processor::WplPipe;
 pub use rule::meta::WplRuleMeta;
 pub use rule::meta::WplTag;
+pub use rule::schema::WplSchema;
 pub use rule::types::{WplExpress, WplRule, WplStatementType};
-pub use syntax::tag::{AnnEnum, AnnFun, TagKvs};
+pub use syntax::tag::{
+    AnnEnum, AnnFun, DEFAULT_RESIDUE_RATIO, FingerprintAlgo, FingerprintConf, TagKvs, WplMatchConf,
+    WplMatchPolicy, WplMatchRank,
+};
 pub use syntax::wpl_sep::WplSep;
+pub(crate) use syntax::wpl_sep::find_sep;
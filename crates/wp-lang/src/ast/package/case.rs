@@ -0,0 +1,81 @@
+use smol_str::SmolStr;
+use std::io::Write;
+
+use crate::ast::debug::{DebugFormat, DepIndent};
+
+/// 期望字段：`name: type(value)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WplExpectField {
+    pub name: SmolStr,
+    pub meta: SmolStr,
+    pub value: String,
+}
+
+/// 包内规则用例：`test [<rule>] { input: "..."; expect: { sip: ip(1.2.3.4) } }`
+///
+/// 仅供测试运行器使用，运行时忽略。未指定规则时按包内规则顺序取首个匹配结果。
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WplTestCase {
+    pub rule: Option<SmolStr>,
+    pub input: String,
+    pub expect: Vec<WplExpectField>,
+}
+
+fn escape_str(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+impl DebugFormat for WplTestCase {
+    fn write<W>(&self, w: &mut W) -> std::io::Result<()>
+    where
+        W: ?Sized + Write + DepIndent,
+    {
+        let depth = w.add_indent();
+        self.write_indent(w, depth)?;
+        match &self.rule {
+            Some(rule) => write!(w, "test {} ", rule)?,
+            None => write!(w, "test ")?,
+        }
+        self.write_open_brace(w)?;
+        self.write_new_line(w)?;
+        self.write_indent(w, depth + 1)?;
+        write!(w, "input: \"{}\";", escape_str(&self.input))?;
+        self.write_new_line(w)?;
+        self.write_indent(w, depth + 1)?;
+        write!(w, "expect: ")?;
+        self.write_open_brace(w)?;
+        self.write_new_line(w)?;
+        for (i, field) in self.expect.iter().enumerate() {
+            self.write_indent(w, depth + 2)?;
+            write!(
+                w,
+                "{}: {}(\"{}\")",
+                field.name,
+                field.meta,
+                escape_str(&field.value)
+            )?;
+            if i + 1 < self.expect.len() {
+                write!(w, ",")?;
+            }
+            self.write_new_line(w)?;
+        }
+        self.write_indent(w, depth + 1)?;
+        self.write_close_brace(w)?;
+        self.write_new_line(w)?;
+        self.write_indent(w, depth)?;
+        self.write_close_brace(w)?;
+        w.sub_indent();
+        Ok(())
+    }
+}
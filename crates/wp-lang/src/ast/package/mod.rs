@@ -13,11 +13,15 @@ use crate::parser::wpl_pkg::wpl_package;
 
 use super::AnnFun;
 
+mod case;
+pub use case::{WplExpectField, WplTestCase};
+
 #[derive(Default, Clone, Getters, Debug)]
 pub struct WplPackage {
     pub name: SmolStr,
     pub rules: VecDeque<WplRule>,
     pub tags: Option<AnnFun>,
+    pub tests: Vec<WplTestCase>,
 }
 
 impl WplPackage {
@@ -75,6 +79,10 @@ impl DebugFormat for WplPackage {
             rule.write(w)?;
             self.write_new_line(w)?;
         }
+        for case in &self.tests {
+            case.write(w)?;
+            self.write_new_line(w)?;
+        }
         self.write_close_brace(w)?;
         self.write_new_line(w)?;
 
@@ -103,6 +111,7 @@ impl WplPackage {
             name,
             rules: VecDeque::from(rules),
             tags: None,
+            tests: Vec::new(),
         }
     }
 
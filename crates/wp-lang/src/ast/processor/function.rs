@@ -1,10 +1,15 @@
 use std::net::IpAddr;
 
+use ipnet::IpNet;
+use regex::Regex;
 use smol_str::SmolStr;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct CharsValue(pub(crate) SmolStr);
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct CidrValue(pub(crate) IpNet);
+
 // ============ Field Existence Check ============
 
 /// Checks if active field exists
@@ -113,6 +118,125 @@ pub struct TargetIpIn {
     pub(crate) value: Vec<IpAddr>,
 }
 
+/// Checks if active field's IP address is in any of the CIDR networks
+#[derive(Clone, Debug, PartialEq)]
+pub struct IpInCidr {
+    pub(crate) value: Vec<IpNet>,
+}
+
+/// Checks if specified field's IP address is in any of the CIDR networks
+#[derive(Clone, Debug, PartialEq)]
+pub struct TargetIpInCidr {
+    pub(crate) target: Option<SmolStr>,
+    pub(crate) value: Vec<IpNet>,
+}
+
+// ============ Pattern / Range Predicates ============
+
+/// Compiled regex argument; equality compares the source pattern
+#[derive(Clone, Debug)]
+pub struct WplRegex {
+    pub(crate) pattern: SmolStr,
+    pub(crate) regex: Regex,
+}
+
+impl WplRegex {
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Ok(Self {
+            pattern: pattern.into(),
+            regex: Regex::new(pattern)?,
+        })
+    }
+}
+
+impl PartialEq for WplRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern
+    }
+}
+
+/// Checks if active field's character value matches a regex
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegexMatch {
+    pub(crate) value: WplRegex,
+}
+
+/// Checks if specified field's character value matches a regex
+#[derive(Clone, Debug, PartialEq)]
+pub struct TargetRegexMatch {
+    pub(crate) target: Option<SmolStr>,
+    pub(crate) value: WplRegex,
+}
+
+/// Checks if active field's numeric value is within `[lo, hi]`
+#[derive(Clone, Debug, PartialEq)]
+pub struct DigitRange {
+    pub(crate) lo: i64,
+    pub(crate) hi: i64,
+}
+
+/// Checks if specified field's numeric value is within `[lo, hi]`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TargetDigitRange {
+    pub(crate) target: Option<SmolStr>,
+    pub(crate) lo: i64,
+    pub(crate) hi: i64,
+}
+
+/// Checks if active field's character count is within `[min, max]`
+#[derive(Clone, Debug, PartialEq)]
+pub struct CharsLen {
+    pub(crate) min: usize,
+    pub(crate) max: usize,
+}
+
+/// Checks if specified field's character count is within `[min, max]`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TargetCharsLen {
+    pub(crate) target: Option<SmolStr>,
+    pub(crate) min: usize,
+    pub(crate) max: usize,
+}
+
+/// Checks if active field's character value starts with a prefix
+#[derive(Clone, Debug, PartialEq)]
+pub struct StartsWith {
+    pub(crate) value: SmolStr,
+}
+
+/// Checks if specified field's character value starts with a prefix
+#[derive(Clone, Debug, PartialEq)]
+pub struct TargetStartsWith {
+    pub(crate) target: Option<SmolStr>,
+    pub(crate) value: SmolStr,
+}
+
+/// Checks if active field's character value ends with a suffix
+#[derive(Clone, Debug, PartialEq)]
+pub struct EndsWith {
+    pub(crate) value: SmolStr,
+}
+
+/// Checks if specified field's character value ends with a suffix
+#[derive(Clone, Debug, PartialEq)]
+pub struct TargetEndsWith {
+    pub(crate) target: Option<SmolStr>,
+    pub(crate) value: SmolStr,
+}
+
+/// Checks if active field's character value contains a substring
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contains {
+    pub(crate) value: SmolStr,
+}
+
+/// Checks if specified field's character value contains a substring
+#[derive(Clone, Debug, PartialEq)]
+pub struct TargetContains {
+    pub(crate) target: Option<SmolStr>,
+    pub(crate) value: SmolStr,
+}
+
 // ============ Legacy/Compatibility ============
 
 #[derive(Clone, Default)]
@@ -127,6 +251,14 @@ pub struct JsonUnescape {}
 #[derive(Clone, Debug, PartialEq)]
 pub struct Base64Decode {}
 
+// ============ Plugin Functions ============
+
+/// Calls the `wp_field_pipe` entry of a loaded WASM plugin on the active field
+#[derive(Clone, Debug, PartialEq)]
+pub struct WasmPipe {
+    pub(crate) plugin: SmolStr,
+}
+
 // ============ Field Selector Functions ============
 
 #[derive(Clone, Debug, PartialEq)]
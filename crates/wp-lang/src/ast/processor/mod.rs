@@ -2,10 +2,12 @@ mod function;
 mod pipe;
 pub(crate) use function::normalize_target;
 pub use function::{
-    Base64Decode, CharsHas, CharsIn, CharsInArg, CharsNotHas, CharsNotHasArg, CharsValue, DigitHas,
-    DigitHasArg, DigitIn, DigitInArg, Has, HasArg, IpIn, IpInArg, JsonUnescape, SelectLast,
-    TakeField, TargetCharsHas, TargetCharsIn, TargetCharsNotHas, TargetDigitHas, TargetDigitIn,
-    TargetHas, TargetIpIn,
+    Base64Decode, CharsHas, CharsIn, CharsInArg, CharsLen, CharsNotHas, CharsNotHasArg, CharsValue,
+    CidrValue, Contains, DigitHas, DigitHasArg, DigitIn, DigitInArg, DigitRange, EndsWith, Has,
+    HasArg, IpIn, IpInArg, IpInCidr, JsonUnescape, RegexMatch, SelectLast, StartsWith, TakeField,
+    TargetCharsHas, TargetCharsIn, TargetCharsLen, TargetCharsNotHas, TargetContains,
+    TargetDigitHas, TargetDigitIn, TargetDigitRange, TargetEndsWith, TargetHas, TargetIpIn,
+    TargetIpInCidr, TargetRegexMatch, TargetStartsWith, WasmPipe, WplRegex,
 };
 pub use pipe::WplFun;
 pub use pipe::WplPipe;
@@ -2,9 +2,11 @@ use derive_getters::Getters;
 use smol_str::SmolStr;
 
 use super::function::{
-    CharsHas, CharsIn, CharsNotHas, DigitHas, DigitIn, Has, IpIn, JsonUnescape, SelectLast,
-    TakeField, TargetCharsHas, TargetCharsIn, TargetCharsNotHas, TargetDigitHas, TargetDigitIn,
-    TargetHas, TargetIpIn,
+    CharsHas, CharsIn, CharsLen, CharsNotHas, Contains, DigitHas, DigitIn, DigitRange, EndsWith,
+    Has, IpIn, IpInCidr, JsonUnescape, RegexMatch, SelectLast, StartsWith, TakeField,
+    TargetCharsHas, TargetCharsIn, TargetCharsLen, TargetCharsNotHas, TargetContains,
+    TargetDigitHas, TargetDigitIn, TargetDigitRange, TargetEndsWith, TargetHas, TargetIpIn,
+    TargetIpInCidr, TargetRegexMatch, TargetStartsWith, WasmPipe,
 };
 use crate::ast::{group::WplGroup, processor::Base64Decode};
 
@@ -27,12 +29,29 @@ pub enum WplFun {
     // IP address comparison
     TargetIpIn(TargetIpIn),
     IpIn(IpIn),
+    TargetIpInCidr(TargetIpInCidr),
+    IpInCidr(IpInCidr),
+    // Pattern / range predicates
+    TargetRegexMatch(TargetRegexMatch),
+    RegexMatch(RegexMatch),
+    TargetDigitRange(TargetDigitRange),
+    DigitRange(DigitRange),
+    TargetCharsLen(TargetCharsLen),
+    CharsLen(CharsLen),
+    TargetStartsWith(TargetStartsWith),
+    StartsWith(StartsWith),
+    TargetEndsWith(TargetEndsWith),
+    EndsWith(EndsWith),
+    TargetContains(TargetContains),
+    Contains(Contains),
     // Field existence check
     TargetHas(TargetHas),
     Has(Has),
     // Transformation functions
     TransJsonUnescape(JsonUnescape),
     TransBase64Decode(Base64Decode),
+    // Plugin functions
+    Wasm(WasmPipe),
 }
 
 #[derive(Debug, Clone, PartialEq, Getters)]
@@ -1,2 +1,3 @@
 pub mod meta;
+pub mod schema;
 pub mod types;
@@ -0,0 +1,223 @@
+use wp_model_core::model::DataType;
+
+use crate::ast::group::{WplGroup, WplGroupType};
+use crate::ast::{AnnFun, WplField, WplFun, WplPipe, WplRule, WplStatementType};
+use crate::types::WildMap;
+
+/// WPL 规则输出的字段名与类型（由规则 AST 静态推导）
+///
+/// `json`/`kv` 等按键展开的字段只收录声明的子字段，其余键在运行期才确定，
+/// 此时 `is_open()` 为真，未收录的字段名不能判定为不存在。
+#[derive(Debug, Clone)]
+pub struct WplSchema {
+    fields: WildMap<DataType>,
+    open: bool,
+}
+
+impl Default for WplSchema {
+    fn default() -> Self {
+        Self {
+            fields: WildMap::new(),
+            open: false,
+        }
+    }
+}
+
+impl WplSchema {
+    /// 字段类型；类型在运行期才确定时为 `auto`
+    pub fn field_type(&self, name: &str) -> Option<&DataType> {
+        self.fields.get(name)
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// 追加规则之外产生的字段（如引擎注入的 `wp_src_key`），名称可含 `*`
+    pub fn add_field<S: Into<String>>(&mut self, name: S, meta: DataType) {
+        self.fields.insert(name.into(), meta);
+    }
+
+    /// 全部字段名，含通配名
+    pub fn names(&self) -> Vec<&str> {
+        self.entries().into_iter().map(|(name, _)| name).collect()
+    }
+
+    fn entries(&self) -> Vec<(&str, &DataType)> {
+        let mut out: Vec<(&str, &DataType)> = self
+            .fields
+            .exact_iter()
+            .map(|(name, meta)| (name.as_str(), meta))
+            .collect();
+        out.extend(
+            self.fields
+                .wild_iter()
+                .map(|(name, _, meta)| (name.as_str(), meta)),
+        );
+        out
+    }
+
+    fn add_group(&mut self, group: &WplGroup) {
+        let WplGroupType::Repeat(repeat) = &group.meta else {
+            self.add_fields(&group.fields);
+            return;
+        };
+        // repeat 输出 `name[i]`，指定 collect 时收集为数组字段
+        let mut inner = WplSchema::default();
+        inner.add_fields(&group.fields);
+        self.open |= inner.open;
+        match &repeat.collect {
+            Some(name) => self.add_field(name.as_str(), DataType::Auto),
+            None => {
+                for (name, meta) in inner.entries() {
+                    self.add_field(format!("{}[*]", name), meta.clone());
+                }
+            }
+        }
+    }
+
+    fn add_fields(&mut self, fields: &[WplField]) {
+        for field in fields {
+            self.add_wpl_field(field);
+        }
+    }
+
+    fn add_wpl_field(&mut self, field: &WplField) {
+        for pipe in &field.pipe {
+            match pipe {
+                WplPipe::Group(group) => self.add_group(group),
+                WplPipe::Fun(WplFun::Wasm(_)) => self.open = true,
+                WplPipe::Fun(_) => {}
+            }
+        }
+        match &field.meta_type {
+            DataType::Ignore => {}
+            DataType::Json
+            | DataType::ExactJson
+            | DataType::KV
+            | DataType::KvArr
+            | DataType::ProtoText
+            | DataType::Auto => {
+                // 只有 exact_json 拒绝未声明的键
+                self.open |= field.meta_type != DataType::ExactJson;
+                if field.meta_type == DataType::Auto {
+                    self.add_field(field.safe_name().to_string(), DataType::Auto);
+                }
+                let Some(subs) = &field.sub_fields else {
+                    return;
+                };
+                let exact = subs.conf_items().exact_iter().map(|x| (&x.0, &x.1));
+                let wild = subs
+                    .conf_items()
+                    .wild_iter()
+                    .map(|(path, _, sub)| (path, sub));
+                for (path, sub) in exact.chain(wild) {
+                    if sub.meta_type != DataType::Ignore {
+                        let name = sub.run_key_str(path.as_str()).to_string();
+                        self.add_field(name, output_type(&sub.meta_type));
+                    }
+                }
+            }
+            meta => self.add_field(field.safe_name().to_string(), output_type(meta)),
+        }
+    }
+
+    fn add_tags(&mut self, tags: &Option<AnnFun>) {
+        let Some(ann) = tags else {
+            return;
+        };
+        for key in ann.tags.keys() {
+            self.add_field(key.as_str(), DataType::Chars);
+        }
+        if let Some((key, name)) = &ann.copy_raw
+            && key == "name"
+        {
+            self.add_field(name.as_str(), DataType::Chars);
+        }
+        // 源标签在运行期才确定
+        if ann.copy_tags.is_some() {
+            self.open = true;
+        }
+        if let Some(name) = &ann.ingest_time {
+            self.add_field(name.as_str(), DataType::Time);
+        }
+        if let Some(conf) = &ann.fingerprint {
+            self.add_field(conf.name.as_str(), DataType::Chars);
+        }
+    }
+}
+
+// 解析器实际输出的类型：时间族统一为 time，port 按 digit 输出
+fn output_type(meta: &DataType) -> DataType {
+    match meta {
+        DataType::TimeCLF
+        | DataType::TimeISO
+        | DataType::TimeRFC3339
+        | DataType::TimeRFC2822
+        | DataType::TimeTIMESTAMP => DataType::Time,
+        DataType::Port => DataType::Digit,
+        other => other.clone(),
+    }
+}
+
+impl WplRule {
+    /// 推导规则输出的字段名与类型，包含注解（`tag`、`copy_raw` 等）追加的字段
+    pub fn output_schema(&self) -> WplSchema {
+        let mut schema = WplSchema::default();
+        match &self.statement {
+            WplStatementType::Express(express) => {
+                for group in &express.group {
+                    schema.add_group(group);
+                }
+                schema.add_tags(&express.tags);
+            }
+        }
+        schema
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::WplPackage;
+    use orion_error::TestAssert;
+    use wp_model_core::model::DataType;
+
+    #[test]
+    fn test_output_schema() {
+        let mut code = r#"package /demo {
+   #[tag(dev:"nginx"), copy_raw(name:"raw")]
+   rule access {
+        (ip:sip,_^2,time/clf:recv_time<[,]>,http/request",http/status,digit,chars",http/agent",_")
+   }
+   rule api { (digit:code, exact_json(chars@user, digit@detail/id:uid), repeat(1..3)(ip:peer)) }
+   rule kv { (kv(ip@src, digit@port)) }
+}"#;
+        let pkg = WplPackage::parse(&mut code, "demo.wpl").assert();
+
+        let schema = pkg.rules[0].output_schema();
+        assert!(!schema.is_open());
+        assert_eq!(schema.field_type("sip"), Some(&DataType::IP));
+        assert_eq!(schema.field_type("recv_time"), Some(&DataType::Time));
+        assert_eq!(
+            schema.field_type("http/status"),
+            Some(&DataType::HttpStatus)
+        );
+        assert_eq!(schema.field_type("digit"), Some(&DataType::Digit));
+        assert_eq!(schema.field_type("dev"), Some(&DataType::Chars));
+        assert_eq!(schema.field_type("raw"), Some(&DataType::Chars));
+        assert_eq!(schema.field_type("_"), None);
+
+        let schema = pkg.rules[1].output_schema();
+        assert!(!schema.is_open());
+        assert_eq!(schema.field_type("user"), Some(&DataType::Chars));
+        assert_eq!(schema.field_type("uid"), Some(&DataType::Digit));
+        assert_eq!(schema.field_type("detail/id"), None);
+        assert_eq!(schema.field_type("peer[2]"), Some(&DataType::IP));
+        assert_eq!(schema.field_type("peer"), None);
+
+        let schema = pkg.rules[2].output_schema();
+        assert!(schema.is_open());
+        assert_eq!(schema.field_type("src"), Some(&DataType::IP));
+        assert_eq!(schema.names(), vec!["src", "port"]);
+    }
+}
@@ -11,7 +11,7 @@ use smol_str::SmolStr;
 
 use crate::{
     ast::{
-        AnnFun, WplField,
+        AnnFun, WplField, WplMatchConf,
         debug::{DebugFormat, DepIndent},
         group::WplGroup,
     },
@@ -216,6 +216,24 @@ impl WplRule {
     pub fn path(&self, pkg_name: &str) -> String {
         format!("{}/{}", pkg_name, self.get_name())
     }
+
+    /// `#[priority(N)]`，未声明时为 0，数值越大越先尝试
+    pub fn priority(&self) -> i64 {
+        self.statement
+            .tags()
+            .as_ref()
+            .and_then(|x| x.priority)
+            .unwrap_or(0)
+    }
+
+    /// 匹配策略，通常由包注解 `#[match(...)]` 合并而来
+    pub fn match_conf(&self) -> WplMatchConf {
+        self.statement
+            .tags()
+            .as_ref()
+            .and_then(|x| x.matching)
+            .unwrap_or_default()
+    }
 }
 
 impl MergeTags for VecDeque<WplRule> {
@@ -1,3 +1,4 @@
+use std::cmp::{Ordering, Reverse};
 use std::collections::BTreeMap;
 use std::io::Write;
 
@@ -11,15 +12,157 @@ use crate::parser::MergeTags;
 pub type TagKvs = BTreeMap<SmolStr, SmolStr>;
 pub type CopyRaw = (SmolStr, SmolStr);
 
+/// 残留比例默认阈值：未解析部分占比不超过该值时按部分匹配（Partial）接受
+pub const DEFAULT_RESIDUE_RATIO: f64 = 0.2;
+
+/// 多规则匹配策略
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum WplMatchPolicy {
+    /// 按当前顺序（优先级、命中次数）取首个可接受的结果
+    #[default]
+    First,
+    /// 取优先级最高的可接受结果，同优先级按声明顺序
+    Priority,
+    /// 取残留最少的结果
+    Longest,
+    /// 只接受无残留的结果
+    Strict,
+}
+
+impl WplMatchPolicy {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "first" => Some(Self::First),
+            "priority" => Some(Self::Priority),
+            "longest" => Some(Self::Longest),
+            "strict" => Some(Self::Strict),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::First => "first",
+            Self::Priority => "priority",
+            Self::Longest => "longest",
+            Self::Strict => "strict",
+        }
+    }
+}
+
+/// `#[match(longest, residue: 0.1)]`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct WplMatchConf {
+    pub policy: WplMatchPolicy,
+    pub residue: f64,
+}
+
+impl Default for WplMatchConf {
+    fn default() -> Self {
+        Self {
+            policy: WplMatchPolicy::First,
+            residue: DEFAULT_RESIDUE_RATIO,
+        }
+    }
+}
+
+impl WplMatchConf {
+    /// 残留是否可接受；`strict` 忽略阈值，只接受无残留
+    pub fn accept(&self, residue: usize, total: usize) -> bool {
+        if residue == 0 {
+            return true;
+        }
+        if self.policy == WplMatchPolicy::Strict || total == 0 {
+            return false;
+        }
+        residue as f64 / total as f64 <= self.residue
+    }
+
+    /// 以本策略比较两个可接受的结果：`cand` 是否优于已选的 `best`
+    pub fn prefer(&self, cand: &WplMatchRank, best: &WplMatchRank) -> bool {
+        let by_rank = (Reverse(cand.priority), cand.index) < (Reverse(best.priority), best.index);
+        match self.policy {
+            WplMatchPolicy::Longest => match cand.residue.cmp(&best.residue) {
+                Ordering::Less => true,
+                Ordering::Equal => by_rank,
+                Ordering::Greater => false,
+            },
+            _ => by_rank,
+        }
+    }
+
+    /// 已选出 `best` 时，优先级为 `next` 的后续规则是否已不可能更优（规则按优先级降序尝试）
+    pub fn settled(&self, best: &WplMatchRank, next: i64) -> bool {
+        match self.policy {
+            WplMatchPolicy::Priority => next < best.priority,
+            WplMatchPolicy::Longest => best.residue == 0,
+            WplMatchPolicy::First | WplMatchPolicy::Strict => true,
+        }
+    }
+}
+
+/// 一次可接受的匹配在择优时的依据：规则优先级、声明序号与残留长度
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct WplMatchRank {
+    pub priority: i64,
+    pub index: usize,
+    pub residue: usize,
+}
+
+/// 事件指纹哈希算法
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum FingerprintAlgo {
+    #[default]
+    Xxh64,
+    Md5,
+}
+
+impl FingerprintAlgo {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "xxh64" => Some(Self::Xxh64),
+            "md5" => Some(Self::Md5),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Xxh64 => "xxh64",
+            Self::Md5 => "md5",
+        }
+    }
+}
+
+/// `fingerprint(name:"fp", fields:[a, b], algo:"xxh64")`；`fields` 为空时对全部字段取指纹
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct FingerprintConf {
+    pub name: SmolStr,
+    pub fields: Vec<SmolStr>,
+    pub algo: FingerprintAlgo,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum AnnEnum {
     Tags(TagKvs),
     Copy(CopyRaw),
+    Priority(i64),
+    Match(WplMatchConf),
+    CopyTags(SmolStr),
+    IngestTime(SmolStr),
+    Fingerprint(FingerprintConf),
 }
 #[derive(Debug, PartialEq, Default, Clone)]
 pub struct AnnFun {
     pub tags: TagKvs,
     pub copy_raw: Option<CopyRaw>,
+    pub priority: Option<i64>,
+    pub matching: Option<WplMatchConf>,
+    /// 复制 SourceEvent 标签，值为字段名前缀
+    pub copy_tags: Option<SmolStr>,
+    /// 写入接收时间的字段名
+    pub ingest_time: Option<SmolStr>,
+    pub fingerprint: Option<FingerprintConf>,
 }
 
 impl MergeTags for AnnFun {
@@ -34,6 +177,21 @@ impl MergeTags for AnnFun {
             if self.copy_raw.is_none() {
                 self.copy_raw = atags.copy_raw.clone()
             }
+            if self.priority.is_none() {
+                self.priority = atags.priority;
+            }
+            if self.matching.is_none() {
+                self.matching = atags.matching;
+            }
+            if self.copy_tags.is_none() {
+                self.copy_tags = atags.copy_tags.clone();
+            }
+            if self.ingest_time.is_none() {
+                self.ingest_time = atags.ingest_time.clone();
+            }
+            if self.fingerprint.is_none() {
+                self.fingerprint = atags.fingerprint.clone();
+            }
         }
     }
 }
@@ -53,38 +211,44 @@ impl DebugFormat for AnnFun {
     where
         W: ?Sized + Write + DepIndent,
     {
-        write!(w, "#[tag")?;
-        let tag_count = self.tags.len();
-
-        for (index, t) in self.tags.iter().enumerate() {
-            if tag_count == 1 && index == 0 {
-                self.write_open_parenthesis(w)?;
-                write!(w, "{}:\"{}\"", t.0, t.1)?;
-                self.write_close_parenthesis(w)?;
-
-                write!(w, "]")?;
-                self.write_new_line(w)?;
-                return Ok(());
-            }
-            if index == 0 {
-                self.write_open_parenthesis(w)?;
-            }
-            write!(w, "{}:\"{}\"", t.0, t.1)?;
-
-            if index == tag_count - 1 {
-                self.write_close_parenthesis(w)?;
-            } else {
-                write!(w, ", ")?;
-            }
+        let mut items = Vec::new();
+        if !self.tags.is_empty() {
+            let kvs: Vec<String> = self
+                .tags
+                .iter()
+                .map(|(k, v)| format!("{}:\"{}\"", k, v))
+                .collect();
+            items.push(format!("tag({})", kvs.join(", ")));
         }
-
         if let Some((ck, cv)) = &self.copy_raw {
-            write!(w, ", copy_raw")?;
-            self.write_open_parenthesis(w)?;
-            write!(w, "{}:\"{}\"", ck, cv)?;
-            self.write_close_parenthesis(w)?;
+            items.push(format!("copy_raw({}:\"{}\")", ck, cv));
+        }
+        if let Some(priority) = self.priority {
+            items.push(format!("priority({})", priority));
         }
-        write!(w, "]")?;
+        if let Some(conf) = &self.matching {
+            items.push(format!(
+                "match({}, residue: {})",
+                conf.policy.name(),
+                conf.residue
+            ));
+        }
+        if let Some(prefix) = &self.copy_tags {
+            items.push(format!("copy_tags(prefix:\"{}\")", prefix));
+        }
+        if let Some(name) = &self.ingest_time {
+            items.push(format!("ingest_time(name:\"{}\")", name));
+        }
+        if let Some(fp) = &self.fingerprint {
+            let fields: Vec<&str> = fp.fields.iter().map(|f| f.as_str()).collect();
+            items.push(format!(
+                "fingerprint(name:\"{}\", fields:[{}], algo:\"{}\")",
+                fp.name,
+                fields.join(", "),
+                fp.algo.name()
+            ));
+        }
+        write!(w, "#[{}]", items.join(", "))?;
         self.write_new_line(w)?;
         Ok(())
     }
@@ -97,3 +261,55 @@ impl Accumulate<()> for AnnFun {
 
     fn accumulate(&mut self, _acc: ()) {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conf(policy: WplMatchPolicy) -> WplMatchConf {
+        WplMatchConf {
+            policy,
+            residue: DEFAULT_RESIDUE_RATIO,
+        }
+    }
+
+    fn rank(priority: i64, index: usize, residue: usize) -> WplMatchRank {
+        WplMatchRank {
+            priority,
+            index,
+            residue,
+        }
+    }
+
+    #[test]
+    fn test_match_prefer() {
+        let first = conf(WplMatchPolicy::First);
+        // 非 longest：高优先级优先，同优先级按声明顺序，残留不参与比较
+        assert!(first.prefer(&rank(5, 3, 9), &rank(0, 0, 0)));
+        assert!(first.prefer(&rank(0, 0, 9), &rank(0, 1, 0)));
+        assert!(!first.prefer(&rank(0, 2, 0), &rank(0, 1, 9)));
+        assert!(conf(WplMatchPolicy::Priority).prefer(&rank(1, 9, 5), &rank(0, 0, 0)));
+
+        let longest = conf(WplMatchPolicy::Longest);
+        // longest：残留少者优先，残留相同再按优先级、声明顺序
+        assert!(longest.prefer(&rank(0, 9, 1), &rank(5, 0, 2)));
+        assert!(!longest.prefer(&rank(5, 0, 3), &rank(0, 9, 2)));
+        assert!(longest.prefer(&rank(5, 9, 2), &rank(0, 0, 2)));
+        assert!(!longest.prefer(&rank(0, 2, 2), &rank(0, 1, 2)));
+    }
+
+    #[test]
+    fn test_match_settled() {
+        let best = rank(5, 0, 3);
+        assert!(conf(WplMatchPolicy::First).settled(&best, 5));
+        assert!(conf(WplMatchPolicy::Strict).settled(&best, 9));
+        // priority：只有更低优先级的后续规则才不可能更优
+        let priority = conf(WplMatchPolicy::Priority);
+        assert!(!priority.settled(&best, 5));
+        assert!(priority.settled(&best, 4));
+        // longest：只有无残留才结束
+        let longest = conf(WplMatchPolicy::Longest);
+        assert!(!longest.settled(&best, 0));
+        assert!(longest.settled(&rank(0, 0, 0), 9));
+    }
+}
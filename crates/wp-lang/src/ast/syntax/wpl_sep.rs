@@ -1,11 +1,12 @@
 use crate::ast::{GenFmt, WplFmt};
 use crate::parser::utils::{quot_r_str, quot_str, take_to_end};
 use derive_getters::Getters;
+use memchr::{memchr, memchr2, memmem};
 use smol_str::SmolStr;
 use std::fmt::{Display, Formatter};
-use winnow::combinator::{alt, opt, separated};
-use winnow::stream::Range;
-use winnow::token::{literal, take_until, take_while};
+use winnow::combinator::{alt, fail, opt};
+use winnow::error::ErrMode;
+use winnow::token::literal;
 use wp_parser::Parser;
 use wp_parser::WResult;
 use wp_parser::symbol::ctx_desc;
@@ -199,17 +200,17 @@ impl WplSep {
     }
 
     pub fn read_until_any_char<'a>(end1: &str, end2: &str, data: &mut &'a str) -> WResult<&'a str> {
-        let ends1 = end1.as_bytes();
-        let ends2 = end2.as_bytes();
-        alt((
-            quot_r_str,
-            quot_str,
-            take_while(0.., |c: char| {
-                !(ends1.contains(&(c as u8)) || ends2.contains(&(c as u8)))
-            }),
-            take_to_end,
-        ))
-        .parse_next(data)
+        if let Some(buf) = take_quoted(data)? {
+            return Ok(buf);
+        }
+        let s = *data;
+        let idx = match (end1.as_bytes(), end2.as_bytes()) {
+            ([a], [b]) => memchr2(*a, *b, s.as_bytes()),
+            (ends1, ends2) => {
+                s.find(|c: char| ends1.contains(&(c as u8)) || ends2.contains(&(c as u8)))
+            }
+        };
+        Ok(split_at_or_end(data, idx))
     }
 
     pub fn read_until_sep(&self, data: &mut &str) -> WResult<String> {
@@ -220,7 +221,7 @@ impl WplSep {
             return Ok(buf.to_string());
         }
         if let Some(ups) = &self.ups_val {
-            // 快路径：单字符对，使用按字符扫描，天然最近结束优先
+            // 快路径：单字符对，memchr2 一次扫描，天然最近结束优先
             if self.sep_str().len() == 1 && ups.len() == 1 {
                 let buf = Self::read_until_any_char(self.sep_str(), ups.as_str(), data)?;
                 return Ok(buf.to_string());
@@ -234,43 +235,75 @@ impl WplSep {
                 let buf = alt((quot_r_str, quot_str)).parse_next(data)?;
                 return Ok(buf.to_string());
             }
-            let p = s.find(self.sep_str());
-            let q = s.find(ups.as_str());
-            let idx = match (p, q) {
+            let idx = match (find_sep(s, self.sep_str()), find_sep(s, ups.as_str())) {
                 (Some(i), Some(j)) => Some(i.min(j)),
-                (Some(i), None) => Some(i),
-                (None, Some(j)) => Some(j),
-                (None, None) => None,
+                (i, j) => i.or(j),
             };
-            if let Some(i) = idx {
-                let (left, right) = s.split_at(i);
-                *data = right; // 保持与 take_until 一致：不消费结束符本身
-                return Ok(left.to_string());
-            }
-            let buf = take_to_end.parse_next(data)?;
+            // 保持与 take_until 一致：不消费结束符本身
+            return Ok(split_at_or_end(data, idx).to_string());
+        }
+        // 无次级结束符：引号整体读取，否则读到分隔符或末尾
+        if let Some(buf) = take_quoted(data)? {
             return Ok(buf.to_string());
         }
-        // 无次级结束符：原有语义
-        let buf = alt((
-            quot_r_str,
-            quot_str,
-            take_until(0.., self.sep_str()),
-            take_to_end,
-        ))
-        .parse_next(data)?;
-        Ok(buf.to_string())
+        let idx = find_sep(data, self.sep_str());
+        Ok(split_at_or_end(data, idx).to_string())
     }
     pub fn read_until_sep_repeat(&self, num: usize, data: &mut &str) -> WResult<String> {
-        let buffer: Vec<&str> = separated(
-            Range::from(num),
-            take_until(1.., self.sep_str()),
-            self.sep_str(),
-        )
-        .parse_next(data)?;
+        // 等价于 separated(num, take_until(1.., sep), sep)：每段非空且其后必须跟分隔符
+        let sep = self.sep_str();
+        let mut rest = *data;
+        for i in 0..num {
+            if i > 0 {
+                match rest.strip_prefix(sep) {
+                    Some(x) => rest = x,
+                    None => return fail.context(ctx_desc("take <sep>")).parse_next(data),
+                }
+            }
+            match find_sep(rest, sep) {
+                Some(idx) if idx >= 1 => rest = &rest[idx..],
+                _ => return fail.context(ctx_desc("take <sep>")).parse_next(data),
+            }
+        }
+        let (take, left) = data.split_at(data.len() - rest.len());
+        *data = left;
+        Ok(take.to_string())
+    }
+}
+
+/// 定位分隔符首次出现的位置：单字节用 `memchr`，多字节用 `memmem`（SIMD 加速）。
+/// UTF-8 自同步，命中位置必然落在字符边界上。
+#[inline]
+pub(crate) fn find_sep(hay: &str, sep: &str) -> Option<usize> {
+    match sep.as_bytes() {
+        [] => Some(0),
+        [b] => memchr(*b, hay.as_bytes()),
+        bytes => memmem::find(hay.as_bytes(), bytes),
+    }
+}
 
-        let msg = buffer.join(self.sep_str());
-        Ok(msg)
+// 以引号开头时整体读取引号串；不是引号串则不消费输入
+fn take_quoted<'a>(data: &mut &'a str) -> WResult<Option<&'a str>> {
+    let s = *data;
+    if !(s.starts_with('"') || s.starts_with('r')) {
+        return Ok(None);
     }
+    match alt((quot_r_str, quot_str)).parse_next(data) {
+        Ok(buf) => Ok(Some(buf)),
+        Err(ErrMode::Backtrack(_)) => {
+            *data = s;
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+// 在 idx 处切分（不消费结束符），未找到时读到末尾
+fn split_at_or_end<'a>(data: &mut &'a str, idx: Option<usize>) -> &'a str {
+    let s = *data;
+    let (take, left) = s.split_at(idx.unwrap_or(s.len()));
+    *data = left;
+    take
 }
 
 impl Display for WplFmt<&WplSep> {
@@ -292,3 +325,63 @@ impl Display for GenFmt<&WplSep> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_sep() {
+        assert_eq!(find_sep("中文,a", ","), Some(6));
+        assert_eq!(find_sep("a||b|c", "||"), Some(1));
+        assert_eq!(find_sep("abc", "|"), None);
+        assert_eq!(find_sep("abc", ""), Some(0));
+    }
+
+    #[test]
+    fn test_read_until_sep() {
+        let sep = WplSep::field_sep(",");
+        let mut data = "abc,def";
+        assert_eq!(sep.read_until_sep(&mut data).unwrap(), "abc");
+        assert_eq!(data, ",def");
+        let mut data = "\"a,b\",c";
+        assert_eq!(sep.read_until_sep(&mut data).unwrap(), "a,b");
+        assert_eq!(data, ",c");
+        let mut data = "\"open,c";
+        assert_eq!(sep.read_until_sep(&mut data).unwrap(), "\"open");
+        let mut data = "rest";
+        assert_eq!(sep.read_until_sep(&mut data).unwrap(), "rest");
+        assert_eq!(data, "");
+
+        let sep = WplSep::field_sep("::");
+        let mut data = "a:b::c";
+        assert_eq!(sep.read_until_sep(&mut data).unwrap(), "a:b");
+        assert_eq!(data, "::c");
+
+        // 次级结束符：最近结束优先
+        let sep = WplSep::field_sep_until(",", "]", true);
+        let mut data = "a]b,c";
+        assert_eq!(sep.read_until_sep(&mut data).unwrap(), "a");
+        assert_eq!(data, "]b,c");
+        let sep = WplSep::field_sep_until("||", "]", true);
+        let mut data = "a||b]";
+        assert_eq!(sep.read_until_sep(&mut data).unwrap(), "a");
+        assert_eq!(data, "||b]");
+    }
+
+    #[test]
+    fn test_read_until_sep_repeat() {
+        let sep = WplSep::field_sep(" ");
+        let mut data = "May 15 2023 07:09:12 host";
+        assert_eq!(
+            sep.read_until_sep_repeat(3, &mut data).unwrap(),
+            "May 15 2023"
+        );
+        assert_eq!(data, " 07:09:12 host");
+        let mut data = "a  b c";
+        assert!(sep.read_until_sep_repeat(3, &mut data).is_err());
+        assert_eq!(data, "a  b c");
+        let mut data = "a b";
+        assert!(sep.read_until_sep_repeat(2, &mut data).is_err());
+    }
+}
@@ -0,0 +1,92 @@
+use wp_parse_api::{PipeProcessor, RawData, WparseResult};
+
+use super::{raw_bytes, rewrap_raw};
+
+const ESC: u8 = 0x1b;
+const BEL: u8 = 0x07;
+
+/// 去除 ANSI 转义序列：CSI（`ESC [ ... m` 等）、OSC（`ESC ] ... BEL|ESC \`）及其他 ESC 序列
+fn strip_ansi(input: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(input.len());
+    let mut i = 0;
+    while i < input.len() {
+        if input[i] != ESC {
+            out.push(input[i]);
+            i += 1;
+            continue;
+        }
+        i += 1;
+        match input.get(i) {
+            Some(b'[') => {
+                i += 1;
+                // 参数与中间字节，直到结束字节 0x40..=0x7E
+                while i < input.len() && !(0x40..=0x7e).contains(&input[i]) {
+                    i += 1;
+                }
+                i += 1;
+            }
+            Some(b']') => {
+                i += 1;
+                while i < input.len() {
+                    if input[i] == BEL {
+                        i += 1;
+                        break;
+                    }
+                    if input[i] == ESC && input.get(i + 1) == Some(&b'\\') {
+                        i += 2;
+                        break;
+                    }
+                    i += 1;
+                }
+            }
+            Some(_) => {
+                while i < input.len() && (0x20..=0x2f).contains(&input[i]) {
+                    i += 1;
+                }
+                i += 1;
+            }
+            None => {}
+        }
+    }
+    out
+}
+
+#[derive(Debug)]
+pub struct AnsiStripProc;
+
+impl PipeProcessor for AnsiStripProc {
+    /// Removes ANSI escape sequences (colors, cursor control, titles) while preserving the input container type.
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        let out = strip_ansi(raw_bytes(&data));
+        Ok(rewrap_raw(&data, out))
+    }
+
+    fn name(&self) -> &'static str {
+        "strip/ansi"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::AnyResult;
+    use bytes::Bytes;
+
+    #[test]
+    fn test_strip_ansi() -> AnyResult<()> {
+        let data = RawData::from_string(
+            "\x1b[1;31mERROR\x1b[0m disk \x1b]0;title\x07full\x1b(B 中文\x1b[K".to_string(),
+        );
+        let result = AnsiStripProc.process(data)?;
+        assert!(matches!(result, RawData::String(_)));
+        assert_eq!(
+            crate::eval::builtins::raw_to_utf8_string(&result),
+            "ERROR disk full 中文"
+        );
+
+        let data = RawData::Bytes(Bytes::from_static(b"plain\x1b"));
+        let result = AnsiStripProc.process(data)?;
+        assert_eq!(crate::eval::builtins::raw_to_utf8_string(&result), "plain");
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use encoding_rs::Encoding;
+
+use wp_parse_api::{PipeProcessor, RawData, WparseResult};
+
+use super::{raw_bytes, rewrap_raw};
+
+/// `decode/charset(<label>)`：按指定字符集（gbk、gb18030、big5、shift_jis ...）转为 UTF-8
+///
+/// 非法字节替换为 U+FFFD；字符串输入已是 UTF-8，原样返回。
+#[derive(Debug)]
+pub struct CharsetProc {
+    encoding: &'static Encoding,
+}
+
+impl CharsetProc {
+    pub fn from_label(label: &str) -> Option<Self> {
+        Encoding::for_label(label.trim().as_bytes()).map(|encoding| Self { encoding })
+    }
+}
+
+impl PipeProcessor for CharsetProc {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        if let RawData::String(_) = data {
+            return Ok(data);
+        }
+        let (text, _, _) = self.encoding.decode(raw_bytes(&data));
+        let out = text.into_owned().into_bytes();
+        Ok(rewrap_raw(&data, out))
+    }
+
+    fn name(&self) -> &'static str {
+        "decode/charset"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::AnyResult;
+    use bytes::Bytes;
+
+    #[test]
+    fn test_charset_gbk() -> AnyResult<()> {
+        let gbk = CharsetProc::from_label("gbk").expect("gbk");
+        // "中文" in GBK
+        let data = RawData::Bytes(Bytes::from_static(&[0xD6, 0xD0, 0xCE, 0xC4, b'!']));
+        let result = gbk.process(data)?;
+        assert!(matches!(result, RawData::Bytes(_)));
+        assert_eq!(crate::eval::builtins::raw_to_utf8_string(&result), "中文!");
+
+        let data = RawData::from_string("已解码".to_string());
+        let result = gbk.process(data)?;
+        assert_eq!(crate::eval::builtins::raw_to_utf8_string(&result), "已解码");
+
+        assert!(CharsetProc::from_label("klingon").is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use flate2::read::{MultiGzDecoder, ZlibDecoder};
+use orion_error::{ErrorOwe, ErrorWith};
+use std::io::Read;
+
+use wp_parse_api::{PipeProcessor, RawData, WparseResult};
+
+use super::{raw_bytes, rewrap_raw};
+
+/// 单条数据解压后的上限，防止压缩炸弹
+const MAX_INFLATE_SIZE: u64 = 64 * 1024 * 1024;
+
+fn inflate<R: Read>(reader: R) -> std::io::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    reader.take(MAX_INFLATE_SIZE + 1).read_to_end(&mut out)?;
+    if out.len() as u64 > MAX_INFLATE_SIZE {
+        return Err(std::io::Error::other(format!(
+            "inflated size exceeds {} bytes",
+            MAX_INFLATE_SIZE
+        )));
+    }
+    Ok(out)
+}
+
+#[derive(Debug)]
+pub struct GzipProc;
+
+impl PipeProcessor for GzipProc {
+    /// Inflates gzip data (multi-member streams included) while preserving the input container type.
+    /// For string inputs, falls back to bytes when the result is not valid UTF-8.
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        let out = inflate(MultiGzDecoder::new(raw_bytes(&data)))
+            .owe_data()
+            .want("gzip decode")?;
+        Ok(rewrap_raw(&data, out))
+    }
+
+    fn name(&self) -> &'static str {
+        "decode/gzip"
+    }
+}
+
+#[derive(Debug)]
+pub struct ZlibProc;
+
+impl PipeProcessor for ZlibProc {
+    /// Inflates zlib (RFC 1950) data while preserving the input container type.
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        let out = inflate(ZlibDecoder::new(raw_bytes(&data)))
+            .owe_data()
+            .want("zlib decode")?;
+        Ok(rewrap_raw(&data, out))
+    }
+
+    fn name(&self) -> &'static str {
+        "decode/zlib"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::builtins::base64::Base64Proc;
+    use crate::types::AnyResult;
+    use base64::{Engine as _, engine::general_purpose};
+    use bytes::Bytes;
+    use flate2::Compression;
+    use flate2::write::{GzEncoder, ZlibEncoder};
+    use std::io::Write;
+    use std::sync::Arc;
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut enc = GzEncoder::new(Vec::new(), Compression::default());
+        enc.write_all(data).unwrap();
+        enc.finish().unwrap()
+    }
+
+    #[test]
+    fn test_gzip() -> AnyResult<()> {
+        let packed = gzip(b"hello gzip");
+        let result = GzipProc.process(RawData::Bytes(Bytes::from(packed.clone())))?;
+        assert!(matches!(result, RawData::Bytes(_)));
+        assert_eq!(
+            crate::eval::builtins::raw_to_utf8_string(&result),
+            "hello gzip"
+        );
+
+        let result = GzipProc.process(RawData::ArcBytes(Arc::new(packed)))?;
+        assert!(matches!(result, RawData::ArcBytes(_)));
+
+        // base64 包裹的 gzip：| decode/base64 | decode/gzip |
+        let wrapped = general_purpose::STANDARD.encode(gzip(b"{\"a\":1}"));
+        let unwrapped = Base64Proc.process(RawData::from_string(wrapped))?;
+        assert!(matches!(unwrapped, RawData::Bytes(_)));
+        let result = GzipProc.process(unwrapped)?;
+        assert_eq!(
+            crate::eval::builtins::raw_to_utf8_string(&result),
+            "{\"a\":1}"
+        );
+
+        assert!(
+            GzipProc
+                .process(RawData::from_string("plain".to_string()))
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_zlib() -> AnyResult<()> {
+        let mut enc = ZlibEncoder::new(Vec::new(), Compression::default());
+        enc.write_all(b"hello zlib")?;
+        let packed = enc.finish()?;
+        let result = ZlibProc.process(RawData::Bytes(Bytes::from(packed)))?;
+        assert_eq!(
+            crate::eval::builtins::raw_to_utf8_string(&result),
+            "hello zlib"
+        );
+        assert!(
+            ZlibProc
+                .process(RawData::Bytes(Bytes::from_static(b"xx")))
+                .is_err()
+        );
+        Ok(())
+    }
+}
@@ -14,8 +14,11 @@ impl PipeProcessor for HexProc {
         match data {
             RawData::String(s) => {
                 let decoded = hex::decode(s.as_bytes()).owe_data().want("hex decode")?;
-                let vstring = String::from_utf8(decoded).owe_data().want("to-json")?;
-                Ok(RawData::from_string(vstring))
+                // 解码结果不是 UTF-8（如 gzip 负载）时降级为 Bytes，供后续单元继续处理
+                match String::from_utf8(decoded) {
+                    Ok(vstring) => Ok(RawData::from_string(vstring)),
+                    Err(e) => Ok(RawData::Bytes(Bytes::from(e.into_bytes()))),
+                }
             }
             RawData::Bytes(b) => {
                 let decoded = hex::decode(b.as_ref()).owe_data().want("hex decode")?;
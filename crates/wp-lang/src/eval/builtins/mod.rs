@@ -1,17 +1,27 @@
+use bytes::Bytes;
 use std::fmt::Debug;
 use std::sync::{Arc, Once};
 
 use wp_parse_api::{PipeHold, RawData};
 
+pub mod ansi;
 pub mod base64;
+pub mod charset;
+pub mod gzip;
 pub mod hex;
 mod pipe_fun;
 pub mod quotation;
 pub mod registry;
+pub mod urldecode;
+pub mod wasm;
 
+use ansi::AnsiStripProc;
 use base64::Base64Proc;
+use charset::CharsetProc;
+use gzip::{GzipProc, ZlibProc};
 use hex::HexProc;
 use quotation::EscQuotaProc;
+use urldecode::UrlDecodeProc;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct PipeLineResult {
@@ -27,6 +37,26 @@ pub fn raw_to_utf8_string(data: &RawData) -> String {
     }
 }
 
+pub(crate) fn raw_bytes(data: &RawData) -> &[u8] {
+    match data {
+        RawData::String(s) => s.as_bytes(),
+        RawData::Bytes(b) => b.as_ref(),
+        RawData::ArcBytes(b) => b.as_ref(),
+    }
+}
+
+/// 按输入容器类型回填处理结果；字符串输入的结果不是合法 UTF-8 时降级为 Bytes
+pub(crate) fn rewrap_raw(origin: &RawData, out: Vec<u8>) -> RawData {
+    match origin {
+        RawData::String(_) => match String::from_utf8(out) {
+            Ok(s) => RawData::from_string(s),
+            Err(e) => RawData::Bytes(Bytes::from(e.into_bytes())),
+        },
+        RawData::Bytes(_) => RawData::Bytes(Bytes::from(out)),
+        RawData::ArcBytes(_) => RawData::ArcBytes(Arc::new(out)),
+    }
+}
+
 static BUILTIN_PIPE_INIT: Once = Once::new();
 
 fn decode_base64_stage() -> PipeHold {
@@ -41,11 +71,37 @@ fn unquote_unescape_stage() -> PipeHold {
     Arc::new(EscQuotaProc)
 }
 
+fn decode_gzip_stage() -> PipeHold {
+    Arc::new(GzipProc)
+}
+
+fn decode_zlib_stage() -> PipeHold {
+    Arc::new(ZlibProc)
+}
+
+fn decode_url_stage() -> PipeHold {
+    Arc::new(UrlDecodeProc)
+}
+
+fn decode_charset_stage(label: &str) -> Option<PipeHold> {
+    CharsetProc::from_label(label).map(|proc| Arc::new(proc) as PipeHold)
+}
+
+fn strip_ansi_stage() -> PipeHold {
+    Arc::new(AnsiStripProc)
+}
+
 /// Ensure core decode/unquote pipe units are registered in the plg_pipe registry.
 pub fn ensure_builtin_pipe_units() {
     BUILTIN_PIPE_INIT.call_once(|| {
         registry::register_pipe_unit("decode/base64", decode_base64_stage);
         registry::register_pipe_unit("decode/hex", decode_hex_stage);
         registry::register_pipe_unit("unquote/unescape", unquote_unescape_stage);
+        registry::register_pipe_unit("decode/gzip", decode_gzip_stage);
+        registry::register_pipe_unit("decode/zlib", decode_zlib_stage);
+        registry::register_pipe_unit("decode/url", decode_url_stage);
+        registry::register_pipe_unit_with_args("decode/charset", decode_charset_stage);
+        registry::register_pipe_unit("strip/ansi", strip_ansi_stage);
+        registry::register_pipe_unit_with_args("wasm", wasm::wasm_unit_stage);
     });
 }
@@ -1,13 +1,19 @@
 use crate::ast::WplFun;
 use crate::ast::processor::{
-    Base64Decode, CharsHas, CharsIn, CharsNotHas, DigitHas, DigitIn, Has, IpIn, JsonUnescape,
-    SelectLast, TakeField, TargetCharsHas, TargetCharsIn, TargetCharsNotHas, TargetDigitHas,
-    TargetDigitIn, TargetHas, TargetIpIn,
+    Base64Decode, CharsHas, CharsIn, CharsLen, CharsNotHas, Contains, DigitHas, DigitIn,
+    DigitRange, EndsWith, Has, IpIn, IpInCidr, JsonUnescape, RegexMatch, SelectLast, StartsWith,
+    TakeField, TargetCharsHas, TargetCharsIn, TargetCharsLen, TargetCharsNotHas, TargetContains,
+    TargetDigitHas, TargetDigitIn, TargetDigitRange, TargetEndsWith, TargetHas, TargetIpIn,
+    TargetIpInCidr, TargetRegexMatch, TargetStartsWith, WasmPipe,
 };
 use crate::eval::runtime::field_pipe::{FieldIndex, FieldPipe, FieldSelector, FieldSelectorSpec};
+use crate::wasm::{WasmEntry, abi, wasm_plugin};
 use base64::Engine;
 use base64::engine::general_purpose;
-use winnow::combinator::fail;
+use ipnet::IpNet;
+use std::net::IpAddr;
+use winnow::combinator::{cut_err, fail};
+use wp_log::error_data;
 use wp_model_core::model::{DataField, Value};
 use wp_parser::symbol::ctx_desc;
 use wp_parser::{Parser, WResult};
@@ -253,6 +259,198 @@ impl FieldPipe for IpIn {
     }
 }
 
+// ---------------- Predicates ----------------
+
+#[inline]
+fn check_chars(
+    field: Option<&mut DataField>,
+    what: &'static str,
+    check: impl Fn(&str) -> bool,
+) -> WResult<()> {
+    if let Some(item) = field
+        && let Value::Chars(value) = item.get_value()
+        && check(value.as_str())
+    {
+        return Ok(());
+    }
+    fail.context(ctx_desc(what)).parse_next(&mut "")
+}
+
+#[inline]
+fn check_digit(field: Option<&mut DataField>, lo: i64, hi: i64) -> WResult<()> {
+    if let Some(item) = field
+        && let Value::Digit(value) = item.get_value()
+        && (lo..=hi).contains(value)
+    {
+        return Ok(());
+    }
+    fail.context(ctx_desc("<pipe> | out of range"))
+        .parse_next(&mut "")
+}
+
+// IP 字段或可解析为 IP 的字符字段（如 json 中的字符串）
+#[inline]
+fn check_cidr(field: Option<&mut DataField>, nets: &[IpNet]) -> WResult<()> {
+    let ip = field.and_then(|item| match item.get_value() {
+        Value::IpAddr(ip) => Some(*ip),
+        Value::Chars(s) => s.parse::<IpAddr>().ok(),
+        _ => None,
+    });
+    if let Some(ip) = ip
+        && nets.iter().any(|net| net.contains(&ip))
+    {
+        return Ok(());
+    }
+    fail.context(ctx_desc("<pipe> | not in cidr"))
+        .parse_next(&mut "")
+}
+
+impl FieldPipe for TargetRegexMatch {
+    #[inline]
+    fn process(&self, field: Option<&mut DataField>) -> WResult<()> {
+        check_chars(field, "<pipe> | not match", |v| {
+            self.value.regex.is_match(v)
+        })
+    }
+
+    fn auto_select<'a>(&'a self) -> Option<FieldSelectorSpec<'a>> {
+        self.target.as_deref().map(FieldSelectorSpec::Take)
+    }
+}
+
+impl FieldPipe for RegexMatch {
+    #[inline]
+    fn process(&self, field: Option<&mut DataField>) -> WResult<()> {
+        check_chars(field, "<pipe> | not match", |v| {
+            self.value.regex.is_match(v)
+        })
+    }
+}
+
+impl FieldPipe for TargetDigitRange {
+    #[inline]
+    fn process(&self, field: Option<&mut DataField>) -> WResult<()> {
+        check_digit(field, self.lo, self.hi)
+    }
+
+    fn auto_select<'a>(&'a self) -> Option<FieldSelectorSpec<'a>> {
+        self.target.as_deref().map(FieldSelectorSpec::Take)
+    }
+}
+
+impl FieldPipe for DigitRange {
+    #[inline]
+    fn process(&self, field: Option<&mut DataField>) -> WResult<()> {
+        check_digit(field, self.lo, self.hi)
+    }
+}
+
+impl FieldPipe for TargetCharsLen {
+    #[inline]
+    fn process(&self, field: Option<&mut DataField>) -> WResult<()> {
+        check_chars(field, "<pipe> | length out of range", |v| {
+            (self.min..=self.max).contains(&v.chars().count())
+        })
+    }
+
+    fn auto_select<'a>(&'a self) -> Option<FieldSelectorSpec<'a>> {
+        self.target.as_deref().map(FieldSelectorSpec::Take)
+    }
+}
+
+impl FieldPipe for CharsLen {
+    #[inline]
+    fn process(&self, field: Option<&mut DataField>) -> WResult<()> {
+        check_chars(field, "<pipe> | length out of range", |v| {
+            (self.min..=self.max).contains(&v.chars().count())
+        })
+    }
+}
+
+impl FieldPipe for TargetStartsWith {
+    #[inline]
+    fn process(&self, field: Option<&mut DataField>) -> WResult<()> {
+        check_chars(field, "<pipe> | not starts with", |v| {
+            v.starts_with(self.value.as_str())
+        })
+    }
+
+    fn auto_select<'a>(&'a self) -> Option<FieldSelectorSpec<'a>> {
+        self.target.as_deref().map(FieldSelectorSpec::Take)
+    }
+}
+
+impl FieldPipe for StartsWith {
+    #[inline]
+    fn process(&self, field: Option<&mut DataField>) -> WResult<()> {
+        check_chars(field, "<pipe> | not starts with", |v| {
+            v.starts_with(self.value.as_str())
+        })
+    }
+}
+
+impl FieldPipe for TargetEndsWith {
+    #[inline]
+    fn process(&self, field: Option<&mut DataField>) -> WResult<()> {
+        check_chars(field, "<pipe> | not ends with", |v| {
+            v.ends_with(self.value.as_str())
+        })
+    }
+
+    fn auto_select<'a>(&'a self) -> Option<FieldSelectorSpec<'a>> {
+        self.target.as_deref().map(FieldSelectorSpec::Take)
+    }
+}
+
+impl FieldPipe for EndsWith {
+    #[inline]
+    fn process(&self, field: Option<&mut DataField>) -> WResult<()> {
+        check_chars(field, "<pipe> | not ends with", |v| {
+            v.ends_with(self.value.as_str())
+        })
+    }
+}
+
+impl FieldPipe for TargetContains {
+    #[inline]
+    fn process(&self, field: Option<&mut DataField>) -> WResult<()> {
+        check_chars(field, "<pipe> | not contains", |v| {
+            v.contains(self.value.as_str())
+        })
+    }
+
+    fn auto_select<'a>(&'a self) -> Option<FieldSelectorSpec<'a>> {
+        self.target.as_deref().map(FieldSelectorSpec::Take)
+    }
+}
+
+impl FieldPipe for Contains {
+    #[inline]
+    fn process(&self, field: Option<&mut DataField>) -> WResult<()> {
+        check_chars(field, "<pipe> | not contains", |v| {
+            v.contains(self.value.as_str())
+        })
+    }
+}
+
+impl FieldPipe for TargetIpInCidr {
+    #[inline]
+    fn process(&self, field: Option<&mut DataField>) -> WResult<()> {
+        check_cidr(field, &self.value)
+    }
+
+    fn auto_select<'a>(&'a self) -> Option<FieldSelectorSpec<'a>> {
+        self.target.as_deref().map(FieldSelectorSpec::Take)
+    }
+}
+
+impl FieldPipe for IpInCidr {
+    #[inline]
+    fn process(&self, field: Option<&mut DataField>) -> WResult<()> {
+        check_cidr(field, &self.value)
+    }
+}
+
 impl FieldPipe for TargetHas {
     #[inline]
     fn process(&self, field: Option<&mut DataField>) -> WResult<()> {
@@ -313,6 +511,50 @@ impl FieldPipe for Base64Decode {
     }
 }
 
+impl FieldPipe for WasmPipe {
+    fn process(&self, field: Option<&mut DataField>) -> WResult<()> {
+        let Some(field) = field else {
+            return fail
+                .context(ctx_desc("wasm | no active field"))
+                .parse_next(&mut "");
+        };
+        let Some(plugin) = wasm_plugin(self.plugin.as_str()) else {
+            return fail
+                .context(ctx_desc("wasm | plugin not loaded"))
+                .parse_next(&mut "");
+        };
+        let input = match field.get_value() {
+            Value::Chars(s) => s.to_string(),
+            other => other.to_string(),
+        };
+        let out = match plugin.call(WasmEntry::FieldPipe, input.as_bytes()) {
+            Ok(Some(out)) => out,
+            // 插件拒绝：与断言失败一致
+            Ok(None) => {
+                return fail
+                    .context(ctx_desc("wasm | rejected"))
+                    .parse_next(&mut "");
+            }
+            // 沙箱错误与拒绝区分：记录并中断
+            Err(e) => {
+                error_data!("wasm pipe {} error: {}", self.plugin, e);
+                return cut_err(fail)
+                    .context(ctx_desc("wasm | call"))
+                    .parse_next(&mut "");
+            }
+        };
+        match abi::decode_fields(&out, field.get_name()) {
+            Ok(mut out) if !out.fields.is_empty() => {
+                *field = out.fields.swap_remove(0);
+                Ok(())
+            }
+            _ => fail
+                .context(ctx_desc("wasm | bad output"))
+                .parse_next(&mut ""),
+        }
+    }
+}
+
 impl WplFun {
     pub fn as_field_pipe(&self) -> Option<&dyn FieldPipe> {
         match self {
@@ -329,10 +571,25 @@ impl WplFun {
             WplFun::DigitIn(fun) => Some(fun),
             WplFun::TargetIpIn(fun) => Some(fun),
             WplFun::IpIn(fun) => Some(fun),
+            WplFun::TargetIpInCidr(fun) => Some(fun),
+            WplFun::IpInCidr(fun) => Some(fun),
+            WplFun::TargetRegexMatch(fun) => Some(fun),
+            WplFun::RegexMatch(fun) => Some(fun),
+            WplFun::TargetDigitRange(fun) => Some(fun),
+            WplFun::DigitRange(fun) => Some(fun),
+            WplFun::TargetCharsLen(fun) => Some(fun),
+            WplFun::CharsLen(fun) => Some(fun),
+            WplFun::TargetStartsWith(fun) => Some(fun),
+            WplFun::StartsWith(fun) => Some(fun),
+            WplFun::TargetEndsWith(fun) => Some(fun),
+            WplFun::EndsWith(fun) => Some(fun),
+            WplFun::TargetContains(fun) => Some(fun),
+            WplFun::Contains(fun) => Some(fun),
             WplFun::TargetHas(fun) => Some(fun),
             WplFun::Has(fun) => Some(fun),
             WplFun::TransJsonUnescape(fun) => Some(fun),
             WplFun::TransBase64Decode(fun) => Some(fun),
+            WplFun::Wasm(fun) => Some(fun),
         }
     }
 
@@ -352,6 +609,13 @@ impl WplFun {
             WplFun::TargetDigitHas(fun) => fun.auto_select(),
             WplFun::TargetDigitIn(fun) => fun.auto_select(),
             WplFun::TargetIpIn(fun) => fun.auto_select(),
+            WplFun::TargetIpInCidr(fun) => fun.auto_select(),
+            WplFun::TargetRegexMatch(fun) => fun.auto_select(),
+            WplFun::TargetDigitRange(fun) => fun.auto_select(),
+            WplFun::TargetCharsLen(fun) => fun.auto_select(),
+            WplFun::TargetStartsWith(fun) => fun.auto_select(),
+            WplFun::TargetEndsWith(fun) => fun.auto_select(),
+            WplFun::TargetContains(fun) => fun.auto_select(),
             WplFun::TargetHas(fun) => fun.auto_select(),
             _ => None,
         }
@@ -425,6 +689,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn wasm_pipe_rewrites_or_rejects_field() {
+        crate::wasm::test_plugin::register_sample("pipe_mask");
+        let pipe = WasmPipe {
+            plugin: "pipe_mask".into(),
+        };
+        let mut fields = vec![DataField::from_chars("pwd".to_string(), "abc".to_string())];
+        pipe.process(fields.get_mut(0)).expect("mask ok");
+        assert_eq!(fields[0].get_name(), "pwd");
+        if let Value::Chars(s) = fields[0].get_value() {
+            assert_eq!(s, "***");
+        } else {
+            panic!("pwd should remain chars");
+        }
+
+        let mut fields = vec![DataField::from_chars("pwd".to_string(), String::new())];
+        assert!(pipe.process(fields.get_mut(0)).is_err());
+        let missing = WasmPipe {
+            plugin: "pipe_missing".into(),
+        };
+        assert!(missing.process(fields.get_mut(0)).is_err());
+    }
+
     #[test]
     fn base64_decode_returns_err_on_invalid_payload() {
         let mut fields = vec![DataField::from_chars(
@@ -458,4 +745,64 @@ mod tests {
         )];
         assert!(JsonUnescape {}.process(fields.get_mut(0)).is_err());
     }
+
+    #[test]
+    fn predicates_check_active_field() {
+        let mut chars = vec![DataField::from_chars("path", "/api/v1/users")];
+        let regex = RegexMatch {
+            value: crate::ast::processor::WplRegex::new(r"^/api/v\d+").unwrap(),
+        };
+        assert!(regex.process(chars.get_mut(0)).is_ok());
+        assert!(
+            CharsLen { min: 1, max: 13 }
+                .process(chars.get_mut(0))
+                .is_ok()
+        );
+        assert!(
+            CharsLen { min: 1, max: 12 }
+                .process(chars.get_mut(0))
+                .is_err()
+        );
+        assert!(
+            StartsWith {
+                value: "/api".into()
+            }
+            .process(chars.get_mut(0))
+            .is_ok()
+        );
+        assert!(
+            EndsWith {
+                value: "user".into()
+            }
+            .process(chars.get_mut(0))
+            .is_err()
+        );
+        assert!(Contains { value: "v1".into() }.process(None).is_err());
+
+        let mut digit = vec![DataField::from_digit("code", 404)];
+        assert!(
+            DigitRange { lo: 400, hi: 499 }
+                .process(digit.get_mut(0))
+                .is_ok()
+        );
+        assert!(
+            DigitRange { lo: 405, hi: 499 }
+                .process(digit.get_mut(0))
+                .is_err()
+        );
+        // 类型不符时不匹配
+        assert!(regex.process(digit.get_mut(0)).is_err());
+
+        let cidr = IpInCidr {
+            value: vec!["10.0.0.0/8".parse().unwrap(), "fd00::/8".parse().unwrap()],
+        };
+        let mut ips = vec![
+            DataField::from_ip("a", "10.9.8.7".parse().unwrap()),
+            DataField::from_chars("b", "fd00::1"),
+            DataField::from_ip("c", "11.0.0.1".parse().unwrap()),
+        ];
+        assert!(cidr.process(ips.get_mut(0)).is_ok());
+        assert!(cidr.process(ips.get_mut(1)).is_ok());
+        assert!(cidr.process(ips.get_mut(2)).is_err());
+    }
 }
@@ -6,10 +6,13 @@ use std::sync::{Mutex, MutexGuard};
 use super::PipeHold;
 
 pub type PlgPipeUnitBuilder = fn() -> PipeHold;
+/// 带参数的预处理单元，如 `decode/charset(gbk)`；参数不合法时返回 None
+pub type PlgPipeArgUnitBuilder = fn(&str) -> Option<PipeHold>;
 
 #[derive(Default)]
 struct PlgPipeUnitRegistry {
     builders: HashMap<SmolStr, PlgPipeUnitBuilder>,
+    arg_builders: HashMap<SmolStr, PlgPipeArgUnitBuilder>,
 }
 
 impl PlgPipeUnitRegistry {
@@ -18,17 +21,43 @@ impl PlgPipeUnitRegistry {
             .insert(SmolStr::from(name.to_ascii_uppercase()), builder);
     }
 
+    fn register_with_args(&mut self, name: &str, builder: PlgPipeArgUnitBuilder) {
+        self.arg_builders
+            .insert(SmolStr::from(name.to_ascii_uppercase()), builder);
+    }
+
     fn create(&self, name: &str) -> Option<PipeHold> {
+        if let Some((base, args)) = split_args(name) {
+            return self
+                .arg_builders
+                .get(&SmolStr::from(base.to_ascii_uppercase()))
+                .and_then(|builder| (builder)(args));
+        }
         self.builders
             .get(&SmolStr::from(name.to_ascii_uppercase()))
             .map(|builder| (builder)())
     }
 
     fn list(&self) -> Vec<SmolStr> {
-        self.builders.keys().cloned().collect()
+        self.builders
+            .keys()
+            .cloned()
+            .chain(
+                self.arg_builders
+                    .keys()
+                    .map(|k| SmolStr::from(format!("{}(..)", k))),
+            )
+            .collect()
     }
 }
 
+// `name(args)` -> (name, args)
+fn split_args(name: &str) -> Option<(&str, &str)> {
+    let (base, rest) = name.split_once('(')?;
+    let args = rest.strip_suffix(')')?;
+    Some((base.trim(), args.trim()))
+}
+
 static PIPE_UNIT_REGISTRY: Lazy<Mutex<PlgPipeUnitRegistry>> =
     Lazy::new(|| Mutex::new(PlgPipeUnitRegistry::default()));
 
@@ -42,6 +71,10 @@ pub fn register_pipe_unit(name: &str, builder: PlgPipeUnitBuilder) {
     registry().register(name, builder);
 }
 
+pub fn register_pipe_unit_with_args(name: &str, builder: PlgPipeArgUnitBuilder) {
+    registry().register_with_args(name, builder);
+}
+
 pub fn create_pipe_unit(name: &str) -> Option<PipeHold> {
     registry().create(name)
 }
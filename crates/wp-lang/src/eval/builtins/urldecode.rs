@@ -0,0 +1,86 @@
+use orion_error::{ErrorOwe, ErrorWith};
+
+use wp_parse_api::{PipeProcessor, RawData, WparseResult};
+
+use super::{raw_bytes, rewrap_raw};
+
+fn hex_val(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// 解码 `%XX`；不完整或非法的转义原样保留，`+` 不做处理
+fn percent_decode(input: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(input.len());
+    let mut i = 0;
+    while i < input.len() {
+        if input[i] == b'%'
+            && i + 2 < input.len()
+            && let (Some(h), Some(l)) = (hex_val(input[i + 1]), hex_val(input[i + 2]))
+        {
+            out.push(h << 4 | l);
+            i += 3;
+            continue;
+        }
+        out.push(input[i]);
+        i += 1;
+    }
+    out
+}
+
+#[derive(Debug)]
+pub struct UrlDecodeProc;
+
+impl PipeProcessor for UrlDecodeProc {
+    /// Percent-decodes data while preserving the input container type.
+    /// String inputs must decode to valid UTF-8.
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        let out = percent_decode(raw_bytes(&data));
+        if let RawData::String(_) = data {
+            let vstring = String::from_utf8(out).owe_data().want("url decode")?;
+            return Ok(RawData::from_string(vstring));
+        }
+        Ok(rewrap_raw(&data, out))
+    }
+
+    fn name(&self) -> &'static str {
+        "decode/url"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::AnyResult;
+    use bytes::Bytes;
+
+    #[test]
+    fn test_url_decode() -> AnyResult<()> {
+        let data = RawData::from_string("q=%E4%B8%AD%E6%96%87&p=a%2Fb+c".to_string());
+        let result = UrlDecodeProc.process(data)?;
+        assert_eq!(
+            crate::eval::builtins::raw_to_utf8_string(&result),
+            "q=中文&p=a/b+c"
+        );
+
+        // 非法或截断的转义保留原样
+        let data = RawData::Bytes(Bytes::from_static(b"100%25 %zz %4"));
+        let result = UrlDecodeProc.process(data)?;
+        assert!(matches!(result, RawData::Bytes(_)));
+        assert_eq!(
+            crate::eval::builtins::raw_to_utf8_string(&result),
+            "100% %zz %4"
+        );
+
+        assert!(
+            UrlDecodeProc
+                .process(RawData::from_string("%FF".to_string()))
+                .is_err()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use orion_error::{ErrorOwe, ErrorWith};
+use std::sync::Arc;
+
+use wp_parse_api::{PipeHold, PipeProcessor, RawData, WparseResult};
+
+use super::{raw_bytes, rewrap_raw};
+use crate::wasm::{WasmEntry, WasmPlugin, wasm_plugin};
+
+/// 预处理单元 `wasm(<plugin>)`：调用插件的 `wp_pipe_unit`，插件拒绝时原样透传
+pub struct WasmUnitProc {
+    plugin: Arc<WasmPlugin>,
+}
+
+impl WasmUnitProc {
+    /// 插件未加载或未导出 `wp_pipe_unit` 时返回 None
+    pub fn from_name(name: &str) -> Option<Self> {
+        let plugin = wasm_plugin(name)?;
+        plugin
+            .supports(WasmEntry::PipeUnit)
+            .then_some(Self { plugin })
+    }
+}
+
+impl PipeProcessor for WasmUnitProc {
+    fn process(&self, data: RawData) -> WparseResult<RawData> {
+        let out = self
+            .plugin
+            .call(WasmEntry::PipeUnit, raw_bytes(&data))
+            .owe_data()
+            .want("wasm pipe unit")
+            .with(self.plugin.name())?;
+        match out {
+            Some(out) => Ok(rewrap_raw(&data, out)),
+            None => Ok(data),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "wasm"
+    }
+}
+
+pub(super) fn wasm_unit_stage(name: &str) -> Option<PipeHold> {
+    WasmUnitProc::from_name(name).map(|proc| Arc::new(proc) as PipeHold)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WplEvaluator;
+    use crate::wasm::test_plugin::register_sample;
+    use orion_error::TestAssert;
+    use wp_model_core::model::Value;
+
+    #[test]
+    fn test_wasm_unit() {
+        register_sample("unit_upper");
+        let proc = WasmUnitProc::from_name("unit_upper").unwrap();
+        let out = proc.process(RawData::from_string("abc 1")).assert();
+        assert_eq!(super::super::raw_to_utf8_string(&out), "ABC 1");
+        assert!(WasmUnitProc::from_name("unit_missing").is_none());
+
+        let ppl = WplEvaluator::from_code(r#"rule r { | wasm(unit_upper) | (chars:a, chars:b) }"#)
+            .assert();
+        let (record, _) = ppl.proc("get post", 0).assert();
+        assert!(matches!(record.items[0].get_value(), Value::Chars(v) if v.as_str() == "GET"));
+        assert!(WplEvaluator::from_code(r#"rule r { | wasm(unit_missing) | (chars) }"#).is_err());
+    }
+}
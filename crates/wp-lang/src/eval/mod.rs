@@ -15,6 +15,8 @@ where
 }
 
 pub use builtins::PipeLineResult;
+pub(crate) use runtime::field::FieldEvalUnit;
+pub use runtime::profile::{FieldProfile, ParseProfiler, ProfileHook, ProfileReport, RuleProfile};
 pub use runtime::vm_unit::OPTIMIZE_TIMES;
 pub use runtime::vm_unit::{DataResult, WplEvaluator};
 pub use value::ParserFactory;
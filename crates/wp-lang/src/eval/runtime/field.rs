@@ -10,12 +10,14 @@ use crate::generator::{FmtField, GenChannel};
 use crate::parser::error::WplCodeResult;
 use crate::types::AnyResult;
 use derive_getters::Getters;
+use std::time::Instant;
 use wp_model_core::model::FNameStr;
 // Use centralized parser result alias for consistency across crates
 use wp_model_core::model::{DataField, DataType};
 use wp_parser::WResult as ModalResult;
 
 use super::pipe_exec::PipeExecutor;
+use super::profile;
 use super::subunit::SubUnitManager;
 
 #[derive(Clone, Getters)]
@@ -27,6 +29,8 @@ pub struct FieldEvalUnit {
     sub_units: SubUnitManager,
     next: Option<Box<FieldEvalUnit>>,
     pub group_enum: WplGroupType,
+    // 仅由开启剖析的求值器置位；未置位时解析路径不做任何剖析检查
+    profiled: bool,
 }
 
 impl FieldEvalUnit {
@@ -39,6 +43,7 @@ impl FieldEvalUnit {
             sub_units: SubUnitManager::new(),
             next: None,
             group_enum,
+            profiled: false,
         }
     }
     fn create_next(
@@ -65,9 +70,9 @@ impl FieldEvalUnit {
         } else {
             None
         };
-        let parser = ParserFactory::create(&meta)?;
         let mut cur_conf = conf.clone();
         cur_conf.meta_type = meta;
+        let parser = ParserFactory::create_field(&cur_conf)?;
         let ins = Self {
             index,
             conf: cur_conf,
@@ -76,6 +81,7 @@ impl FieldEvalUnit {
             sub_units: SubUnitManager::new(),
             next,
             group_enum,
+            profiled: false,
         };
         Ok(ins)
     }
@@ -95,6 +101,9 @@ impl FieldEvalUnit {
     pub fn get_sub_fpu(&self, sub_key: &str) -> Option<&FieldEvalUnit> {
         self.sub_units.get(sub_key)
     }
+    pub(crate) fn set_profiled(&mut self) {
+        self.profiled = true;
+    }
     pub fn conf_mut(&mut self) -> &mut WplField {
         &mut self.conf
     }
@@ -122,6 +131,30 @@ impl FieldEvalUnit {
         data: &mut &str,
         run_key: Option<FNameStr>,
         out: &mut Vec<DataField>,
+    ) -> ModalResult<()> {
+        if !self.profiled || !profile::is_active() {
+            return self.parse_field(upper_sep, data, run_key, out);
+        }
+        let begin = Instant::now();
+        let result = self.parse_field(upper_sep, data, run_key, out);
+        profile::record_field(|| self.profile_label(), begin.elapsed(), result.is_ok());
+        result
+    }
+
+    // 剖析报告中的字段标识：`#序号 类型[:名称]`
+    fn profile_label(&self) -> String {
+        match &self.conf.name {
+            Some(name) => format!("#{} {}:{}", self.index, self.conf.meta_name, name),
+            None => format!("#{} {}", self.index, self.conf.meta_name),
+        }
+    }
+
+    fn parse_field(
+        &self,
+        upper_sep: &WplSep,
+        data: &mut &str,
+        run_key: Option<FNameStr>,
+        out: &mut Vec<DataField>,
     ) -> ModalResult<()> {
         let sep = self.conf.resolve_sep_ref(upper_sep);
 
@@ -1,7 +1,9 @@
 mod alt;
 mod opt;
 mod order;
+mod repeat;
 mod some_of;
 mod traits;
 pub use order::WplEvalGroup;
+pub use repeat::RestProc;
 pub use traits::LogicProc;
@@ -14,7 +14,7 @@ use crate::eval::runtime::vm_unit::StopWatch;
 use crate::eval::desc::group_idx_desc;
 use wp_parser::symbol::ctx_desc;
 
-use super::LogicProc;
+use super::{LogicProc, RestProc};
 
 #[derive(Default, Clone, Getters)]
 pub struct WplEvalGroup {
@@ -121,6 +121,29 @@ impl WplEvalGroup {
             .context(ctx_desc("<someof>"))
             .context(ctx_desc(group_idx_desc(self.index)))
             .parse_next(data),
+            WplGroupType::Repeat(x) => trace("<repeat><group>", move |data: &mut &str| {
+                x.process(self, sep, data, out)
+            })
+            .context(ctx_desc("<repeat>"))
+            .context(ctx_desc(group_idx_desc(self.index)))
+            .parse_next(data),
+        }
+    }
+
+    /// 执行本组后继续 `rest`；repeat 组在 `rest` 失败时减少重复次数回溯重试
+    pub fn proc_then(
+        &self,
+        sep: &WplSep,
+        data: &mut &str,
+        out: &mut Vec<DataField>,
+        rest: &mut RestProc<'_>,
+    ) -> ModalResult<()> {
+        match &self.rule {
+            WplGroupType::Repeat(x) => x.backtrack(self, sep, data, out, rest),
+            _ => {
+                self.proc(sep, data, out)?;
+                rest(data, out)
+            }
         }
     }
 }
@@ -0,0 +1,197 @@
+use crate::WplSep;
+use crate::ast::group::{GroupRepeat, GroupSeq};
+use crate::eval::runtime::group::{LogicProc, WplEvalGroup};
+use winnow::combinator::fail;
+use wp_model_core::model::DataField;
+use wp_parser::Parser;
+use wp_parser::WResult as ModalResult;
+use wp_parser::symbol::ctx_desc;
+
+/// 后续解析：repeat 组确定重复次数后继续执行的剩余组
+pub type RestProc<'r> = dyn FnMut(&mut &str, &mut Vec<DataField>) -> ModalResult<()> + 'r;
+
+impl LogicProc for GroupRepeat {
+    fn process(
+        &self,
+        group: &WplEvalGroup,
+        ups_sep: &WplSep,
+        data: &mut &str,
+        out: &mut Vec<DataField>,
+    ) -> ModalResult<()> {
+        let mut done = |_: &mut &str, _: &mut Vec<DataField>| -> ModalResult<()> { Ok(()) };
+        self.backtrack(group, ups_sep, data, out, &mut done)
+    }
+}
+
+impl GroupRepeat {
+    /// 贪婪匹配后按次数由多到少尝试，直到 `rest` 成功；
+    /// 每次尝试前恢复输入与输出，失败的迭代不会残留字段
+    pub fn backtrack<'a>(
+        &self,
+        group: &WplEvalGroup,
+        ups_sep: &WplSep,
+        data: &mut &'a str,
+        out: &mut Vec<DataField>,
+        rest: &mut RestProc<'_>,
+    ) -> ModalResult<()> {
+        let start = out.len();
+        let marks = self.iterate(group, ups_sep, data, out);
+        let raw: Vec<DataField> = out.drain(start..).collect();
+        let ends: Vec<usize> = marks.iter().map(|(_, len)| len - start).collect();
+
+        let mut last_err = None;
+        for count in (self.min..marks.len()).rev() {
+            *data = marks[count].0;
+            out.truncate(start);
+            self.emit(&raw, &ends[..=count], out);
+            match rest(data, out) {
+                Ok(_) => return Ok(()),
+                Err(e) => last_err = Some(e),
+            }
+        }
+        *data = marks[0].0;
+        out.truncate(start);
+        match last_err {
+            Some(e) => Err(e),
+            None => fail
+                .context(ctx_desc("repeat less than min times"))
+                .parse_next(data),
+        }
+    }
+
+    /// 逐次按 seq 语义匹配组内字段；返回每次迭代结束时的 (剩余输入, 输出长度)，首项为起点
+    fn iterate<'a>(
+        &self,
+        group: &WplEvalGroup,
+        ups_sep: &WplSep,
+        data: &mut &'a str,
+        out: &mut Vec<DataField>,
+    ) -> Vec<(&'a str, usize)> {
+        let mut marks = vec![(*data, out.len())];
+        while !data.is_empty() && self.max.is_none_or(|max| marks.len() <= max) {
+            let (before, len) = (*data, out.len());
+            match GroupSeq.process(group, ups_sep, data, out) {
+                // 未消费输入的迭代会无限重复，视为结束
+                Ok(_) if data.len() < before.len() => marks.push((*data, out.len())),
+                _ => {
+                    *data = before;
+                    out.truncate(len);
+                    break;
+                }
+            }
+        }
+        marks
+    }
+
+    fn emit(&self, raw: &[DataField], ends: &[usize], out: &mut Vec<DataField>) {
+        let mut items = Vec::with_capacity(ends[ends.len() - 1]);
+        for (idx, range) in ends.windows(2).enumerate() {
+            for field in &raw[range[0]..range[1]] {
+                let mut field = field.clone();
+                let name = format!("{}[{}]", field.get_name(), idx);
+                field.set_name(name);
+                items.push(field);
+            }
+        }
+        match &self.collect {
+            Some(name) => out.push(DataField::from_arr(name.to_string(), items)),
+            None => out.append(&mut items),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::WplEvaluator;
+    use orion_error::TestAssert;
+    use wp_model_core::model::{DataRecord, Value};
+
+    fn digit(rec: &DataRecord, name: &str) -> Option<i64> {
+        match rec.field(name).map(|f| f.get_value()) {
+            Some(Value::Digit(v)) => Some(*v),
+            _ => None,
+        }
+    }
+
+    fn parse(rule: &str, data: &str) -> Option<(DataRecord, String)> {
+        let ppl = WplEvaluator::from_code(rule).assert();
+        ppl.proc(data, 0).ok()
+    }
+
+    #[test]
+    fn test_repeat_index_suffix() {
+        let (rec, residue) = parse(
+            "rule r { repeat(1..)(ip:addr), (chars:tail) }",
+            "10.0.0.1 10.0.0.2 10.0.0.3 end",
+        )
+        .unwrap();
+        assert_eq!(residue, "");
+        assert!(rec.field("addr[0]").is_some());
+        assert!(rec.field("addr[2]").is_some());
+        assert!(rec.field("addr[3]").is_none());
+        assert!(matches!(
+            rec.field("tail").map(|f| f.get_value()),
+            Some(Value::Chars(v)) if v.as_str() == "end"
+        ));
+    }
+
+    #[test]
+    fn test_repeat_bounds() {
+        // 达到 max 后停止，剩余交给后续组
+        let (rec, _) = parse("rule r { repeat(1..2)(digit:n), (chars:rest) }", "1 2 3").unwrap();
+        assert_eq!(digit(&rec, "n[1]"), Some(2));
+        assert!(rec.field("n[2]").is_none());
+        assert!(matches!(
+            rec.field("rest").map(|f| f.get_value()),
+            Some(Value::Chars(v)) if v.as_str() == "3"
+        ));
+
+        // 不足 min 次失败
+        assert!(parse("rule r { repeat(2..)(digit:n), (chars:rest) }", "1 x").is_none());
+        // min 为 0 时允许一次都不匹配
+        let (rec, _) = parse("rule r { repeat(..3)(digit:n), (chars:rest) }", "x").unwrap();
+        assert!(rec.field("n[0]").is_none());
+        assert!(rec.field("rest").is_some());
+    }
+
+    #[test]
+    fn test_repeat_backtrack() {
+        // 贪婪匹配吞掉了末尾字段，需回退一次让出给后续组
+        let (rec, residue) =
+            parse("rule r { repeat(1..)(digit:n), (digit:last) }", "1 2 3").unwrap();
+        assert_eq!(residue, "");
+        assert_eq!(digit(&rec, "n[0]"), Some(1));
+        assert_eq!(digit(&rec, "n[1]"), Some(2));
+        assert!(rec.field("n[2]").is_none());
+        assert_eq!(digit(&rec, "last"), Some(3));
+        assert_eq!(rec.items.len(), 3);
+
+        // 多字段序列：不完整的迭代整体回退，不残留字段
+        let (rec, _) = parse(
+            "rule r { repeat(1..)(chars:k, digit:v), (chars:trailer) }",
+            "a 1 b 2 c",
+        )
+        .unwrap();
+        assert_eq!(digit(&rec, "v[1]"), Some(2));
+        assert!(rec.field("k[2]").is_none());
+        assert!(matches!(
+            rec.field("trailer").map(|f| f.get_value()),
+            Some(Value::Chars(v)) if v.as_str() == "c"
+        ));
+    }
+
+    #[test]
+    fn test_repeat_collect() {
+        let (rec, _) = parse(
+            "rule r { repeat(1.., ips)(ip), (chars:tail) }",
+            "10.0.0.1 10.0.0.2 end",
+        )
+        .unwrap();
+        let Some(Value::Array(items)) = rec.field("ips").map(|f| f.get_value()) else {
+            panic!("ips should be array");
+        };
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].get_name(), "ip[1]");
+        assert!(rec.field("ip[0]").is_none());
+    }
+}
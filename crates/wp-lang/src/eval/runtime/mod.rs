@@ -2,5 +2,6 @@ pub mod field;
 pub mod field_pipe;
 pub mod group;
 pub mod pipe_exec;
+pub mod profile;
 pub mod subunit;
 pub mod vm_unit;
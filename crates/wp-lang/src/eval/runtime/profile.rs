@@ -0,0 +1,320 @@
+//! 解析剖析：按规则、按字段解析器采样统计耗时
+//!
+//! 规则级耗时按一次 `WplEvaluator::proc` 计（含预处理管道），未命中的尝试单独统计；
+//! 字段级耗时为包含时间（含子字段与字段管道），仅在被采样的解析中、且只对开启剖析的
+//! 求值器的字段记录；未开启剖析时字段解析不做任何检查。
+
+use serde::Serialize;
+use smol_str::SmolStr;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+thread_local! {
+    // 当前线程正在采样的一次解析中收集的字段耗时
+    static ACTIVE: RefCell<Option<Vec<FieldSample>>> = const { RefCell::new(None) };
+}
+
+struct FieldSample {
+    label: String,
+    nanos: u64,
+    ok: bool,
+}
+
+pub(crate) fn is_active() -> bool {
+    ACTIVE.with(|x| x.borrow().is_some())
+}
+
+pub(crate) fn record_field<F: FnOnce() -> String>(label: F, cost: Duration, ok: bool) {
+    ACTIVE.with(|x| {
+        if let Some(samples) = x.borrow_mut().as_mut() {
+            samples.push(FieldSample {
+                label: label(),
+                nanos: cost.as_nanos() as u64,
+                ok,
+            });
+        }
+    })
+}
+
+#[derive(Default)]
+struct FieldStat {
+    calls: u64,
+    fails: u64,
+    nanos: u64,
+}
+
+#[derive(Default)]
+struct RuleStat {
+    hits: u64,
+    misses: u64,
+    hit_nanos: u64,
+    miss_nanos: u64,
+    fields: BTreeMap<String, FieldStat>,
+}
+
+/// 采样剖析器，可在多个规则、多个线程间共享；每个规则每 `sample_every` 次解析采样一次
+pub struct ParseProfiler {
+    sample_every: u64,
+    rules: Mutex<BTreeMap<SmolStr, RuleStat>>,
+}
+
+impl ParseProfiler {
+    pub fn new(sample_every: u64) -> Self {
+        Self {
+            sample_every: sample_every.max(1),
+            rules: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn sample_every(&self) -> u64 {
+        self.sample_every
+    }
+
+    fn record(&self, rule: &SmolStr, ok: bool, cost: Duration, fields: Vec<FieldSample>) {
+        let mut rules = self.rules.lock().expect("profile lock");
+        let stat = rules.entry(rule.clone()).or_default();
+        let nanos = cost.as_nanos() as u64;
+        if ok {
+            stat.hits += 1;
+            stat.hit_nanos += nanos;
+        } else {
+            stat.misses += 1;
+            stat.miss_nanos += nanos;
+        }
+        for sample in fields {
+            let field = stat.fields.entry(sample.label).or_default();
+            field.calls += 1;
+            field.nanos += sample.nanos;
+            if !sample.ok {
+                field.fails += 1;
+            }
+        }
+    }
+
+    /// 生成按总耗时降序排列的报告
+    pub fn report(&self) -> ProfileReport {
+        let rules = self.rules.lock().expect("profile lock");
+        let mut report: Vec<RuleProfile> = rules
+            .iter()
+            .map(|(name, stat)| {
+                let mut fields: Vec<FieldProfile> = stat
+                    .fields
+                    .iter()
+                    .map(|(label, f)| FieldProfile {
+                        field: label.clone(),
+                        calls: f.calls,
+                        fails: f.fails,
+                        total_ns: f.nanos,
+                        avg_ns: avg(f.nanos, f.calls),
+                    })
+                    .collect();
+                fields.sort_by(|a, b| b.total_ns.cmp(&a.total_ns));
+                RuleProfile {
+                    rule: name.to_string(),
+                    hits: stat.hits,
+                    misses: stat.misses,
+                    total_ns: stat.hit_nanos + stat.miss_nanos,
+                    avg_hit_ns: avg(stat.hit_nanos, stat.hits),
+                    avg_miss_ns: avg(stat.miss_nanos, stat.misses),
+                    miss_ns: stat.miss_nanos,
+                    fields,
+                }
+            })
+            .collect();
+        report.sort_by(|a, b| b.total_ns.cmp(&a.total_ns));
+        ProfileReport {
+            sample_every: self.sample_every,
+            rules: report,
+        }
+    }
+}
+
+fn avg(total: u64, cnt: u64) -> u64 {
+    if cnt == 0 { 0 } else { total / cnt }
+}
+
+/// 挂在 `WplEvaluator` 上的采样入口；每个规则独立计数，避免多规则依次尝试时采样相互错位
+#[derive(Clone)]
+pub struct ProfileHook {
+    rule: SmolStr,
+    tick: Arc<AtomicU64>,
+    profiler: Arc<ParseProfiler>,
+}
+
+impl ProfileHook {
+    pub fn new(rule: &str, profiler: Arc<ParseProfiler>) -> Self {
+        Self {
+            rule: SmolStr::from(rule),
+            tick: Arc::new(AtomicU64::new(0)),
+            profiler,
+        }
+    }
+
+    pub(crate) fn should_sample(&self) -> bool {
+        self.tick.fetch_add(1, Ordering::Relaxed) % self.profiler.sample_every == 0
+    }
+
+    /// 计时执行一次解析，并记录其间字段解析器的耗时
+    pub(crate) fn measure<T, E, F>(&self, parse: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+    {
+        let outer = ACTIVE.with(|x| x.borrow_mut().replace(Vec::new()));
+        let begin = Instant::now();
+        let result = parse();
+        let cost = begin.elapsed();
+        let fields = ACTIVE.with(|x| std::mem::replace(&mut *x.borrow_mut(), outer));
+        self.profiler
+            .record(&self.rule, result.is_ok(), cost, fields.unwrap_or_default());
+        result
+    }
+}
+
+/// 字段解析器耗时（包含时间）
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldProfile {
+    pub field: String,
+    pub calls: u64,
+    pub fails: u64,
+    pub total_ns: u64,
+    pub avg_ns: u64,
+}
+
+/// 规则耗时；`misses`/`miss_ns` 为未命中尝试的代价
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleProfile {
+    pub rule: String,
+    pub hits: u64,
+    pub misses: u64,
+    pub total_ns: u64,
+    pub miss_ns: u64,
+    pub avg_hit_ns: u64,
+    pub avg_miss_ns: u64,
+    pub fields: Vec<FieldProfile>,
+}
+
+/// 剖析报告：规则、字段均按总耗时降序；计数均为采样次数
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileReport {
+    pub sample_every: u64,
+    pub rules: Vec<RuleProfile>,
+}
+
+impl ProfileReport {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+fn us(nanos: u64) -> f64 {
+    nanos as f64 / 1000.0
+}
+
+impl Display for ProfileReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "sampled 1/{}", self.sample_every)?;
+        writeln!(
+            f,
+            "{:<40} {:>8} {:>8} {:>12} {:>12} {:>12}",
+            "rule / field", "hit", "miss", "avg hit(us)", "avg miss(us)", "total(us)"
+        )?;
+        for rule in &self.rules {
+            writeln!(
+                f,
+                "{:<40} {:>8} {:>8} {:>12.1} {:>12.1} {:>12.1}",
+                rule.rule,
+                rule.hits,
+                rule.misses,
+                us(rule.avg_hit_ns),
+                us(rule.avg_miss_ns),
+                us(rule.total_ns)
+            )?;
+            for field in &rule.fields {
+                writeln!(
+                    f,
+                    "  {:<38} {:>8} {:>8} {:>12.1} {:>12} {:>12.1}",
+                    field.field,
+                    field.calls - field.fails,
+                    field.fails,
+                    us(field.avg_ns),
+                    "",
+                    us(field.total_ns)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WplEvaluator;
+
+    #[test]
+    fn test_profile_rules_and_fields() {
+        let profiler = Arc::new(ParseProfiler::new(1));
+        let kv = WplEvaluator::from_code("rule kv { (ip:sip, digit:port, json:body) }")
+            .unwrap()
+            .with_profile(ProfileHook::new("demo/kv", profiler.clone()));
+        let num = WplEvaluator::from_code("rule num { (digit:code) }")
+            .unwrap()
+            .with_profile(ProfileHook::new("demo/num", profiler.clone()));
+
+        for line in ["1.2.3.4 80 {\"a\":1}", "200", "10.0.0.1 443 {\"b\":[1,2]}"] {
+            if kv.proc(line, 0).is_err() {
+                assert!(num.proc(line, 0).is_ok());
+            }
+        }
+        let report = profiler.report();
+        assert_eq!(report.rules.len(), 2);
+        let kv = report.rules.iter().find(|r| r.rule == "demo/kv").unwrap();
+        assert_eq!((kv.hits, kv.misses), (2, 1));
+        let sip = kv.fields.iter().find(|f| f.field == "#1 ip:sip").unwrap();
+        assert_eq!((sip.calls, sip.fails), (3, 1));
+        assert!(kv.fields.iter().any(|f| f.field == "#3 json:body"));
+        let num = report.rules.iter().find(|r| r.rule == "demo/num").unwrap();
+        assert_eq!((num.hits, num.misses), (1, 0));
+        assert!(
+            report
+                .rules
+                .windows(2)
+                .all(|x| x[0].total_ns >= x[1].total_ns)
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json["sample_every"], 1);
+        assert!(report.to_string().contains("demo/kv"));
+        assert!(!is_active());
+    }
+
+    #[test]
+    fn test_profile_sampling() {
+        let profiler = Arc::new(ParseProfiler::new(3));
+        let eval = WplEvaluator::from_code("rule r { (digit) }")
+            .unwrap()
+            .with_profile(ProfileHook::new("r", profiler.clone()));
+        for _ in 0..7 {
+            eval.proc("1", 0).unwrap();
+        }
+        // 第 1、4、7 次被采样
+        assert_eq!(profiler.report().rules[0].hits, 3);
+        assert_eq!(ParseProfiler::new(0).sample_every(), 1);
+    }
+
+    #[test]
+    fn test_unprofiled_fields_skip_hook() {
+        // 未开启剖析的求值器，其字段即使在其他规则的采样期间解析也不记录
+        let profiler = Arc::new(ParseProfiler::new(1));
+        let plain = WplEvaluator::from_code("rule r { (digit, chars) }").unwrap();
+        let hook = ProfileHook::new("outer", profiler.clone());
+        hook.measure(|| plain.proc("1 a", 0)).unwrap();
+        let report = profiler.report();
+        assert_eq!(report.rules[0].hits, 1);
+        assert!(report.rules[0].fields.is_empty());
+    }
+}
@@ -6,6 +6,7 @@ use crate::eval::builtins::{self, PipeLineResult, raw_to_utf8_string};
 use crate::eval::runtime::field::FieldEvalUnit;
 use crate::eval::runtime::field_pipe::PipeEnum;
 use crate::eval::runtime::group::WplEvalGroup;
+use crate::eval::runtime::profile::ProfileHook;
 use std::borrow::Cow;
 use wp_parse_api::{PipeHold, RawData, WparseError, WparseReason};
 
@@ -15,7 +16,7 @@ use anyhow::Result;
 use orion_error::{ErrorWith, ToStructError, UvsDataFrom};
 use orion_overload::new::New3;
 use wp_log::{debug_data, trace_data};
-use wp_model_core::model::DataRecord;
+use wp_model_core::model::{DataField, DataRecord};
 use wp_parser::Parser;
 use wp_parser::WResult as ModalResult;
 
@@ -56,6 +57,7 @@ impl IntoRawData for &str {
 pub struct WplEvaluator {
     preorder: Vec<PipeHold>,
     group_units: Vec<WplEvalGroup>,
+    profile: Option<ProfileHook>,
 }
 unsafe impl Send for WplEvaluator {}
 
@@ -90,11 +92,30 @@ impl WplEvaluator {
         Ok(target)
     }
 
+    /// 开启采样剖析，记录本规则及其字段解析器的耗时
+    pub fn with_profile(mut self, hook: ProfileHook) -> Self {
+        self.profile = Some(hook);
+        self.group_units
+            .iter_mut()
+            .flat_map(|group| group.field_units.iter_mut())
+            .for_each(|unit| unit.set_profiled());
+        self
+    }
+
     pub fn proc<D>(&self, data: D, oth_suc_len: usize) -> DataResult
     where
         D: IntoRawData,
     {
-        let mut working_raw: RawData = data.into_raw();
+        match &self.profile {
+            Some(hook) if hook.should_sample() => {
+                hook.measure(|| self.proc_raw(data.into_raw(), oth_suc_len))
+            }
+            _ => self.proc_raw(data.into_raw(), oth_suc_len),
+        }
+    }
+
+    fn proc_raw(&self, data: RawData, oth_suc_len: usize) -> DataResult {
+        let mut working_raw = data;
         if !self.preorder.is_empty() {
             working_raw = self.pipe_proc(working_raw)?;
         }
@@ -229,15 +250,28 @@ impl WplEvaluator {
         let mut result = Vec::with_capacity(100);
 
         let sep = WplSep::default();
-        for group_unit in self.group_units.iter() {
-            match group_unit.proc(&sep, data, &mut result) {
-                Ok(_) => {}
-                Err(e) => {
-                    return Err(e);
-                }
+        Self::parse_group_seq(&self.group_units, &sep, data, &mut result)?;
+        Ok(DataRecord::from(result))
+    }
+
+    // repeat 组需要回溯：其后的组作为后续解析交给 repeat 组驱动
+    fn parse_group_seq(
+        groups: &[WplEvalGroup],
+        sep: &WplSep,
+        data: &mut &str,
+        out: &mut Vec<DataField>,
+    ) -> ModalResult<()> {
+        for (idx, group_unit) in groups.iter().enumerate() {
+            if let WplGroupType::Repeat(_) = group_unit.rule {
+                let rest = &groups[idx + 1..];
+                let mut next = |data: &mut &str, out: &mut Vec<DataField>| {
+                    Self::parse_group_seq(rest, sep, data, out)
+                };
+                return group_unit.proc_then(sep, data, out, &mut next);
             }
+            group_unit.proc(sep, data, out)?;
         }
-        Ok(DataRecord::from(result))
+        Ok(())
     }
 }
 
@@ -1,4 +1,5 @@
 use crate::ast::WplField;
+use crate::ast::find_sep;
 use winnow::ascii::multispace0;
 use winnow::combinator::{delimited, fail};
 use winnow::error::{StrContext, StrContextValue};
@@ -31,6 +32,15 @@ impl WplField {
         s_end: &str,
         data: &mut &'a str,
     ) -> ModalResult<&'a str> {
+        // 快路径：memchr/memmem 定位结束符；失败时走组合子生成错误信息
+        let s: &'a str = *data;
+        if let Some(body) = s.strip_prefix(s_beg)
+            && let Some(idx) = find_sep(body, s_end)
+            && idx >= 1
+        {
+            *data = &body[idx + s_end.len()..];
+            return Ok(&body[..idx]);
+        }
         let take = delimited(literal(s_beg), take_until(1.., s_end), literal(s_end))
             .context(StrContext::Label("scope"))
             .context(StrContext::Expected(StrContextValue::Description(
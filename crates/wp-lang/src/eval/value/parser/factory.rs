@@ -1,6 +1,7 @@
 use orion_error::{ContextRecord, ErrorOwe, ErrorWith, WithContext};
 
-use crate::eval::value::parse_def::{Hold, ParserHold};
+use crate::ast::{WplExtType, WplField};
+use crate::eval::value::parse_def::{FieldParser, Hold, ParserHold};
 use crate::eval::value::parser::base::digit::{DigitP, FloatP};
 use crate::eval::value::parser::base::hex::HexDigitP;
 use crate::eval::value::parser::base::*;
@@ -8,7 +9,8 @@ use crate::eval::value::parser::compute::device::SnP;
 use crate::eval::value::parser::network::http;
 use crate::eval::value::parser::network::net::{IpNetP, IpPSR};
 use crate::eval::value::parser::physical::time::{
-    TimeCLF, TimeISOP, TimeP, TimeRFC2822, TimeRFC3339, TimeStampPSR,
+    TimeBsdP, TimeCLF, TimeEpochP, TimeFmtP, TimeISOP, TimeP, TimeRFC2822, TimeRFC3339,
+    TimeStampPSR,
 };
 use crate::eval::value::parser::protocol::array::ArrayP;
 use crate::eval::value::parser::protocol::base64::Base64P;
@@ -17,10 +19,53 @@ use crate::eval::value::parser::protocol::json_exact::ExactJsonP;
 use crate::eval::value::parser::protocol::keyval::KeyValP;
 use crate::eval::value::parser::protocol::kvarr::KvArrP;
 use crate::eval::value::parser::protocol::proto_text::ProtoTextP;
+use crate::eval::value::parser::protocol::xml::XmlP;
 use crate::parser::error::{WplCodeError, WplCodeReason, WplCodeResult};
-use wp_model_core::model::DataType;
+use wp_model_core::model::{DataType, FNameStr};
 
 use super::auto::CombinedParser;
+use super::plugin::WasmFieldP;
+type SimpleBuilder = fn() -> ParserHold;
+
+fn simple<P>() -> ParserHold
+where
+    P: FieldParser + Default + Send + Sync + 'static,
+{
+    Hold::new(P::default())
+}
+
+// 基础类型注册表：类型与解析器一一对应
+const SIMPLE_TYPES: &[(DataType, SimpleBuilder)] = &[
+    (DataType::Bool, simple::<BoolP>),
+    (DataType::Chars, simple::<CharsP>),
+    (DataType::Symbol, simple::<SymbolP>),
+    (DataType::PeekSymbol, simple::<PeekSymbolP>),
+    (DataType::Digit, simple::<DigitP>),
+    (DataType::Float, simple::<FloatP>),
+    (DataType::Ignore, simple::<IgnoreP>),
+    (DataType::Time, simple::<TimeP>),
+    (DataType::TimeCLF, simple::<TimeCLF>),
+    (DataType::TimeISO, simple::<TimeISOP>),
+    (DataType::TimeRFC3339, simple::<TimeRFC3339>),
+    (DataType::TimeRFC2822, simple::<TimeRFC2822>),
+    (DataType::TimeTIMESTAMP, simple::<TimeStampPSR>),
+    (DataType::IP, simple::<IpPSR>),
+    (DataType::IpNet, simple::<IpNetP>),
+    (DataType::Port, simple::<DigitP>),
+    (DataType::SN, simple::<SnP>),
+    (DataType::Hex, simple::<HexDigitP>),
+    (DataType::Base64, simple::<Base64P>),
+    (DataType::KvArr, simple::<KvArrP>),
+    (DataType::KV, simple::<KeyValP>),
+    (DataType::Json, simple::<JsonP>),
+    (DataType::ExactJson, simple::<ExactJsonP>),
+    (DataType::HttpRequest, simple::<http::RequestP>),
+    (DataType::HttpStatus, simple::<http::StatusP>),
+    (DataType::HttpAgent, simple::<http::AgentP>),
+    (DataType::HttpMethod, simple::<http::MethodP>),
+    (DataType::ProtoText, simple::<ProtoTextP>),
+];
+
 #[derive(Default)]
 pub struct ParserFactory {}
 
@@ -39,39 +84,52 @@ impl ParserFactory {
     }
 
     pub fn create_simple(meta: &DataType) -> Option<ParserHold> {
-        match *meta {
-            DataType::Bool => Some(Hold::new(BoolP::default())),
-            DataType::Chars => Some(Hold::new(CharsP::default())),
-            DataType::Symbol => Some(Hold::new(SymbolP::default())),
-            DataType::PeekSymbol => Some(Hold::new(PeekSymbolP::default())),
-            DataType::Digit => Some(Hold::new(DigitP::default())),
-            DataType::Float => Some(Hold::new(FloatP::default())),
-            DataType::Ignore => Some(Hold::new(IgnoreP::default())),
-            DataType::Time => Some(Hold::new(TimeP::default())),
-            DataType::TimeCLF => Some(Hold::new(TimeCLF::default())),
-            DataType::TimeISO => Some(Hold::new(TimeISOP::default())),
-            DataType::TimeRFC3339 => Some(Hold::new(TimeRFC3339::default())),
-            DataType::TimeRFC2822 => Some(Hold::new(TimeRFC2822::default())),
-            DataType::TimeTIMESTAMP => Some(Hold::new(TimeStampPSR::default())),
-            DataType::IP => Some(Hold::new(IpPSR::default())),
-            DataType::IpNet => Some(Hold::new(IpNetP::default())),
-            DataType::Port => Some(Hold::new(DigitP::default())),
-            DataType::SN => Some(Hold::new(SnP::default())),
-            DataType::Hex => Some(Hold::new(HexDigitP::default())),
-            DataType::Base64 => Some(Hold::new(Base64P::default())),
-            DataType::KvArr => Some(Hold::new(KvArrP::default())),
-            DataType::KV => Some(Hold::new(KeyValP::default())),
-            DataType::Json => Some(Hold::new(JsonP::default())),
-            DataType::ExactJson => Some(Hold::new(ExactJsonP::default())),
-            DataType::HttpRequest => Some(Hold::new(http::RequestP::default())),
-            DataType::HttpStatus => Some(Hold::new(http::StatusP::default())),
-            DataType::HttpAgent => Some(Hold::new(http::AgentP::default())),
-            DataType::HttpMethod => Some(Hold::new(http::MethodP::default())),
-            DataType::ProtoText => Some(Hold::new(ProtoTextP::default())),
-            _ => None,
+        SIMPLE_TYPES
+            .iter()
+            .find(|(ty, _)| ty == meta)
+            .map(|(_, build)| build())
+    }
+
+    /// 已注册的全部字段类型名：`auto`、基础类型、`array` 与 WPL 扩展类型
+    pub fn type_names() -> Vec<FNameStr> {
+        std::iter::once(FNameStr::from(DataType::Auto.static_name()))
+            .chain(
+                SIMPLE_TYPES
+                    .iter()
+                    .map(|(ty, _)| FNameStr::from(ty.static_name())),
+            )
+            .chain(std::iter::once(FNameStr::from("array")))
+            .chain(WplExtType::ALL.iter().map(|ext| FNameStr::from(ext.name())))
+            .collect()
+    }
+
+    /// 扩展类型解析器，`args` 为类型参数（如 `time/epoch(ms)` 中的 `ms`）
+    pub fn create_ext(ext: WplExtType, args: Option<&str>) -> WplCodeResult<ParserHold> {
+        let mut ctx = WithContext::want("create parser");
+        ctx.record("meta", ext.name());
+        match ext {
+            WplExtType::Xml => Ok(Hold::new(XmlP::new(false))),
+            WplExtType::XmlLocal => Ok(Hold::new(XmlP::new(true))),
+            WplExtType::TimeBsd => Ok(Hold::new(TimeBsdP::from_args(args).with(&ctx)?)),
+            WplExtType::TimeEpoch => Ok(Hold::new(TimeEpochP::from_args(args).with(&ctx)?)),
+            WplExtType::Wasm => Ok(Hold::new(WasmFieldP::from_args(args).with(&ctx)?)),
         }
     }
 
+    /// 按字段配置创建解析器：WPL 扩展类型优先，带格式参数的 `time(fmt=...)` 次之，
+    /// 其余按 `meta_type` 创建
+    pub fn create_field(conf: &WplField) -> WplCodeResult<ParserHold> {
+        if let Some(ext) = conf.ext_type() {
+            return Self::create_ext(ext, conf.content.as_deref());
+        }
+        if let (DataType::Time, Some(args)) = (conf.meta_type(), conf.content.as_deref()) {
+            let mut ctx = WithContext::want("create parser");
+            ctx.record("meta", "time(fmt)");
+            return Ok(Hold::new(TimeFmtP::from_args(args).with(&ctx)?));
+        }
+        Self::create(conf.meta_type())
+    }
+
     fn create_l1(meta: &DataType) -> WplCodeResult<ParserHold> {
         let mut ctx = WithContext::want("create parser");
         ctx.record("meta", meta.to_string());
@@ -102,3 +160,21 @@ impl ParserFactory {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::datatype::meta_of_name;
+
+    #[test]
+    fn test_type_names_resolve() {
+        let names = ParserFactory::type_names();
+        for name in ["auto", "chars", "ip", "http/request", "time/bsd", "wasm"] {
+            assert!(names.iter().any(|x| x.as_str() == name), "{}", name);
+        }
+        // 列出的类型均可由 WPL 解析
+        for name in names.iter().filter(|x| x.as_str() != "array") {
+            assert!(meta_of_name(name.as_str()).is_some(), "{}", name);
+        }
+    }
+}
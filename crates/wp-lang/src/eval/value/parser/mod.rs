@@ -6,5 +6,6 @@ pub mod compute;
 mod factory;
 pub mod network;
 pub mod physical;
+pub mod plugin;
 mod prelude;
 pub mod protocol;
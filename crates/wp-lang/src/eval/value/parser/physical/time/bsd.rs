@@ -0,0 +1,294 @@
+use super::common::TimeArgs;
+use crate::eval::runtime::field::FieldEvalUnit;
+use crate::eval::value::parse_def::PatternParser;
+use crate::generator::{FieldGenConf, GenChannel};
+use crate::parser::error::{WplCodeError, WplCodeReason, WplCodeResult};
+use crate::types::AnyResult;
+use chrono::{Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use winnow::Parser as _;
+use winnow::ascii::{alpha1, digit1, space0, space1};
+use winnow::combinator::{fail, opt, preceded};
+use winnow::token::take_while;
+use wp_model_core::model::DataField;
+use wp_model_core::model::FNameStr;
+use wp_parser::WResult;
+use wp_parser::symbol::ctx_desc;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BsdZone {
+    Local,
+    Fixed(FixedOffset),
+}
+
+/// `time/bsd`：BSD syslog 时间 `Mmm dd hh:mm:ss`（RFC3164），报文中不含年份
+///
+/// - `tz`：发送端时区（`local`、`utc`、`+08:00`），用于计算推断年份时的“当前时间”，默认 `local`；
+/// - `rollover`：允许时间超前当前时间的天数，超出则视为上一年（跨年日志），默认 1，`off` 关闭推断。
+///
+/// 输出与其他时间类型一致，为报文书写的本地时间。
+pub struct TimeBsdP {
+    tz: BsdZone,
+    rollover: Option<i64>,
+}
+
+impl Default for TimeBsdP {
+    fn default() -> Self {
+        Self {
+            tz: BsdZone::Local,
+            rollover: Some(1),
+        }
+    }
+}
+
+impl TimeBsdP {
+    pub fn from_args(content: Option<&str>) -> WplCodeResult<Self> {
+        let mut parser = Self::default();
+        let Some(content) = content else {
+            return Ok(parser);
+        };
+        let args = TimeArgs::parse(content)?;
+        args.check_keys(&["tz", "rollover"])?;
+        if let Some(tz) = args.get("tz") {
+            parser.tz = parse_zone(tz).ok_or_else(|| {
+                WplCodeError::from(WplCodeReason::Syntax(format!("bad time/bsd tz: {}", tz)))
+            })?;
+        }
+        if let Some(days) = args.get("rollover") {
+            parser.rollover = match days {
+                "off" | "false" => None,
+                _ => Some(days.parse::<i64>().map_err(|_| {
+                    WplCodeError::from(WplCodeReason::Syntax(format!(
+                        "bad time/bsd rollover: {}",
+                        days
+                    )))
+                })?),
+            };
+        }
+        Ok(parser)
+    }
+
+    fn now(&self) -> NaiveDateTime {
+        match self.tz {
+            BsdZone::Local => chrono::Local::now().naive_local(),
+            BsdZone::Fixed(tz) => chrono::Utc::now().with_timezone(&tz).naive_local(),
+        }
+    }
+
+    /// 以 `now` 所在年份补全日期；超前 `now` 超过 rollover 天时回退到上一年
+    pub fn infer_year(
+        &self,
+        now: NaiveDateTime,
+        month: u32,
+        day: u32,
+        time: NaiveTime,
+    ) -> Option<NaiveDateTime> {
+        let this_year = NaiveDate::from_ymd_opt(now.year(), month, day).map(|d| d.and_time(time));
+        match (self.rollover, this_year) {
+            (Some(days), Some(ndt)) if ndt <= now + Duration::days(days) => Some(ndt),
+            (None, Some(ndt)) => Some(ndt),
+            // 超前过多（或 2 月 29 日在今年不存在）：取上一年
+            _ => NaiveDate::from_ymd_opt(now.year() - 1, month, day).map(|d| d.and_time(time)),
+        }
+    }
+}
+
+impl TimeBsdP {
+    // 以给定的“当前时间”推断年份
+    fn parse_at(&self, data: &mut &str, now: NaiveDateTime) -> WResult<NaiveDateTime> {
+        space0.parse_next(data)?;
+        let (month, day, time) = parse_bsd_time
+            .context(ctx_desc("<time/bsd>::= Mmm dd hh:mm:ss"))
+            .parse_next(data)?;
+        match self.infer_year(now, month, day, time) {
+            Some(ndt) => Ok(ndt),
+            None => fail
+                .context(ctx_desc("<time/bsd> bad date"))
+                .parse_next(data),
+        }
+    }
+}
+
+fn parse_zone(tz: &str) -> Option<BsdZone> {
+    match tz {
+        "local" => Some(BsdZone::Local),
+        "utc" | "UTC" | "Z" => Some(BsdZone::Fixed(FixedOffset::east_opt(0)?)),
+        _ => {
+            let (sign, rest) = match tz.as_bytes().first()? {
+                b'+' => (1, &tz[1..]),
+                b'-' => (-1, &tz[1..]),
+                _ => return None,
+            };
+            let digits: String = rest.chars().filter(|c| *c != ':').collect();
+            if digits.len() != 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            let hh: i32 = digits[..2].parse().ok()?;
+            let mm: i32 = digits[2..].parse().ok()?;
+            FixedOffset::east_opt(sign * (hh * 3600 + mm * 60)).map(BsdZone::Fixed)
+        }
+    }
+}
+
+// Mmm dd hh:mm:ss[.frac]，日期不足两位时以空格补齐（`Mar  7`）
+fn parse_bsd_time(data: &mut &str) -> WResult<(u32, u32, NaiveTime)> {
+    let month = alpha1
+        .verify_map(|m: &str| MONTHS.iter().position(|x| *x == m).map(|i| i as u32 + 1))
+        .parse_next(data)?;
+    space1.parse_next(data)?;
+    let day = digit1.try_map(str::parse::<u32>).parse_next(data)?;
+    space1.parse_next(data)?;
+    let (h, _, m, _, s) = (
+        digit1.try_map(str::parse::<u32>),
+        ':',
+        digit1.try_map(str::parse::<u32>),
+        ':',
+        digit1.try_map(str::parse::<u32>),
+    )
+        .parse_next(data)?;
+    let frac = opt(preceded(
+        '.',
+        take_while(1..=9, |c: char| c.is_ascii_digit()),
+    ))
+    .parse_next(data)?;
+    let nano = frac
+        .map(|f: &str| f.parse::<u32>().unwrap_or(0) * 10u32.pow(9 - f.len() as u32))
+        .unwrap_or(0);
+    match NaiveTime::from_hms_nano_opt(h, m, s, nano) {
+        Some(time) => Ok((month, day, time)),
+        None => fail
+            .context(ctx_desc("<time/bsd> bad time"))
+            .parse_next(data),
+    }
+}
+
+impl PatternParser for TimeBsdP {
+    fn pattern_parse(
+        &self,
+        _: &FieldEvalUnit,
+        _: &crate::ast::WplSep,
+        data: &mut &str,
+        name: FNameStr,
+        out: &mut Vec<DataField>,
+    ) -> WResult<()> {
+        let ndt = self.parse_at(data, self.now())?;
+        out.push(DataField::from_time(name, ndt));
+        Ok(())
+    }
+
+    fn patten_gen(
+        &self,
+        gnc: &mut GenChannel,
+        f_conf: &crate::ast::WplField,
+        _g_conf: Option<&FieldGenConf>,
+    ) -> AnyResult<DataField> {
+        let time = super::gen_rand_time(gnc);
+        Ok(DataField::from_chars(
+            f_conf.safe_name().to_string(),
+            time.format("%b %e %H:%M:%S").to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::WplField;
+    use crate::eval::value::test_utils::ParserTUnit;
+    use orion_error::TestAssert;
+    use wp_model_core::model::DataType;
+
+    fn ymd_hms(y: i32, mo: u32, d: u32, h: u32, mi: u32, s: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, mo, d)
+            .unwrap()
+            .and_hms_opt(h, mi, s)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_time_bsd_parse() {
+        let conf = WplField::try_parse("time/bsd:ts").assert();
+        let mut data = "Mar  7 08:09:10 host sshd[12]: ok";
+        let fields = ParserTUnit::from_auto(conf).verify_parse_suc_meta(&mut data, DataType::Time);
+        assert_eq!(fields[0].get_name(), "ts");
+
+        // 固定时钟：年份取当前年，跨年时回退到上一年
+        let bsd = TimeBsdP::default();
+        let now = ymd_hms(2025, 6, 1, 12, 0, 0);
+        let mut data = "Mar  7 08:09:10.250 host";
+        let ndt = bsd.parse_at(&mut data, now).assert();
+        assert_eq!(
+            ndt,
+            ymd_hms(2025, 3, 7, 8, 9, 10) + Duration::milliseconds(250)
+        );
+        assert_eq!(data, " host");
+
+        let now = ymd_hms(2026, 1, 1, 0, 0, 3);
+        let mut data = "Dec 31 23:59:59";
+        assert_eq!(
+            bsd.parse_at(&mut data, now).assert(),
+            ymd_hms(2025, 12, 31, 23, 59, 59)
+        );
+        let mut data = "Jan  1 00:00:01";
+        assert_eq!(
+            bsd.parse_at(&mut data, now).assert(),
+            ymd_hms(2026, 1, 1, 0, 0, 1)
+        );
+        // 2 月 29 日在当年不存在时回退到上一年
+        let mut data = "Feb 29 10:00:00";
+        assert_eq!(
+            bsd.parse_at(&mut data, ymd_hms(2025, 3, 1, 0, 0, 0))
+                .assert(),
+            ymd_hms(2024, 2, 29, 10, 0, 0)
+        );
+
+        let conf = WplField::try_parse("time/bsd").assert();
+        let mut data = "Foo  7 08:09:10";
+        ParserTUnit::from_auto(conf).verify_parse_fail(&mut data);
+    }
+
+    #[test]
+    fn test_time_bsd_year_rollover() {
+        let now = ymd_hms(2025, 1, 1, 0, 5, 0);
+        let time = NaiveTime::from_hms_opt(23, 59, 58).unwrap();
+        let bsd = TimeBsdP::default();
+        assert_eq!(
+            bsd.infer_year(now, 12, 31, time),
+            Some(ymd_hms(2024, 12, 31, 23, 59, 58))
+        );
+        assert_eq!(
+            bsd.infer_year(now, 1, 1, NaiveTime::from_hms_opt(0, 6, 0).unwrap()),
+            Some(ymd_hms(2025, 1, 1, 0, 6, 0))
+        );
+        let no_roll = TimeBsdP::from_args(Some("rollover=off")).assert();
+        assert_eq!(
+            no_roll.infer_year(now, 12, 31, time),
+            Some(ymd_hms(2025, 12, 31, 23, 59, 58))
+        );
+    }
+
+    #[test]
+    fn test_time_bsd_args() {
+        let bsd = TimeBsdP::from_args(Some(r#"tz="+08:00", rollover=3"#)).assert();
+        assert_eq!(
+            bsd.tz,
+            BsdZone::Fixed(FixedOffset::east_opt(8 * 3600).unwrap())
+        );
+        assert_eq!(bsd.rollover, Some(3));
+        assert_eq!(
+            TimeBsdP::from_args(Some("tz=utc")).assert().tz,
+            BsdZone::Fixed(FixedOffset::east_opt(0).unwrap())
+        );
+        assert!(TimeBsdP::from_args(Some("tz=mars")).is_err());
+        assert!(TimeBsdP::from_args(Some("year=2024")).is_err());
+    }
+
+    #[test]
+    fn test_time_bsd_gen() {
+        let conf = WplField::try_parse("time/bsd").assert();
+        ParserTUnit::from_auto(conf).verify_gen_parse_suc();
+    }
+}
@@ -1,6 +1,10 @@
+use crate::parser::error::{WplCodeError, WplCodeReason, WplCodeResult};
+use crate::parser::utils::{quot_str, take_var_name};
 use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
 use winnow::Parser as _;
-use winnow::combinator::fail;
+use winnow::ascii::multispace0;
+use winnow::combinator::{alt, fail, opt, separated, terminated};
+use winnow::token::{one_of, take_while};
 use wp_parser::symbol::ctx_desc;
 
 // Shared helper to parse a fixed chrono::format::Item sequence and advance input
@@ -122,3 +126,75 @@ pub fn fast_apache_dt(s: &str) -> Option<(usize, NaiveDateTime)> {
     }
     Some((consumed, ndt))
 }
+
+/// 时间类型参数：`time(fmt="...")`、`time/bsd(tz="+08:00", rollover=1)`、`time/epoch(ms)`
+///
+/// 参数以逗号分隔，形如 `key=value`、`key:value` 或位置参数 `value`，值可用双引号包裹。
+pub struct TimeArgs<'a> {
+    items: Vec<(Option<&'a str>, &'a str)>,
+}
+
+impl<'a> TimeArgs<'a> {
+    pub fn parse(content: &'a str) -> WplCodeResult<Self> {
+        let mut input = content;
+        let items: Vec<(Option<&'a str>, &'a str)> = separated(0.., time_arg, ',')
+            .parse_next(&mut input)
+            .map_err(|_| Self::bad_args(content))?;
+        if !input.trim().is_empty() {
+            return Err(Self::bad_args(content));
+        }
+        Ok(Self { items })
+    }
+
+    /// 仅允许给定的参数名（位置参数不受限制）
+    pub fn check_keys(&self, allow: &[&str]) -> WplCodeResult<()> {
+        for (key, val) in &self.items {
+            if let Some(key) = key
+                && !allow.contains(key)
+            {
+                return Err(WplCodeError::from(WplCodeReason::Syntax(format!(
+                    "unknown time arg '{}={}', expect one of {:?}",
+                    key, val, allow
+                ))));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&'a str> {
+        self.items
+            .iter()
+            .find(|(k, _)| *k == Some(key))
+            .map(|(_, v)| *v)
+    }
+
+    pub fn positional(&self) -> Option<&'a str> {
+        self.items
+            .iter()
+            .find(|(k, _)| k.is_none())
+            .map(|(_, v)| *v)
+    }
+
+    fn bad_args(content: &str) -> WplCodeError {
+        WplCodeError::from(WplCodeReason::Syntax(format!(
+            "bad time args: ({})",
+            content
+        )))
+    }
+}
+
+fn time_arg<'a>(input: &mut &'a str) -> wp_parser::WResult<(Option<&'a str>, &'a str)> {
+    multispace0.parse_next(input)?;
+    let key = opt(terminated(
+        take_var_name,
+        (multispace0, one_of(['=', ':']), multispace0),
+    ))
+    .parse_next(input)?;
+    let val = alt((
+        quot_str,
+        take_while(1.., |c: char| c != ',' && !c.is_whitespace()),
+    ))
+    .parse_next(input)?;
+    multispace0.parse_next(input)?;
+    Ok((key, val))
+}
@@ -0,0 +1,215 @@
+use super::common::TimeArgs;
+use crate::eval::runtime::field::FieldEvalUnit;
+use crate::eval::value::parse_def::PatternParser;
+use crate::generator::{FieldGenConf, GenChannel};
+use crate::parser::error::{WplCodeError, WplCodeReason, WplCodeResult};
+use crate::types::AnyResult;
+use chrono::DateTime;
+use winnow::Parser as _;
+use winnow::ascii::digit1;
+use winnow::combinator::{fail, opt, preceded};
+use wp_model_core::model::DataField;
+use wp_model_core::model::FNameStr;
+use wp_parser::WResult;
+use wp_parser::symbol::ctx_desc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EpochUnit {
+    #[default]
+    Sec,
+    Milli,
+    Micro,
+    Nano,
+}
+
+impl EpochUnit {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "s" | "sec" => Some(Self::Sec),
+            "ms" => Some(Self::Milli),
+            "us" => Some(Self::Micro),
+            "ns" => Some(Self::Nano),
+            _ => None,
+        }
+    }
+}
+
+/// `time/epoch(ms)`：显式单位的 unix 时间戳，单位为 `s`（默认）、`ms`、`us`、`ns`
+///
+/// 与 `time/timestamp` 按位数猜测单位不同，这里不限制位数，允许负数；
+/// 单位为秒时可带小数部分（`1700000000.125`）。输出为 UTC 时间。
+#[derive(Default)]
+pub struct TimeEpochP {
+    unit: EpochUnit,
+}
+
+impl TimeEpochP {
+    pub fn new(unit: EpochUnit) -> Self {
+        Self { unit }
+    }
+
+    pub fn from_args(content: Option<&str>) -> WplCodeResult<Self> {
+        let Some(content) = content else {
+            return Ok(Self::default());
+        };
+        let args = TimeArgs::parse(content)?;
+        args.check_keys(&["unit"])?;
+        match args.get("unit").or(args.positional()) {
+            None => Ok(Self::default()),
+            Some(name) => EpochUnit::from_name(name).map(Self::new).ok_or_else(|| {
+                WplCodeError::from(WplCodeReason::Syntax(format!(
+                    "bad time/epoch unit: {}, expect s|ms|us|ns",
+                    name
+                )))
+            }),
+        }
+    }
+
+    fn to_time(&self, neg: bool, int: &str, frac: Option<&str>) -> Option<DateTime<chrono::Utc>> {
+        let val: i64 = int.parse().ok()?;
+        let val = if neg { -val } else { val };
+        match self.unit {
+            EpochUnit::Sec => {
+                let nanos = match frac {
+                    Some(f) if f.len() <= 9 => {
+                        let n = f.parse::<i64>().ok()? * 10i64.pow(9 - f.len() as u32);
+                        if neg { -n } else { n }
+                    }
+                    Some(_) => return None,
+                    None => 0,
+                };
+                let total = (val as i128) * 1_000_000_000 + nanos as i128;
+                let secs = total.div_euclid(1_000_000_000) as i64;
+                let nsec = total.rem_euclid(1_000_000_000) as u32;
+                DateTime::from_timestamp(secs, nsec)
+            }
+            EpochUnit::Milli => DateTime::from_timestamp_millis(val),
+            EpochUnit::Micro => DateTime::from_timestamp_micros(val),
+            EpochUnit::Nano => Some(DateTime::from_timestamp_nanos(val)),
+        }
+    }
+}
+
+impl PatternParser for TimeEpochP {
+    fn pattern_parse(
+        &self,
+        _: &FieldEvalUnit,
+        _: &crate::ast::WplSep,
+        data: &mut &str,
+        name: FNameStr,
+        out: &mut Vec<DataField>,
+    ) -> WResult<()> {
+        let neg = opt('-').parse_next(data)?.is_some();
+        let int = digit1
+            .context(ctx_desc("<time/epoch> digits"))
+            .parse_next(data)?;
+        let frac = if self.unit == EpochUnit::Sec {
+            opt(preceded('.', digit1)).parse_next(data)?
+        } else {
+            None
+        };
+        match self.to_time(neg, int, frac) {
+            Some(dt) => {
+                out.push(DataField::from_time(name, dt.naive_utc()));
+                Ok(())
+            }
+            None => fail
+                .context(ctx_desc("<time/epoch> out of range"))
+                .parse_next(data),
+        }
+    }
+
+    fn patten_gen(
+        &self,
+        gnc: &mut GenChannel,
+        f_conf: &crate::ast::WplField,
+        _g_conf: Option<&FieldGenConf>,
+    ) -> AnyResult<DataField> {
+        let time = super::gen_rand_time(gnc);
+        let val = match self.unit {
+            EpochUnit::Sec => time.timestamp(),
+            EpochUnit::Milli => time.timestamp_millis(),
+            EpochUnit::Micro => time.timestamp_micros(),
+            EpochUnit::Nano => time.timestamp_nanos_opt().unwrap_or_default(),
+        };
+        Ok(DataField::from_chars(
+            f_conf.safe_name().to_string(),
+            val.to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::WplField;
+    use crate::eval::value::test_utils::ParserTUnit;
+    use chrono::NaiveDate;
+    use orion_error::TestAssert;
+    use wp_model_core::model::DataType;
+
+    fn parse_epoch(rule: &str, data: &str) -> DataField {
+        let conf = WplField::try_parse(rule).assert();
+        let mut data = data;
+        let fields = ParserTUnit::from_auto(conf).verify_parse_suc_meta(&mut data, DataType::Time);
+        fields[0].clone()
+    }
+
+    #[test]
+    fn test_time_epoch_units() {
+        let base = NaiveDate::from_ymd_opt(2023, 11, 14)
+            .unwrap()
+            .and_hms_opt(22, 13, 20)
+            .unwrap();
+        let cases = [
+            ("time/epoch:t", "1700000000", base),
+            (
+                "time/epoch(s):t",
+                "1700000000.125",
+                base + chrono::Duration::milliseconds(125),
+            ),
+            (
+                "time/epoch(ms):t",
+                "1700000000125",
+                base + chrono::Duration::milliseconds(125),
+            ),
+            (
+                "time/epoch(unit=us):t",
+                "1700000000000001",
+                base + chrono::Duration::microseconds(1),
+            ),
+            (
+                "time/epoch(ns):t",
+                "1700000000000000001",
+                base + chrono::Duration::nanoseconds(1),
+            ),
+        ];
+        for (rule, data, expect) in cases {
+            assert_eq!(parse_epoch(rule, data), DataField::from_time("t", expect));
+        }
+        let neg = NaiveDate::from_ymd_opt(1969, 12, 31)
+            .unwrap()
+            .and_hms_milli_opt(23, 59, 59, 500)
+            .unwrap();
+        assert_eq!(
+            parse_epoch("time/epoch:t", "-0.5"),
+            DataField::from_time("t", neg)
+        );
+    }
+
+    #[test]
+    fn test_time_epoch_fail() {
+        let conf = WplField::try_parse("time/epoch(ms)").assert();
+        let mut data = "abc";
+        ParserTUnit::from_auto(conf).verify_parse_fail(&mut data);
+        assert!(TimeEpochP::from_args(Some("min")).is_err());
+    }
+
+    #[test]
+    fn test_time_epoch_gen() {
+        for rule in ["time/epoch", "time/epoch(ms)", "time/epoch(ns)"] {
+            let conf = WplField::try_parse(rule).assert();
+            ParserTUnit::from_auto(conf).verify_gen_parse_suc();
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use super::common::TimeArgs;
+use crate::eval::runtime::field::FieldEvalUnit;
+use crate::eval::value::parse_def::PatternParser;
+use crate::generator::{FieldGenConf, GenChannel};
+use crate::parser::error::{WplCodeError, WplCodeReason, WplCodeResult};
+use crate::types::AnyResult;
+use chrono::format::{Item, Parsed, StrftimeItems, parse_and_remainder};
+use winnow::Parser as _;
+use winnow::combinator::fail;
+use wp_model_core::model::DataField;
+use wp_model_core::model::FNameStr;
+use wp_parser::WResult;
+use wp_parser::symbol::ctx_desc;
+
+/// `time(fmt="%d-%b-%Y %H:%M:%S.%f")`：按 strftime 格式解析厂商自定义时间
+///
+/// 与常见日志工具一致，`.%f` 视为小数秒（等价于 chrono 的 `%.f`）。
+/// 格式中没有时区时按书写的本地时间输出，与其他时间类型保持一致。
+pub struct TimeFmtP {
+    fmt: String,
+}
+
+impl TimeFmtP {
+    pub fn new(fmt: &str) -> WplCodeResult<Self> {
+        let fmt = fmt.replace(".%f", "%.f");
+        if fmt.is_empty() || StrftimeItems::new(&fmt).any(|item| matches!(item, Item::Error)) {
+            return Err(WplCodeError::from(WplCodeReason::Syntax(format!(
+                "bad time fmt: \"{}\"",
+                fmt
+            ))));
+        }
+        Ok(Self { fmt })
+    }
+
+    pub fn from_args(content: &str) -> WplCodeResult<Self> {
+        let args = TimeArgs::parse(content)?;
+        args.check_keys(&["fmt"])?;
+        let fmt = args.get("fmt").or(args.positional()).unwrap_or_default();
+        Self::new(fmt)
+    }
+}
+
+impl PatternParser for TimeFmtP {
+    fn pattern_parse(
+        &self,
+        _: &FieldEvalUnit,
+        _: &crate::ast::WplSep,
+        data: &mut &str,
+        name: FNameStr,
+        out: &mut Vec<DataField>,
+    ) -> WResult<()> {
+        let mut parsed = Parsed::new();
+        if let Ok(remain) = parse_and_remainder(&mut parsed, data, StrftimeItems::new(&self.fmt))
+            && let Ok(ndt) = parsed.to_naive_datetime_with_offset(0)
+        {
+            *data = remain;
+            out.push(DataField::from_time(name, ndt));
+            return Ok(());
+        }
+        fail.context(ctx_desc("<time(fmt)> parse failed"))
+            .parse_next(data)
+    }
+
+    fn patten_gen(
+        &self,
+        gnc: &mut GenChannel,
+        f_conf: &crate::ast::WplField,
+        _g_conf: Option<&FieldGenConf>,
+    ) -> AnyResult<DataField> {
+        let time = super::gen_rand_time(gnc);
+        Ok(DataField::from_chars(
+            f_conf.safe_name().to_string(),
+            time.format(&self.fmt).to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::WplField;
+    use crate::eval::value::test_utils::ParserTUnit;
+    use chrono::NaiveDate;
+    use orion_error::TestAssert;
+    use wp_model_core::model::DataType;
+
+    #[test]
+    fn test_time_fmt_vendor() {
+        let conf = WplField::try_parse(r#"time(fmt="%d-%b-%Y %H:%M:%S.%f"):recv"#).assert();
+        assert_eq!(
+            conf.content.as_deref(),
+            Some(r#"fmt="%d-%b-%Y %H:%M:%S.%f""#)
+        );
+        let mut data = "17-Mar-2024 08:09:10.250 rest";
+        let fields = ParserTUnit::from_auto(conf).verify_parse_suc_meta(&mut data, DataType::Time);
+        let expect = NaiveDate::from_ymd_opt(2024, 3, 17)
+            .unwrap()
+            .and_hms_milli_opt(8, 9, 10, 250)
+            .unwrap();
+        assert_eq!(fields[0], DataField::from_time("recv", expect));
+        assert_eq!(data, "rest");
+    }
+
+    #[test]
+    fn test_time_fmt_fail() {
+        let conf = WplField::try_parse(r#"time(fmt="%Y/%m/%d %H:%M:%S")"#).assert();
+        let mut data = "2024-03-17 08:09:10";
+        ParserTUnit::from_auto(conf).verify_parse_fail(&mut data);
+        assert!(TimeFmtP::from_args(r#"fmt="%Y %Q""#).is_err());
+        assert!(TimeFmtP::from_args(r#"zone="+08:00""#).is_err());
+    }
+
+    #[test]
+    fn test_time_fmt_gen() {
+        let conf = WplField::try_parse(r#"time(fmt="%Y/%m/%d %H:%M:%S")"#).assert();
+        ParserTUnit::from_auto(conf).verify_gen_parse_suc();
+    }
+}
@@ -1,22 +1,26 @@
 // time module split: CLF (Common Log Format), RFC (3339/2822/ISO), and TIMESTAMP
 // This module orchestrates submodules and exposes the public surface.
 
+mod bsd; // BSD syslog `Mmm dd hh:mm:ss` with year inference
 mod clf;
-mod common; // shared helpers (parse_fixed, fast_apache_dt)
+mod common; // shared helpers (parse_fixed, fast_apache_dt, TimeArgs)
+mod epoch; // unix epoch with explicit unit (s/ms/us/ns)
+mod fmt; // strftime pattern: time(fmt="...")
 mod rfc; // RFC3339 / RFC2822 / flexible time parser
 mod timestamp; // unix timestamp family (s/ms/us) // Common Log Format fast path
 
+pub use bsd::TimeBsdP;
 pub use clf::TimeCLF;
+pub use epoch::TimeEpochP;
+pub use fmt::TimeFmtP;
 pub use rfc::{TimeISOP, TimeP, TimeRFC2822, TimeRFC3339, parse_time};
 pub use timestamp::TimeStampPSR;
 use wp_model_core::model::DataField;
 
-// Shared generator used by all time parsers when synthesizing test/bench data
-pub fn gen_time(
+// Random time (UTC offset) shared by the time generators below
+pub(crate) fn gen_rand_time(
     gnc: &mut crate::generator::GenChannel,
-    f_conf: &crate::ast::WplField,
-    g_conf: Option<&crate::generator::FieldGenConf>,
-) -> crate::types::AnyResult<DataField> {
+) -> chrono::DateTime<chrono::FixedOffset> {
     use chrono::TimeZone; // bring with_ymd_and_hms into scope
     use rand::Rng as _;
     let y = gnc.rng.random_range(2020..2023);
@@ -26,10 +30,19 @@ pub fn gen_time(
     let min = gnc.rng.random_range(0..59);
     let s = gnc.rng.random_range(0..59);
 
-    let time: chrono::DateTime<chrono::FixedOffset> = chrono::FixedOffset::east_opt(0)
+    chrono::FixedOffset::east_opt(0)
         .unwrap()
         .with_ymd_and_hms(y, mon, d, h, min, s)
-        .unwrap();
+        .unwrap()
+}
+
+// Shared generator used by all time parsers when synthesizing test/bench data
+pub fn gen_time(
+    gnc: &mut crate::generator::GenChannel,
+    f_conf: &crate::ast::WplField,
+    g_conf: Option<&crate::generator::FieldGenConf>,
+) -> crate::types::AnyResult<DataField> {
+    let time = gen_rand_time(gnc);
     if let Some(conf) = g_conf
         && let Some(fmt) = &conf.gen_fmt
     {
@@ -0,0 +1,134 @@
+use super::prelude::*;
+use std::sync::Arc;
+use wp_model_core::model::FNameStr;
+
+use crate::eval::runtime::field::FieldEvalUnit;
+use crate::parser::error::{WplCodeReason, WplCodeResult};
+use crate::wasm::{WasmEntry, WasmPlugin, abi, wasm_plugin};
+use orion_error::ToStructError;
+use wp_log::error_data;
+
+/// `wasm(<plugin>)` 字段：剩余输入交给插件的 `wp_parse`，按插件返回的消费长度前进
+pub struct WasmFieldP {
+    plugin: Arc<WasmPlugin>,
+}
+
+impl WasmFieldP {
+    /// 插件须已加载并导出 `wp_parse`
+    pub fn from_args(args: Option<&str>) -> WplCodeResult<Self> {
+        let Some(name) = args.map(str::trim).filter(|x| !x.is_empty()) else {
+            return WplCodeReason::Plugin("wasm field requires plugin name: wasm(<name>)".into())
+                .err_result();
+        };
+        match wasm_plugin(name) {
+            Some(plugin) if plugin.supports(WasmEntry::Parse) => Ok(Self { plugin }),
+            Some(_) => WplCodeReason::Plugin(format!("wasm plugin '{}' has no wp_parse", name))
+                .err_result(),
+            None => {
+                WplCodeReason::Plugin(format!("wasm plugin '{}' not loaded", name)).err_result()
+            }
+        }
+    }
+}
+
+impl PatternParser for WasmFieldP {
+    fn pattern_parse(
+        &self,
+        fpu: &FieldEvalUnit,
+        _ups_sep: &WplSep,
+        data: &mut &str,
+        name: FNameStr,
+        out: &mut Vec<DataField>,
+    ) -> ModalResult<()> {
+        let buf = match self.plugin.call(WasmEntry::Parse, data.as_bytes()) {
+            Ok(Some(buf)) => buf,
+            Ok(None) => return fail.context(ctx_desc("wasm parse")).parse_next(data),
+            // 沙箱错误（燃料、内存、陷入）不是普通的不匹配：记录并中断，不回退到其他分支
+            Err(e) => {
+                error_data!("wasm parse {} error: {}", self.plugin.name(), e);
+                let start = data.checkpoint();
+                return Err(ErrMode::Cut(context_error(
+                    data,
+                    &start,
+                    "wasm plugin error",
+                )));
+            }
+        };
+        let name = if name.is_empty() {
+            fpu.conf().safe_name()
+        } else {
+            name
+        };
+        let Ok(parsed) = abi::decode_fields(&buf, name.as_str()) else {
+            return fail.context(ctx_desc("wasm parse output")).parse_next(data);
+        };
+        if parsed.consumed > data.len() || !data.is_char_boundary(parsed.consumed) {
+            return fail
+                .context(ctx_desc("wasm parse consumed"))
+                .parse_next(data);
+        }
+        out.extend(parsed.fields);
+        *data = &data[parsed.consumed..];
+        Ok(())
+    }
+
+    fn patten_gen(
+        &self,
+        _gen: &mut GenChannel,
+        _f_conf: &WplField,
+        _g_conf: Option<&FieldGenConf>,
+    ) -> AnyResult<DataField> {
+        unimplemented!("wasm generate")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParserFactory;
+    use crate::WplEvaluator;
+    use crate::ast::group::{GroupSeq, WplGroupType};
+    use crate::wasm::WasmLimits;
+    use crate::wasm::test_plugin::register_sample;
+    use orion_error::TestAssert;
+    use wp_model_core::model::DataType;
+
+    #[test]
+    fn test_wasm_field() {
+        register_sample("field_digits");
+        let ppl =
+            WplEvaluator::from_code(r#"rule r { (wasm(field_digits):port, chars:rest) }"#).assert();
+        let (record, residue) = ppl.proc("8080 tail", 0).assert();
+        assert_eq!(residue, "");
+        assert_eq!(record.items[0].get_name(), "port");
+        assert_eq!(*record.items[0].get_meta(), DataType::Digit);
+        assert!(ppl.proc("abc tail", 0).is_err());
+
+        assert!(WplEvaluator::from_code(r#"rule r { (wasm(field_missing):x) }"#).is_err());
+    }
+
+    #[test]
+    fn test_wasm_field_trap_is_error() {
+        let trap = r#"(module
+            (memory (export "memory") 1)
+            (func (export "wp_abi_version") (result i32) (i32.const 1))
+            (func (export "wp_alloc") (param i32) (result i32) (i32.const 0))
+            (func (export "wp_parse") (param i32 i32) (result i64) unreachable))"#;
+        let wasm = wat::parse_str(trap).unwrap();
+        let plugin = WasmPlugin::from_bytes("trap", &wasm, WasmLimits::default()).assert();
+        let parser = WasmFieldP {
+            plugin: Arc::new(plugin),
+        };
+        let fpu = FieldEvalUnit::new(
+            0,
+            WplField::default(),
+            ParserFactory::crate_auto().assert(),
+            WplGroupType::Seq(GroupSeq),
+        );
+        let mut out = Vec::new();
+        let mut data = "8080";
+        let ret = parser.pattern_parse(&fpu, &WplSep::default(), &mut data, "x".into(), &mut out);
+        // 陷入不能被当作普通的不匹配（Backtrack）
+        assert!(matches!(ret, Err(ErrMode::Cut(_))));
+    }
+}
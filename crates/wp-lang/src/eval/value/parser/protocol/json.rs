@@ -599,6 +599,35 @@ mod tests {
         Ok(())
     }
     #[test]
+    fn test_json_8_6() -> AnyResult<()> {
+        let data = r#"{ "path": "/api/v1/users", "code": 404, "src": "10.1.2.3"}"#;
+        let ok_rules = [
+            r#"rule test { (json | f_regex_match(path, '^/api/v\d+/') ) }"#,
+            r#"rule test { (json | f_digit_range(code, 400, 499) ) }"#,
+            r#"rule test { (json | f_chars_len(path, 1, 16) ) }"#,
+            r#"rule test { (json | f_starts_with(path, /api) | f_ends_with(path, "users") ) }"#,
+            r#"rule test { (json | f_contains(path, v1) ) }"#,
+            r#"rule test { (json | f_ip_in_cidr(src, [10.0.0.0/8, fd00::/8]) ) }"#,
+        ];
+        for rule in ok_rules {
+            let pipe = WplEvaluator::from_code(rule)?;
+            assert!(pipe.proc(data, 0).is_ok(), "{}", rule);
+        }
+        let miss_rules = [
+            r#"rule test { (json | f_regex_match(path, '^/admin') ) }"#,
+            r#"rule test { (json | f_digit_range(code, 500, 599) ) }"#,
+            r#"rule test { (json | f_chars_len(path, 1, 8) ) }"#,
+            r#"rule test { (json | f_contains(path, v2) ) }"#,
+            r#"rule test { (json | f_ip_in_cidr(src, [192.168.0.0/16]) ) }"#,
+            r#"rule test { (json | f_starts_with(missing, x) ) }"#,
+        ];
+        for rule in miss_rules {
+            let pipe = WplEvaluator::from_code(rule)?;
+            assert!(pipe.proc(data, 0).is_err(), "{}", rule);
+        }
+        Ok(())
+    }
+    #[test]
     fn test_json_9() -> AnyResult<()> {
         let rule = r#"rule test { (json(time_timestamp@found_time:occur_time,@virus_name:alert_name,@virus_type:origin_alert_cat_name,@risk_level:severity,@iplist:terminal_ip,@host_name:terminal_name,@virus_name:malware_name,@file_md5,chars@file_path,@file_size:file_bytes,@state:protect_action,@agent_id,_@*)) }"#;
         let data = r#"{"_id":"6C941E33DDA773F19AEF2F21203863542E053D94","file_md5":"7e5432f32a3b6f25666e0cc9acff00bf","virus_name":"Suspicious.Win32.Save.a","risk_level":0,"create_time":1671693072,"state":"已处理","time":1671695066,"found_time":1671695066,"agent_id":"3358992609","file_path":"c:\\users\\fc\\desktop\\tr-shopbot\\7e5432f32a3b6f25666e0cc9acff00bf","virus_type":"其他病毒","threat_file":"Suspicious.Win32.Save.a","host_name":"DESKTOP-ARRA948","iplist":"10.122.163.99"}"#;
@@ -0,0 +1,500 @@
+use super::super::prelude::*;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use wp_model_core::model::FNameStr;
+
+use crate::eval::runtime::field::FieldEvalUnit;
+use crate::eval::value::parse_def::PatternParser;
+
+/// XML 解析：元素与属性按 `/` 拼接为字段路径（与 json 的 `a/b` 命名一致）
+///
+/// - 属性：`<Provider Name="x"/>` -> `Provider/Name`
+/// - 同名兄弟元素：按出现顺序加下标 `Data[0]`、`Data[1]`
+/// - 展开深度：`xml[N]`，超出深度的元素以内部原文作为字符串输出
+/// - `xml/local`：去除命名空间前缀（`soap:Body` -> `Body`）并忽略 `xmlns` 声明
+#[derive(Default)]
+pub struct XmlP {
+    strip_ns: bool,
+}
+
+impl XmlP {
+    pub fn new(strip_ns: bool) -> Self {
+        Self { strip_ns }
+    }
+}
+
+struct XmlNode<'a> {
+    name: &'a str,
+    attrs: Vec<(&'a str, String)>,
+    text: String,
+    children: Vec<XmlNode<'a>>,
+    inner: &'a str,
+}
+
+impl PatternParser for XmlP {
+    fn pattern_parse(
+        &self,
+        fpu: &FieldEvalUnit,
+        ups_sep: &WplSep,
+        data: &mut &str,
+        name: FNameStr,
+        out: &mut Vec<DataField>,
+    ) -> ModalResult<()> {
+        multispace0.parse_next(data)?;
+        let mut input = *data;
+        let root = match XmlScan::take_document(&mut input) {
+            Ok(root) => root,
+            Err(desc) => return fail.context(ctx_desc(desc)).parse_next(data),
+        };
+        let max_depth = fpu.conf().length().unwrap_or(XmlScan::MAX_DEPTH);
+        self.proc_node(fpu, ups_sep, name.as_str(), &root, None, out, 1, max_depth)?;
+        *data = input;
+        Ok(())
+    }
+
+    fn patten_gen(
+        &self,
+        _gen: &mut GenChannel,
+        _f_conf: &WplField,
+        _g_conf: Option<&FieldGenConf>,
+    ) -> AnyResult<DataField> {
+        unimplemented!("xml generate")
+    }
+}
+
+impl XmlP {
+    #[inline]
+    fn xml_path(parent: &str, cur: &str) -> String {
+        if parent.is_empty() {
+            return cur.to_string();
+        }
+        let mut path = String::with_capacity(parent.len() + 1 + cur.len());
+        path.push_str(parent);
+        path.push('/');
+        path.push_str(cur);
+        path
+    }
+
+    #[inline]
+    fn local_name<'a>(&self, name: &'a str) -> &'a str {
+        if self.strip_ns {
+            name.split_once(':').map(|(_, local)| local).unwrap_or(name)
+        } else {
+            name
+        }
+    }
+
+    #[inline]
+    fn is_ns_decl(&self, attr: &str) -> bool {
+        self.strip_ns && (attr == "xmlns" || attr.starts_with("xmlns:"))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn proc_node(
+        &self,
+        fpu: &FieldEvalUnit,
+        ups_sep: &WplSep,
+        parent: &str,
+        node: &XmlNode,
+        index: Option<usize>,
+        out: &mut Vec<DataField>,
+        depth: usize,
+        max_depth: usize,
+    ) -> ModalResult<()> {
+        let mut node_name = self.local_name(node.name).to_string();
+        if let Some(idx) = index {
+            node_name.push('[');
+            node_name.push_str(idx.to_string().as_str());
+            node_name.push(']');
+        }
+        let path = Self::xml_path(parent, node_name.as_str());
+        let mut attr_cnt = 0;
+        for (key, val) in &node.attrs {
+            if self.is_ns_decl(key) {
+                continue;
+            }
+            attr_cnt += 1;
+            let attr_path = Self::xml_path(path.as_str(), self.local_name(key));
+            Self::proc_value(fpu, ups_sep, attr_path.as_str(), val.as_str(), out)?;
+        }
+        if !node.children.is_empty() && depth >= max_depth {
+            return Self::proc_value(fpu, ups_sep, path.as_str(), node.inner.trim(), out);
+        }
+        let text = node.text.trim();
+        if !text.is_empty() || (node.children.is_empty() && attr_cnt == 0) {
+            Self::proc_value(fpu, ups_sep, path.as_str(), text, out)?;
+        }
+
+        // 同名兄弟元素按出现顺序编号
+        let mut name_cnt: HashMap<&str, (usize, usize)> = HashMap::new();
+        for child in &node.children {
+            name_cnt.entry(self.local_name(child.name)).or_default().0 += 1;
+        }
+        for child in &node.children {
+            let index = match name_cnt.get_mut(self.local_name(child.name)) {
+                Some((total, seen)) if *total > 1 => {
+                    *seen += 1;
+                    Some(*seen - 1)
+                }
+                _ => None,
+            };
+            self.proc_node(
+                fpu,
+                ups_sep,
+                path.as_str(),
+                child,
+                index,
+                out,
+                depth + 1,
+                max_depth,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn proc_value(
+        fpu: &FieldEvalUnit,
+        upper_sep: &WplSep,
+        path: &str,
+        value: &str,
+        out: &mut Vec<DataField>,
+    ) -> ModalResult<()> {
+        let sub_conf = fpu
+            .conf()
+            .sub_fields()
+            .as_ref()
+            .and_then(|subs| subs.get(path));
+        if let Some(sub_conf) = sub_conf {
+            let run_key = sub_conf.run_key_str(path);
+            if let Some(sub_fpu) = fpu.get_sub_fpu(path) {
+                let mut ups_sep = sub_fpu.conf().resolve_sep(upper_sep);
+                if ups_sep.is_space_sep() {
+                    ups_sep.set_current("\\0")
+                }
+                let mut raw = value;
+                return sub_fpu.parse(&ups_sep, &mut raw, Some(run_key), out);
+            }
+            out.push(DataField::from_chars(run_key, value.to_string()));
+            return Ok(());
+        }
+        out.push(DataField::from_chars(path, value.to_string()));
+        Ok(())
+    }
+}
+
+/// 轻量 XML 扫描：只处理日志场景需要的子集（元素、属性、文本、CDATA），
+/// 跳过声明、注释与处理指令，不解析 DTD。
+struct XmlScan {}
+
+impl XmlScan {
+    // 最大嵌套深度，防止极端输入导致栈溢出
+    const MAX_DEPTH: usize = 128;
+
+    fn take_document<'a>(data: &mut &'a str) -> Result<XmlNode<'a>, &'static str> {
+        loop {
+            *data = data.trim_start();
+            if data.starts_with("<?") {
+                Self::skip_until(data, "?>")?;
+            } else if data.starts_with("<!--") {
+                Self::skip_until(data, "-->")?;
+            } else if data.starts_with("<!") {
+                Self::skip_until(data, ">")?;
+            } else {
+                break;
+            }
+        }
+        Self::take_element(data, 1)
+    }
+
+    fn skip_until(data: &mut &str, end: &str) -> Result<(), &'static str> {
+        match data.find(end) {
+            Some(pos) => {
+                *data = &data[pos + end.len()..];
+                Ok(())
+            }
+            None => Err("xml markup not closed"),
+        }
+    }
+
+    fn take_name<'a>(data: &mut &'a str) -> &'a str {
+        let end = data
+            .find(|c: char| c.is_whitespace() || matches!(c, '=' | '>' | '/' | '<'))
+            .unwrap_or(data.len());
+        let (name, rest) = data.split_at(end);
+        *data = rest;
+        name
+    }
+
+    fn take_element<'a>(data: &mut &'a str, depth: usize) -> Result<XmlNode<'a>, &'static str> {
+        if depth > Self::MAX_DEPTH {
+            return Err("xml nested too deep");
+        }
+        *data = data.strip_prefix('<').ok_or("xml element start '<'")?;
+        let name = Self::take_name(data);
+        if name.is_empty() {
+            return Err("xml element name");
+        }
+        let mut node = XmlNode {
+            name,
+            attrs: Vec::new(),
+            text: String::new(),
+            children: Vec::new(),
+            inner: "",
+        };
+        loop {
+            *data = data.trim_start();
+            if let Some(rest) = data.strip_prefix("/>") {
+                *data = rest;
+                return Ok(node);
+            }
+            if let Some(rest) = data.strip_prefix('>') {
+                *data = rest;
+                break;
+            }
+            let key = Self::take_name(data);
+            if key.is_empty() {
+                return Err("xml attribute name");
+            }
+            *data = data.trim_start();
+            *data = data.strip_prefix('=').ok_or("xml attribute '='")?;
+            *data = data.trim_start();
+            let quote = match data.chars().next() {
+                Some(q @ ('"' | '\'')) => q,
+                _ => return Err("xml attribute quoted value"),
+            };
+            let body = &data[1..];
+            let end = body.find(quote).ok_or("xml attribute value not closed")?;
+            node.attrs
+                .push((key, Self::unescape(&body[..end]).into_owned()));
+            *data = &body[end + 1..];
+        }
+
+        let content = *data;
+        loop {
+            if data.is_empty() {
+                return Err("xml element not closed");
+            }
+            if let Some(rest) = data.strip_prefix("</") {
+                node.inner = &content[..content.len() - data.len()];
+                *data = rest;
+                let end_name = Self::take_name(data);
+                if end_name != node.name {
+                    return Err("xml end tag mismatch");
+                }
+                *data = data.trim_start();
+                *data = data.strip_prefix('>').ok_or("xml end tag '>'")?;
+                return Ok(node);
+            }
+            if data.starts_with("<!--") {
+                Self::skip_until(data, "-->")?;
+            } else if let Some(rest) = data.strip_prefix("<![CDATA[") {
+                let end = rest.find("]]>").ok_or("xml cdata not closed")?;
+                node.text.push_str(&rest[..end]);
+                *data = &rest[end + 3..];
+            } else if data.starts_with("<?") {
+                Self::skip_until(data, "?>")?;
+            } else if data.starts_with('<') {
+                let child = Self::take_element(data, depth + 1)?;
+                node.children.push(child);
+            } else {
+                let end = data.find('<').unwrap_or(data.len());
+                node.text.push_str(&Self::unescape(&data[..end]));
+                *data = &data[end..];
+            }
+        }
+    }
+
+    fn unescape(raw: &str) -> Cow<'_, str> {
+        if !raw.contains('&') {
+            return Cow::Borrowed(raw);
+        }
+        let mut buf = String::with_capacity(raw.len());
+        let mut rest = raw;
+        while let Some(pos) = rest.find('&') {
+            buf.push_str(&rest[..pos]);
+            rest = &rest[pos..];
+            let decoded = rest.find(';').and_then(|end| {
+                let ch = match &rest[1..end] {
+                    "lt" => Some('<'),
+                    "gt" => Some('>'),
+                    "amp" => Some('&'),
+                    "quot" => Some('"'),
+                    "apos" => Some('\''),
+                    ent => {
+                        let code = if let Some(hex) = ent.strip_prefix("#x") {
+                            u32::from_str_radix(hex, 16).ok()
+                        } else if let Some(dec) = ent.strip_prefix('#') {
+                            dec.parse::<u32>().ok()
+                        } else {
+                            None
+                        };
+                        code.and_then(char::from_u32)
+                    }
+                };
+                ch.map(|c| (c, end))
+            });
+            match decoded {
+                Some((c, end)) => {
+                    buf.push(c);
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    // 非法实体原样保留
+                    buf.push('&');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        buf.push_str(rest);
+        Cow::Owned(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::value::test_utils::ParserTUnit;
+    use crate::types::AnyResult;
+    use orion_error::TestAssert;
+    use std::net::IpAddr;
+    use std::str::FromStr;
+    use wp_model_core::model::DataRecord;
+
+    const WIN_EVENT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Event xmlns="http://schemas.microsoft.com/win/2004/08/events/event">
+  <System>
+    <Provider Name="Microsoft-Windows-Security-Auditing" Guid="{54849625}"/>
+    <EventID>4624</EventID>
+    <TimeCreated SystemTime="2024-05-01T08:00:00.000Z"/>
+    <Computer>dc01.corp.local</Computer>
+  </System>
+  <EventData>
+    <Data Name="TargetUserName">alice</Data>
+    <Data Name="IpAddress">10.0.0.8</Data>
+  </EventData>
+</Event> tail"#;
+
+    #[test]
+    fn test_xml_flatten() -> AnyResult<()> {
+        let conf = WplField::try_parse("xml").assert();
+        let mut data = WIN_EVENT;
+        let fields = ParserTUnit::new(XmlP::default(), conf)
+            .verify_parse_suc(&mut data)
+            .assert();
+        assert_eq!(data, "tail");
+        let record = DataRecord::from(fields);
+        assert_eq!(
+            record.field("Event/System/EventID"),
+            Some(&DataField::from_chars("Event/System/EventID", "4624"))
+        );
+        assert_eq!(
+            record.field("Event/System/Provider/Name"),
+            Some(&DataField::from_chars(
+                "Event/System/Provider/Name",
+                "Microsoft-Windows-Security-Auditing"
+            ))
+        );
+        assert_eq!(
+            record.field("Event/EventData/Data[1]/Name"),
+            Some(&DataField::from_chars(
+                "Event/EventData/Data[1]/Name",
+                "IpAddress"
+            ))
+        );
+        assert_eq!(
+            record.field("Event/EventData/Data[1]"),
+            Some(&DataField::from_chars(
+                "Event/EventData/Data[1]",
+                "10.0.0.8"
+            ))
+        );
+        assert!(record.field("Event/xmlns").is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_xml_sub_fields() -> AnyResult<()> {
+        let conf = WplField::try_parse(
+            "xml(digit@Event/System/EventID:event_id, ip@Event/EventData/Data[1]:sip, _@Event/System/*)",
+        )
+        .assert();
+        let mut data = WIN_EVENT;
+        let fields = ParserTUnit::from_auto(conf)
+            .verify_parse_suc(&mut data)
+            .assert();
+        let record = DataRecord::from(fields);
+        assert_eq!(
+            record.field("event_id"),
+            Some(&DataField::from_digit("event_id", 4624))
+        );
+        assert_eq!(
+            record.field("sip"),
+            Some(&DataField::from_ip(
+                "sip",
+                IpAddr::from_str("10.0.0.8").unwrap()
+            ))
+        );
+        assert_eq!(
+            record.field("Event/System/Computer"),
+            Some(&DataField::from_ignore("Event/System/Computer"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_xml_strip_namespace() -> AnyResult<()> {
+        let conf = WplField::try_parse("xml/local").assert();
+        assert_eq!(conf.meta_name.as_str(), "xml/local");
+        let mut data = r#"<soap:Envelope xmlns:soap="http://www.w3.org/2003/05/soap-envelope"><soap:Body><m:Alert xmlns:m="urn:ids" m:level="3">port &amp; scan</m:Alert></soap:Body></soap:Envelope>"#;
+        let fields = ParserTUnit::from_auto(conf)
+            .verify_parse_suc(&mut data)
+            .assert();
+        let record = DataRecord::from(fields);
+        assert_eq!(
+            record.field("Envelope/Body/Alert"),
+            Some(&DataField::from_chars("Envelope/Body/Alert", "port & scan"))
+        );
+        assert_eq!(
+            record.field("Envelope/Body/Alert/level"),
+            Some(&DataField::from_chars("Envelope/Body/Alert/level", "3"))
+        );
+        assert!(record.field("Envelope/xmlns").is_none());
+        assert!(record.field("Envelope/Body/Alert/m").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_xml_depth_limit() -> AnyResult<()> {
+        let conf = WplField::try_parse("xml[2]").assert();
+        let mut data = r#"<a><b>1</b><c><d>2</d><![CDATA[<raw>]]></c></a>"#;
+        let fields = ParserTUnit::new(XmlP::default(), conf)
+            .verify_parse_suc(&mut data)
+            .assert();
+        let record = DataRecord::from(fields);
+        assert_eq!(
+            record.field("a/b"),
+            Some(&DataField::from_chars("a/b", "1"))
+        );
+        assert_eq!(
+            record.field("a/c"),
+            Some(&DataField::from_chars("a/c", "<d>2</d><![CDATA[<raw>]]>"))
+        );
+        assert!(record.field("a/c/d").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_xml_bad_input() {
+        let conf = WplField::try_parse("xml").assert();
+        for bad in [
+            r#"<a><b>1</a>"#,
+            r#"<a x=1/>"#,
+            r#"<a>open"#,
+            r#"plain text"#,
+        ] {
+            let mut data = bad;
+            ParserTUnit::new(XmlP::default(), conf.clone()).verify_parse_fail(&mut data);
+            assert_eq!(data, bad);
+        }
+    }
+}
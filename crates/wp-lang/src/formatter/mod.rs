@@ -0,0 +1,671 @@
+//! WPL 规范格式化
+//!
+//! 解析 → 输出 → 再解析得到相同的语法树：输出后会重新解析校验，不一致时报错而不是
+//! 返回改变语义的结果。规范形式：
+//! - 两空格缩进，组内每行一个字段，组与组之间以 `,` 换行分隔
+//! - 分隔符逐字符写作 `\x`，字符串参数统一为双引号并规范转义，数组参数以 `, ` 分隔
+//! - 注解按 tag/copy_raw/priority/match/copy_tags/ingest_time/fingerprint 顺序输出，tag 按键排序
+//! - 行注释与块注释保留：规则体内的注释挂到其后的管道/组/字段之前，规则体外的注释
+//!   挂到其后的规则/用例之前
+//!
+//! 包内 `type`/`macro` 定义在解析期展开、不保留在语法树中，这类文件返回
+//! [`WplFormatted::Unsupported`]，由调用方原样保留；`--check` 无法确认其格式，按未通过处理。
+
+mod printer;
+
+use std::collections::VecDeque;
+
+use orion_error::ToStructError;
+use smol_str::SmolStr;
+use winnow::ascii::{digit1, multispace0, multispace1};
+use winnow::combinator::{alt, delimited, eof, fail, opt};
+use winnow::stream::Stream;
+use winnow::token::literal;
+use wp_parser::Parser;
+use wp_parser::WResult;
+use wp_parser::comment::CommentParser;
+use wp_parser::symbol::ctx_desc;
+
+use crate::ast::{AnnFun, WplRule, WplTestCase};
+use crate::parser::error::{WPLCodeErrorTrait, WplCodeError, WplCodeReason, WplCodeResult};
+use crate::parser::utils;
+use crate::parser::wpl_anno::ann_fun;
+use crate::parser::wpl_field::{wpl_field, wpl_sep_str};
+use crate::parser::wpl_group::wpl_repeat_meta;
+use crate::parser::wpl_pkg::{wpl_package, wpl_test_case};
+use crate::parser::wpl_rule::{pip_proc, wpl_rule};
+use printer::{GroupNotes, Printer, RuleNotes};
+
+/// 格式化结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum WplFormatted {
+    /// 规范文本
+    Text(String),
+    /// 合法但暂不支持格式化的代码（附原因），应原样保留
+    Unsupported(String),
+}
+
+/// 格式化 WPL 代码（单个 package 或若干独立 rule）
+pub fn format_wpl(code: &str) -> WplCodeResult<WplFormatted> {
+    let masked = mask(code)?;
+    let Some(layout) = Layout::parse(&masked)? else {
+        // 不支持格式化的包仍须是合法代码，语法错误照常报告
+        let mut input = masked.as_str();
+        (multispace0, wpl_package, eof)
+            .parse_next(&mut input)
+            .map_err(|e| WplCodeError::from_syntax(e, input, ""))?;
+        return Ok(WplFormatted::Unsupported(
+            "type/macro definitions are expanded at parse time".to_string(),
+        ));
+    };
+    let comments = Comments::collect(code, &masked);
+    let formatted = layout.print(comments);
+
+    // 保证往返：格式化结果必须解析出同一语法树
+    let again = Layout::parse(&mask(&formatted)?)?;
+    if !again.is_some_and(|again| layout.same_ast(&again)) {
+        return WplCodeReason::UnSupport("wpl format changes syntax tree".to_string()).err_result();
+    }
+    Ok(WplFormatted::Text(formatted))
+}
+
+/// `--check`：代码是否已是规范格式（不支持格式化的代码无法确认，视为未通过）
+pub fn check_wpl(code: &str) -> WplCodeResult<bool> {
+    Ok(match format_wpl(code)? {
+        WplFormatted::Text(formatted) => formatted == code,
+        WplFormatted::Unsupported(_) => false,
+    })
+}
+
+fn mask(code: &str) -> WplCodeResult<String> {
+    CommentParser::mask_comment(&mut &*code).map_err(|e| {
+        WplCodeError::from(WplCodeReason::Syntax(format!("comment proc error {} ", e)))
+    })
+}
+
+#[derive(Debug)]
+enum Item {
+    Rule(WplRule, RuleMarks),
+    Case(WplTestCase),
+}
+
+impl Item {
+    fn same_ast(&self, other: &Item) -> bool {
+        match (self, other) {
+            (Item::Rule(a, _), Item::Rule(b, _)) => a == b,
+            (Item::Case(a), Item::Case(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// 规则体内各位置在源码中的偏移，用于把注释放回原处
+#[derive(Debug, Default)]
+struct RuleMarks {
+    // `{` 之后
+    body: usize,
+    // 预处理管道结束
+    pipe: Option<usize>,
+    groups: Vec<GroupMarks>,
+    // 结束 `}`
+    close: usize,
+}
+
+#[derive(Debug, Default)]
+struct GroupMarks {
+    start: usize,
+    // 各字段结束
+    fields: Vec<usize>,
+    // 结束 `)`
+    close: usize,
+}
+
+impl RuleMarks {
+    /// 沿 `wpl_rule` 的结构重走一遍已解析成功的规则，只记录偏移
+    fn parse(code: &str, input: &mut &str) -> WResult<Self> {
+        let offset = |rest: &str| code.len() - rest.len();
+        let mut marks = RuleMarks::default();
+        opt(ann_fun).parse_next(input)?;
+        (multispace0, "rule", multispace0, utils::take_exact_path).parse_next(input)?;
+        (multispace0, "{").parse_next(input)?;
+        marks.body = offset(input);
+        multispace0.parse_next(input)?;
+        if opt(pip_proc).parse_next(input)?.is_some() {
+            marks.pipe = Some(offset(input));
+        }
+        loop {
+            multispace0.parse_next(input)?;
+            let mut group = GroupMarks {
+                start: offset(input),
+                ..Default::default()
+            };
+            if !input.starts_with('(') && opt(wpl_repeat_meta).parse_next(input)?.is_none() {
+                alt(("alt", "opt", "some_of", "seq")).parse_next(input)?;
+            }
+            (multispace0, "(").parse_next(input)?;
+            loop {
+                multispace0.parse_next(input)?;
+                if input.is_empty() || input.starts_with(')') {
+                    break;
+                }
+                wpl_field.parse_next(input)?;
+                group.fields.push(offset(input));
+                opt((multispace0, ",")).parse_next(input)?;
+            }
+            group.close = offset(input);
+            ")".parse_next(input)?;
+            opt(delimited("[", digit1, "]")).parse_next(input)?;
+            opt(wpl_sep_str).parse_next(input)?;
+            marks.groups.push(group);
+            if !utils::is_sep_next(input) {
+                break;
+            }
+        }
+        multispace0.parse_next(input)?;
+        marks.close = offset(input);
+        Ok(marks)
+    }
+}
+
+/// 条目及其在源码中的结束偏移
+struct Span {
+    end: usize,
+    item: Item,
+}
+
+struct PkgHead {
+    tags: Option<AnnFun>,
+    name: SmolStr,
+    // `{` 之后的偏移
+    body: usize,
+    // 结束 `}` 的偏移
+    close: usize,
+}
+
+struct Layout {
+    head: Option<PkgHead>,
+    items: Vec<Span>,
+}
+
+impl Layout {
+    /// 包内含 `type`/`macro` 定义时返回 `None`
+    fn parse(code: &str) -> WplCodeResult<Option<Self>> {
+        let mut input = code;
+        Self::parse_impl(code, &mut input).map_err(|e| WplCodeError::from_syntax(e, input, ""))
+    }
+
+    fn parse_impl(code: &str, input: &mut &str) -> WResult<Option<Self>> {
+        let offset = |rest: &str| code.len() - rest.len();
+        multispace0.parse_next(input)?;
+        let cp = input.checkpoint();
+        let tags = opt(ann_fun).parse_next(input)?;
+        if !utils::is_next("package", input) {
+            // 独立规则文件：注解属于首条规则
+            input.reset(&cp);
+            let mut items = Vec::new();
+            loop {
+                let item = Self::rule_item(code, input)?;
+                items.push(Span {
+                    end: offset(input),
+                    item,
+                });
+                multispace0.parse_next(input)?;
+                if input.is_empty() {
+                    return Ok(Some(Self { head: None, items }));
+                }
+            }
+        }
+
+        let name = (literal("package"), multispace1, utils::take_key)
+            .map(|x| SmolStr::from(x.2))
+            .parse_next(input)?;
+        (multispace0, literal("{")).parse_next(input)?;
+        let body = offset(input);
+        let mut items = Vec::new();
+        loop {
+            multispace0.parse_next(input)?;
+            if input.is_empty() || input.starts_with('}') {
+                break;
+            }
+            if utils::is_next(alt(("type", "macro")), input) {
+                return Ok(None);
+            }
+            let item = if utils::is_next("test", input) {
+                Item::Case(wpl_test_case.parse_next(input)?)
+            } else {
+                Self::rule_item(code, input)?
+            };
+            items.push(Span {
+                end: offset(input),
+                item,
+            });
+        }
+        if !items.iter().any(|x| matches!(x.item, Item::Rule(..))) {
+            return fail
+                .context(ctx_desc("rule <name> {...}"))
+                .parse_next(input);
+        }
+        let close = offset(input);
+        (literal("}"), multispace0, eof)
+            .context(ctx_desc("package end"))
+            .parse_next(input)?;
+        Ok(Some(Self {
+            head: Some(PkgHead {
+                tags,
+                name,
+                body,
+                close,
+            }),
+            items,
+        }))
+    }
+
+    fn rule_item(code: &str, input: &mut &str) -> WResult<Item> {
+        let mut walk = *input;
+        let rule = wpl_rule.parse_next(input)?;
+        let marks = RuleMarks::parse(code, &mut walk)?;
+        Ok(Item::Rule(rule, marks))
+    }
+
+    fn same_ast(&self, other: &Layout) -> bool {
+        let head = match (&self.head, &other.head) {
+            (Some(a), Some(b)) => a.name == b.name && a.tags == b.tags,
+            (None, None) => true,
+            _ => false,
+        };
+        head && self.items.len() == other.items.len()
+            && self
+                .items
+                .iter()
+                .zip(&other.items)
+                .all(|(a, b)| a.item.same_ast(&b.item))
+    }
+
+    fn print(&self, mut comments: Comments) -> String {
+        let mut p = Printer::default();
+        let head_end = self.head.as_ref().map(|h| h.body).unwrap_or(0);
+        comments.emit_before(head_end, &mut p);
+        if let Some(head) = &self.head {
+            if let Some(tags) = &head.tags {
+                p.ann(tags);
+            }
+            p.line(&format!("package {} {{", head.name));
+            p.push();
+        }
+        for (index, span) in self.items.iter().enumerate() {
+            if index != 0 {
+                p.blank_line();
+            }
+            match &span.item {
+                Item::Rule(rule, marks) => {
+                    comments.emit_before(marks.body, &mut p);
+                    let notes = comments.rule_notes(marks);
+                    p.rule(rule, &notes);
+                }
+                Item::Case(case) => {
+                    // 用例内部的注释一并提到用例之前
+                    comments.emit_before(span.end, &mut p);
+                    p.test_case(case);
+                }
+            }
+        }
+        if let Some(head) = &self.head {
+            comments.emit_before(head.close, &mut p);
+            p.pop();
+            p.line("}");
+        }
+        comments.emit_before(usize::MAX, &mut p);
+        p.finish()
+    }
+}
+
+/// 源码中被注释占据的行片段（按出现顺序）
+struct Comments {
+    lines: VecDeque<(usize, String)>,
+}
+
+impl Comments {
+    fn collect(code: &str, masked: &str) -> Self {
+        let mut lines = VecDeque::new();
+        let mut offset = 0;
+        for (raw, mask) in code.split('\n').zip(masked.split('\n')) {
+            let (raw_b, mask_b) = (raw.as_bytes(), mask.as_bytes());
+            let first = raw_b.iter().zip(mask_b).position(|(a, b)| a != b);
+            let last = raw_b.iter().zip(mask_b).rposition(|(a, b)| a != b);
+            if let (Some(first), Some(last)) = (first, last) {
+                lines.push_back((offset + first, raw[first..=last].trim().to_string()));
+            }
+            offset += raw.len() + 1;
+        }
+        Self { lines }
+    }
+
+    fn take_before(&mut self, pos: usize) -> Vec<String> {
+        let mut notes = Vec::new();
+        while self.lines.front().is_some_and(|(at, _)| *at < pos) {
+            if let Some((_, note)) = self.lines.pop_front() {
+                notes.push(note);
+            }
+        }
+        notes
+    }
+
+    fn emit_before(&mut self, pos: usize, p: &mut Printer) {
+        for text in self.take_before(pos) {
+            p.line(&text);
+        }
+    }
+
+    fn rule_notes(&mut self, marks: &RuleMarks) -> RuleNotes {
+        let mut notes = RuleNotes::default();
+        if let Some(pipe) = marks.pipe {
+            notes.pipe = self.take_before(pipe);
+        }
+        for group in &marks.groups {
+            let start = self.take_before(group.start);
+            let fields = group.fields.iter().map(|x| self.take_before(*x)).collect();
+            notes.groups.push(GroupNotes {
+                start,
+                fields,
+                close: self.take_before(group.close),
+            });
+        }
+        notes.close = self.take_before(marks.close);
+        notes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orion_error::TestAssert;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::path::{Path, PathBuf};
+
+    fn text(code: &str) -> String {
+        match format_wpl(code).assert() {
+            WplFormatted::Text(text) => text,
+            WplFormatted::Unsupported(why) => panic!("unsupported: {}", why),
+        }
+    }
+
+    fn assert_idempotent(code: &str) -> String {
+        let once = text(code);
+        let twice = text(&once);
+        assert_eq!(once, twice, "format is not idempotent:\n{}", once);
+        assert!(check_wpl(&once).assert());
+        once
+    }
+
+    #[test]
+    fn test_format_package() {
+        let code = r#"#[tag(t2:"b", t1 : "a\"q")]
+package /example {
+   #[priority(3), match(longest,residue:0.5)]
+   rule nginx {   |decode/charset(gbk)|base64|
+        (ip:sip,_^2,time<[,]>,http/request",3*chars\|,symbol(<190>)[5])\,, alt(digit:code | digit_in([200,404]), chars:code | f_chars_has(_, OK))
+    }
+   rule kv { some_of(json(chars@a:x\;, opt(digit)@b|digit_range(1,10)):obj, kv(ip@src)) , repeat(1..3, ips)(ip) }
+    test nginx { input: "1.1.1.1 - - [x] \"GET\""; expect: { sip: ip(1.1.1.1) } }
+}"#;
+        let out = assert_idempotent(code);
+        assert_eq!(
+            out,
+            r#"#[tag(t1:"a\"q", t2:"b")]
+package /example {
+  #[priority(3), match(longest, residue: 0.5)]
+  rule nginx {
+    |decode/charset(gbk)|base64|
+    (
+      ip:sip,
+      _^2,
+      time<[,]>,
+      http/request",
+      3*chars\|,
+      symbol(<190>)[5]
+    )\,,
+    alt(
+      digit:code | digit_in([200, 404]),
+      chars:code | f_chars_has(_, OK)
+    )
+  }
+
+  rule kv {
+    some_of(
+      json(
+        chars@a:x\;,
+        opt(digit)@b | digit_range(1, 10)
+      ):obj,
+      kv(
+        ip@src
+      )
+    ),
+    repeat(1..3, ips)(
+      ip
+    )
+  }
+
+  test nginx {
+    input: "1.1.1.1 - - [x] \"GET\"";
+    expect: {
+      sip: ip("1.1.1.1")
+    }
+  }
+}
+"#
+        );
+        // 包注解仍按原语义合并到规则
+        let origin = wpl_package.parse(code).assert();
+        let formatted = wpl_package.parse(out.as_str()).assert();
+        assert_eq!(origin.rules, formatted.rules);
+        assert_eq!(origin.tests, formatted.tests);
+    }
+
+    #[test]
+    fn test_format_comments() {
+        let code = "// header\npackage p {\n  // first rule\n  rule a {\n    // inside\n    (digit,\n    /* before\n    ip\n    */ ip\n     // last\n    ),\n  // next group\n  (chars)\n    // after groups\n  }\n  /*\n   block\n   */\n  rule b { (chars) }\n  // tail of body\n}\n// eof\n";
+        let out = assert_idempotent(code);
+        assert_eq!(
+            out,
+            "// header\npackage p {\n  // first rule\n  rule a {\n    // inside\n    (\n      digit,\n      /* before\n      ip\n      */\n      ip\n      // last\n    ),\n    // next group\n    (\n      chars\n    )\n    // after groups\n  }\n\n  /*\n  block\n  */\n  rule b {\n    (\n      chars\n    )\n  }\n  // tail of body\n}\n// eof\n"
+        );
+    }
+
+    #[test]
+    fn test_format_bare_rules() {
+        let out = assert_idempotent("rule a { (ip, chars\\0) }\nrule b {(digit)}");
+        assert!(out.starts_with("rule a {\n  (\n    ip,\n    chars\\0\n  )\n}\n\nrule b {"));
+        assert!(!check_wpl("rule a {(ip)}").assert());
+    }
+
+    #[test]
+    fn test_format_unsupported() {
+        let code = "package p {\n type head = (ip:sip)\n rule a { (head) }\n}";
+        assert!(matches!(
+            format_wpl(code).assert(),
+            WplFormatted::Unsupported(_)
+        ));
+        // check 不能把未格式化的内容当作已通过
+        assert!(!check_wpl(code).assert());
+        // 不支持格式化的包仍报告语法错误
+        assert!(format_wpl("package p {\n type head = (ip:sip)\n rule a { (head }\n}").is_err());
+        assert!(format_wpl("package p { rule a { (ip }").is_err());
+        assert!(format_wpl("package p { rule a { (ip) } } x").is_err());
+    }
+
+    fn corpus(dir: &Path, out: &mut Vec<PathBuf>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                corpus(&path, out);
+            } else if path.extension().is_some_and(|x| x == "wpl") {
+                out.push(path);
+            }
+        }
+    }
+
+    /// 仓库内现有 .wpl 语料：能被现有解析器接受的文件，格式化须幂等且语义不变
+    #[test]
+    fn test_format_corpus() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        let mut files = Vec::new();
+        corpus(&root.join("tests"), &mut files);
+        corpus(&root.join("crates/wp-proj/src/example"), &mut files);
+        let mut checked = 0;
+        for file in files {
+            let code = std::fs::read_to_string(&file).unwrap();
+            let pure = CommentParser::ignore_comment(&mut code.as_str()).unwrap();
+            let accepted =
+                wpl_package.parse(pure.as_str()).is_ok() || wpl_rule.parse(pure.as_str()).is_ok();
+            if !accepted {
+                continue;
+            }
+            let out = match format_wpl(&code) {
+                Ok(WplFormatted::Text(out)) => out,
+                Ok(WplFormatted::Unsupported(_)) => continue,
+                Err(e) => panic!("format {} failed: {}", file.display(), e),
+            };
+            assert_eq!(text(&out), out, "{}", file.display());
+            assert_eq!(parse_ast(&code), parse_ast(&out), "{}", file.display());
+            checked += 1;
+        }
+        assert!(checked > 0);
+    }
+
+    /// 去掉注释后的语法树：包取其规则与用例，独立规则文件取各规则
+    fn parse_ast(code: &str) -> (Vec<WplRule>, Vec<WplTestCase>) {
+        let pure = CommentParser::ignore_comment(&mut &*code).unwrap();
+        if let Ok(pkg) = wpl_package.parse(pure.as_str()) {
+            return (pkg.rules.into_iter().collect(), pkg.tests);
+        }
+        let mut input = pure.trim_start();
+        let mut rules = Vec::new();
+        while !input.is_empty() {
+            rules.push(wpl_rule.parse_next(&mut input).expect("rule"));
+            input = input.trim_start();
+        }
+        (rules, Vec::new())
+    }
+
+    /// 随机生成规则：在所有可插空白的位置随机插入空白、换行与注释
+    struct Gen {
+        rng: StdRng,
+        comments: usize,
+    }
+
+    impl Gen {
+        const FIELDS: [&'static str; 12] = [
+            "ip:sip",
+            "digit",
+            "chars\\,",
+            "time<[,]>",
+            "http/request\"",
+            "3*chars",
+            "_^2",
+            "digit:code | digit_in([200,404])",
+            "chars | f_chars_in(_, [ GET , POST ])",
+            "json(chars@a, opt(digit)@b):obj",
+            "kv(ip@src)",
+            "symbol(<190>)",
+        ];
+        const METAS: [&'static str; 4] = ["", "alt", "some_of", "repeat(1..3)"];
+
+        fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+            items[self.rng.random_range(0..items.len())]
+        }
+
+        // 注释须独占行首
+        fn ws(&mut self) -> String {
+            match self.rng.random_range(0..6) {
+                0 => String::new(),
+                1 => " ".to_string(),
+                2 => "\n      ".to_string(),
+                3 => " \t ".to_string(),
+                4 => {
+                    self.comments += 1;
+                    format!("\n  // c{};\n ", self.comments)
+                }
+                _ => {
+                    self.comments += 1;
+                    format!("\n /* c{};\n  more\n*/ ", self.comments)
+                }
+            }
+        }
+
+        fn group(&mut self) -> String {
+            let mut out = format!("{}{}({}", self.pick(&Self::METAS), self.ws(), self.ws());
+            let count = self.rng.random_range(1..4);
+            for index in 0..count {
+                if index != 0 {
+                    out += &format!("{},{}", self.ws(), self.ws());
+                }
+                out += self.pick(&Self::FIELDS);
+            }
+            out += &self.ws();
+            out += ")";
+            out += self.pick(&["", "\\,", "\\|"]);
+            out
+        }
+
+        fn rule(&mut self, index: usize) -> String {
+            let ann = self.pick(&["", "#[priority(2)]\n", "#[tag(k:\"v\")] "]);
+            let mut out = format!("{}rule r{}{}{{{}", ann, index, self.ws(), self.ws());
+            out += self.pick(&["", "|decode/charset(gbk)|base64|", "|base64|"]);
+            let count = self.rng.random_range(1..4);
+            for i in 0..count {
+                out += &self.ws();
+                if i != 0 {
+                    out += &format!(",{}", self.ws());
+                }
+                out += &self.group();
+            }
+            out += &self.ws();
+            out += "}";
+            out
+        }
+
+        fn code(&mut self) -> String {
+            let count = self.rng.random_range(1..4);
+            let rules: Vec<String> = (0..count).map(|i| self.rule(i)).collect();
+            let body = rules
+                .into_iter()
+                .map(|rule| format!("{}{}", self.ws(), rule))
+                .collect::<String>();
+            if self.rng.random_bool(0.5) {
+                format!("{}\n", body)
+            } else {
+                format!("package p{}{{{}{}}}\n", self.ws(), body, self.ws())
+            }
+        }
+    }
+
+    /// 性质：format(format(x)) == format(x)，格式化前后语法树一致，注释不丢失
+    #[test]
+    fn test_format_properties() {
+        for seed in 0..300 {
+            let mut g = Gen {
+                rng: StdRng::seed_from_u64(seed),
+                comments: 0,
+            };
+            let code = g.code();
+            let once = text(&code);
+            assert_eq!(text(&once), once, "seed {}:\n{}", seed, code);
+            assert!(check_wpl(&once).assert());
+            assert_eq!(
+                parse_ast(&code),
+                parse_ast(&once),
+                "seed {}:\n{}",
+                seed,
+                code
+            );
+            for index in 1..=g.comments {
+                let mark = format!("c{};", index);
+                assert_eq!(once.matches(&mark).count(), 1, "seed {}:\n{}", seed, once);
+            }
+        }
+    }
+}
@@ -0,0 +1,492 @@
+use std::fmt::Display;
+
+use winnow::combinator::eof;
+use wp_parser::Parser;
+
+use crate::ast::group::{GroupSeq, WplGroup, WplGroupType};
+use crate::ast::{
+    AnnFun, DEFAULT_FIELD_KEY, DEFAULT_RESIDUE_RATIO, WplField, WplFieldFmt, WplFun, WplPipe,
+    WplRule, WplSep, WplStatementType, WplTestCase,
+};
+use crate::parser::utils::take_ref_path;
+use wp_model_core::model::DataType;
+
+const INDENT: &str = "  ";
+
+/// 规则体内的注释行，按其在源码中的位置挂到对应的管道/组/字段之前
+#[derive(Default)]
+pub(crate) struct RuleNotes {
+    pub pipe: Vec<String>,
+    pub groups: Vec<GroupNotes>,
+    pub close: Vec<String>,
+}
+
+#[derive(Default)]
+pub(crate) struct GroupNotes {
+    pub start: Vec<String>,
+    pub fields: Vec<Vec<String>>,
+    pub close: Vec<String>,
+}
+
+/// 规范 WPL 输出：两空格缩进、每行一个字段、注解按固定顺序输出
+#[derive(Default)]
+pub(crate) struct Printer {
+    out: String,
+    depth: usize,
+}
+
+impl Printer {
+    pub fn finish(self) -> String {
+        self.out
+    }
+
+    pub fn indent(&mut self) {
+        for _ in 0..self.depth {
+            self.out.push_str(INDENT);
+        }
+    }
+
+    pub fn line(&mut self, text: &str) {
+        self.indent();
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+
+    pub fn blank_line(&mut self) {
+        self.out.push('\n');
+    }
+
+    pub fn push(&mut self) {
+        self.depth += 1;
+    }
+
+    pub fn pop(&mut self) {
+        self.depth -= 1;
+    }
+
+    fn notes(&mut self, notes: &[String]) {
+        for note in notes {
+            self.line(note);
+        }
+    }
+
+    pub fn ann(&mut self, ann: &AnnFun) {
+        self.line(&ann_text(ann));
+    }
+
+    pub fn rule(&mut self, rule: &WplRule, notes: &RuleNotes) {
+        let WplStatementType::Express(express) = &rule.statement;
+        if let Some(tags) = &express.tags {
+            self.ann(tags);
+        }
+        self.line(&format!("rule {} {{", rule.name));
+        self.push();
+        self.notes(&notes.pipe);
+        if !express.pipe_process.is_empty() {
+            self.line(&format!("|{}|", express.pipe_process.join("|")));
+        }
+        for (index, group) in express.group.iter().enumerate() {
+            let group_notes = notes.groups.get(index);
+            if let Some(n) = group_notes {
+                self.notes(&n.start);
+                if group.fields.is_empty() {
+                    self.notes(&n.close);
+                }
+            }
+            self.indent();
+            self.group(group, group_notes);
+            if index + 1 < express.group.len() {
+                self.out.push(',');
+            }
+            self.out.push('\n');
+        }
+        self.notes(&notes.close);
+        self.pop();
+        self.line("}");
+    }
+
+    pub fn test_case(&mut self, case: &WplTestCase) {
+        match &case.rule {
+            Some(rule) => self.line(&format!("test {} {{", rule)),
+            None => self.line("test {"),
+        }
+        self.push();
+        self.line(&format!("input: {};", quote(&case.input)));
+        self.line("expect: {");
+        self.push();
+        for (index, field) in case.expect.iter().enumerate() {
+            let name = if is_ref_path(&field.name) {
+                field.name.to_string()
+            } else {
+                // 引号形式的名称解析时不做反转义，原样写回
+                format!("\"{}\"", field.name)
+            };
+            let sep = if index + 1 < case.expect.len() {
+                ","
+            } else {
+                ""
+            };
+            self.line(&format!(
+                "{}: {}({}){}",
+                name,
+                field.meta,
+                quote(&field.value),
+                sep
+            ));
+        }
+        self.pop();
+        self.line("}");
+        self.pop();
+        self.line("}");
+    }
+
+    // 调用前已输出缩进，结束时不换行；`notes` 仅用于规则的顶层组
+    fn group(&mut self, group: &WplGroup, notes: Option<&GroupNotes>) {
+        if group.meta != WplGroupType::Seq(GroupSeq) {
+            self.out.push_str(&group.meta.to_string());
+        }
+        if group.fields.is_empty() {
+            self.out.push_str("()");
+        } else {
+            self.out.push_str("(\n");
+            self.push();
+            for (index, field) in group.fields.iter().enumerate() {
+                if let Some(n) = notes.and_then(|n| n.fields.get(index)) {
+                    self.notes(n);
+                }
+                self.indent();
+                self.field(field, group.base_group_len);
+                if index + 1 < group.fields.len() {
+                    self.out.push(',');
+                }
+                self.out.push('\n');
+            }
+            if let Some(n) = notes {
+                self.notes(&n.close);
+            }
+            self.pop();
+            self.indent();
+            self.out.push(')');
+        }
+        if let Some(len) = group.base_group_len {
+            self.out.push_str(&format!("[{}]", len));
+        }
+        if let Some(sep) = &group.base_group_sep {
+            self.out.push_str(&sep_text(sep));
+        }
+    }
+
+    // 组长度会写入每个字段，与组长度相同的字段长度不再重复输出
+    fn field(&mut self, field: &WplField, group_len: Option<usize>) {
+        if field.continuous {
+            if let Some(cnt) = field.continuous_cnt {
+                self.out.push_str(&cnt.to_string());
+            }
+            self.out.push('*');
+        }
+        self.out.push_str(meta_text(field));
+        if let Some(content) = &field.content {
+            self.out.push_str(&format!("({})", content));
+        }
+        if let Some(subs) = &field.sub_fields {
+            self.out.push_str("(\n");
+            self.push();
+            let items = subs.conf_items();
+            let keys = items
+                .exact_iter()
+                .map(|(k, f)| (k, f))
+                .chain(items.wild_iter().map(|(k, _, f)| (k, f)));
+            let total = items.len();
+            for (index, (key, sub)) in keys.enumerate() {
+                self.indent();
+                self.sub_field(key, sub);
+                if index + 1 < total {
+                    self.out.push(',');
+                }
+                self.out.push('\n');
+            }
+            self.pop();
+            self.indent();
+            self.out.push(')');
+        }
+        if let Some(name) = &field.name {
+            self.out.push_str(&format!(":{}", name));
+        }
+        if let Some(len) = field.length
+            && Some(len) != group_len
+        {
+            self.out.push_str(&format!("[{}]", len));
+        }
+        self.field_tail(field);
+    }
+
+    fn sub_field(&mut self, key: &str, field: &WplField) {
+        if field.is_opt {
+            self.out.push_str(&format!("opt({})", meta_text(field)));
+        } else {
+            self.out.push_str(meta_text(field));
+        }
+        if let Some(content) = &field.content {
+            self.out.push_str(&format!("({})", content));
+        }
+        if key != DEFAULT_FIELD_KEY {
+            self.out.push_str(&format!("@{}", key));
+        }
+        if let Some(name) = &field.name {
+            self.out.push_str(&format!(":{}", name));
+        }
+        self.field_tail(field);
+    }
+
+    fn field_tail(&mut self, field: &WplField) {
+        self.out.push_str(&fmt_text(&field.fmt_conf));
+        if let Some(sep) = &field.separator {
+            self.out.push_str(&sep_text(sep));
+        }
+        for pipe in &field.pipe {
+            self.out.push_str(" | ");
+            match pipe {
+                WplPipe::Fun(fun) => self.out.push_str(&fun_text(fun)),
+                WplPipe::Group(group) => self.group(group, None),
+            }
+        }
+    }
+}
+
+// peek_symbol 解析后类型名被改写为 symbol，按类型还原
+fn meta_text(field: &WplField) -> &str {
+    if field.meta_type == DataType::PeekSymbol {
+        "peek_symbol"
+    } else {
+        field.meta_name.as_str()
+    }
+}
+
+fn fmt_text(fmt: &WplFieldFmt) -> String {
+    if let Some(cnt) = fmt.field_cnt {
+        return format!("^{}", cnt);
+    }
+    match (&fmt.scope_beg, &fmt.scope_end) {
+        (Some(beg), Some(end)) if beg == "\"" && end == "\"" => "\"".to_string(),
+        // 解析时按首个 `,` 切分，内容原样输出
+        (Some(beg), Some(end)) => format!("<{},{}>", beg, end),
+        _ => String::new(),
+    }
+}
+
+/// 分隔符逐字符写作 `\x`
+fn sep_text(sep: &WplSep) -> String {
+    if sep.is_unset() {
+        String::new()
+    } else if sep.is_to_end() {
+        "\\0".to_string()
+    } else {
+        sep.sep_str().chars().map(|c| format!("\\{}", c)).collect()
+    }
+}
+
+pub(crate) fn ann_text(ann: &AnnFun) -> String {
+    let mut items = Vec::new();
+    if !ann.tags.is_empty() {
+        let kvs: Vec<String> = ann
+            .tags
+            .iter()
+            .map(|(k, v)| format!("{}:{}", k, quote(v)))
+            .collect();
+        items.push(format!("tag({})", kvs.join(", ")));
+    }
+    if let Some((k, v)) = &ann.copy_raw {
+        items.push(format!("copy_raw({}:{})", k, quote(v)));
+    }
+    if let Some(priority) = ann.priority {
+        items.push(format!("priority({})", priority));
+    }
+    if let Some(conf) = &ann.matching {
+        if conf.residue == DEFAULT_RESIDUE_RATIO {
+            items.push(format!("match({})", conf.policy.name()));
+        } else {
+            items.push(format!(
+                "match({}, residue: {:?})",
+                conf.policy.name(),
+                conf.residue
+            ));
+        }
+    }
+    if let Some(prefix) = &ann.copy_tags {
+        if prefix.is_empty() {
+            items.push("copy_tags()".to_string());
+        } else {
+            items.push(format!("copy_tags(prefix:{})", quote(prefix)));
+        }
+    }
+    if let Some(name) = &ann.ingest_time {
+        items.push(format!("ingest_time(name:{})", quote(name)));
+    }
+    if let Some(fp) = &ann.fingerprint {
+        let mut args = vec![format!("name:{}", quote(&fp.name))];
+        if !fp.fields.is_empty() {
+            let fields: Vec<&str> = fp.fields.iter().map(|f| f.as_str()).collect();
+            args.push(format!("fields:[{}]", fields.join(", ")));
+        }
+        args.push(format!("algo:{}", quote(fp.algo.name())));
+        items.push(format!("fingerprint({})", args.join(", ")));
+    }
+    format!("#[{}]", items.join(", "))
+}
+
+fn target(t: &Option<smol_str::SmolStr>) -> &str {
+    t.as_deref().unwrap_or("_")
+}
+
+fn arr<T: Display>(items: &[T]) -> String {
+    let items: Vec<String> = items.iter().map(|x| x.to_string()).collect();
+    format!("[{}]", items.join(", "))
+}
+
+/// 正则原样输出：优先 `'...'`，含单引号时用 `r#"..."#`
+fn regex_text(pattern: &str) -> String {
+    if !pattern.contains('\'') {
+        format!("'{}'", pattern)
+    } else if !pattern.contains("\"#") {
+        format!("r#\"{}\"#", pattern)
+    } else {
+        format!("\"{}\"", pattern)
+    }
+}
+
+pub(crate) fn fun_text(fun: &WplFun) -> String {
+    match fun {
+        WplFun::SelectTake(x) => format!("take({})", x.target),
+        WplFun::SelectLast(_) => "last()".to_string(),
+        WplFun::TargetCharsHas(x) => {
+            format!("f_chars_has({}, {})", target(&x.target), x.value)
+        }
+        WplFun::CharsHas(x) => format!("chars_has({})", x.value),
+        WplFun::TargetCharsNotHas(x) => {
+            format!("f_chars_not_has({}, {})", target(&x.target), x.value)
+        }
+        WplFun::CharsNotHas(x) => format!("chars_not_has({})", x.value),
+        WplFun::TargetCharsIn(x) => {
+            format!("f_chars_in({}, {})", target(&x.target), arr(&x.value))
+        }
+        WplFun::CharsIn(x) => format!("chars_in({})", arr(&x.value)),
+        WplFun::TargetDigitHas(x) => {
+            format!("f_digit_has({}, {})", target(&x.target), x.value)
+        }
+        WplFun::DigitHas(x) => format!("digit_has({})", x.value),
+        WplFun::TargetDigitIn(x) => {
+            format!("f_digit_in({}, {})", target(&x.target), arr(&x.value))
+        }
+        WplFun::DigitIn(x) => format!("digit_in({})", arr(&x.value)),
+        WplFun::TargetIpIn(x) => format!("f_ip_in({}, {})", target(&x.target), arr(&x.value)),
+        WplFun::IpIn(x) => format!("ip_in({})", arr(&x.value)),
+        WplFun::TargetIpInCidr(x) => {
+            format!("f_ip_in_cidr({}, {})", target(&x.target), arr(&x.value))
+        }
+        WplFun::IpInCidr(x) => format!("ip_in_cidr({})", arr(&x.value)),
+        WplFun::TargetRegexMatch(x) => format!(
+            "f_regex_match({}, {})",
+            target(&x.target),
+            regex_text(&x.value.pattern)
+        ),
+        WplFun::RegexMatch(x) => format!("regex_match({})", regex_text(&x.value.pattern)),
+        WplFun::TargetDigitRange(x) => {
+            format!("f_digit_range({}, {}, {})", target(&x.target), x.lo, x.hi)
+        }
+        WplFun::DigitRange(x) => format!("digit_range({}, {})", x.lo, x.hi),
+        WplFun::TargetCharsLen(x) => {
+            format!("f_chars_len({}, {}, {})", target(&x.target), x.min, x.max)
+        }
+        WplFun::CharsLen(x) => format!("chars_len({}, {})", x.min, x.max),
+        WplFun::TargetStartsWith(x) => {
+            format!("f_starts_with({}, {})", target(&x.target), quote(&x.value))
+        }
+        WplFun::StartsWith(x) => format!("starts_with({})", quote(&x.value)),
+        WplFun::TargetEndsWith(x) => {
+            format!("f_ends_with({}, {})", target(&x.target), quote(&x.value))
+        }
+        WplFun::EndsWith(x) => format!("ends_with({})", quote(&x.value)),
+        WplFun::TargetContains(x) => {
+            format!("f_contains({}, {})", target(&x.target), quote(&x.value))
+        }
+        WplFun::Contains(x) => format!("contains({})", quote(&x.value)),
+        WplFun::TargetHas(x) => format!("f_has({})", target(&x.target)),
+        WplFun::Has(_) => "has()".to_string(),
+        WplFun::TransJsonUnescape(_) => "json_unescape()".to_string(),
+        WplFun::TransBase64Decode(_) => "base64_decode()".to_string(),
+        WplFun::Wasm(x) => format!("wasm({})", x.plugin),
+    }
+}
+
+/// 双引号字符串，转义规则与 `decode_escapes` 对应
+pub(crate) fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c if c.is_ascii_control() => out.push_str(&format!("\\x{:02x}", c as u8)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn is_ref_path(name: &str) -> bool {
+    (take_ref_path, eof).parse(name).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::wpl_fun::wpl_fun;
+    use orion_error::TestAssert;
+
+    #[test]
+    fn test_fun_text_round_trip() {
+        let funs = [
+            "take(src)",
+            "last()",
+            "f_chars_has(_, GET)",
+            "chars_not_has(POST)",
+            "f_chars_in(method, [GET, POST])",
+            "digit_in([200, 404])",
+            "f_digit_has(code, 200)",
+            "ip_in([10.0.0.1, ::1])",
+            "f_ip_in_cidr(src, [10.0.0.0/8, 192.168.1.1/32])",
+            "regex_match('^a\\d+$')",
+            "f_regex_match(_, r#\"it's\"#)",
+            "digit_range(-5, 10)",
+            "f_chars_len(name, 1, 8)",
+            "starts_with(\"a \\\"b\\\"\\t\")",
+            "f_contains(path, \"/api\")",
+            "f_has(x)",
+            "has()",
+            "json_unescape()",
+            "base64_decode()",
+            "wasm(mask)",
+        ];
+        for src in funs {
+            let fun = wpl_fun.parse(src).assert();
+            let text = fun_text(&fun);
+            assert_eq!(text, src);
+            assert_eq!(wpl_fun.parse(text.as_str()).assert(), fun);
+        }
+        // 数组参数的逗号前后空白统一为 `, `
+        let fun = wpl_fun.parse("f_digit_in(code, [200 ,404,  500])").assert();
+        assert_eq!(fun_text(&fun), "f_digit_in(code, [200, 404, 500])");
+    }
+
+    #[test]
+    fn test_quote_escapes() {
+        assert_eq!(quote("a\"b\\c\n"), r#""a\"b\\c\n""#);
+        assert_eq!(quote("\u{1}"), r#""\x01""#);
+        assert!(is_ref_path("a/b[0]"));
+        assert!(!is_ref_path("a b"));
+    }
+}
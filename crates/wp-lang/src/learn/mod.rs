@@ -0,0 +1,443 @@
+//! 从样本日志推断 WPL 规则（`wpl learn`）
+//!
+//! 流程：选择分隔符 → 顶层切分 → 逐列用 `auto` 解析器识别类型 → 跨样本合并列类型
+//! → 生成规则 → 回放样本校验覆盖率。
+
+mod token;
+
+use std::collections::BTreeMap;
+
+use orion_error::ToStructError;
+use wp_model_core::model::{DataField, DataType};
+
+use crate::ast::group::{GroupSeq, WplGroupType};
+use crate::ast::{DEFAULT_META_NAME, WplField, WplSep};
+use crate::eval::FieldEvalUnit;
+use crate::parser::error::{WplCodeReason, WplCodeResult};
+use crate::{ParserFactory, WplEvaluator};
+pub use token::{SEP_CANDIDATES, Token, TokenWrap, detect_sep, split_top};
+
+/// 推断出的列类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LearnKind {
+    Ignore,
+    Ip,
+    Time,
+    Digit,
+    Float,
+    Hex,
+    Kv,
+    Json,
+    Chars,
+}
+
+impl LearnKind {
+    pub fn wpl_type(&self) -> &'static str {
+        match self {
+            LearnKind::Ignore => "_",
+            LearnKind::Ip => "ip",
+            LearnKind::Time => "time",
+            LearnKind::Digit => "digit",
+            LearnKind::Float => "float",
+            LearnKind::Hex => "hex",
+            LearnKind::Kv => "kv",
+            LearnKind::Json => "json",
+            LearnKind::Chars => "chars",
+        }
+    }
+
+    /// kv / json 展开为多个字段，不另行命名
+    fn named(&self) -> bool {
+        !matches!(self, LearnKind::Ignore | LearnKind::Kv | LearnKind::Json)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LearnColumn {
+    pub field: String,
+    pub kind: LearnKind,
+    pub wrap: TokenWrap,
+    pub example: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleStatus {
+    /// 完整解析，无剩余
+    Full,
+    /// 解析成功但有剩余
+    Partial,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleCheck {
+    pub line: String,
+    pub status: SampleStatus,
+    pub residue: String,
+    /// 列数与多数样本一致
+    pub aligned: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct LearnReport {
+    pub rule: String,
+    pub separator: char,
+    pub columns: Vec<LearnColumn>,
+    pub samples: Vec<SampleCheck>,
+}
+
+impl LearnReport {
+    pub fn matched(&self) -> usize {
+        self.samples
+            .iter()
+            .filter(|s| s.status == SampleStatus::Full)
+            .count()
+    }
+
+    /// 完整解析的样本占比（0.0 ~ 1.0）
+    pub fn coverage(&self) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        self.matched() as f64 / self.samples.len() as f64
+    }
+}
+
+/// 单个样本切分后的一列
+#[derive(Debug, Clone)]
+struct Cell<'a> {
+    text: &'a str,
+    wrap: TokenWrap,
+    kind: LearnKind,
+}
+
+struct Classifier {
+    auto: FieldEvalUnit,
+}
+
+impl Classifier {
+    fn new() -> WplCodeResult<Self> {
+        let auto = FieldEvalUnit::new(
+            0,
+            WplField::default(),
+            ParserFactory::crate_auto()?,
+            WplGroupType::Seq(GroupSeq),
+        );
+        Ok(Self { auto })
+    }
+
+    // 以 auto 组合解析器解析单列，返回字段与剩余文本
+    fn auto_parse<'a>(&self, text: &'a str) -> Option<(Vec<DataField>, &'a str)> {
+        let mut data = text;
+        let mut out = Vec::new();
+        self.auto
+            .parse(
+                &WplSep::default(),
+                &mut data,
+                Some(DEFAULT_META_NAME.into()),
+                &mut out,
+            )
+            .ok()?;
+        Some((out, data))
+    }
+
+    fn classify(&self, text: &str) -> LearnKind {
+        let text = text.trim();
+        if text == "-" {
+            return LearnKind::Ignore;
+        }
+        if text.is_empty() {
+            return LearnKind::Chars;
+        }
+        // auto 解析器中 float 先于 digit，整数需单独识别
+        if text.parse::<i64>().is_ok() {
+            return LearnKind::Digit;
+        }
+        let Some((items, residue)) = self.auto_parse(text) else {
+            return LearnKind::Chars;
+        };
+        if !residue.trim().is_empty() {
+            return LearnKind::Chars;
+        }
+        if text.starts_with('{') && !items.is_empty() {
+            return LearnKind::Json;
+        }
+        let Some(first) = items.first() else {
+            return LearnKind::Chars;
+        };
+        match first.get_meta() {
+            DataType::IP => LearnKind::Ip,
+            DataType::Time => LearnKind::Time,
+            DataType::Digit => LearnKind::Digit,
+            DataType::Float => LearnKind::Float,
+            DataType::Hex => LearnKind::Hex,
+            _ if first.get_name() != DEFAULT_META_NAME => LearnKind::Kv,
+            _ => LearnKind::Chars,
+        }
+    }
+
+    /// 切分一行；空格分隔时把能组成时间的相邻两列合并（如 `2023-05-15 14:19:16`）
+    fn cells<'a>(&self, line: &'a str, sep: char) -> Vec<Cell<'a>> {
+        let tokens = split_top(line, sep);
+        let mut cells = Vec::with_capacity(tokens.len());
+        let mut i = 0;
+        while i < tokens.len() {
+            let cur = &tokens[i];
+            if sep == ' '
+                && let Some(next) = tokens.get(i + 1)
+                && cur.wrap == TokenWrap::Plain
+                && next.wrap == TokenWrap::Plain
+            {
+                let joined = &line[cur.start..next.end];
+                if self.classify(cur.inner) != LearnKind::Time
+                    && self.classify(joined) == LearnKind::Time
+                {
+                    cells.push(Cell {
+                        text: joined,
+                        wrap: TokenWrap::Plain,
+                        kind: LearnKind::Time,
+                    });
+                    i += 2;
+                    continue;
+                }
+            }
+            cells.push(Cell {
+                text: cur.inner,
+                wrap: cur.wrap,
+                kind: self.classify(cur.inner),
+            });
+            i += 1;
+        }
+        cells
+    }
+}
+
+/// 跨样本合并同一列的类型
+fn merge_kind(kinds: &[LearnKind]) -> LearnKind {
+    let first = kinds[0];
+    if kinds.iter().all(|k| *k == first) {
+        return first;
+    }
+    if kinds
+        .iter()
+        .all(|k| matches!(k, LearnKind::Digit | LearnKind::Float))
+    {
+        return LearnKind::Float;
+    }
+    LearnKind::Chars
+}
+
+fn field_expr(col: usize, kind: LearnKind, wrap: TokenWrap) -> String {
+    let mut expr = String::from(kind.wpl_type());
+    if kind.named() {
+        expr.push_str(&format!(":{}_{}", kind.wpl_type(), col));
+    }
+    match wrap {
+        TokenWrap::Quote => expr.push('"'),
+        TokenWrap::Bracket => expr.push_str("<[,]>"),
+        TokenWrap::Plain => {}
+    }
+    expr
+}
+
+fn rule_text(name: &str, sep: char, fields: &[String]) -> String {
+    let sep_suffix = if sep == ' ' {
+        String::new()
+    } else {
+        format!("\\{sep}")
+    };
+    format!(
+        "rule {name} {{\n    ({}){sep_suffix}\n}}",
+        fields.join(", ")
+    )
+}
+
+/// 根据样本推断规则并回放校验
+pub fn learn_rule(name: &str, samples: &[&str]) -> WplCodeResult<LearnReport> {
+    let samples: Vec<&str> = samples
+        .iter()
+        .map(|s| s.trim_end_matches(['\r', '\n']))
+        .filter(|s| !s.trim().is_empty())
+        .collect();
+    if samples.is_empty() {
+        return WplCodeReason::Empty("no sample lines".into()).err_result();
+    }
+    let separator = detect_sep(&samples);
+    let classifier = Classifier::new()?;
+    let rows: Vec<Vec<Cell>> = samples
+        .iter()
+        .map(|s| classifier.cells(s, separator))
+        .collect();
+
+    // 以出现最多的列数为准，其余样本视为未对齐
+    let mut width_count: BTreeMap<usize, usize> = BTreeMap::new();
+    for row in &rows {
+        *width_count.entry(row.len()).or_default() += 1;
+    }
+    let width = width_count
+        .iter()
+        .max_by_key(|(w, c)| (**c, **w))
+        .map(|(w, _)| *w)
+        .unwrap_or_default();
+    let aligned: Vec<&Vec<Cell>> = rows.iter().filter(|r| r.len() == width).collect();
+
+    let mut columns = Vec::with_capacity(width);
+    for col in 0..width {
+        let cells: Vec<&Cell> = aligned.iter().map(|r| &r[col]).collect();
+        let kinds: Vec<LearnKind> = cells.iter().map(|c| c.kind).collect();
+        let kind = merge_kind(&kinds);
+        let wrap = if cells.iter().all(|c| c.wrap == cells[0].wrap) {
+            cells[0].wrap
+        } else {
+            TokenWrap::Plain
+        };
+        let example = cells
+            .iter()
+            .find(|c| c.kind != LearnKind::Ignore)
+            .unwrap_or(&cells[0])
+            .text
+            .to_string();
+        columns.push(LearnColumn {
+            field: field_expr(col, kind, wrap),
+            kind,
+            wrap,
+            example,
+        });
+    }
+
+    let fields: Vec<String> = columns.iter().map(|c| c.field.clone()).collect();
+    let rule = rule_text(name, separator, &fields);
+    let evaluator = WplEvaluator::from_code(&rule)?;
+    let samples = samples
+        .iter()
+        .zip(rows.iter())
+        .map(|(line, row)| {
+            let (status, residue) = match evaluator.proc(line.to_string(), 0) {
+                Ok((_, residue)) if residue.trim().is_empty() => (SampleStatus::Full, residue),
+                Ok((_, residue)) => (SampleStatus::Partial, residue),
+                Err(e) => (SampleStatus::Failed, e.to_string()),
+            };
+            SampleCheck {
+                line: line.to_string(),
+                status,
+                residue,
+                aligned: row.len() == width,
+            }
+        })
+        .collect();
+
+    Ok(LearnReport {
+        rule,
+        separator,
+        columns,
+        samples,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orion_error::TestAssert;
+
+    #[test]
+    fn test_classify() {
+        let c = Classifier::new().assert();
+        assert_eq!(c.classify("192.168.1.2"), LearnKind::Ip);
+        assert_eq!(c.classify("200"), LearnKind::Digit);
+        assert_eq!(c.classify("3.14"), LearnKind::Float);
+        assert_eq!(c.classify("-"), LearnKind::Ignore);
+        assert_eq!(c.classify("2023-05-15 14:19:16"), LearnKind::Time);
+        assert_eq!(c.classify("user=root"), LearnKind::Kv);
+        assert_eq!(c.classify(r#"{"a": 1}"#), LearnKind::Json);
+        assert_eq!(c.classify("GET / HTTP/1.1"), LearnKind::Chars);
+    }
+
+    #[test]
+    fn test_merge_kind() {
+        use LearnKind::*;
+        assert_eq!(merge_kind(&[Digit, Digit]), Digit);
+        assert_eq!(merge_kind(&[Digit, Float]), Float);
+        assert_eq!(merge_kind(&[Ignore, Ignore]), Ignore);
+        assert_eq!(merge_kind(&[Ignore, Ip]), Chars);
+    }
+
+    #[test]
+    fn test_learn_space_log() {
+        let samples = [
+            r#"192.168.1.2 - [06/Aug/2019:12:12:19 +0800] "GET /a.png HTTP/1.1" 200 368"#,
+            r#"10.0.0.8 - [06/Aug/2019:12:13:01 +0800] "POST /login HTTP/1.1" 302 0"#,
+            r#"10.0.0.9 - [06/Aug/2019:12:13:05 +0800] "GET / HTTP/1.1" 404 12"#,
+        ];
+        let report = learn_rule("nginx", &samples).assert();
+        assert_eq!(report.separator, ' ');
+        let kinds: Vec<LearnKind> = report.columns.iter().map(|c| c.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                LearnKind::Ip,
+                LearnKind::Ignore,
+                LearnKind::Time,
+                LearnKind::Chars,
+                LearnKind::Digit,
+                LearnKind::Digit
+            ]
+        );
+        assert_eq!(
+            report.rule,
+            "rule nginx {\n    (ip:ip_0, _, time:time_2<[,]>, chars:chars_3\", digit:digit_4, digit:digit_5)\n}"
+        );
+        assert_eq!(report.matched(), 3);
+        assert_eq!(report.coverage(), 1.0);
+    }
+
+    #[test]
+    fn test_learn_csv() {
+        let samples = [
+            "2023-05-15 14:19:16,10.180.8.8,alarm,3",
+            "2023-05-15 14:20:01,10.180.8.9,alarm,4.5",
+            "2023-05-15 14:21:44,10.180.8.10,warn,7",
+        ];
+        let report = learn_rule("csv", &samples).assert();
+        assert_eq!(report.separator, ',');
+        assert_eq!(
+            report.rule,
+            "rule csv {\n    (time:time_0, ip:ip_1, chars:chars_2, float:float_3)\\,\n}"
+        );
+        assert_eq!(report.coverage(), 1.0);
+        assert!(report.samples.iter().all(|s| s.aligned));
+    }
+
+    #[test]
+    fn test_learn_unaligned() {
+        let samples = ["a|1|x", "b|2|y", "c|3"];
+        let report = learn_rule("p", &samples).assert();
+        assert_eq!(report.separator, '|');
+        assert_eq!(report.columns.len(), 3);
+        assert!(!report.samples[2].aligned);
+        assert!(report.matched() < 3);
+
+        assert!(learn_rule("p", &["", "  "]).is_err());
+    }
+
+    #[test]
+    fn test_learn_tsv() {
+        let samples = [
+            "2023-05-15 14:19:16\t10.180.8.8\tlogin ok, a\t3",
+            "2023-05-15 14:20:01\t10.180.8.9\tlogout, b\t4",
+        ];
+        let report = learn_rule("tsv", &samples).assert();
+        assert_eq!(report.separator, '\t');
+        let kinds: Vec<LearnKind> = report.columns.iter().map(|c| c.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                LearnKind::Time,
+                LearnKind::Ip,
+                LearnKind::Chars,
+                LearnKind::Digit
+            ]
+        );
+        assert!(report.rule.ends_with(")\\\t\n}"), "{}", report.rule);
+        assert_eq!(report.coverage(), 1.0);
+    }
+}
@@ -0,0 +1,133 @@
+/// 字段外层包裹：影响生成规则中的格式后缀（`"` / `<[,]>`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenWrap {
+    Plain,
+    Quote,
+    Bracket,
+}
+
+/// 一行样本中的顶层记号；`start..end` 为含包裹符的原始区间
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token<'a> {
+    pub start: usize,
+    pub end: usize,
+    pub inner: &'a str,
+    pub wrap: TokenWrap,
+}
+
+/// 候选分隔符，按优先级排列；制表符最强，空格最弱
+pub const SEP_CANDIDATES: [char; 5] = ['\t', ',', '|', ';', ' '];
+
+/// 按分隔符切分，忽略引号与括号内部的分隔符；空格分隔时合并连续空格
+pub fn split_top(line: &str, sep: char) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut depth = 0usize;
+    let mut in_quote = false;
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, c) in line.char_indices() {
+        if in_quote {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_quote = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_quote = true,
+            '[' | '{' | '(' => depth += 1,
+            ']' | '}' | ')' => depth = depth.saturating_sub(1),
+            _ if c == sep && depth == 0 => {
+                push_token(line, start, i, sep, &mut tokens);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    push_token(line, start, line.len(), sep, &mut tokens);
+    tokens
+}
+
+fn push_token<'a>(line: &'a str, start: usize, end: usize, sep: char, out: &mut Vec<Token<'a>>) {
+    let raw = &line[start..end];
+    let trimmed = raw.trim();
+    if trimmed.is_empty() && sep == ' ' {
+        return;
+    }
+    let start = start + (raw.len() - raw.trim_start().len());
+    let end = start + trimmed.len();
+    let wrapped = |open: char, close: char| {
+        trimmed.len() >= 2 && trimmed.starts_with(open) && trimmed.ends_with(close)
+    };
+    let (inner, wrap) = if wrapped('"', '"') {
+        (&trimmed[1..trimmed.len() - 1], TokenWrap::Quote)
+    } else if wrapped('[', ']') {
+        (&trimmed[1..trimmed.len() - 1], TokenWrap::Bracket)
+    } else {
+        (trimmed, TokenWrap::Plain)
+    };
+    out.push(Token {
+        start,
+        end,
+        inner,
+        wrap,
+    });
+}
+
+/// 选择在所有样本中切分出相同列数（且多于一列）的分隔符；都不一致时取最少列数最大者
+pub fn detect_sep(samples: &[&str]) -> char {
+    let mut best = (' ', 0usize);
+    for sep in SEP_CANDIDATES {
+        let counts: Vec<usize> = samples.iter().map(|s| split_top(s, sep).len()).collect();
+        let min = counts.iter().copied().min().unwrap_or(0);
+        if min > 1 && counts.iter().all(|c| *c == min) {
+            return sep;
+        }
+        if min > best.1 {
+            best = (sep, min);
+        }
+    }
+    best.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_top() {
+        let line = r#"1.2.3.4 - [10/Oct/2000:13:55:36 -0700] "GET / HTTP/1.1" 200 {"a": [1, 2]}"#;
+        let tokens = split_top(line, ' ');
+        let inner: Vec<&str> = tokens.iter().map(|t| t.inner).collect();
+        assert_eq!(
+            inner,
+            vec![
+                "1.2.3.4",
+                "-",
+                "10/Oct/2000:13:55:36 -0700",
+                "GET / HTTP/1.1",
+                "200",
+                r#"{"a": [1, 2]}"#
+            ]
+        );
+        assert_eq!(tokens[2].wrap, TokenWrap::Bracket);
+        assert_eq!(tokens[3].wrap, TokenWrap::Quote);
+        assert_eq!(&line[tokens[3].start..tokens[3].end], r#""GET / HTTP/1.1""#);
+
+        let tokens = split_top(r#"a,"x,y",,c"#, ',');
+        assert_eq!(tokens.len(), 4);
+        assert_eq!(tokens[1].inner, "x,y");
+        assert_eq!(tokens[2].inner, "");
+    }
+
+    #[test]
+    fn test_detect_sep() {
+        assert_eq!(detect_sep(&["a,b c,d", "e,f g,h"]), ',');
+        assert_eq!(detect_sep(&["a|b|c", "d|e|f"]), '|');
+        assert_eq!(detect_sep(&["a b c", "d e"]), ' ');
+        // 制表符分隔的列内可含空格与逗号
+        assert_eq!(detect_sep(&["a b\tc,d\te", "f\tg h\ti,j"]), '\t');
+    }
+}
@@ -7,25 +7,28 @@ extern crate winnow;
 
 mod ast;
 pub mod eval;
+pub mod formatter;
 pub mod parser;
 #[macro_use]
 pub mod macro_def;
 //mod checker;
 pub mod generator;
+pub mod learn;
 mod pkg;
 pub mod precompile;
 mod setting;
 //pub mod traits;
 pub mod types;
 pub mod util;
-//pub mod wasm;
+pub mod wasm;
 
 pub use ast::WplCode;
 pub use ast::WplRule;
+pub use ast::WplSchema;
 pub use ast::WplSep;
 pub use ast::WplStatementType;
 pub use ast::ann_func::{AnnotationFunc, AnnotationType};
-pub use ast::{WplExpress, WplPackage, WplPkgMeta};
+pub use ast::{WplExpectField, WplExpress, WplPackage, WplPkgMeta, WplTestCase};
 pub use eval::DataTypeParser;
 pub use eval::OPTIMIZE_TIMES;
 pub use eval::PipeLineResult;
@@ -35,6 +38,7 @@ pub use eval::builtins::registry::{
     register_pipe_unit as register_preorder_pipe_unit,
     register_wpl_pipe_batch as register_preorder_pipe_unit_batch,
 };
+pub use eval::{ParseProfiler, ProfileHook, ProfileReport};
 // Note: DataResult is now provided by wp-parse-api for plugin development
 pub use eval::{WparseError, WparseReason, WparseResult};
 pub use parser::error::error_detail;
@@ -46,6 +50,7 @@ pub use pkg::gen_pkg_id;
 pub use setting::{PattenMode, WplSetting, check_level_or_stop};
 //pub use engine::field::parser::base::DigitRange;
 pub use ast::AnnFun;
+pub use ast::{WplMatchConf, WplMatchPolicy, WplMatchRank};
 pub use eval::ParserFactory;
 pub use parser::error::{WplCodeError, WplCodeResult};
 pub use precompile::{CompiledRule as WplCompiledRule, compile_rule as wpl_compile_rule};
@@ -1,4 +1,5 @@
 use crate::DataTypeParser;
+use crate::ast::WplExtType;
 use crate::parser::utils::take_meta_name;
 use smol_str::SmolStr;
 use std::fmt::Display;
@@ -31,6 +32,35 @@ pub fn take_datatype_impl(data: &mut &str) -> WResult<DataType> {
     }
 }
 
+/// 字段类型：先按 `DataType` 解析，失败时再匹配 WPL 扩展类型（如 `xml`）。
+/// 返回 (承载类型, 类型名)，类型名即 `WplField::meta_name`。
+pub fn take_field_meta(data: &mut &str) -> WResult<(DataType, FNameStr)> {
+    take_field_meta_impl
+        .context(StrContext::Label("<datatype>"))
+        .parse_next(data)
+}
+
+fn take_field_meta_impl(data: &mut &str) -> WResult<(DataType, FNameStr)> {
+    let _ = multispace0.parse_next(data)?;
+    let cp = data.checkpoint();
+    let meta_str = take_meta_name.parse_next(data)?;
+    if let Some(meta) = meta_of_name(meta_str) {
+        Ok(meta)
+    } else {
+        data.reset(&cp);
+        fail.context(ctx_desc("DataType from str fail"))
+            .parse_next(&mut "")
+    }
+}
+
+pub fn meta_of_name(meta_str: &str) -> Option<(DataType, FNameStr)> {
+    if let Ok(meta) = DataType::from(meta_str) {
+        let name = FNameStr::from(meta.static_name());
+        return Some((meta, name));
+    }
+    WplExtType::from_name(meta_str).map(|ext| (ext.carrier(), FNameStr::from(ext.name())))
+}
+
 pub fn field_ins<N: Into<FNameStr>, V: Into<SmolStr> + Display>(
     meta: DataType,
     name: N,
@@ -1,6 +1,7 @@
 use crate::ast::WplExpress;
 use crate::parser::utils::is_sep_next;
-use crate::parser::wpl_group::wpl_group;
+use crate::parser::wpl_group::wpl_group_with;
+use crate::parser::wpl_pkg::PkgTypeScope;
 use crate::parser::wpl_rule;
 use crate::types::AnyResult;
 use anyhow::anyhow;
@@ -15,12 +16,19 @@ use super::wpl_anno::ann_fun;
 //parentheses
 
 pub fn wpl_express(input: &mut &str) -> WResult<WplExpress> {
+    wpl_express_with(input, None)
+}
+
+pub(crate) fn wpl_express_with(
+    input: &mut &str,
+    types: Option<&PkgTypeScope>,
+) -> WResult<WplExpress> {
     let mut rule = WplExpress::default();
     if let Some(mut pipe) = opt(wpl_rule::pip_proc).parse_next(input)? {
         rule.pipe_process.append(&mut pipe);
     }
     loop {
-        wpl_group
+        (|i: &mut &str| wpl_group_with(i, types))
             .context(ctx_desc("group"))
             .map(|x| rule.group.push(x))
             .parse_next(input)?;
@@ -552,4 +560,32 @@ package test {
         let packages = wpl_package.parse(data).assert();
         assert_eq!(packages.to_string(), expect);
     }
+
+    #[test]
+    fn test_match_policy_annotation() {
+        let data = r#"
+#[match(priority, residue: 0.1)]
+package test {
+        #[priority(10)]
+        rule high { (digit:id) }
+        rule low { (digit:id) }
+}
+    "#;
+        let package = wpl_package.parse(data).assert();
+        let high = &package.rules[0];
+        let low = &package.rules[1];
+        assert_eq!(high.priority(), 10);
+        assert_eq!(low.priority(), 0);
+        assert_eq!(high.match_conf(), low.match_conf());
+        assert_eq!(
+            low.match_conf().policy,
+            crate::ast::WplMatchPolicy::Priority
+        );
+        assert_eq!(low.match_conf().residue, 0.1);
+
+        let printed = package.to_string();
+        assert!(printed.contains("#[priority(10), match(priority, residue: 0.1)]"));
+        let reparsed = wpl_package.parse(printed.as_str()).assert();
+        assert_eq!(reparsed.rules[0].priority(), 10);
+    }
 }
@@ -169,7 +169,8 @@ pub fn take_parentheses<'a>(input: &mut &'a str) -> WResult<&'a str> {
 }
 
 // #[tag(tag : "hello", raw_copy : "raw" ), copy_raw(name:"hello")]
-fn decode_escapes(s: &str) -> String {
+/// 还原 `quot_str` 内容中的转义（\" \\ \n \t \r \xHH）
+pub fn decode_escapes(s: &str) -> String {
     let mut out: Vec<u8> = Vec::with_capacity(s.len());
     let mut it = s.chars().peekable();
     while let Some(c) = it.next() {
@@ -1,10 +1,13 @@
 use crate::ast::AnnEnum;
 use crate::ast::AnnFun;
 use crate::ast::TagKvs;
+use crate::ast::{
+    DEFAULT_RESIDUE_RATIO, FingerprintAlgo, FingerprintConf, WplMatchConf, WplMatchPolicy,
+};
 use crate::parser::utils;
 use smol_str::SmolStr;
-use winnow::ascii::multispace0;
-use winnow::combinator::{alt, cut_err, delimited, separated};
+use winnow::ascii::{alpha1, dec_int, float, multispace0};
+use winnow::combinator::{alt, cut_err, delimited, opt, preceded, separated};
 use winnow::token::literal;
 use wp_parser::Parser;
 use wp_parser::WResult;
@@ -35,13 +38,175 @@ fn copy_raw(input: &mut &str) -> WResult<AnnEnum> {
     Ok(AnnEnum::Copy(obj))
 }
 
+fn priority(input: &mut &str) -> WResult<AnnEnum> {
+    let val: i64 = delimited(
+        (multispace0, literal("priority"), multispace0, literal('(')),
+        cut_err(delimited(multispace0, dec_int, multispace0)).context(ctx_desc("priority(<int>)")),
+        literal(')'),
+    )
+    .parse_next(input)?;
+    Ok(AnnEnum::Priority(val))
+}
+
+fn match_conf(input: &mut &str) -> WResult<WplMatchConf> {
+    multispace0.parse_next(input)?;
+    let policy = alpha1
+        .verify_map(WplMatchPolicy::from_name)
+        .parse_next(input)?;
+    let residue = opt(preceded(
+        (
+            multispace0,
+            literal(','),
+            multispace0,
+            literal("residue"),
+            multispace0,
+            literal(':'),
+            multispace0,
+        ),
+        float.verify(|v: &f64| (0.0..=1.0).contains(v)),
+    ))
+    .parse_next(input)?;
+    multispace0.parse_next(input)?;
+    Ok(WplMatchConf {
+        policy,
+        residue: residue.unwrap_or(DEFAULT_RESIDUE_RATIO),
+    })
+}
+
+// match(first|priority|longest|strict, residue: 0.2)
+fn match_policy(input: &mut &str) -> WResult<AnnEnum> {
+    let conf = delimited(
+        (multispace0, literal("match"), multispace0, literal('(')),
+        cut_err(match_conf).context(ctx_desc(
+            "match(first|priority|longest|strict, residue: <0.0..1.0>)",
+        )),
+        literal(')'),
+    )
+    .parse_next(input)?;
+    Ok(AnnEnum::Match(conf))
+}
+
+// copy_tags(prefix:"src_") / copy_tags()
+fn copy_tags(input: &mut &str) -> WResult<AnnEnum> {
+    let prefix = delimited(
+        (multispace0, literal("copy_tags"), multispace0, literal('(')),
+        cut_err(opt(
+            utils::take_tag_kv.verify(|(k, _): &(SmolStr, SmolStr)| k == "prefix")
+        ))
+        .context(ctx_desc("copy_tags(prefix: \"...\")")),
+        (multispace0, literal(')')),
+    )
+    .parse_next(input)?;
+    Ok(AnnEnum::CopyTags(
+        prefix.map(|(_, v)| v).unwrap_or_default(),
+    ))
+}
+
+// ingest_time(name:"recv_ts")
+fn ingest_time(input: &mut &str) -> WResult<AnnEnum> {
+    let (_, name) = delimited(
+        (
+            multispace0,
+            literal("ingest_time"),
+            multispace0,
+            literal('('),
+        ),
+        cut_err(
+            utils::take_tag_kv.verify(|(k, v): &(SmolStr, SmolStr)| k == "name" && !v.is_empty()),
+        )
+        .context(ctx_desc("ingest_time(name: \"...\")")),
+        (multispace0, literal(')')),
+    )
+    .parse_next(input)?;
+    Ok(AnnEnum::IngestTime(name))
+}
+
+enum FpArg {
+    Name(SmolStr),
+    Algo(FingerprintAlgo),
+    Fields(Vec<SmolStr>),
+}
+
+fn fingerprint_arg(input: &mut &str) -> WResult<FpArg> {
+    alt((
+        preceded(
+            (
+                multispace0,
+                literal("fields"),
+                multispace0,
+                literal(':'),
+                multispace0,
+            ),
+            delimited(
+                (literal('['), multispace0),
+                separated(
+                    0..,
+                    delimited(multispace0, utils::take_key, multispace0).map(SmolStr::from),
+                    literal(','),
+                ),
+                (multispace0, literal(']')),
+            ),
+        )
+        .map(FpArg::Fields),
+        utils::take_tag_kv.verify_map(|(k, v)| match k.as_str() {
+            "name" if !v.is_empty() => Some(FpArg::Name(v)),
+            "algo" => FingerprintAlgo::from_name(v.as_str()).map(FpArg::Algo),
+            _ => None,
+        }),
+    ))
+    .parse_next(input)
+}
+
+fn fingerprint_conf(input: &mut &str) -> WResult<FingerprintConf> {
+    let args: Vec<FpArg> = separated(1.., fingerprint_arg, literal(',')).parse_next(input)?;
+    let mut conf = FingerprintConf::default();
+    for arg in args {
+        match arg {
+            FpArg::Name(v) => conf.name = v,
+            FpArg::Algo(v) => conf.algo = v,
+            FpArg::Fields(v) => conf.fields = v,
+        }
+    }
+    Ok(conf)
+}
+
+// fingerprint(name:"fp", fields:[a, b], algo:"xxh64|md5")
+fn fingerprint(input: &mut &str) -> WResult<AnnEnum> {
+    let conf = delimited(
+        (
+            multispace0,
+            literal("fingerprint"),
+            multispace0,
+            literal('('),
+        ),
+        cut_err(fingerprint_conf.verify(|c: &FingerprintConf| !c.name.is_empty())).context(
+            ctx_desc("fingerprint(name: \"...\", fields: [...], algo: \"xxh64|md5\")"),
+        ),
+        (multispace0, literal(')')),
+    )
+    .parse_next(input)?;
+    Ok(AnnEnum::Fingerprint(conf))
+}
+
 pub fn ann_fun(input: &mut &str) -> WResult<AnnFun> {
     multispace0.parse_next(input)?;
     literal("#[")
         .context(ctx_desc("annotation start"))
         .parse_next(input)?;
-    let x: Vec<AnnEnum> =
-        separated(0.., alt((wpl_tags, copy_raw)), literal(",")).parse_next(input)?;
+    let x: Vec<AnnEnum> = separated(
+        0..,
+        alt((
+            wpl_tags,
+            copy_raw,
+            copy_tags,
+            ingest_time,
+            fingerprint,
+            priority,
+            match_policy,
+        )),
+        literal(","),
+    )
+    .parse_next(input)?;
     multispace0.parse_next(input)?;
     literal("]")
         .context(ctx_desc("annotation end"))
@@ -56,6 +221,21 @@ pub fn ann_fun(input: &mut &str) -> WResult<AnnFun> {
             AnnEnum::Tags(v) => {
                 af.tags = v;
             }
+            AnnEnum::Priority(v) => {
+                af.priority = Some(v);
+            }
+            AnnEnum::Match(v) => {
+                af.matching = Some(v);
+            }
+            AnnEnum::CopyTags(v) => {
+                af.copy_tags = Some(v);
+            }
+            AnnEnum::IngestTime(v) => {
+                af.ingest_time = Some(v);
+            }
+            AnnEnum::Fingerprint(v) => {
+                af.fingerprint = Some(v);
+            }
         }
     }
     Ok(af)
@@ -65,7 +245,10 @@ pub fn ann_fun(input: &mut &str) -> WResult<AnnFun> {
 mod tests {
     use std::collections::BTreeMap;
 
-    use crate::ast::{AnnEnum, AnnFun};
+    use crate::ast::{
+        AnnEnum, AnnFun, DEFAULT_RESIDUE_RATIO, FingerprintAlgo, FingerprintConf, WplMatchConf,
+        WplMatchPolicy,
+    };
     use crate::parser::utils::take_tag_kv;
     use crate::parser::wpl_anno::{ann_fun, wpl_tags};
     use orion_error::TestAssert;
@@ -144,6 +327,7 @@ mod tests {
                     ("cc_y".into(), "qw_/e".into())
                 ]),
                 copy_raw: Some(("name".into(), "tq".into())),
+                ..Default::default()
             }
         );
 
@@ -161,6 +345,7 @@ mod tests {
                     ("cc_y".into(), "qw_/e".into())
                 ]),
                 copy_raw: None,
+                ..Default::default()
             }
         );
 
@@ -175,7 +360,76 @@ mod tests {
             AnnFun {
                 tags: Default::default(),
                 copy_raw: Some(("name".into(), "tq".into())),
+                ..Default::default()
             }
         );
     }
+
+    #[test]
+    fn test_match_annotation() {
+        let af = ann_fun
+            .parse(r#"#[tag(t:"x"), priority(-3), match(longest, residue: 0.05)]"#)
+            .assert();
+        assert_eq!(af.priority, Some(-3));
+        assert_eq!(
+            af.matching,
+            Some(WplMatchConf {
+                policy: WplMatchPolicy::Longest,
+                residue: 0.05,
+            })
+        );
+
+        let af = ann_fun.parse("#[match(strict)]").assert();
+        assert_eq!(
+            af.matching,
+            Some(WplMatchConf {
+                policy: WplMatchPolicy::Strict,
+                residue: DEFAULT_RESIDUE_RATIO,
+            })
+        );
+
+        assert!(ann_fun.parse("#[match(best)]").is_err());
+        assert!(ann_fun.parse("#[match(first, residue: 1.5)]").is_err());
+        assert!(ann_fun.parse("#[priority(high)]").is_err());
+    }
+
+    #[test]
+    fn test_source_annotation() {
+        let af = ann_fun
+            .parse(
+                r#"#[copy_tags(prefix:"src_"), ingest_time(name:"recv_ts"), fingerprint(name:"fp", fields:[sip, dip, msg], algo:"md5")]"#,
+            )
+            .assert();
+        assert_eq!(af.copy_tags, Some("src_".into()));
+        assert_eq!(af.ingest_time, Some("recv_ts".into()));
+        assert_eq!(
+            af.fingerprint,
+            Some(FingerprintConf {
+                name: "fp".into(),
+                fields: vec!["sip".into(), "dip".into(), "msg".into()],
+                algo: FingerprintAlgo::Md5,
+            })
+        );
+
+        let af = ann_fun
+            .parse(r#"#[copy_tags(), fingerprint(name:"fp")]"#)
+            .assert();
+        assert_eq!(af.copy_tags, Some("".into()));
+        assert_eq!(
+            af.fingerprint,
+            Some(FingerprintConf {
+                name: "fp".into(),
+                ..Default::default()
+            })
+        );
+
+        assert!(ann_fun.parse(r#"#[ingest_time()]"#).is_err());
+        assert!(ann_fun.parse(r#"#[copy_tags(name:"x")]"#).is_err());
+        assert!(ann_fun.parse(r#"#[fingerprint(fields:[a])]"#).is_err());
+        assert!(
+            ann_fun
+                .parse(r#"#[fingerprint(name:"fp", algo:"sha3")]"#)
+                .is_err()
+        );
+    }
 }
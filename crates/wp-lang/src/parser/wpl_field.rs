@@ -1,12 +1,13 @@
 use super::wpl_fun;
 use crate::ast::WplSep;
 use crate::ast::fld_fmt::WplFieldFmt;
-use crate::ast::{DEFAULT_FIELD_KEY, WplField, WplFieldSet, WplPipe};
-use crate::parser::datatype::take_datatype;
+use crate::ast::{DEFAULT_FIELD_KEY, WplExtType, WplField, WplFieldSet, WplPipe};
+use crate::parser::datatype::{meta_of_name, take_field_meta};
 use crate::parser::utils::{
     peek_next, peek_str, take_key, take_parentheses, take_ref_path, take_to_end, take_var_name,
 };
-use crate::parser::wpl_group::wpl_group;
+use crate::parser::wpl_group::wpl_group_with;
+use crate::parser::wpl_pkg::PkgTypeScope;
 use crate::types::WildMap;
 use winnow::ascii::{digit0, digit1, multispace0};
 use winnow::combinator::{alt, delimited, fail, opt, preceded, repeat};
@@ -14,7 +15,7 @@ use winnow::error::{StrContext, StrContextValue};
 use winnow::stream::Stream;
 use winnow::token::{literal, take, take_till};
 // Use workspace-wide parser result alias to decouple from winnow's concrete type
-use wp_model_core::model::DataType;
+use wp_model_core::model::{DataType, FNameStr};
 use wp_parser::Parser;
 use wp_parser::WResult as ModalResult;
 use wp_parser::symbol::{ctx_desc, ctx_literal};
@@ -109,7 +110,7 @@ fn wpl_field_fmt(input: &mut &str) -> ModalResult<WplFieldFmt> {
     }
     Ok(WplFieldFmt::default())
 }
-fn wpl_opt_meta(input: &mut &str) -> ModalResult<(DataType, bool)> {
+fn wpl_opt_meta(input: &mut &str) -> ModalResult<(DataType, FNameStr, bool)> {
     multispace0.parse_next(input)?;
     let mut is_opt = true;
     let meta_key = opt(delimited(
@@ -126,21 +127,25 @@ fn wpl_opt_meta(input: &mut &str) -> ModalResult<(DataType, bool)> {
         Some((_, v, _)) => Some(v),
     };
     if let Some(mk) = meta_key {
-        match DataType::from(mk) {
-            Ok(meta) => return Ok((meta, is_opt)),
-            Err(_) => {
+        match meta_of_name(mk) {
+            Some((meta, meta_name)) => return Ok((meta, meta_name, is_opt)),
+            None => {
                 fail.context(ctx_desc("bad meta")).parse_next(input)?;
             }
         }
     }
-    Ok((DataType::Chars, is_opt))
+    Ok((
+        DataType::Chars,
+        DataType::Chars.static_name().into(),
+        is_opt,
+    ))
 }
 
 #[allow(clippy::bind_instead_of_map)]
 fn wpl_id_field<'a>(input: &mut &'a str) -> ModalResult<(&'a str, WplField)> {
     let before_len = input.len();
     let mut content = None;
-    let (meta_type, is_opt) = wpl_opt_meta.parse_next(input)?;
+    let (meta_type, meta_name, is_opt) = wpl_opt_meta.parse_next(input)?;
     /*
     let (meta_type, f_meta) = preceded(multispace0, opt(take_key).map(|x| x.unwrap_or("chars")))
         .try_map::<_, _, MetaErr>(|x| {
@@ -150,7 +155,7 @@ fn wpl_id_field<'a>(input: &mut &'a str) -> ModalResult<(&'a str, WplField)> {
         .parse_next(input)?;
         */
 
-    if meta_type == DataType::Symbol {
+    if meta_type == DataType::Symbol || has_time_args(&meta_type, &meta_name) {
         content = opt(take_parentheses)
             .parse_next(input)?
             .and_then(|x| Some(x.to_string()));
@@ -176,7 +181,7 @@ fn wpl_id_field<'a>(input: &mut &'a str) -> ModalResult<(&'a str, WplField)> {
 
     let mut conf = WplField {
         name: f_key.map(|s| s.into()),
-        meta_name: meta_type.static_name().into(),
+        meta_name,
         meta_type,
         fmt_conf,
         separator: sep,
@@ -221,12 +226,19 @@ fn wpl_field_subs(input: &mut &str) -> ModalResult<WplFieldSet> {
 }
 
 pub fn wpl_field(data: &mut &str) -> ModalResult<WplField> {
-    wpl_field_impl
+    wpl_field_with(data, None)
+}
+
+pub(crate) fn wpl_field_with(
+    data: &mut &str,
+    types: Option<&PkgTypeScope>,
+) -> ModalResult<WplField> {
+    (|i: &mut &str| wpl_field_impl(i, types))
         .context(ctx_desc("<<< <field>"))
         .parse_next(data)
 }
 
-fn wpl_field_impl(input: &mut &str) -> ModalResult<WplField> {
+fn wpl_field_impl(input: &mut &str, types: Option<&PkgTypeScope>) -> ModalResult<WplField> {
     let mut conf = WplField::default();
     multispace0.parse_next(input)?;
 
@@ -240,11 +252,13 @@ fn wpl_field_impl(input: &mut &str) -> ModalResult<WplField> {
             conf.continuous_cnt = Some(rep_cnt.parse::<usize>().unwrap_or(255));
         }
     }
-    let main_meta = take_datatype.parse_next(input)?;
-    conf.meta_name = main_meta.static_name().into();
+    let (main_meta, meta_name) = take_field_meta.parse_next(input)?;
+    conf.meta_name = meta_name;
     conf.meta_type = main_meta;
     parse_symbol(input, &mut conf)?;
     parse_peek_symbol(input, &mut conf)?;
+    parse_time_args(input, &mut conf)?;
+    parse_plugin_args(input, &mut conf)?;
 
     multispace0.parse_next(input)?;
     if peek_str("(", input).is_ok() {
@@ -271,7 +285,7 @@ fn wpl_field_impl(input: &mut &str) -> ModalResult<WplField> {
     let fmt_conf = wpl_field_fmt.parse_next(input)?;
     let opt_sep = wpl_sep.parse_next(input)?;
     conf.separator = opt_sep;
-    conf.pipe = repeat(0.., wpl_pipe).parse_next(input)?;
+    conf.pipe = repeat(0.., |i: &mut &str| wpl_pipe_with(i, types)).parse_next(input)?;
 
     conf.fmt_conf = fmt_conf;
     /*
@@ -288,15 +302,19 @@ fn wpl_field_impl(input: &mut &str) -> ModalResult<WplField> {
 }
 
 pub fn wpl_pipe(data: &mut &str) -> ModalResult<WplPipe> {
+    wpl_pipe_with(data, None)
+}
+
+fn wpl_pipe_with(data: &mut &str, types: Option<&PkgTypeScope>) -> ModalResult<WplPipe> {
     multispace0.parse_next(data)?;
     literal('|').parse_next(data)?;
     multispace0.parse_next(data)?;
     //let mut pipe_exp= STMExpress::default();
-    let pipe = alt((wpl_pipe_fun, wpl_pipe_group)).parse_next(data)?;
+    let pipe = alt((wpl_pipe_fun, |i: &mut &str| wpl_pipe_group(i, types))).parse_next(data)?;
     Ok(pipe)
 }
-fn wpl_pipe_group(data: &mut &str) -> ModalResult<WplPipe> {
-    let group = wpl_group.parse_next(data)?;
+fn wpl_pipe_group(data: &mut &str, types: Option<&PkgTypeScope>) -> ModalResult<WplPipe> {
+    let group = wpl_group_with(data, types)?;
     Ok(WplPipe::Group(group))
 }
 fn wpl_pipe_fun(data: &mut &str) -> ModalResult<WplPipe> {
@@ -322,6 +340,33 @@ fn parse_peek_symbol(input: &mut &str, conf: &mut WplField) -> ModalResult<()> {
     Ok(())
 }
 
+// time(fmt="...")、time/bsd(tz=...)、time/epoch(ms) 的类型参数
+fn has_time_args(meta: &DataType, meta_name: &str) -> bool {
+    *meta == DataType::Time
+        && matches!(
+            WplExtType::from_name(meta_name),
+            None | Some(WplExtType::TimeBsd) | Some(WplExtType::TimeEpoch)
+        )
+}
+fn parse_time_args(input: &mut &str, conf: &mut WplField) -> ModalResult<()> {
+    if has_time_args(&conf.meta_type, conf.meta_name.as_str()) {
+        let content = opt(take_parentheses).parse_next(input)?;
+        conf.content = content.map(|x| x.to_string());
+    }
+    Ok(())
+}
+
+// wasm(plugin) 的插件名
+fn parse_plugin_args(input: &mut &str, conf: &mut WplField) -> ModalResult<()> {
+    if WplExtType::from_name(conf.meta_name.as_str()) == Some(WplExtType::Wasm) {
+        let content = take_parentheses
+            .context(ctx_desc("wasm(<plugin>)"))
+            .parse_next(input)?;
+        conf.content = Some(content.trim().to_string());
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,34 +1,41 @@
 use std::net::IpAddr;
 
+use ipnet::IpNet;
 use smol_str::SmolStr;
 use winnow::{
     Parser,
-    ascii::{digit1, multispace0},
-    combinator::alt,
+    ascii::{dec_int, digit1, multispace0},
+    combinator::{alt, delimited},
+    error::{ContextError, ErrMode, FromExternalError},
+    token::{any, none_of, take_escaped, take_until, take_while},
 };
 use wp_parser::{
     WResult,
     fun::{fun_trait::Fun0Builder, parser::call_fun_args0},
+    symbol::ctx_desc,
 };
 use wp_parser::{
     atom::take_path,
     fun::{
-        fun_trait::{Fun1Builder, Fun2Builder, ParseNext},
-        parser::{call_fun_args1, call_fun_args2, take_arr},
+        fun_trait::{Fun1Builder, Fun2Builder, Fun3Builder, ParseNext},
+        parser::{call_fun_args1, call_fun_args2, call_fun_args3, take_arr},
     },
 };
 
 use crate::ast::{
     WplFun,
     processor::{
-        CharsHas, CharsIn, CharsInArg, CharsNotHas, CharsNotHasArg, CharsValue, DigitHas,
-        DigitHasArg, DigitIn, DigitInArg, Has, HasArg, IpIn, IpInArg, SelectLast, TakeField,
-        TargetCharsHas, TargetCharsIn, TargetCharsNotHas, TargetDigitHas, TargetDigitIn, TargetHas,
-        TargetIpIn, normalize_target,
+        CharsHas, CharsIn, CharsInArg, CharsLen, CharsNotHas, CharsNotHasArg, CharsValue,
+        CidrValue, Contains, DigitHas, DigitHasArg, DigitIn, DigitInArg, DigitRange, EndsWith, Has,
+        HasArg, IpIn, IpInArg, IpInCidr, RegexMatch, SelectLast, StartsWith, TakeField,
+        TargetCharsHas, TargetCharsIn, TargetCharsLen, TargetCharsNotHas, TargetContains,
+        TargetDigitHas, TargetDigitIn, TargetDigitRange, TargetEndsWith, TargetHas, TargetIpIn,
+        TargetIpInCidr, TargetRegexMatch, TargetStartsWith, WasmPipe, WplRegex, normalize_target,
     },
 };
 
-use super::utils::take_key;
+use super::string::decode_escapes;
+use super::utils::{quot_r_str, quot_str, take_key};
 
 pub fn wpl_fun(input: &mut &str) -> WResult<WplFun> {
     multispace0.parse_next(input)?;
@@ -46,17 +53,59 @@ pub fn wpl_fun(input: &mut &str) -> WResult<WplFun> {
         call_fun_args1::<DigitHasArg>.map(|arg| WplFun::DigitHas(DigitHas { value: arg.value })),
         call_fun_args2::<TargetDigitIn>.map(WplFun::TargetDigitIn),
         call_fun_args1::<DigitInArg>.map(|arg| WplFun::DigitIn(DigitIn { value: arg.value })),
+        call_fun_args2::<TargetIpInCidr>.map(WplFun::TargetIpInCidr),
+        call_fun_args1::<IpInCidr>.map(WplFun::IpInCidr),
         call_fun_args2::<TargetIpIn>.map(WplFun::TargetIpIn),
         call_fun_args1::<IpInArg>.map(|arg| WplFun::IpIn(IpIn { value: arg.value })),
         call_fun_args1::<TargetHas>.map(WplFun::TargetHas),
         call_fun_args0::<HasArg>.map(|_| WplFun::Has(Has)),
         call_fun_args0::<JsonUnescape>.map(WplFun::TransJsonUnescape),
         call_fun_args0::<Base64Decode>.map(WplFun::TransBase64Decode),
+        wpl_predicate_fun,
+        wpl_plugin_fun,
     ))
     .parse_next(input)?;
     Ok(fun)
 }
 
+fn wpl_predicate_fun(input: &mut &str) -> WResult<WplFun> {
+    alt((
+        call_fun_args2::<TargetRegexMatch>.map(WplFun::TargetRegexMatch),
+        call_fun_args1::<RegexMatch>.map(WplFun::RegexMatch),
+        // 区间须满足 lo <= hi，反序在解析期即拒绝
+        call_fun_args3::<TargetDigitRange>
+            .verify(|x: &TargetDigitRange| x.lo <= x.hi)
+            .context(ctx_desc("f_digit_range(<field>, lo, hi): lo <= hi"))
+            .map(WplFun::TargetDigitRange),
+        call_fun_args2::<DigitRange>
+            .verify(|x: &DigitRange| x.lo <= x.hi)
+            .context(ctx_desc("digit_range(lo, hi): lo <= hi"))
+            .map(WplFun::DigitRange),
+        call_fun_args3::<TargetCharsLen>
+            .verify(|x: &TargetCharsLen| x.min <= x.max)
+            .context(ctx_desc("f_chars_len(<field>, min, max): min <= max"))
+            .map(WplFun::TargetCharsLen),
+        call_fun_args2::<CharsLen>
+            .verify(|x: &CharsLen| x.min <= x.max)
+            .context(ctx_desc("chars_len(min, max): min <= max"))
+            .map(WplFun::CharsLen),
+        call_fun_args2::<TargetStartsWith>.map(WplFun::TargetStartsWith),
+        call_fun_args1::<StartsWith>.map(WplFun::StartsWith),
+        call_fun_args2::<TargetEndsWith>.map(WplFun::TargetEndsWith),
+        call_fun_args1::<EndsWith>.map(WplFun::EndsWith),
+        call_fun_args2::<TargetContains>.map(WplFun::TargetContains),
+        call_fun_args1::<Contains>.map(WplFun::Contains),
+    ))
+    .parse_next(input)
+}
+
+// 插件管道可改写字段，也可拒绝（等同断言失败）
+fn wpl_plugin_fun(input: &mut &str) -> WResult<WplFun> {
+    call_fun_args1::<WasmPipe>
+        .map(WplFun::Wasm)
+        .parse_next(input)
+}
+
 impl Fun2Builder for TargetDigitHas {
     type ARG1 = SmolStr;
     type ARG2 = i64;
@@ -347,6 +396,382 @@ impl Fun2Builder for TargetIpIn {
     }
 }
 
+// ---------------- Predicates ----------------
+
+fn take_target(data: &mut &str) -> WResult<SmolStr> {
+    multispace0.parse_next(data)?;
+    let val = take_key.parse_next(data)?;
+    Ok(val.into())
+}
+
+// 引号字符串（支持转义）或裸路径
+fn take_chars_arg(data: &mut &str) -> WResult<SmolStr> {
+    multispace0.parse_next(data)?;
+    alt((
+        quot_str.map(|s| SmolStr::from(decode_escapes(s))),
+        take_path.map(SmolStr::from),
+    ))
+    .parse_next(data)
+}
+
+// 正则原样传给 regex：'...'、"..."（不解码转义）或 r#"..."#
+fn take_regex(data: &mut &str) -> WResult<WplRegex> {
+    multispace0.parse_next(data)?;
+    let pattern = alt((
+        quot_r_str,
+        delimited('\'', take_until(0.., "'"), '\''),
+        delimited('"', take_escaped(none_of(['\\', '"']), '\\', any), '"'),
+    ))
+    .context(ctx_desc("<regex>::= '...' | \"...\" | r#\"...\"#"))
+    .parse_next(data)?;
+    WplRegex::new(pattern).map_err(|e| ErrMode::Cut(ContextError::from_external_error(data, e)))
+}
+
+fn take_i64(data: &mut &str) -> WResult<i64> {
+    multispace0.parse_next(data)?;
+    dec_int.parse_next(data)
+}
+
+fn take_usize(data: &mut &str) -> WResult<usize> {
+    multispace0.parse_next(data)?;
+    digit1.try_map(str::parse::<usize>).parse_next(data)
+}
+
+impl ParseNext<CidrValue> for CidrValue {
+    fn parse_next(input: &mut &str) -> WResult<CidrValue> {
+        multispace0.parse_next(input)?;
+        let net = take_while(1.., |c: char| {
+            c.is_ascii_hexdigit() || matches!(c, '.' | ':' | '/')
+        })
+        .verify_map(|s: &str| {
+            s.parse::<IpNet>()
+                .ok()
+                .or_else(|| s.parse::<IpAddr>().ok().map(IpNet::from))
+        })
+        .context(ctx_desc("<cidr>::= <ip>/<prefix> | <ip>"))
+        .parse_next(input)?;
+        multispace0.parse_next(input)?;
+        Ok(CidrValue(net))
+    }
+}
+
+impl Fun1Builder for RegexMatch {
+    type ARG1 = WplRegex;
+
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        take_regex(data)
+    }
+
+    fn fun_name() -> &'static str {
+        "regex_match"
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        Self { value: args }
+    }
+}
+
+impl Fun2Builder for TargetRegexMatch {
+    type ARG1 = SmolStr;
+    type ARG2 = WplRegex;
+
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        take_target(data)
+    }
+    fn args2(data: &mut &str) -> WResult<Self::ARG2> {
+        take_regex(data)
+    }
+
+    fn fun_name() -> &'static str {
+        "f_regex_match"
+    }
+
+    fn build(args: (Self::ARG1, Self::ARG2)) -> Self {
+        Self {
+            target: normalize_target(args.0),
+            value: args.1,
+        }
+    }
+}
+
+impl Fun2Builder for DigitRange {
+    type ARG1 = i64;
+    type ARG2 = i64;
+
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        take_i64(data)
+    }
+    fn args2(data: &mut &str) -> WResult<Self::ARG2> {
+        take_i64(data)
+    }
+
+    fn fun_name() -> &'static str {
+        "digit_range"
+    }
+
+    fn build(args: (Self::ARG1, Self::ARG2)) -> Self {
+        Self {
+            lo: args.0,
+            hi: args.1,
+        }
+    }
+}
+
+impl Fun3Builder for TargetDigitRange {
+    type ARG1 = SmolStr;
+    type ARG2 = i64;
+    type ARG3 = i64;
+
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        take_target(data)
+    }
+    fn args2(data: &mut &str) -> WResult<Self::ARG2> {
+        take_i64(data)
+    }
+    fn args3(data: &mut &str) -> WResult<Self::ARG3> {
+        take_i64(data)
+    }
+
+    fn fun_name() -> &'static str {
+        "f_digit_range"
+    }
+
+    fn build(args: (Self::ARG1, Self::ARG2, Self::ARG3)) -> Self {
+        Self {
+            target: normalize_target(args.0),
+            lo: args.1,
+            hi: args.2,
+        }
+    }
+}
+
+impl Fun2Builder for CharsLen {
+    type ARG1 = usize;
+    type ARG2 = usize;
+
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        take_usize(data)
+    }
+    fn args2(data: &mut &str) -> WResult<Self::ARG2> {
+        take_usize(data)
+    }
+
+    fn fun_name() -> &'static str {
+        "chars_len"
+    }
+
+    fn build(args: (Self::ARG1, Self::ARG2)) -> Self {
+        Self {
+            min: args.0,
+            max: args.1,
+        }
+    }
+}
+
+impl Fun3Builder for TargetCharsLen {
+    type ARG1 = SmolStr;
+    type ARG2 = usize;
+    type ARG3 = usize;
+
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        take_target(data)
+    }
+    fn args2(data: &mut &str) -> WResult<Self::ARG2> {
+        take_usize(data)
+    }
+    fn args3(data: &mut &str) -> WResult<Self::ARG3> {
+        take_usize(data)
+    }
+
+    fn fun_name() -> &'static str {
+        "f_chars_len"
+    }
+
+    fn build(args: (Self::ARG1, Self::ARG2, Self::ARG3)) -> Self {
+        Self {
+            target: normalize_target(args.0),
+            min: args.1,
+            max: args.2,
+        }
+    }
+}
+
+impl Fun1Builder for WasmPipe {
+    type ARG1 = SmolStr;
+
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        multispace0.parse_next(data)?;
+        let val = take_key.parse_next(data)?;
+        Ok(val.into())
+    }
+
+    fn fun_name() -> &'static str {
+        "wasm"
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        Self { plugin: args }
+    }
+}
+
+impl Fun1Builder for StartsWith {
+    type ARG1 = SmolStr;
+
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        take_chars_arg(data)
+    }
+
+    fn fun_name() -> &'static str {
+        "starts_with"
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        Self { value: args }
+    }
+}
+
+impl Fun2Builder for TargetStartsWith {
+    type ARG1 = SmolStr;
+    type ARG2 = SmolStr;
+
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        take_target(data)
+    }
+    fn args2(data: &mut &str) -> WResult<Self::ARG2> {
+        take_chars_arg(data)
+    }
+
+    fn fun_name() -> &'static str {
+        "f_starts_with"
+    }
+
+    fn build(args: (Self::ARG1, Self::ARG2)) -> Self {
+        Self {
+            target: normalize_target(args.0),
+            value: args.1,
+        }
+    }
+}
+
+impl Fun1Builder for EndsWith {
+    type ARG1 = SmolStr;
+
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        take_chars_arg(data)
+    }
+
+    fn fun_name() -> &'static str {
+        "ends_with"
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        Self { value: args }
+    }
+}
+
+impl Fun2Builder for TargetEndsWith {
+    type ARG1 = SmolStr;
+    type ARG2 = SmolStr;
+
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        take_target(data)
+    }
+    fn args2(data: &mut &str) -> WResult<Self::ARG2> {
+        take_chars_arg(data)
+    }
+
+    fn fun_name() -> &'static str {
+        "f_ends_with"
+    }
+
+    fn build(args: (Self::ARG1, Self::ARG2)) -> Self {
+        Self {
+            target: normalize_target(args.0),
+            value: args.1,
+        }
+    }
+}
+
+impl Fun1Builder for Contains {
+    type ARG1 = SmolStr;
+
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        take_chars_arg(data)
+    }
+
+    fn fun_name() -> &'static str {
+        "contains"
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        Self { value: args }
+    }
+}
+
+impl Fun2Builder for TargetContains {
+    type ARG1 = SmolStr;
+    type ARG2 = SmolStr;
+
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        take_target(data)
+    }
+    fn args2(data: &mut &str) -> WResult<Self::ARG2> {
+        take_chars_arg(data)
+    }
+
+    fn fun_name() -> &'static str {
+        "f_contains"
+    }
+
+    fn build(args: (Self::ARG1, Self::ARG2)) -> Self {
+        Self {
+            target: normalize_target(args.0),
+            value: args.1,
+        }
+    }
+}
+
+impl Fun1Builder for IpInCidr {
+    type ARG1 = Vec<CidrValue>;
+
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        take_arr::<CidrValue>(data)
+    }
+
+    fn fun_name() -> &'static str {
+        "ip_in_cidr"
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        Self {
+            value: args.into_iter().map(|i| i.0).collect(),
+        }
+    }
+}
+
+impl Fun2Builder for TargetIpInCidr {
+    type ARG1 = SmolStr;
+    type ARG2 = Vec<CidrValue>;
+
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        take_target(data)
+    }
+    fn args2(data: &mut &str) -> WResult<Self::ARG2> {
+        take_arr::<CidrValue>(data)
+    }
+
+    fn fun_name() -> &'static str {
+        "f_ip_in_cidr"
+    }
+
+    fn build(args: (Self::ARG1, Self::ARG2)) -> Self {
+        Self {
+            target: normalize_target(args.0),
+            value: args.1.into_iter().map(|i| i.0).collect(),
+        }
+    }
+}
+
 // ---------------- String Mode ----------------
 use crate::ast::processor::JsonUnescape;
 
@@ -530,4 +955,111 @@ mod tests {
         assert_eq!(fun, WplFun::TransBase64Decode(Base64Decode {}));
         assert!(wpl_fun.parse("base64_decode(decoded)").is_err());
     }
+
+    #[test]
+    fn test_parse_predicate_fun() {
+        let fun = wpl_fun.parse(r"regex_match('^\d{3}-\w+$')").assert();
+        let WplFun::RegexMatch(m) = &fun else {
+            panic!("unexpected {:?}", fun);
+        };
+        assert!(m.value.regex.is_match("404-not_found"));
+        assert_eq!(m.value.pattern, r"^\d{3}-\w+$");
+
+        let fun = wpl_fun
+            .parse(r##"f_regex_match(msg, r#"a,b(c)"#)"##)
+            .assert();
+        assert!(matches!(
+            fun,
+            WplFun::TargetRegexMatch(TargetRegexMatch {
+                target: Some(_),
+                ..
+            })
+        ));
+        assert!(wpl_fun.parse("regex_match('(unclosed')").is_err());
+
+        assert_eq!(
+            wpl_fun.parse("digit_range(-10, 20)").assert(),
+            WplFun::DigitRange(DigitRange { lo: -10, hi: 20 })
+        );
+        assert_eq!(
+            wpl_fun.parse("f_digit_range(code, 400,499)").assert(),
+            WplFun::TargetDigitRange(TargetDigitRange {
+                target: Some("code".into()),
+                lo: 400,
+                hi: 499
+            })
+        );
+        assert_eq!(
+            wpl_fun.parse("chars_len(1, 64)").assert(),
+            WplFun::CharsLen(CharsLen { min: 1, max: 64 })
+        );
+        assert_eq!(
+            wpl_fun.parse("f_chars_len(_, 0, 3)").assert(),
+            WplFun::TargetCharsLen(TargetCharsLen {
+                target: None,
+                min: 0,
+                max: 3
+            })
+        );
+        assert!(wpl_fun.parse("digit_range(9, 1)").is_err());
+        assert!(wpl_fun.parse("f_digit_range(code, 499, 400)").is_err());
+        assert!(wpl_fun.parse("chars_len(64, 1)").is_err());
+        assert!(wpl_fun.parse("f_chars_len(_, 3, 0)").is_err());
+        assert!(wpl_fun.parse("digit_range(5, 5)").is_ok());
+        assert_eq!(
+            wpl_fun.parse("wasm(mask_ip)").assert(),
+            WplFun::Wasm(WasmPipe {
+                plugin: "mask_ip".into()
+            })
+        );
+        assert_eq!(
+            wpl_fun.parse("starts_with(GET)").assert(),
+            WplFun::StartsWith(StartsWith {
+                value: "GET".into()
+            })
+        );
+        assert_eq!(
+            wpl_fun.parse(r#"f_ends_with(url, ".php")"#).assert(),
+            WplFun::TargetEndsWith(TargetEndsWith {
+                target: Some("url".into()),
+                value: ".php".into()
+            })
+        );
+        assert_eq!(
+            wpl_fun.parse(r#"contains("a \"b\"")"#).assert(),
+            WplFun::Contains(Contains {
+                value: r#"a "b""#.into()
+            })
+        );
+        assert_eq!(
+            wpl_fun.parse("f_contains(ua, curl)").assert(),
+            WplFun::TargetContains(TargetContains {
+                target: Some("ua".into()),
+                value: "curl".into()
+            })
+        );
+
+        assert_eq!(
+            wpl_fun
+                .parse("ip_in_cidr([10.0.0.0/8, fd00::/8, 1.2.3.4])")
+                .assert(),
+            WplFun::IpInCidr(IpInCidr {
+                value: vec![
+                    "10.0.0.0/8".parse().unwrap(),
+                    "fd00::/8".parse().unwrap(),
+                    "1.2.3.4/32".parse().unwrap(),
+                ]
+            })
+        );
+        assert_eq!(
+            wpl_fun
+                .parse("f_ip_in_cidr(sip, [192.168.0.0/16])")
+                .assert(),
+            WplFun::TargetIpInCidr(TargetIpInCidr {
+                target: Some("sip".into()),
+                value: vec!["192.168.0.0/16".parse().unwrap()]
+            })
+        );
+        assert!(wpl_fun.parse("ip_in_cidr([10.0.0.0/33])").is_err());
+    }
 }
@@ -1,30 +1,40 @@
 use crate::ast::WplSep;
-use crate::ast::group::WplGroup;
+use crate::ast::group::{GroupRepeat, WplGroup, WplGroupType};
 use crate::parser::constants::{CTX_EXPECT_GROUP_META, CTX_GROUP_CONTENT, CTX_GROUP_META_HINT};
-use crate::parser::utils::peek_str;
+use crate::parser::utils::{peek_str, take_key};
 use crate::parser::wpl_field::wpl_sep_str;
+use crate::parser::wpl_pkg::PkgTypeScope;
 use crate::parser::wpl_rule;
+use smol_str::SmolStr;
 use winnow::ascii::{digit1, multispace0};
-use winnow::combinator::{alt, delimited, opt};
+use winnow::combinator::{alt, cut_err, delimited, fail, opt, preceded};
 use wp_parser::Parser;
 use wp_parser::WResult;
 use wp_parser::symbol::{ctx_desc, ctx_literal};
 
 pub fn wpl_group(input: &mut &str) -> WResult<WplGroup> {
+    wpl_group_with(input, None)
+}
+
+pub(crate) fn wpl_group_with(input: &mut &str, types: Option<&PkgTypeScope>) -> WResult<WplGroup> {
     let mut group = WplGroup::default();
     multispace0.parse_next(input)?;
 
     if peek_str("(", input).is_err() {
-        let meta_str = alt(("alt", "opt", "some_of", "seq"))
-            .context(ctx_literal(CTX_GROUP_META_HINT))
-            .context(ctx_desc(CTX_EXPECT_GROUP_META))
-            .parse_next(input)?;
-        group.meta_from(Some(meta_str));
+        if let Some(repeat) = opt(wpl_repeat_meta).parse_next(input)? {
+            group.meta = WplGroupType::Repeat(repeat);
+        } else {
+            let meta_str = alt(("alt", "opt", "some_of", "seq"))
+                .context(ctx_literal(CTX_GROUP_META_HINT))
+                .context(ctx_desc(CTX_EXPECT_GROUP_META))
+                .parse_next(input)?;
+            group.meta_from(Some(meta_str));
+        }
     }
 
     let mut fields = delimited(
         (multispace0, '('),
-        wpl_rule::wpl_field_vec,
+        |i: &mut &str| wpl_rule::wpl_field_vec_with(i, types),
         (multispace0, ')'),
     )
     .context(ctx_literal("( ... )"))
@@ -50,6 +60,47 @@ pub fn wpl_group(input: &mut &str) -> WResult<WplGroup> {
     group.fields.append(&mut fields);
     Ok(group)
 }
+fn repeat_count(input: &mut &str) -> WResult<usize> {
+    digit1.try_map(str::parse::<usize>).parse_next(input)
+}
+
+/// `repeat(n)` / `repeat(min..max)` / `repeat(min..)` / `repeat(..max)`，可选 `, <数组字段名>`
+pub fn wpl_repeat_meta(input: &mut &str) -> WResult<GroupRepeat> {
+    ("repeat", multispace0, '(', multispace0).parse_next(input)?;
+    let (min, range, collect, _) = cut_err((
+        opt(repeat_count),
+        opt(preceded(
+            (multispace0, ".."),
+            opt(preceded(multispace0, repeat_count)),
+        )),
+        opt(preceded((multispace0, ',', multispace0), take_key)),
+        (multispace0, ')'),
+    ))
+    .context(ctx_literal("repeat(min..max)"))
+    .parse_next(input)?;
+    let (min, max) = match (min, range) {
+        (Some(n), None) => (n, Some(n)),
+        (min, Some(max)) => (min.unwrap_or(0), max),
+        (None, None) => {
+            return cut_err(fail)
+                .context(ctx_desc("repeat count required"))
+                .parse_next(input);
+        }
+    };
+    if max.is_some_and(|max| max == 0 || max < min) {
+        return cut_err(fail)
+            .context(ctx_desc(
+                "repeat range invalid: need 0 < max and min <= max",
+            ))
+            .parse_next(input);
+    }
+    Ok(GroupRepeat {
+        min,
+        max,
+        collect: collect.map(SmolStr::from),
+    })
+}
+
 // old alternative implementation removed (kept in VCS history)
 
 #[cfg(test)]
@@ -94,6 +145,50 @@ mod tests {
         assert_eq!(group.fields.len(), 9);
     }
 
+    #[test]
+    fn test_parse_group_repeat() {
+        let group = wpl_group.parse(r#"repeat(1..3)(ip:addr)"#).assert();
+        assert_eq!(
+            group.meta,
+            WplGroupType::Repeat(GroupRepeat {
+                min: 1,
+                max: Some(3),
+                collect: None
+            })
+        );
+        assert_eq!(group.fields.len(), 1);
+
+        let group = wpl_group
+            .parse(r#"repeat ( 2.. , addrs ) (ip, digit)\,"#)
+            .assert();
+        assert_eq!(
+            group.meta,
+            WplGroupType::Repeat(GroupRepeat {
+                min: 2,
+                max: None,
+                collect: Some("addrs".into())
+            })
+        );
+        assert_eq!(group.fields.len(), 2);
+        assert!(group.base_group_sep.is_some());
+
+        for (code, meta) in [
+            ("repeat(4)(ip)", "repeat(4)"),
+            ("repeat(..2)(ip)", "repeat(0..2)"),
+            ("repeat(1.., ips)(ip)", "repeat(1.., ips)"),
+        ] {
+            let group = wpl_group.parse(code).assert();
+            assert_eq!(group.meta.to_string(), meta);
+            // 格式化输出可再次解析
+            let again = wpl_group.parse(group.to_string().as_str()).assert();
+            assert_eq!(again.meta, group.meta);
+        }
+
+        assert!(wpl_group.parse(r#"repeat()(ip)"#).is_err());
+        assert!(wpl_group.parse(r#"repeat(3..1)(ip)"#).is_err());
+        assert!(wpl_group.parse(r#"repeat(0)(ip)"#).is_err());
+    }
+
     #[test]
     fn test_parse_group_empty() {
         let group = wpl_group.parse(r#"()"#).assert();
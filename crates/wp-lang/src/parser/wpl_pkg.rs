@@ -1,34 +1,401 @@
 use super::wpl_anno::ann_fun;
-use crate::ast::{WplPackage, WplRule};
+use crate::ast::{WplExpectField, WplField, WplPackage, WplRule, WplTestCase};
+use crate::parser::datatype::meta_of_name;
+use crate::parser::error::WplCodeReason;
+use crate::parser::string::decode_escapes;
 use crate::parser::{MergeTags, utils, wpl_rule};
 use smol_str::SmolStr;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use winnow::ascii::{multispace0, multispace1};
-use winnow::combinator::{alt, cut_err, delimited, opt, repeat};
-use winnow::error::{ContextError, StrContext};
-use winnow::token::literal;
+use winnow::combinator::{alt, cut_err, delimited, opt, repeat, separated};
+use winnow::error::{ContextError, ErrMode, FromExternalError, StrContext};
+use winnow::stream::Stream;
+use winnow::token::{literal, take_while};
 use wp_parser::Parser;
 use wp_parser::WResult;
 use wp_parser::symbol::{ctx_desc, ctx_label, ctx_literal};
 
-pub fn wpl_pkg_body2(input: &mut &str) -> WResult<Vec<WplRule>> {
+pub fn wpl_pkg_body2(input: &mut &str) -> WResult<(Vec<WplRule>, Vec<WplTestCase>)> {
+    let types = PkgTypeScope::new(input);
+    pkg_body_with(input, &types)
+}
+
+fn pkg_body_with(
+    input: &mut &str,
+    types: &PkgTypeScope,
+) -> WResult<(Vec<WplRule>, Vec<WplTestCase>)> {
     let mut rules = Vec::new();
+    let mut tests = Vec::new();
     loop {
-        wpl_rule::wpl_rule
+        if utils::is_next(alt(("type", "macro")), input) {
+            wpl_type_def(input, types)?;
+        } else if utils::is_next("test", input) {
+            wpl_test_case.map(|x| tests.push(x)).parse_next(input)?;
+        } else {
+            (|i: &mut &str| wpl_rule::wpl_rule_with(i, Some(types)))
+                .context(StrContext::Expected("rule <name> {...}".into()))
+                .map(|x| rules.push(x))
+                .parse_next(input)?;
+        }
+        if !utils::is_next(alt(("rule", "#[", "type", "macro", "test")), input) {
+            break;
+        }
+    }
+    if rules.is_empty() {
+        (|i: &mut &str| wpl_rule::wpl_rule_with(i, Some(types)))
             .context(StrContext::Expected("rule <name> {...}".into()))
             .map(|x| rules.push(x))
             .parse_next(input)?;
-        if !utils::is_next(alt(("rule", "#[")), input) {
+    }
+    Ok((rules, tests))
+}
+
+/// 规则用例：`test [<rule>] { input: "..."; expect: { <name>: <type>(<value>), ... } }`
+///
+/// 期望值可写作 `ip(1.2.3.4)` 或 `chars("a b")`，引号内支持转义。
+pub(crate) fn wpl_test_case(input: &mut &str) -> WResult<WplTestCase> {
+    ("test", multispace0)
+        .context(ctx_label("wpl keyword"))
+        .context(ctx_desc("test [<rule>] {...}"))
+        .parse_next(input)?;
+    let rule = opt(utils::take_exact_path)
+        .parse_next(input)?
+        .map(SmolStr::from);
+    (multispace0, '{', multispace0).parse_next(input)?;
+    let raw_input = cut_err(delimited(
+        ("input", multispace0, ':', multispace0),
+        utils::quot_str,
+        (multispace0, opt(';'), multispace0),
+    ))
+    .context(ctx_desc("input: \"<raw>\";"))
+    .parse_next(input)?;
+    cut_err(("expect", multispace0, ':', multispace0, '{'))
+        .context(ctx_desc("expect: { <name>: <type>(<value>), ... }"))
+        .parse_next(input)?;
+    let mut expect = Vec::new();
+    loop {
+        multispace0.parse_next(input)?;
+        if opt('}').parse_next(input)?.is_some() {
             break;
         }
+        expect.push(
+            cut_err(wpl_expect_field)
+                .context(ctx_desc("<name>: <type>(<value>)"))
+                .parse_next(input)?,
+        );
+        multispace0.parse_next(input)?;
+        if opt(',').parse_next(input)?.is_none() {
+            cut_err('}')
+                .context(ctx_literal("}"))
+                .context(ctx_desc("expect end"))
+                .parse_next(input)?;
+            break;
+        }
+    }
+    cut_err((multispace0, opt(';'), multispace0, '}'))
+        .context(ctx_literal("}"))
+        .context(ctx_desc("test end"))
+        .parse_next(input)?;
+    Ok(WplTestCase {
+        rule,
+        input: decode_escapes(raw_input),
+        expect,
+    })
+}
+
+fn wpl_expect_field(input: &mut &str) -> WResult<WplExpectField> {
+    let name = alt((utils::quot_str, utils::take_ref_path)).parse_next(input)?;
+    (multispace0, ':', multispace0).parse_next(input)?;
+    let meta = utils::take_meta_name.parse_next(input)?;
+    let value = alt((
+        delimited(('(', multispace0), utils::quot_str, (multispace0, ')')).map(decode_escapes),
+        utils::take_parentheses.map(|raw: &str| raw.replace("\\)", ")")),
+    ))
+    .parse_next(input)?;
+    Ok(WplExpectField {
+        name: name.into(),
+        meta: meta.into(),
+        value,
+    })
+}
+
+/// 包级自定义类型：`type <name>(<param>, ...) = (<field>, ...)`（`macro` 为同义关键字）
+///
+/// 定义须出现在使用之前；规则中以类型名引用，展开为定义中的字段序列：
+/// `access_head(src_)`。参数在定义体中写作 `${param}`，通常用作字段名前缀，
+/// 未传入的参数按空串处理。展开在解析阶段完成，包内规则中不保留类型引用。
+#[derive(Debug, Clone)]
+struct WplTypeDef {
+    params: Vec<String>,
+    body: String,
+    line: usize,
+    head: String,
+}
+
+/// 单个 package 的类型作用域，由 package 解析器创建并显式传入规则解析器
+pub(crate) struct PkgTypeScope {
+    src: String,
+    defs: RefCell<HashMap<String, WplTypeDef>>,
+    // 正在展开的类型（嵌套引用时用于定位使用处）
+    expanding: RefCell<Vec<String>>,
+}
+
+impl PkgTypeScope {
+    fn new(src: &str) -> Self {
+        Self {
+            src: src.to_string(),
+            defs: RefCell::new(HashMap::new()),
+            expanding: RefCell::new(Vec::new()),
+        }
+    }
+
+    // 当前位置的行号；src 自 package 所在文件开头起算，WplCode 遮蔽注释时保留了原始行
+    fn line_of(&self, input: &str) -> usize {
+        let offset = self.src.len().saturating_sub(input.len());
+        self.src[..offset].matches('\n').count() + 1
+    }
+
+    // 使用处：行号，嵌套展开时为外层类型名
+    fn use_site(&self, input: &str) -> String {
+        match self.expanding.borrow().last() {
+            Some(outer) => format!("in type '{}'", outer),
+            None => format!("at line {}", self.line_of(input)),
+        }
+    }
+}
+
+fn type_err(input: &&str, msg: String) -> ErrMode<ContextError> {
+    ErrMode::Cut(ContextError::from_external_error(
+        input,
+        WplCodeReason::Syntax(msg),
+    ))
+}
+
+fn take_type_name<'a>(input: &mut &'a str) -> WResult<&'a str> {
+    take_while(1.., |c: char| c.is_alphanumeric() || c == '_').parse_next(input)
+}
+
+// 将 `${ident}` 替换为等长的 `_`，以便按字段语法确定定义体的范围
+fn mask_params(code: &str) -> String {
+    let mut out = String::with_capacity(code.len());
+    let mut rest = code;
+    while let Some(pos) = rest.find("${") {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos + 2..];
+        let ident_len = tail
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(tail.len());
+        if ident_len > 0 && tail[ident_len..].starts_with('}') {
+            out.extend(std::iter::repeat_n('_', ident_len + 3));
+            rest = &tail[ident_len + 1..];
+        } else {
+            out.push_str("${");
+            rest = tail;
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn undeclared_param(body: &str, params: &[String]) -> Option<String> {
+    let mut rest = body;
+    while let Some(pos) = rest.find("${") {
+        let tail = &rest[pos + 2..];
+        let end = tail.find('}')?;
+        let name = &tail[..end];
+        if !params.iter().any(|p| p == name) {
+            return Some(name.to_string());
+        }
+        rest = &tail[end + 1..];
+    }
+    None
+}
+
+fn wpl_type_def(input: &mut &str, types: &PkgTypeScope) -> WResult<()> {
+    let start = *input;
+    let line = types.line_of(input);
+    (alt(("type", "macro")), multispace1)
+        .context(ctx_label("wpl keyword"))
+        .context(ctx_desc("type <name>(<param>) = (...)"))
+        .parse_next(input)?;
+    let name = cut_err(take_type_name)
+        .context(ctx_desc("<<< type <name>"))
+        .parse_next(input)?;
+    let params: Vec<&str> = opt(delimited(
+        (multispace0, '('),
+        separated(
+            0..,
+            delimited(multispace0, take_type_name, multispace0),
+            ',',
+        ),
+        ')',
+    ))
+    .parse_next(input)?
+    .unwrap_or_default();
+    cut_err((multispace0, '=', multispace0))
+        .context(ctx_literal("="))
+        .context(ctx_desc("type <name> = (...)"))
+        .parse_next(input)?;
+    let head = start[..start.len() - input.len()]
+        .trim_end_matches(|c: char| c == '=' || c.is_whitespace())
+        .to_string();
+
+    if meta_of_name(name).is_some() || matches!(name, "rule" | "package" | "type" | "macro") {
+        return Err(type_err(
+            input,
+            format!("type '{}' at line {} shadows a builtin name", name, line),
+        ));
+    }
+    let prev = types.defs.borrow().get(name).cloned();
+    if let Some(prev) = prev {
+        return Err(type_err(
+            input,
+            format!(
+                "type '{}' redefined at line {}\n  --> first defined at line {}: {}",
+                name, line, prev.line, prev.head
+            ),
+        ));
+    }
+
+    // 定义体按字段序列校验（参数占位替换为合法名称）
+    let masked = mask_params(input);
+    let mut probe = masked.as_str();
+    delimited(
+        '(',
+        |i: &mut &str| wpl_rule::wpl_field_vec_with(i, Some(types)),
+        (multispace0, ')'),
+    )
+    .parse_next(&mut probe)
+    .map_err(|_| {
+        type_err(
+            input,
+            format!("type '{}' at line {} has an invalid field list", name, line),
+        )
+    })?;
+    let consumed = masked.len() - probe.len();
+    let body = input[1..consumed - 1].to_string();
+    let params: Vec<String> = params.into_iter().map(String::from).collect();
+    if let Some(p) = undeclared_param(&body, &params) {
+        return Err(type_err(
+            input,
+            format!(
+                "type '{}' at line {} uses undeclared param '${{{}}}'",
+                name, line, p
+            ),
+        ));
+    }
+    *input = &input[consumed..];
+    opt((multispace0, ';')).parse_next(input)?;
+
+    types.defs.borrow_mut().insert(
+        name.to_string(),
+        WplTypeDef {
+            params,
+            body,
+            line,
+            head,
+        },
+    );
+    Ok(())
+}
+
+/// 规则字段位置上的包级类型引用，展开为字段序列；不在 package 内或名称未定义时返回 `None`
+pub(crate) fn wpl_type_ref(
+    input: &mut &str,
+    types: Option<&PkgTypeScope>,
+) -> WResult<Option<Vec<WplField>>> {
+    let Some(types) = types.filter(|x| !x.defs.borrow().is_empty()) else {
+        return Ok(None);
+    };
+    let cp = input.checkpoint();
+    multispace0.parse_next(input)?;
+    let use_start = *input;
+    let Ok(name) = take_type_name.parse_next(input) else {
+        input.reset(&cp);
+        return Ok(None);
+    };
+    let found = types.defs.borrow().get(name).cloned();
+    // 名称后紧跟 `/` 等字符时为内置类型（如 `time/clf`）
+    let Some(def) = found.filter(|_| !input.starts_with(['/', '@', '.', '-'])) else {
+        input.reset(&cp);
+        return Ok(None);
+    };
+    let site = types.use_site(use_start);
+    let args: Vec<&str> = opt(delimited(
+        '(',
+        separated(
+            0..,
+            delimited(multispace0, opt(utils::take_var_name), multispace0),
+            ',',
+        ),
+        ')',
+    ))
+    .parse_next(input)?
+    .map(|x: Vec<Option<&str>>| x.into_iter().map(|a| a.unwrap_or_default()).collect())
+    .unwrap_or_default();
+    // `name()` 视为未传参数
+    let args = if args == [""] { Vec::new() } else { args };
+    let use_text = use_start[..use_start.len() - input.len()].to_string();
+    let report = |msg: String| {
+        format!(
+            "{}\n  --> defined at line {}: {}\n  --> used {}: {}",
+            msg, def.line, def.head, site, use_text
+        )
+    };
+    if args.len() > def.params.len() {
+        return Err(type_err(
+            input,
+            report(format!(
+                "type '{}' expects at most {} arg(s), got {}",
+                name,
+                def.params.len(),
+                args.len()
+            )),
+        ));
+    }
+    if utils::peek_next((multispace0, ':'), input).is_ok() {
+        return Err(type_err(
+            input,
+            report(format!(
+                "type '{}' can't be named, use a param as name prefix",
+                name
+            )),
+        ));
+    }
+
+    let mut code = def.body.clone();
+    for (i, param) in def.params.iter().enumerate() {
+        code = code.replace(
+            &format!("${{{}}}", param),
+            args.get(i).copied().unwrap_or(""),
+        );
+    }
+    types.expanding.borrow_mut().push(name.to_string());
+    let mut expand = code.as_str();
+    let fields = (
+        |i: &mut &str| wpl_rule::wpl_field_vec_with(i, Some(types)),
+        multispace0,
+    )
+        .map(|x| x.0)
+        .parse_next(&mut expand);
+    types.expanding.borrow_mut().pop();
+    match fields {
+        Ok(fields) if expand.is_empty() => Ok(Some(fields)),
+        _ => Err(type_err(
+            input,
+            report(format!(
+                "type '{}' expands to invalid fields: ({})",
+                name, code
+            )),
+        )),
     }
-    Ok(rules)
 }
 
 pub fn wpl_pkg_body<'a, 'b>(
     package: &'b mut WplPackage,
 ) -> impl Parser<&'a str, (), ContextError> + 'b {
     move |input: &mut &'a str| {
-        use winnow::error::{ContextError, ErrMode};
         delimited(
             multispace0,
             repeat(
@@ -49,6 +416,7 @@ pub fn wpl_pkg_body<'a, 'b>(
 
 #[allow(clippy::field_reassign_with_default)]
 pub fn wpl_package(input: &mut &str) -> WResult<WplPackage> {
+    let types = PkgTypeScope::new(input);
     let mut package = WplPackage::default();
     opt(ann_fun).map(|t| package.tags = t).parse_next(input)?;
     package.name = (
@@ -63,14 +431,192 @@ pub fn wpl_package(input: &mut &str) -> WResult<WplPackage> {
         .map(|x| SmolStr::from(x.3))
         .parse_next(input)?;
 
-    let rules = delimited(
+    let (rules, tests) = delimited(
         (multispace0, literal("{"), multispace0),
-        cut_err(wpl_pkg_body2).context(ctx_desc("{ rule ... }")),
+        cut_err(|i: &mut &str| pkg_body_with(i, &types)).context(ctx_desc("{ rule ... }")),
         (multispace0, literal("}"), multispace0),
     )
     .parse_next(input)?;
 
     package.append(rules);
+    package.tests = tests;
     package.merge_tags(&None);
     Ok(package)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orion_error::TestAssert;
+
+    fn names(pkg: &WplPackage) -> Vec<String> {
+        pkg.rules[0]
+            .statement
+            .first_group()
+            .unwrap()
+            .fields
+            .iter()
+            .map(|f| f.name.as_ref().map(|x| x.to_string()).unwrap_or_default())
+            .collect()
+    }
+
+    fn parse_err(code: &str) -> String {
+        let mut input = code;
+        match wpl_package.parse_next(&mut input) {
+            Err(ErrMode::Cut(e)) | Err(ErrMode::Backtrack(e)) => e.to_string(),
+            other => panic!("expect error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_pkg_type_expand() {
+        let code = r#"package nginx {
+    type access_head(p) = (ip:${p}sip, _^2, time/clf:${p}recv_time<[,]>)
+    rule access {
+        (access_head(a_), access_head, chars:msg)
+    }
+}"#;
+        let pkg = wpl_package.parse(code).assert();
+        assert_eq!(
+            names(&pkg),
+            vec!["a_sip", "", "a_recv_time", "sip", "", "recv_time", "msg"]
+        );
+        let fields = &pkg.rules[0].statement.first_group().unwrap().fields;
+        assert_eq!(fields[2].meta_name.as_str(), "time/clf");
+    }
+
+    #[test]
+    fn test_pkg_type_nested() {
+        let code = r#"package p {
+    macro addr(p) = (ip:${p}ip, digit:${p}port);
+    type conn(s, d) = (addr(${s}), addr(${d}))
+    rule r { (conn(src_, dst_)) }
+}"#;
+        let pkg = wpl_package.parse(code).assert();
+        assert_eq!(
+            names(&pkg),
+            vec!["src_ip", "src_port", "dst_ip", "dst_port"]
+        );
+    }
+
+    #[test]
+    fn test_pkg_type_scope() {
+        // 类型仅在定义它的 package 内可见
+        let code = "package a {\n type addr = (ip:sip)\n rule r { (addr) }\n}";
+        assert_eq!(names(&wpl_package.parse(code).assert()), vec!["sip"]);
+        assert!(
+            wpl_package
+                .parse("package b {\n rule r { (addr) }\n}")
+                .is_err()
+        );
+        assert!(wpl_rule::wpl_rule.parse("rule r { (addr) }").is_err());
+
+        // 单独解析包体时同样可用
+        let mut body = "type addr(p) = (ip:${p}ip)\n rule r { (addr(d_), digit:port) }";
+        let (rules, _) = wpl_pkg_body2(&mut body).assert();
+        let fields = &rules[0].statement.first_group().unwrap().fields;
+        let names: Vec<_> = fields
+            .iter()
+            .map(|f| f.name.as_ref().map(|x| x.to_string()).unwrap_or_default())
+            .collect();
+        assert_eq!(names, vec!["d_ip", "port"]);
+    }
+
+    #[test]
+    fn test_pkg_type_errors() {
+        let err = parse_err(
+            r#"package p {
+    type addr(p) = (ip:${p}ip)
+    rule r {
+        (addr(a, b))
+    }
+}"#,
+        );
+        assert!(err.contains("expects at most 1 arg(s), got 2"), "{}", err);
+        assert!(err.contains("defined at line 2: type addr(p)"), "{}", err);
+        assert!(err.contains("used at line 4: addr(a, b)"), "{}", err);
+
+        let err = parse_err("package p {\n type ip(p) = (ip:${p}ip)\n rule r { (ip) }\n}");
+        assert!(err.contains("shadows a builtin name"), "{}", err);
+
+        let err = parse_err("package p {\n type a(p) = (ip:${q}ip)\n rule r { (a) }\n}");
+        assert!(err.contains("undeclared param '${q}'"), "{}", err);
+
+        let err = parse_err("package p {\n type a = (ip)\n type a = (digit)\n rule r { (a) }\n}");
+        assert!(err.contains("first defined at line 2"), "{}", err);
+    }
+
+    #[test]
+    fn test_pkg_type_error_lines_with_comments() {
+        let src = r#"// 头部注释
+
+/*
+ * 多行注释
+ */
+package p {
+    // 地址类型
+
+    type addr(p) = (ip:${p}ip)
+
+    // 重复定义
+    type addr = (digit)
+    rule r { (addr) }
+}"#;
+        let code = crate::WplCode::try_from(src).assert();
+        let err = parse_err(code.get_code());
+        assert!(err.contains("redefined at line 12"), "{}", err);
+        assert!(err.contains("first defined at line 9"), "{}", err);
+
+        let src = r#"// 头部注释
+
+package p {
+    // 地址类型
+    type addr(p) = (ip:${p}ip)
+
+    // 规则
+    rule r {
+        // 字段
+        (addr(a, b))
+    }
+}"#;
+        let code = crate::WplCode::try_from(src).assert();
+        let err = parse_err(code.get_code());
+        assert!(err.contains("defined at line 5: type addr(p)"), "{}", err);
+        assert!(err.contains("used at line 10: addr(a, b)"), "{}", err);
+    }
+
+    #[test]
+    fn test_pkg_test_case() {
+        let code = r#"package nginx {
+    rule access { (ip:sip, chars:msg") }
+    test access {
+        input: "1.2.3.4 \"hello (world)\"";
+        expect: { sip: ip(1.2.3.4), msg: chars("hello (world)"), }
+    }
+    test { input: "5.6.7.8 x"; expect: { sip: ip(5.6.7.8) } }
+}"#;
+        let pkg = wpl_package.parse(code).assert();
+        assert_eq!(pkg.rules.len(), 1);
+        assert_eq!(pkg.tests.len(), 2);
+        let case = &pkg.tests[0];
+        assert_eq!(case.rule.as_deref(), Some("access"));
+        assert_eq!(case.input, r#"1.2.3.4 "hello (world)""#);
+        assert_eq!(
+            case.expect[1],
+            WplExpectField {
+                name: "msg".into(),
+                meta: "chars".into(),
+                value: "hello (world)".to_string(),
+            }
+        );
+        assert_eq!(pkg.tests[1].rule, None);
+
+        // 输出后可再次解析
+        let again = wpl_package.parse(pkg.to_string().as_str()).assert();
+        assert_eq!(again.tests, pkg.tests);
+
+        let err =
+            parse_err("package p {\n rule r { (ip) }\n test { input: \"x\"; expect: { ip } }\n}");
+        assert!(err.contains("<name>: <type>(<value>)"), "{}", err);
+    }
+}
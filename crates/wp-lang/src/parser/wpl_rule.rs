@@ -1,10 +1,11 @@
 use super::wpl_anno::ann_fun;
 use crate::ast::{WplField, WplRule, WplStatementType};
-use crate::parser::wpl_field::wpl_field;
+use crate::parser::wpl_field::wpl_field_with;
+use crate::parser::wpl_pkg::{PkgTypeScope, wpl_type_ref};
 use crate::parser::{parse_code, utils};
 use smol_str::SmolStr;
 use winnow::ascii::multispace0;
-use winnow::combinator::{alt, opt, repeat};
+use winnow::combinator::{alt, delimited, opt, repeat};
 use winnow::error::StrContext;
 use winnow::token::literal;
 use wp_parser::Parser;
@@ -37,6 +38,23 @@ fn take_plg_pipe_step(input: &mut &str) -> wp_parser::WResult<SmolStr> {
         .parse_next(input)
 }
 
+/// 内置预处理单元，可带参数：`decode/charset(gbk)`
+fn take_unit_step(input: &mut &str) -> wp_parser::WResult<SmolStr> {
+    let name = utils::take_key
+        .context(StrContext::Label("expect [a-z],[A-Z],[/],[_]"))
+        .parse_next(input)?;
+    let args = opt(delimited(
+        (literal('('), multispace0),
+        utils::take_key,
+        (multispace0, literal(')')),
+    ))
+    .parse_next(input)?;
+    Ok(match args {
+        Some(args) => SmolStr::from(format!("{}({})", name, args)),
+        None => SmolStr::from(name),
+    })
+}
+
 pub fn pip_proc(input: &mut &str) -> wp_parser::WResult<Vec<SmolStr>> {
     let x: Vec<_> = repeat(
         1..,
@@ -45,9 +63,7 @@ pub fn pip_proc(input: &mut &str) -> wp_parser::WResult<Vec<SmolStr>> {
             multispace0,
             alt((
                 take_plg_pipe_step.context(StrContext::Label("expect plg_pipe/<name>")),
-                utils::take_key
-                    .context(StrContext::Label("expect [a-z],[A-Z],[/],[_]"))
-                    .map(SmolStr::from),
+                take_unit_step,
             )),
             multispace0,
         )
@@ -63,6 +79,13 @@ pub fn pip_proc(input: &mut &str) -> wp_parser::WResult<Vec<SmolStr>> {
 }
 
 pub fn wpl_rule(input: &mut &str) -> wp_parser::WResult<WplRule> {
+    wpl_rule_with(input, None)
+}
+
+pub(crate) fn wpl_rule_with(
+    input: &mut &str,
+    types: Option<&PkgTypeScope>,
+) -> wp_parser::WResult<WplRule> {
     let atags = opt(ann_fun).parse_next(input)?;
     (multispace0, "rule", multispace0)
         .context(ctx_label("wpl keyword"))
@@ -74,7 +97,7 @@ pub fn wpl_rule(input: &mut &str) -> wp_parser::WResult<WplRule> {
     (multispace0, "{", multispace0).parse_next(input)?;
 
     let stm = WplStatementType::Express(
-        parse_code::wpl_express
+        (|i: &mut &str| parse_code::wpl_express_with(i, types))
             .context(ctx_label("group"))
             .context(ctx_desc("+<group>"))
             .parse_next(input)?,
@@ -88,11 +111,24 @@ pub fn wpl_rule(input: &mut &str) -> wp_parser::WResult<WplRule> {
 }
 
 pub(crate) fn wpl_field_vec(input: &mut &str) -> wp_parser::WResult<Vec<WplField>> {
+    wpl_field_vec_with(input, None)
+}
+
+pub(crate) fn wpl_field_vec_with(
+    input: &mut &str,
+    types: Option<&PkgTypeScope>,
+) -> wp_parser::WResult<Vec<WplField>> {
     let mut field_vec = Vec::new();
     multispace0.parse_next(input)?;
     while utils::peek_next((multispace0, ")"), input).is_err() && !input.is_empty() {
-        let field = wpl_field.context(ctx_desc("<field>")).parse_next(input)?;
-        field_vec.push(field);
+        if let Some(mut fields) = wpl_type_ref(input, types)? {
+            field_vec.append(&mut fields);
+        } else {
+            let field = (|i: &mut &str| wpl_field_with(i, types))
+                .context(ctx_desc("<field>"))
+                .parse_next(input)?;
+            field_vec.push(field);
+        }
         if utils::peek_next((multispace0, ")"), input).is_err() && !input.is_empty() {
             (multispace0, ",")
                 .context(ctx_label("symbol"))
@@ -164,4 +200,29 @@ mod tests {
         let items = pip_proc.parse_next(&mut input).assert();
         assert_eq!(items, vec!["plg_pipe/mock_stage", "decode/base64"]);
     }
+
+    #[test]
+    fn test_preproc_with_args() {
+        let mut input = "| decode/base64 | decode/gzip | decode/charset( gbk ) | strip/ansi |";
+        let items = pip_proc.parse_next(&mut input).assert();
+        assert_eq!(
+            items,
+            vec![
+                "decode/base64",
+                "decode/gzip",
+                "decode/charset(gbk)",
+                "strip/ansi"
+            ]
+        );
+
+        let rule = r#"rule x { | decode/url | decode/charset(gb18030) | (chars:q) }"#;
+        let rule = wpl_rule::wpl_rule.parse(rule).assert();
+        let crate::ast::WplStatementType::Express(express) = &rule.statement;
+        assert!(crate::WplEvaluator::from(express, None).is_ok());
+
+        let rule = r#"rule x { | decode/charset(klingon) | (chars:q) }"#;
+        let rule = wpl_rule::wpl_rule.parse(rule).assert();
+        let crate::ast::WplStatementType::Express(express) = &rule.statement;
+        assert!(crate::WplEvaluator::from(express, None).is_err());
+    }
 }
@@ -55,8 +55,11 @@ pub fn compile_rule(
                     let f_conf_cloned = f_conf.clone();
                     let sep_cloned = sep.clone();
                     let field_fn: FieldGenFn = Box::new(move |ch: &mut GenChannel| {
-                        let meta = DataType::from(f_conf_cloned.meta_name.as_str())?;
-                        let parser = ParserFactory::create(&meta)?;
+                        // 扩展类型与 time(fmt=...) 依赖字段配置中的类型参数
+                        let parser = match DataType::from(f_conf_cloned.meta_name.as_str()) {
+                            Ok(meta) if meta != DataType::Time => ParserFactory::create(&meta)?,
+                            _ => ParserFactory::create_field(&f_conf_cloned)?,
+                        };
                         let f = parser.generate(ch, &sep_cloned, &f_conf_cloned, gconf.as_ref())?;
                         Ok(f)
                     });
@@ -0,0 +1,128 @@
+//! 插件 ABI v1：导出函数名与字段输出编码
+
+use wp_model_core::model::{DataField, DataType};
+
+pub const ABI_VERSION: i32 = 1;
+
+pub const EXPORT_MEMORY: &str = "memory";
+pub const EXPORT_VERSION: &str = "wp_abi_version";
+pub const EXPORT_ALLOC: &str = "wp_alloc";
+pub const EXPORT_DEALLOC: &str = "wp_dealloc";
+pub const EXPORT_PARSE: &str = "wp_parse";
+pub const EXPORT_FIELD_PIPE: &str = "wp_field_pipe";
+pub const EXPORT_PIPE_UNIT: &str = "wp_pipe_unit";
+
+/// 入口返回值：负数表示不匹配/拒绝，否则为 `(out_ptr << 32) | out_len`
+pub fn unpack_ret(ret: i64) -> Option<(u32, u32)> {
+    if ret < 0 {
+        return None;
+    }
+    Some(((ret >> 32) as u32, ret as u32))
+}
+
+/// 字段类型标签
+pub fn meta_of_tag(tag: u8) -> Option<DataType> {
+    match tag {
+        0 => Some(DataType::Chars),
+        1 => Some(DataType::Digit),
+        2 => Some(DataType::Float),
+        3 => Some(DataType::Bool),
+        4 => Some(DataType::IP),
+        5 => Some(DataType::Time),
+        _ => None,
+    }
+}
+
+/// 类型化输出：`u32 consumed, u32 count`，随后 `count` 个字段
+/// `u8 tag, u32 name_len, name, u32 value_len, value`（小端，UTF-8）
+#[derive(Debug, Clone)]
+pub struct TypedOutput {
+    pub consumed: usize,
+    pub fields: Vec<DataField>,
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|end| *end <= self.buf.len())
+            .ok_or_else(|| format!("output truncated at {}", self.pos))?;
+        let out = &self.buf[self.pos..end];
+        self.pos = end;
+        Ok(out)
+    }
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+    fn u32(&mut self) -> Result<u32, String> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+    fn str(&mut self) -> Result<&'a str, String> {
+        let len = self.u32()? as usize;
+        std::str::from_utf8(self.take(len)?).map_err(|e| e.to_string())
+    }
+}
+
+/// 解码类型化输出；名称为空的字段使用 `default_name`
+pub fn decode_fields(buf: &[u8], default_name: &str) -> Result<TypedOutput, String> {
+    let mut rd = Reader { buf, pos: 0 };
+    let consumed = rd.u32()? as usize;
+    let count = rd.u32()? as usize;
+    let mut fields = Vec::with_capacity(count.min(64));
+    for _ in 0..count {
+        let tag = rd.u8()?;
+        let meta = meta_of_tag(tag).ok_or_else(|| format!("unknown field tag {}", tag))?;
+        let name = match rd.str()? {
+            "" => default_name,
+            name => name,
+        };
+        let value = rd.str()?;
+        let field = DataField::from_str(meta.clone(), name, value)
+            .map_err(|_| format!("field {}: invalid {} value '{}'", name, meta, value))?;
+        fields.push(field);
+    }
+    Ok(TypedOutput { consumed, fields })
+}
+
+/// 按 ABI 编码字段（测试与宿主侧工具使用）
+pub fn encode_fields(consumed: u32, fields: &[(u8, &str, &str)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(&consumed.to_le_bytes());
+    out.extend_from_slice(&(fields.len() as u32).to_le_bytes());
+    for (tag, name, value) in fields {
+        out.push(*tag);
+        out.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        out.extend_from_slice(value.as_bytes());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields_roundtrip() {
+        let buf = encode_fields(7, &[(4, "sip", "10.0.0.1"), (1, "", "42")]);
+        let out = decode_fields(&buf, "port").unwrap();
+        assert_eq!(out.consumed, 7);
+        assert_eq!(out.fields[0].get_name(), "sip");
+        assert_eq!(*out.fields[0].get_meta(), DataType::IP);
+        assert_eq!(out.fields[1].get_name(), "port");
+        assert_eq!(*out.fields[1].get_meta(), DataType::Digit);
+
+        assert!(decode_fields(&buf[..buf.len() - 1], "x").is_err());
+        assert!(decode_fields(&encode_fields(0, &[(9, "a", "b")]), "x").is_err());
+        assert_eq!(unpack_ret(-1), None);
+        assert_eq!(unpack_ret((8i64 << 32) | 3), Some((8, 3)));
+    }
+}
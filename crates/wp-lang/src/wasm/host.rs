@@ -0,0 +1,342 @@
+use std::path::Path;
+use std::sync::Mutex;
+
+use orion_error::ToStructError;
+use smol_str::SmolStr;
+use wasmi::{
+    Config, Engine, Instance, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder,
+    TypedFunc,
+};
+
+use super::abi::{self, ABI_VERSION};
+use crate::parser::error::{WplCodeReason, WplCodeResult};
+
+pub const DEFAULT_FUEL: u64 = 10_000_000;
+pub const DEFAULT_MEMORY_MB: usize = 16;
+
+/// 沙箱限制：每次调用的燃料（指令配额）与线性内存上限
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WasmLimits {
+    pub fuel: u64,
+    pub memory_mb: usize,
+}
+
+impl Default for WasmLimits {
+    fn default() -> Self {
+        Self {
+            fuel: DEFAULT_FUEL,
+            memory_mb: DEFAULT_MEMORY_MB,
+        }
+    }
+}
+
+/// 插件导出的入口
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WasmEntry {
+    Parse,
+    FieldPipe,
+    PipeUnit,
+}
+
+impl WasmEntry {
+    pub fn export_name(&self) -> &'static str {
+        match self {
+            WasmEntry::Parse => abi::EXPORT_PARSE,
+            WasmEntry::FieldPipe => abi::EXPORT_FIELD_PIPE,
+            WasmEntry::PipeUnit => abi::EXPORT_PIPE_UNIT,
+        }
+    }
+}
+
+struct HostState {
+    limits: StoreLimits,
+}
+
+type EntryFn = TypedFunc<(i32, i32), i64>;
+
+struct Runtime {
+    store: Store<HostState>,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+    dealloc: Option<TypedFunc<(i32, i32), ()>>,
+    parse: Option<EntryFn>,
+    field_pipe: Option<EntryFn>,
+    pipe_unit: Option<EntryFn>,
+}
+
+/// 已加载的插件：模块只编译一次，实例按需创建并放回空闲池，多个解析线程可并发调用
+pub struct WasmPlugin {
+    name: SmolStr,
+    limits: WasmLimits,
+    engine: Engine,
+    module: Module,
+    entries: Vec<WasmEntry>,
+    /// 空闲实例；调用出错（陷入、燃料耗尽、越界）的实例直接丢弃，不再复用
+    idle: Mutex<Vec<Runtime>>,
+    max_idle: usize,
+}
+
+impl std::fmt::Debug for WasmPlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmPlugin")
+            .field("name", &self.name)
+            .field("limits", &self.limits)
+            .finish()
+    }
+}
+
+fn plugin_err<T>(name: &str, msg: impl std::fmt::Display) -> WplCodeResult<T> {
+    WplCodeReason::Plugin(format!("wasm plugin '{}': {}", name, msg)).err_result()
+}
+
+impl WasmPlugin {
+    /// 从 `.wasm` 文件加载；启用 `wat` 特性时也接受 `.wat` 文本
+    pub fn load<P: AsRef<Path>>(name: &str, path: P, limits: WasmLimits) -> WplCodeResult<Self> {
+        let path = path.as_ref();
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) => return plugin_err(name, format!("read {}: {}", path.display(), e)),
+        };
+        if path.extension().is_some_and(|ext| ext == "wat") {
+            return Self::from_wat(name, &bytes, limits);
+        }
+        Self::from_bytes(name, &bytes, limits)
+    }
+
+    #[cfg(feature = "wat")]
+    fn from_wat(name: &str, text: &[u8], limits: WasmLimits) -> WplCodeResult<Self> {
+        match wat::parse_bytes(text) {
+            Ok(wasm) => Self::from_bytes(name, &wasm, limits),
+            Err(e) => plugin_err(name, e),
+        }
+    }
+
+    #[cfg(not(feature = "wat"))]
+    fn from_wat(name: &str, _text: &[u8], _limits: WasmLimits) -> WplCodeResult<Self> {
+        plugin_err(name, "`.wat` plugins require the `wat` feature")
+    }
+
+    pub fn from_bytes(name: &str, wasm: &[u8], limits: WasmLimits) -> WplCodeResult<Self> {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = match Module::new(&engine, wasm) {
+            Ok(module) => module,
+            Err(e) => return plugin_err(name, e),
+        };
+        // 加载时实例化一次：校验 ABI 与导出，并作为首个空闲实例
+        let runtime = Self::instantiate(name, &engine, &module, limits)?;
+        let entries = [WasmEntry::Parse, WasmEntry::FieldPipe, WasmEntry::PipeUnit]
+            .into_iter()
+            .filter(|e| runtime.entry(*e).is_some())
+            .collect();
+        Ok(Self {
+            name: SmolStr::from(name),
+            limits,
+            engine,
+            module,
+            entries,
+            idle: Mutex::new(vec![runtime]),
+            max_idle: std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(4),
+        })
+    }
+
+    fn instantiate(
+        name: &str,
+        engine: &Engine,
+        module: &Module,
+        limits: WasmLimits,
+    ) -> WplCodeResult<Runtime> {
+        let state = HostState {
+            limits: StoreLimitsBuilder::new()
+                .memory_size(limits.memory_mb * 1024 * 1024)
+                .instances(1)
+                .memories(1)
+                .tables(1)
+                .build(),
+        };
+        let mut store = Store::new(engine, state);
+        store.limiter(|state| &mut state.limits);
+        if let Err(e) = store.set_fuel(limits.fuel) {
+            return plugin_err(name, e);
+        }
+        // 不提供任何宿主导入：需要导入的模块无法实例化
+        let linker = Linker::<HostState>::new(engine);
+        let instance = match linker
+            .instantiate(&mut store, module)
+            .and_then(|pre| pre.start(&mut store))
+        {
+            Ok(instance) => instance,
+            Err(e) => return plugin_err(name, e),
+        };
+        Self::bind(name, store, instance)
+    }
+
+    fn bind(name: &str, mut store: Store<HostState>, instance: Instance) -> WplCodeResult<Runtime> {
+        let Some(memory) = instance.get_memory(&store, abi::EXPORT_MEMORY) else {
+            return plugin_err(name, "missing export 'memory'");
+        };
+        let version = match instance.get_typed_func::<(), i32>(&store, abi::EXPORT_VERSION) {
+            Ok(f) => f.call(&mut store, ()),
+            Err(_) => return plugin_err(name, "missing export 'wp_abi_version'"),
+        };
+        match version {
+            Ok(ABI_VERSION) => {}
+            Ok(v) => return plugin_err(name, format!("unsupported abi version {}", v)),
+            Err(e) => return plugin_err(name, e),
+        }
+        let Ok(alloc) = instance.get_typed_func::<i32, i32>(&store, abi::EXPORT_ALLOC) else {
+            return plugin_err(name, "missing export 'wp_alloc'");
+        };
+        let dealloc = instance
+            .get_typed_func::<(i32, i32), ()>(&store, abi::EXPORT_DEALLOC)
+            .ok();
+        let entry = |e: WasmEntry| {
+            instance
+                .get_typed_func::<(i32, i32), i64>(&store, e.export_name())
+                .ok()
+        };
+        let runtime = Runtime {
+            parse: entry(WasmEntry::Parse),
+            field_pipe: entry(WasmEntry::FieldPipe),
+            pipe_unit: entry(WasmEntry::PipeUnit),
+            memory,
+            alloc,
+            dealloc,
+            store,
+        };
+        if runtime.parse.is_none() && runtime.field_pipe.is_none() && runtime.pipe_unit.is_none() {
+            return plugin_err(name, "exports none of wp_parse/wp_field_pipe/wp_pipe_unit");
+        }
+        Ok(runtime)
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn limits(&self) -> WasmLimits {
+        self.limits
+    }
+
+    pub fn supports(&self, entry: WasmEntry) -> bool {
+        self.entries.contains(&entry)
+    }
+
+    /// 调用入口：`Ok(None)` 表示插件返回不匹配/拒绝；陷入、燃料耗尽、越界均为错误
+    pub fn call(&self, entry: WasmEntry, input: &[u8]) -> WplCodeResult<Option<Vec<u8>>> {
+        if !self.supports(entry) {
+            return plugin_err(
+                &self.name,
+                format!("missing export '{}'", entry.export_name()),
+            );
+        }
+        let Ok(in_len) = i32::try_from(input.len()) else {
+            return plugin_err(&self.name, "input too large");
+        };
+        let mut rt = self.checkout()?;
+        // 出错的实例可能处于不一致状态：丢弃，下次调用重新实例化
+        let out = rt.invoke(entry, self.limits.fuel, input, in_len);
+        match out {
+            Ok(out) => {
+                self.checkin(rt);
+                Ok(out)
+            }
+            Err(e) => plugin_err(&self.name, e),
+        }
+    }
+
+    fn checkout(&self) -> WplCodeResult<Runtime> {
+        let idle = self.idle.lock().expect("wasm plugin poisoned").pop();
+        match idle {
+            Some(rt) => Ok(rt),
+            None => Self::instantiate(&self.name, &self.engine, &self.module, self.limits),
+        }
+    }
+
+    fn checkin(&self, rt: Runtime) {
+        let mut idle = self.idle.lock().expect("wasm plugin poisoned");
+        if idle.len() < self.max_idle {
+            idle.push(rt);
+        }
+    }
+}
+
+impl Runtime {
+    fn entry(&self, entry: WasmEntry) -> Option<EntryFn> {
+        match entry {
+            WasmEntry::Parse => self.parse,
+            WasmEntry::FieldPipe => self.field_pipe,
+            WasmEntry::PipeUnit => self.pipe_unit,
+        }
+    }
+
+    fn invoke(
+        &mut self,
+        entry: WasmEntry,
+        fuel: u64,
+        input: &[u8],
+        in_len: i32,
+    ) -> Result<Option<Vec<u8>>, String> {
+        let Some(func) = self.entry(entry) else {
+            return Err(format!("missing export '{}'", entry.export_name()));
+        };
+        self.store.set_fuel(fuel).map_err(|e| e.to_string())?;
+        let in_ptr = match self.alloc.call(&mut self.store, in_len) {
+            Ok(ptr) if ptr >= 0 => ptr,
+            Ok(_) => return Err("wp_alloc failed".into()),
+            Err(e) => return Err(e.to_string()),
+        };
+        if let Err(e) = self.memory.write(&mut self.store, in_ptr as usize, input) {
+            self.release(fuel, in_ptr, in_len);
+            return Err(e.to_string());
+        }
+        let ret = match func.call(&mut self.store, (in_ptr, in_len)) {
+            Ok(ret) => ret,
+            Err(e) => {
+                self.release(fuel, in_ptr, in_len);
+                return Err(e.to_string());
+            }
+        };
+        let out = abi::unpack_ret(ret);
+        // 插件原地处理时输出与输入共用缓冲，只释放一次
+        if out.is_none_or(|(ptr, _)| ptr as i32 != in_ptr) {
+            self.free(in_ptr, in_len)?;
+        }
+        let Some((out_ptr, out_len)) = out else {
+            return Ok(None);
+        };
+        // 输出区间须落在实例线性内存内，避免按插件给出的长度在宿主侧分配
+        let data = self.memory.data(&self.store);
+        let end = u64::from(out_ptr) + u64::from(out_len);
+        if end > data.len() as u64 {
+            return Err(format!(
+                "output {}+{} out of memory bounds ({})",
+                out_ptr,
+                out_len,
+                data.len()
+            ));
+        }
+        let buf = data[out_ptr as usize..end as usize].to_vec();
+        self.free(out_ptr as i32, out_len as i32)?;
+        Ok(Some(buf))
+    }
+
+    /// 出错路径上尽力释放输入缓冲；实例随后被丢弃
+    fn release(&mut self, fuel: u64, ptr: i32, len: i32) {
+        if self.store.set_fuel(fuel).is_ok() {
+            let _ = self.free(ptr, len);
+        }
+    }
+
+    fn free(&mut self, ptr: i32, len: i32) -> Result<(), String> {
+        if let Some(dealloc) = self.dealloc
+            && let Err(e) = dealloc.call(&mut self.store, (ptr, len))
+        {
+            return Err(format!("wp_dealloc: {}", e));
+        }
+        Ok(())
+    }
+}
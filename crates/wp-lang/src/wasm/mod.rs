@@ -0,0 +1,225 @@
+//! WASM 插件宿主
+//!
+//! 插件在 wasmi 解释器中运行：不提供任何宿主导入，每次调用重置燃料配额，
+//! 线性内存受 `memory_mb` 限制。同一插件可导出以下任意入口，在 WPL 中统一以
+//! `wasm(<插件名>)` 引用：
+//!
+//! | 入口 | WPL 用法 | 输入 | 输出 |
+//! |------|----------|------|------|
+//! | `wp_parse` | 字段类型 `wasm(name):field` | 剩余待解析文本 | 类型化字段（含消费字节数） |
+//! | `wp_field_pipe` | 字段管道 `\| wasm(name)` | 当前字段值 | 类型化字段（取首个替换当前字段） |
+//! | `wp_pipe_unit` | 预处理 `\| wasm(name) \|` | 原始数据 | 原始数据 |
+//!
+//! ABI v1 约定见 [`abi`]：插件还须导出 `memory`、`wp_abi_version() -> i32`、
+//! `wp_alloc(len) -> ptr`，可选导出 `wp_dealloc(ptr, len)`。
+
+pub mod abi;
+mod host;
+
+use once_cell::sync::Lazy;
+use smol_str::SmolStr;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+pub use host::{DEFAULT_FUEL, DEFAULT_MEMORY_MB, WasmEntry, WasmLimits, WasmPlugin};
+
+use crate::parser::error::WplCodeResult;
+
+static WASM_PLUGINS: Lazy<RwLock<HashMap<SmolStr, Arc<WasmPlugin>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// 注册插件；同名插件被替换
+pub fn register_wasm_plugin(plugin: WasmPlugin) {
+    WASM_PLUGINS
+        .write()
+        .expect("wasm plugin registry poisoned")
+        .insert(SmolStr::from(plugin.name()), Arc::new(plugin));
+}
+
+/// 加载并注册插件
+pub fn load_wasm_plugin<P: AsRef<Path>>(
+    name: &str,
+    path: P,
+    limits: WasmLimits,
+) -> WplCodeResult<()> {
+    register_wasm_plugin(WasmPlugin::load(name, path, limits)?);
+    Ok(())
+}
+
+pub fn wasm_plugin(name: &str) -> Option<Arc<WasmPlugin>> {
+    WASM_PLUGINS
+        .read()
+        .expect("wasm plugin registry poisoned")
+        .get(name)
+        .cloned()
+}
+
+pub fn list_wasm_plugins() -> Vec<SmolStr> {
+    let mut names: Vec<SmolStr> = WASM_PLUGINS
+        .read()
+        .expect("wasm plugin registry poisoned")
+        .keys()
+        .cloned()
+        .collect();
+    names.sort();
+    names
+}
+
+#[cfg(test)]
+pub(crate) mod test_plugin {
+    use super::*;
+
+    pub const SAMPLE_WAT: &str = include_str!("testdata/sample.wat");
+
+    /// 以给定名称注册测试插件（各测试使用不同名称，互不干扰）
+    pub fn register_sample(name: &str) {
+        let wasm = wat::parse_str(SAMPLE_WAT).expect("sample wat");
+        let plugin = WasmPlugin::from_bytes(name, &wasm, WasmLimits::default()).expect("load");
+        register_wasm_plugin(plugin);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_plugin::{SAMPLE_WAT, register_sample};
+    use super::*;
+    use wp_model_core::model::{DataType, Value};
+
+    fn load_wat(src: &str, limits: WasmLimits) -> WplCodeResult<WasmPlugin> {
+        WasmPlugin::from_bytes("t", &wat::parse_str(src).unwrap(), limits)
+    }
+
+    #[test]
+    fn test_sample_entries() {
+        let plugin = load_wat(SAMPLE_WAT, WasmLimits::default()).unwrap();
+        assert!(plugin.supports(WasmEntry::Parse));
+
+        let out = plugin.call(WasmEntry::PipeUnit, b"get /a").unwrap();
+        assert_eq!(out.as_deref(), Some(&b"GET /A"[..]));
+
+        let out = plugin
+            .call(WasmEntry::Parse, b"8080 rest")
+            .unwrap()
+            .unwrap();
+        let out = abi::decode_fields(&out, "port").unwrap();
+        assert_eq!(out.consumed, 4);
+        assert_eq!(*out.fields[0].get_meta(), DataType::Digit);
+        assert!(plugin.call(WasmEntry::Parse, b"abc").unwrap().is_none());
+
+        let out = plugin
+            .call(WasmEntry::FieldPipe, b"secret")
+            .unwrap()
+            .unwrap();
+        let out = abi::decode_fields(&out, "pwd").unwrap();
+        assert!(matches!(out.fields[0].get_value(), Value::Chars(v) if v.as_str() == "******"));
+        assert!(plugin.call(WasmEntry::FieldPipe, b"").unwrap().is_none());
+
+        // 重复调用不泄漏内存
+        for _ in 0..1000 {
+            plugin.call(WasmEntry::PipeUnit, &[b'a'; 4096]).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_sandbox_limits() {
+        let spin = r#"(module
+            (memory (export "memory") 1)
+            (func (export "wp_abi_version") (result i32) (i32.const 1))
+            (func (export "wp_alloc") (param i32) (result i32) (i32.const 0))
+            (func (export "wp_pipe_unit") (param i32 i32) (result i64)
+                (loop $l (br $l)) (i64.const -1)))"#;
+        let plugin = load_wat(
+            spin,
+            WasmLimits {
+                fuel: 10_000,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(plugin.call(WasmEntry::PipeUnit, b"x").is_err());
+        // 燃料每次调用重置，陷入后实例被丢弃并重新实例化
+        assert!(plugin.call(WasmEntry::PipeUnit, b"x").is_err());
+
+        // 输出长度越界：在宿主分配缓冲前拒绝
+        let huge = r#"(module
+            (memory (export "memory") 1)
+            (func (export "wp_abi_version") (result i32) (i32.const 1))
+            (func (export "wp_alloc") (param i32) (result i32) (i32.const 0))
+            (func (export "wp_pipe_unit") (param i32 i32) (result i64)
+                (i64.const 0xffffffff)))"#;
+        let plugin = load_wat(huge, WasmLimits::default()).unwrap();
+        assert!(plugin.call(WasmEntry::PipeUnit, b"x").is_err());
+        assert!(plugin.call(WasmEntry::FieldPipe, b"x").is_err());
+
+        let big = r#"(module
+            (memory (export "memory") 64)
+            (func (export "wp_abi_version") (result i32) (i32.const 1))
+            (func (export "wp_alloc") (param i32) (result i32) (i32.const 0))
+            (func (export "wp_pipe_unit") (param i32 i32) (result i64) (i64.const -1)))"#;
+        let limits = WasmLimits {
+            memory_mb: 1,
+            ..Default::default()
+        };
+        assert!(load_wat(big, limits).is_err());
+
+        let import = r#"(module
+            (import "env" "now" (func (result i64)))
+            (memory (export "memory") 1))"#;
+        assert!(load_wat(import, WasmLimits::default()).is_err());
+
+        let old = r#"(module
+            (memory (export "memory") 1)
+            (func (export "wp_abi_version") (result i32) (i32.const 0)))"#;
+        assert!(load_wat(old, WasmLimits::default()).is_err());
+    }
+
+    #[test]
+    fn test_trap_reinstantiates() {
+        // 第二次调用起陷入：全局计数器记录调用次数，只有新实例才会从 0 开始
+        let flaky = r#"(module
+            (memory (export "memory") 1)
+            (global $n (mut i32) (i32.const 0))
+            (func (export "wp_abi_version") (result i32) (i32.const 1))
+            (func (export "wp_alloc") (param i32) (result i32) (i32.const 0))
+            (func (export "wp_pipe_unit") (param i32 i32) (result i64)
+                (global.set $n (i32.add (global.get $n) (i32.const 1)))
+                (if (i32.gt_u (global.get $n) (i32.const 1)) (then unreachable))
+                (i64.extend_i32_u (local.get 1))))"#;
+        let plugin = load_wat(flaky, WasmLimits::default()).unwrap();
+        assert!(plugin.call(WasmEntry::PipeUnit, b"a").unwrap().is_some());
+        assert!(plugin.call(WasmEntry::PipeUnit, b"a").is_err());
+        // 陷入的实例未放回池中
+        assert!(plugin.call(WasmEntry::PipeUnit, b"a").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_concurrent_calls() {
+        let plugin = Arc::new(load_wat(SAMPLE_WAT, WasmLimits::default()).unwrap());
+        let workers: Vec<_> = (0..4)
+            .map(|_| {
+                let plugin = plugin.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..200 {
+                        let out = plugin.call(WasmEntry::PipeUnit, b"get /a").unwrap();
+                        assert_eq!(out.as_deref(), Some(&b"GET /A"[..]));
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_registry() {
+        register_sample("reg_sample");
+        assert!(wasm_plugin("reg_sample").is_some());
+        assert!(list_wasm_plugins().contains(&SmolStr::from("reg_sample")));
+        assert!(wasm_plugin("reg_missing").is_none());
+        assert!(
+            load_wasm_plugin("reg_file", "/nonexistent/p.wasm", WasmLimits::default()).is_err()
+        );
+    }
+}
@@ -0,0 +1,98 @@
+;; 测试用插件（ABI v1），行为与 tools/wasm-plugin-sample 一致：
+;;   wp_pipe_unit  : ASCII 小写转大写（原地返回）
+;;   wp_parse      : 取开头连续数字为 digit 字段
+;;   wp_field_pipe : 字段值掩码为等长 `*`，空值拒绝
+;; 宿主每次调用只分配一次输入缓冲，wp_alloc 直接复位为单次竞技场，因此不导出 wp_dealloc。
+(module
+  (memory (export "memory") 1)
+  (global $heap (mut i32) (i32.const 1024))
+
+  (func (export "wp_abi_version") (result i32) (i32.const 1))
+
+  (func (export "wp_alloc") (param $len i32) (result i32)
+    (global.set $heap (i32.add (i32.const 1024) (local.get $len)))
+    (i32.const 1024))
+
+  (func $take (param $len i32) (result i32)
+    (local $p i32)
+    (local.set $p (global.get $heap))
+    (global.set $heap (i32.add (local.get $p) (local.get $len)))
+    (local.get $p))
+
+  (func $pack (param $ptr i32) (param $len i32) (result i64)
+    (i64.or
+      (i64.shl (i64.extend_i32_u (local.get $ptr)) (i64.const 32))
+      (i64.extend_i32_u (local.get $len))))
+
+  ;; 单字段输出头：consumed, count=1, tag, name_len=0, value_len；值从 out+17 开始
+  (func $header (param $out i32) (param $consumed i32) (param $tag i32) (param $vlen i32)
+    (i32.store (local.get $out) (local.get $consumed))
+    (i32.store offset=4 (local.get $out) (i32.const 1))
+    (i32.store8 offset=8 (local.get $out) (local.get $tag))
+    (i32.store offset=9 (local.get $out) (i32.const 0))
+    (i32.store offset=13 (local.get $out) (local.get $vlen)))
+
+  (func (export "wp_pipe_unit") (param $ptr i32) (param $len i32) (result i64)
+    (local $i i32)
+    (local $c i32)
+    (block $done
+      (loop $next
+        (br_if $done (i32.ge_u (local.get $i) (local.get $len)))
+        (local.set $c (i32.load8_u (i32.add (local.get $ptr) (local.get $i))))
+        (if (i32.and
+              (i32.ge_u (local.get $c) (i32.const 97))
+              (i32.le_u (local.get $c) (i32.const 122)))
+          (then
+            (i32.store8
+              (i32.add (local.get $ptr) (local.get $i))
+              (i32.sub (local.get $c) (i32.const 32)))))
+        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+        (br $next)))
+    (call $pack (local.get $ptr) (local.get $len)))
+
+  (func (export "wp_parse") (param $ptr i32) (param $len i32) (result i64)
+    (local $n i32)
+    (local $c i32)
+    (local $out i32)
+    (local $i i32)
+    (block $done
+      (loop $next
+        (br_if $done (i32.ge_u (local.get $n) (local.get $len)))
+        (local.set $c (i32.load8_u (i32.add (local.get $ptr) (local.get $n))))
+        (br_if $done
+          (i32.or
+            (i32.lt_u (local.get $c) (i32.const 48))
+            (i32.gt_u (local.get $c) (i32.const 57))))
+        (local.set $n (i32.add (local.get $n) (i32.const 1)))
+        (br $next)))
+    (if (i32.eqz (local.get $n))
+      (then (return (i64.const -1))))
+    (local.set $out (call $take (i32.add (local.get $n) (i32.const 17))))
+    (call $header (local.get $out) (local.get $n) (i32.const 1) (local.get $n))
+    (block $copied
+      (loop $copy
+        (br_if $copied (i32.ge_u (local.get $i) (local.get $n)))
+        (i32.store8
+          (i32.add (i32.add (local.get $out) (i32.const 17)) (local.get $i))
+          (i32.load8_u (i32.add (local.get $ptr) (local.get $i))))
+        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+        (br $copy)))
+    (call $pack (local.get $out) (i32.add (local.get $n) (i32.const 17))))
+
+  (func (export "wp_field_pipe") (param $ptr i32) (param $len i32) (result i64)
+    (local $out i32)
+    (local $i i32)
+    (if (i32.eqz (local.get $len))
+      (then (return (i64.const -1))))
+    (local.set $out (call $take (i32.add (local.get $len) (i32.const 17))))
+    (call $header (local.get $out) (i32.const 0) (i32.const 0) (local.get $len))
+    (block $filled
+      (loop $fill
+        (br_if $filled (i32.ge_u (local.get $i) (local.get $len)))
+        (i32.store8
+          (i32.add (i32.add (local.get $out) (i32.const 17)) (local.get $i))
+          (i32.const 42))
+        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+        (br $fill)))
+    (call $pack (local.get $out) (i32.add (local.get $len) (i32.const 17))))
+)
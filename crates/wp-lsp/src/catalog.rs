@@ -0,0 +1,410 @@
+//! 内置条目：补全候选与悬停说明
+
+use oml::language::{
+    FUN_NOW_DATE, FUN_NOW_HOUR, FUN_NOW_TIME, PIPE_BASE64_DECODE, PIPE_BASE64_ENCODE, PIPE_GET,
+    PIPE_HTML_ESCAPE, PIPE_HTML_UNESCAPE, PIPE_IP4_TO_INT, PIPE_JSON_ESCAPE, PIPE_JSON_UNESCAPE,
+    PIPE_NTH, PIPE_PATH, PIPE_SKIP_EMPTY, PIPE_STR_ESCAPE, PIPE_TIME_TO_TS, PIPE_TIME_TO_TS_MS,
+    PIPE_TIME_TO_TS_US, PIPE_TIME_TO_TS_ZONE, PIPE_TO_JSON, PIPE_TO_STR, PIPE_URL,
+};
+
+use std::borrow::Cow;
+use wpl::ParserFactory;
+use wpl::eval::builtins::ensure_builtin_pipe_units;
+
+use crate::document::Lang;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemKind {
+    Keyword,
+    Type,
+    Function,
+    Preproc,
+}
+
+impl ItemKind {
+    /// LSP `CompletionItemKind`
+    pub fn completion_kind(&self) -> u32 {
+        match self {
+            ItemKind::Keyword => 14,
+            ItemKind::Type => 22,
+            ItemKind::Function => 3,
+            ItemKind::Preproc => 12,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Builtin {
+    pub name: Cow<'static, str>,
+    pub kind: ItemKind,
+    pub doc: Cow<'static, str>,
+}
+
+const fn item(name: &'static str, kind: ItemKind, doc: &'static str) -> Builtin {
+    Builtin {
+        name: Cow::Borrowed(name),
+        kind,
+        doc: Cow::Borrowed(doc),
+    }
+}
+
+use ItemKind::*;
+
+pub const WPL_KEYWORDS: &[Builtin] = &[
+    item("package", Keyword, "`package <name> { rule ... }` 规则包"),
+    item("rule", Keyword, "`rule <name> { |pre| (fields) }` 解析规则"),
+    item("tag", Keyword, "`#[tag(k:\"v\")]` 为解析结果追加常量标签"),
+    item(
+        "copy_raw",
+        Keyword,
+        "`#[copy_raw(name:\"raw\")]` 将原始日志复制到字段",
+    ),
+    item(
+        "copy_tags",
+        Keyword,
+        "`#[copy_tags(prefix:\"src_\")]` 复制数据源标签",
+    ),
+    item(
+        "ingest_time",
+        Keyword,
+        "`#[ingest_time(name:\"recv_ts\")]` 追加接收时间",
+    ),
+    item(
+        "fingerprint",
+        Keyword,
+        "`#[fingerprint(name:\"fp\", fields:[...], algo:\"xxh64\")]` 事件指纹",
+    ),
+    item(
+        "priority",
+        Keyword,
+        "`#[priority(N)]` 规则优先级，越大越先匹配",
+    ),
+    item(
+        "match",
+        Keyword,
+        "`#[match(first|priority|longest|strict, residue: 0.2)]` 包内匹配策略",
+    ),
+];
+
+// 类型说明；可用类型以解析器注册表为准，未在此列出的类型无说明
+const WPL_TYPE_DOCS: &[(&str, &str)] = &[
+    ("auto", "自动推断类型"),
+    ("chars", "字符串，默认类型"),
+    ("digit", "整数"),
+    ("float", "浮点数"),
+    ("bool", "布尔值 true/false"),
+    ("hex", "十六进制数"),
+    ("sn", "序列号（字母数字组合）"),
+    ("symbol", "`symbol(text)` 匹配固定文本"),
+    (
+        "peek_symbol",
+        "`peek_symbol(text)` 预读固定文本，不消费输入",
+    ),
+    ("ip", "IPv4/IPv6 地址"),
+    ("ipnet", "CIDR 网段"),
+    ("port", "端口号"),
+    ("http/request", "HTTP 请求行，如 `GET /index HTTP/1.1`"),
+    ("http/status", "HTTP 状态码"),
+    ("http/agent", "User-Agent"),
+    ("http/method", "HTTP 方法"),
+    (
+        "time",
+        "常见格式时间；`time(fmt=\"%d-%b-%Y\")` 指定 strftime 格式",
+    ),
+    ("time_3339", "RFC 3339 时间"),
+    ("time_timestamp", "Unix 时间戳"),
+    ("time/clf", "通用日志格式时间 `10/Oct/2000:13:55:36 -0700`"),
+    (
+        "time/bsd",
+        "BSD syslog 时间 `Mmm dd hh:mm:ss`，可设 `tz=`、`rollover=`",
+    ),
+    (
+        "time/epoch",
+        "`time/epoch(s|ms|us|ns)` 指定单位的 Unix 时间戳",
+    ),
+    ("wasm", "`wasm(plugin)` 由 WASM 插件解析字段"),
+    ("json", "JSON 对象，子字段按路径展开"),
+    ("exact_json", "严格 JSON，子字段须显式声明"),
+    ("kv", "`key=value` 键值对"),
+    ("kvarr", "`kvarr(type@key, ...)` 键值对数组"),
+    (
+        "xml",
+        "XML，元素与属性按 `/` 路径展开；`xml/local` 去除命名空间",
+    ),
+    ("array", "`array/<type>` 数组"),
+    ("base64", "Base64 编码内容"),
+    ("proto_text", "Protobuf 文本格式"),
+];
+
+pub const WPL_PIPE_FUNS: &[Builtin] = &[
+    item("take", Function, "`take(name)` 选择后续管道函数作用的字段"),
+    item("last", Function, "`last()` 选择最后一个字段"),
+    item("has", Function, "`has()` 当前字段存在"),
+    item("f_has", Function, "`f_has(name)` 指定字段存在"),
+    item("chars_has", Function, "`chars_has(val)` 当前字段等于字符串"),
+    item(
+        "f_chars_has",
+        Function,
+        "`f_chars_has(name, val)` 指定字段等于字符串",
+    ),
+    item(
+        "chars_not_has",
+        Function,
+        "`chars_not_has(val)` 当前字段不等于字符串",
+    ),
+    item("f_chars_not_has", Function, "`f_chars_not_has(name, val)`"),
+    item("chars_in", Function, "`chars_in([a, b])` 当前字段在集合中"),
+    item("f_chars_in", Function, "`f_chars_in(name, [a, b])`"),
+    item("digit_has", Function, "`digit_has(n)` 当前字段等于整数"),
+    item("f_digit_has", Function, "`f_digit_has(name, n)`"),
+    item(
+        "digit_in",
+        Function,
+        "`digit_in([1, 2])` 当前字段在整数集合中",
+    ),
+    item("f_digit_in", Function, "`f_digit_in(name, [1, 2])`"),
+    item(
+        "digit_range",
+        Function,
+        "`digit_range(lo, hi)` 整数在闭区间内",
+    ),
+    item("f_digit_range", Function, "`f_digit_range(name, lo, hi)`"),
+    item("ip_in", Function, "`ip_in([ip, ...])` IP 在列表中"),
+    item("f_ip_in", Function, "`f_ip_in(name, [ip, ...])`"),
+    item(
+        "ip_in_cidr",
+        Function,
+        "`ip_in_cidr([10.0.0.0/8, ...])` IP 在网段内",
+    ),
+    item(
+        "f_ip_in_cidr",
+        Function,
+        "`f_ip_in_cidr(name, [cidr, ...])`",
+    ),
+    item("regex_match", Function, "`regex_match('pattern')` 正则匹配"),
+    item(
+        "f_regex_match",
+        Function,
+        "`f_regex_match(name, 'pattern')`",
+    ),
+    item(
+        "chars_len",
+        Function,
+        "`chars_len(min, max)` 字符长度在区间内",
+    ),
+    item("f_chars_len", Function, "`f_chars_len(name, min, max)`"),
+    item("starts_with", Function, "`starts_with(val)` 前缀匹配"),
+    item("f_starts_with", Function, "`f_starts_with(name, val)`"),
+    item("ends_with", Function, "`ends_with(val)` 后缀匹配"),
+    item("f_ends_with", Function, "`f_ends_with(name, val)`"),
+    item("contains", Function, "`contains(val)` 包含子串"),
+    item("f_contains", Function, "`f_contains(name, val)`"),
+    item(
+        "json_unescape",
+        Function,
+        "`json_unescape()` 反转义 JSON 字符串",
+    ),
+    item(
+        "base64_decode",
+        Function,
+        "`base64_decode()` Base64 解码当前字段",
+    ),
+    item(
+        "wasm",
+        Function,
+        "`wasm(plugin)` 调用 WASM 插件处理当前字段",
+    ),
+];
+
+// 预处理单元说明；可用单元以 plg_pipe 注册表为准
+const WPL_PREPROC_DOCS: &[(&str, &str)] = &[
+    ("decode/base64", "预处理：Base64 解码整条日志"),
+    ("decode/hex", "预处理：十六进制解码"),
+    ("decode/gzip", "预处理：gzip 解压"),
+    ("decode/zlib", "预处理：zlib 解压"),
+    ("decode/url", "预处理：URL 百分号解码"),
+    (
+        "decode/charset",
+        "预处理：`decode/charset(gbk)` 按字符集转码为 UTF-8",
+    ),
+    ("strip/ansi", "预处理：去除 ANSI 控制序列"),
+    ("unquote/unescape", "预处理：去除外层引号并反转义"),
+    ("wasm", "预处理：`wasm(plugin)` 调用 WASM 插件"),
+];
+
+fn doc_of(docs: &[(&str, &'static str)], name: &str) -> &'static str {
+    docs.iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, d)| *d)
+        .unwrap_or_default()
+}
+
+/// 解析器注册表中的字段类型
+pub fn wpl_types() -> Vec<Builtin> {
+    ParserFactory::type_names()
+        .into_iter()
+        .map(|name| Builtin {
+            doc: Cow::Borrowed(doc_of(WPL_TYPE_DOCS, name.as_str())),
+            name: Cow::Owned(name.to_string()),
+            kind: Type,
+        })
+        .collect()
+}
+
+/// plg_pipe 注册表中的预处理单元（含插件注册的单元），带参数的单元去掉 `(..)`
+pub fn wpl_preproc() -> Vec<Builtin> {
+    ensure_builtin_pipe_units();
+    let mut names: Vec<String> = wpl::list_preorder_pipe_units()
+        .iter()
+        .map(|x| x.trim_end_matches("(..)").to_ascii_lowercase())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .map(|name| Builtin {
+            doc: Cow::Borrowed(doc_of(WPL_PREPROC_DOCS, &name)),
+            name: Cow::Owned(name),
+            kind: Preproc,
+        })
+        .collect()
+}
+
+pub const OML_KEYWORDS: &[Builtin] = &[
+    item("name", Keyword, "`name : <model>` 模型名"),
+    item("rule", Keyword, "`rule : <wpl rule> ...` 适用的 WPL 规则"),
+    item("take", Keyword, "`take(field)` 取出字段（取后移除）"),
+    item("read", Keyword, "`read(field)` 读取字段（不移除）"),
+    item("option", Keyword, "`take(option:[a, b])` 依次尝试多个字段"),
+    item("keys", Keyword, "`take(keys:[a, b])` 按多个键取值"),
+    item("in", Keyword, "`in` 匹配集合"),
+    item("match", Keyword, "`match read(x) { ... }` 条件映射"),
+    item("object", Keyword, "`object { ... }` 构造对象"),
+    item("collect", Keyword, "`collect take(keys:[...])` 收集为数组"),
+    item("fmt", Keyword, "`fmt(\"{}-{}\", @a, @b)` 格式化字符串"),
+    item("pipe", Keyword, "`pipe take(x) | fun | ...` 管道"),
+    item(
+        "query",
+        Keyword,
+        "`select ... from ... where ...` 知识库查询",
+    ),
+    item("get", Keyword, "`get(key)` 取对象成员"),
+];
+
+pub const OML_FUNS: &[Builtin] = &[
+    item(FUN_NOW_TIME, Function, "当前时间"),
+    item(FUN_NOW_DATE, Function, "当前日期（整数 YYYYMMDD）"),
+    item(FUN_NOW_HOUR, Function, "当前小时（整数 YYYYMMDDHH）"),
+    item(PIPE_TIME_TO_TS, Function, "时间转 Unix 秒"),
+    item(PIPE_TIME_TO_TS_MS, Function, "时间转 Unix 毫秒"),
+    item(PIPE_TIME_TO_TS_US, Function, "时间转 Unix 微秒"),
+    item(
+        PIPE_TIME_TO_TS_ZONE,
+        Function,
+        "`Time::to_ts_zone(8, ms)` 按时区转时间戳",
+    ),
+    item(PIPE_NTH, Function, "`nth(i)` 取数组第 i 个元素"),
+    item(PIPE_GET, Function, "`get(key)` 取对象成员"),
+    item(PIPE_BASE64_ENCODE, Function, "Base64 编码"),
+    item(
+        PIPE_BASE64_DECODE,
+        Function,
+        "`base64_decode(Utf8)` Base64 解码",
+    ),
+    item(PIPE_PATH, Function, "`path(name|path)` 取文件路径的一部分"),
+    item(
+        PIPE_URL,
+        Function,
+        "`url(domain|host|uri|path|params)` 取 URL 的一部分",
+    ),
+    item(PIPE_HTML_ESCAPE, Function, "HTML 转义"),
+    item(PIPE_HTML_UNESCAPE, Function, "HTML 反转义"),
+    item(PIPE_STR_ESCAPE, Function, "字符串转义"),
+    item(PIPE_JSON_ESCAPE, Function, "JSON 转义"),
+    item(PIPE_JSON_UNESCAPE, Function, "JSON 反转义"),
+    item(PIPE_TO_JSON, Function, "转为 JSON 字符串"),
+    item(PIPE_TO_STR, Function, "转为字符串"),
+    item(PIPE_SKIP_EMPTY, Function, "跳过空值"),
+    item(PIPE_IP4_TO_INT, Function, "IPv4 转整数"),
+];
+
+/// 按语言与上下文给出候选：`|` 之后只给管道函数
+pub fn candidates(lang: Lang, after_pipe: bool) -> Vec<Builtin> {
+    let statics: &[&[Builtin]] = match (lang, after_pipe) {
+        (Lang::Wpl, true) => &[WPL_PIPE_FUNS],
+        (Lang::Wpl, false) => &[WPL_KEYWORDS],
+        (Lang::Oml, true) => &[OML_FUNS],
+        (Lang::Oml, false) => &[OML_KEYWORDS, OML_FUNS],
+    };
+    let mut items = match (lang, after_pipe) {
+        (Lang::Wpl, true) => wpl_preproc(),
+        (Lang::Wpl, false) => wpl_types(),
+        (Lang::Oml, _) => Vec::new(),
+    };
+    items.extend(statics.iter().flat_map(|g| g.iter()).cloned());
+    items
+}
+
+/// 悬停查找；WPL 的 `type:name` 按类型部分查找
+pub fn lookup(lang: Lang, word: &str) -> Option<Builtin> {
+    let mut items: Vec<Builtin> = match lang {
+        Lang::Wpl => wpl_types(),
+        Lang::Oml => Vec::new(),
+    };
+    let statics: &[&[Builtin]] = match lang {
+        Lang::Wpl => &[WPL_PIPE_FUNS],
+        Lang::Oml => &[OML_FUNS, OML_KEYWORDS],
+    };
+    items.extend(statics.iter().flat_map(|g| g.iter()).cloned());
+    if lang == Lang::Wpl {
+        items.extend(wpl_preproc());
+        items.extend(WPL_KEYWORDS.iter().cloned());
+    }
+    let find = |w: &str| items.iter().find(|b| b.name == w).cloned();
+    find(word).or_else(|| match lang {
+        Lang::Wpl => word.split_once(':').and_then(|(head, _)| find(head)),
+        Lang::Oml => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(lookup(Lang::Wpl, "ip:sip").unwrap().kind, Type);
+        assert_eq!(lookup(Lang::Wpl, "decode/gzip").unwrap().kind, Preproc);
+        assert_eq!(lookup(Lang::Oml, "Time::to_ts_ms").unwrap().kind, Function);
+        assert!(lookup(Lang::Oml, "ip").is_none());
+        assert!(candidates(Lang::Wpl, true).iter().all(|b| b.kind != Type));
+    }
+
+    #[test]
+    fn test_catalog_from_registry() {
+        // 类型与预处理单元来自注册表
+        let types = wpl_types();
+        for name in [
+            "auto",
+            "chars",
+            "ip",
+            "json",
+            "time/bsd",
+            "xml/local",
+            "wasm",
+        ] {
+            assert!(types.iter().any(|b| b.name == name), "{}", name);
+        }
+        assert!(!lookup(Lang::Wpl, "ip").unwrap().doc.is_empty());
+
+        wpl::register_preorder_pipe_unit("demo/unit", || {
+            wpl::create_preorder_pipe_unit("decode/base64").unwrap()
+        });
+        let pre = candidates(Lang::Wpl, true);
+        for name in ["decode/charset", "strip/ansi", "demo/unit"] {
+            assert!(pre.iter().any(|b| b.name == name), "{}", name);
+        }
+        assert!(pre.iter().all(|b| !b.name.ends_with(')')));
+        assert_eq!(lookup(Lang::Wpl, "demo/unit").unwrap().kind, Preproc);
+    }
+}
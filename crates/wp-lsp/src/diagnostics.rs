@@ -0,0 +1,216 @@
+//! 诊断：语法错误取自 winnow 解析失败时的剩余输入位置，
+//! WPL 规则再尝试构建求值器以发现未注册的预处理单元等语义错误
+
+use serde_json::{Value, json};
+use winnow::error::{ContextError, ErrMode};
+use wp_parser::Parser;
+use wp_parser::comment::CommentParser;
+use wpl::parser::wpl_rule::wpl_rule;
+use wpl::{WplEvaluator, WplRule, WplStatementType, wpl_package};
+
+use crate::document::{Document, Lang};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error = 1,
+    Warning = 2,
+}
+
+/// 以字节偏移表示的诊断区间，输出时再换算为 LSP 位置
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub start: usize,
+    pub end: usize,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn to_lsp(&self, doc: &Document) -> Value {
+        json!({
+            "range": { "start": doc.position(self.start), "end": doc.position(self.end) },
+            "severity": self.severity as u8,
+            "source": match doc.lang { Lang::Wpl => "wpl", Lang::Oml => "oml" },
+            "message": self.message,
+        })
+    }
+}
+
+pub fn check(lang: Lang, text: &str) -> Vec<Diagnostic> {
+    // 注释替换为等长空白，偏移与原文一致
+    let masked = CommentParser::mask_comment(&mut &*text).unwrap_or_else(|_| text.to_string());
+    match lang {
+        Lang::Wpl => check_wpl(&masked),
+        Lang::Oml => check_oml(&masked),
+    }
+}
+
+fn check_wpl(code: &str) -> Vec<Diagnostic> {
+    let mut input = code;
+    let head = input.trim_start();
+    let parsed = if head.starts_with("rule") {
+        wpl_rule.parse_next(&mut input).map(|rule| vec![rule])
+    } else {
+        wpl_package
+            .parse_next(&mut input)
+            .map(|pkg| pkg.rules.into_iter().collect())
+    };
+    let rules: Vec<WplRule> = match parsed {
+        Ok(rules) => rules,
+        Err(e) => return vec![syntax_error(code, input, e)],
+    };
+    if !input.trim().is_empty() {
+        let start = skip_ws(code, input);
+        return vec![Diagnostic {
+            start,
+            end: token_end(code, start),
+            severity: Severity::Error,
+            message: "unexpected content after definition".into(),
+        }];
+    }
+
+    let mut out = Vec::new();
+    for rule in &rules {
+        let WplStatementType::Express(express) = &rule.statement;
+        if let Err(e) = WplEvaluator::from(express, None) {
+            let start = rule_name_offset(code, rule.name.as_str()).unwrap_or(0);
+            out.push(Diagnostic {
+                start,
+                end: start + rule.name.len(),
+                severity: Severity::Error,
+                message: e.to_string(),
+            });
+        }
+    }
+    out
+}
+
+fn check_oml(code: &str) -> Vec<Diagnostic> {
+    let mut input = code;
+    let parsed = if oml::parser::is_oml_library(code) {
+        oml::parser::oml_parse_lib_raw(&mut input).map(|_| ())
+    } else {
+        oml::parser::oml_parse_raw(&mut input).map(|_| ())
+    };
+    match parsed {
+        Ok(_) => Vec::new(),
+        Err(e) => vec![syntax_error(code, input, e)],
+    }
+}
+
+fn syntax_error(code: &str, rest: &str, err: ErrMode<ContextError>) -> Diagnostic {
+    let message = match err {
+        ErrMode::Backtrack(e) | ErrMode::Cut(e) => e.to_string(),
+        ErrMode::Incomplete(_) => String::new(),
+    };
+    let message = if message.trim().is_empty() {
+        "syntax error".to_string()
+    } else {
+        format!("syntax error: {}", message.replace('\n', "; "))
+    };
+    let start = skip_ws(code, rest);
+    Diagnostic {
+        start,
+        end: token_end(code, start),
+        severity: Severity::Error,
+        message,
+    }
+}
+
+/// 失败位置跳过空白，落在出错的记号上
+fn skip_ws(code: &str, rest: &str) -> usize {
+    code.len() - rest.trim_start().len()
+}
+
+fn token_end(code: &str, start: usize) -> usize {
+    code[start..]
+        .char_indices()
+        .find(|(_, c)| c.is_whitespace() || matches!(c, ',' | ')' | '}' | '|'))
+        .map(|(i, c)| start + i.max(c.len_utf8()))
+        .unwrap_or(code.len())
+}
+
+fn rule_name_offset(code: &str, name: &str) -> Option<usize> {
+    code.match_indices("rule").find_map(|(i, kw)| {
+        let after = &code[i + kw.len()..];
+        let trimmed = after.trim_start();
+        let at = code.len() - trimmed.len();
+        (trimmed.len() < after.len() && trimmed.starts_with(name)).then_some(at)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_col(code: &str, d: &Diagnostic) -> (usize, usize) {
+        let before = &code[..d.start];
+        let line = before.matches('\n').count();
+        let col = before.len() - before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        (line, col)
+    }
+
+    #[test]
+    fn test_wpl_ok() {
+        let code = r#"
+// 注释不影响定位
+package /nginx {
+    /* 多行
+       注释 */
+    rule access {
+        (ip:sip, 2*_, time<[,]>, http/request", http/status, digit)
+    }
+}"#;
+        assert!(check(Lang::Wpl, code).is_empty());
+        assert!(check(Lang::Wpl, "rule x { (ip:sip) }").is_empty());
+    }
+
+    #[test]
+    fn test_wpl_syntax_span() {
+        let code = "// head\npackage /nginx {\n    rule access {\n        (ip:sip, px)\n    }\n}";
+        let diags = check(Lang::Wpl, code);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].severity, Severity::Error);
+        let (line, _) = line_col(code, &diags[0]);
+        assert!((2..=3).contains(&line), "line {}: {:?}", line, diags);
+    }
+
+    #[test]
+    fn test_wpl_semantic() {
+        let code = "package /p {\n  rule bad { | decode/klingon | (chars:a) }\n}";
+        let diags = check(Lang::Wpl, code);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(&code[diags[0].start..diags[0].end], "bad");
+        assert!(diags[0].message.contains("decode/klingon"));
+    }
+
+    #[test]
+    fn test_oml() {
+        let code = "name : test\n---\n// ok\nversion = chars(1.0.0) ;\n";
+        assert!(
+            check(Lang::Oml, code).is_empty(),
+            "{:?}",
+            check(Lang::Oml, code)
+        );
+
+        let code = "name : test\n---\nversion = chars(1.0.0) ;\npos = tak( ) ;\n";
+        let diags = check(Lang::Oml, code);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(line_col(code, &diags[0]).0, 3);
+    }
+
+    #[test]
+    fn test_oml_library() {
+        let code = "// ecs\nimport \"base.oml\" ;\nblock geo {\n  city = chars(bj) ;\n}\n";
+        assert!(
+            check(Lang::Oml, code).is_empty(),
+            "{:?}",
+            check(Lang::Oml, code)
+        );
+
+        let code = "import \"base.oml\" ;\nblock geo {\n  city = chars(bj) ;\n  x = tak( ) ;\n}\n";
+        let diags = check(Lang::Oml, code);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(line_col(code, &diags[0]).0, 3);
+    }
+}
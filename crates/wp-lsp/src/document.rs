@@ -0,0 +1,162 @@
+//! 打开的文档与位置换算（LSP 列号按 UTF-16 码元计）
+
+use serde_json::{Value, json};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    Wpl,
+    Oml,
+}
+
+impl Lang {
+    /// 优先使用客户端给出的 languageId，否则按扩展名判断
+    pub fn detect(uri: &str, language_id: Option<&str>) -> Option<Self> {
+        match language_id {
+            Some("wpl") => return Some(Lang::Wpl),
+            Some("oml") => return Some(Lang::Oml),
+            _ => {}
+        }
+        let ext = uri.rsplit('.').next()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "wpl" => Some(Lang::Wpl),
+            "oml" => Some(Lang::Oml),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub lang: Lang,
+    pub version: i64,
+    pub text: String,
+}
+
+impl Document {
+    pub fn new(lang: Lang, version: i64, text: String) -> Self {
+        Self {
+            lang,
+            version,
+            text,
+        }
+    }
+
+    pub fn position(&self, offset: usize) -> Value {
+        offset_to_position(&self.text, offset)
+    }
+
+    pub fn offset(&self, pos: &Value) -> usize {
+        let line = pos["line"].as_u64().unwrap_or_default() as usize;
+        let character = pos["character"].as_u64().unwrap_or_default() as usize;
+        position_to_offset(&self.text, line, character)
+    }
+
+    /// 光标所在（或紧邻其前）的单词及其起止偏移；单词包含 `/`、`:`、`_`
+    pub fn word_at(&self, offset: usize) -> Option<(usize, usize, &str)> {
+        let offset = floor_char_boundary(&self.text, offset);
+        let start = self.text[..offset]
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| is_word_char(*c))
+            .last()
+            .map(|(i, _)| i)
+            .unwrap_or(offset);
+        let end = self.text[offset..]
+            .char_indices()
+            .find(|(_, c)| !is_word_char(*c))
+            .map(|(i, _)| offset + i)
+            .unwrap_or(self.text.len());
+        (start < end).then(|| (start, end, &self.text[start..end]))
+    }
+
+    /// 光标前的单词片段（补全前缀）
+    pub fn prefix_at(&self, offset: usize) -> &str {
+        let offset = floor_char_boundary(&self.text, offset);
+        let start = self.text[..offset]
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| is_word_char(*c))
+            .last()
+            .map(|(i, _)| i)
+            .unwrap_or(offset);
+        &self.text[start..offset]
+    }
+
+    /// 前缀之前第一个非空白字符
+    pub fn prev_char(&self, offset: usize) -> Option<char> {
+        let offset = floor_char_boundary(&self.text, offset);
+        let start = offset - self.prefix_at(offset).len();
+        self.text[..start]
+            .chars()
+            .rev()
+            .find(|c| !c.is_whitespace())
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '/' | ':')
+}
+
+fn floor_char_boundary(text: &str, offset: usize) -> usize {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+pub fn offset_to_position(text: &str, offset: usize) -> Value {
+    let offset = floor_char_boundary(text, offset);
+    let before = &text[..offset];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let character: usize = before[line_start..].chars().map(char::len_utf16).sum();
+    json!({ "line": line, "character": character })
+}
+
+pub fn position_to_offset(text: &str, line: usize, character: usize) -> usize {
+    let mut line_start = 0;
+    for _ in 0..line {
+        match text[line_start..].find('\n') {
+            Some(i) => line_start += i + 1,
+            None => return text.len(),
+        }
+    }
+    let mut units = 0;
+    for (i, c) in text[line_start..].char_indices() {
+        if units >= character || c == '\n' {
+            return line_start + i;
+        }
+        units += c.len_utf16();
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_roundtrip() {
+        let text = "package /a {\n  rule 中文 { (ip:sip) }\n}";
+        let offset = text.find("{ (ip").unwrap();
+        let pos = offset_to_position(text, offset);
+        assert_eq!(pos, json!({ "line": 1, "character": 10 }));
+        assert_eq!(position_to_offset(text, 1, 10), offset);
+        assert_eq!(position_to_offset(text, 9, 0), text.len());
+    }
+
+    #[test]
+    fn test_word_at() {
+        let doc = Document::new(Lang::Wpl, 1, "(http/request:req, ip)".into());
+        let (_, _, word) = doc.word_at(3).unwrap();
+        assert_eq!(word, "http/request:req");
+        assert_eq!(doc.prefix_at(5), "http");
+        assert_eq!(doc.prev_char(20), Some(','));
+        assert_eq!(Lang::detect("file:///x/y.OML", None), Some(Lang::Oml));
+        assert_eq!(
+            Lang::detect("file:///x/y.txt", Some("wpl")),
+            Some(Lang::Wpl)
+        );
+    }
+}
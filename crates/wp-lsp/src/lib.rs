@@ -0,0 +1,18 @@
+//! # wp-lsp
+//!
+//! 基于 stdio 的 WPL / OML 语言服务（Language Server Protocol）。
+//!
+//! - **诊断**：复用 `wp-lang` / `wp-oml` 的 winnow 解析器，错误按原始代码偏移定位
+//! - **补全**：WPL 字段类型、管道函数、预处理单元，OML 关键字与函数
+//! - **悬停**：内置类型与函数的说明
+//!
+//! 仅实现全量文档同步（`TextDocumentSyncKind::Full`），消息读写见 [`protocol`]。
+
+pub mod catalog;
+pub mod diagnostics;
+pub mod document;
+pub mod protocol;
+pub mod server;
+
+pub use document::Lang;
+pub use server::LspServer;
@@ -0,0 +1,17 @@
+use std::io::{self, BufReader};
+
+use wp_lsp::LspServer;
+
+fn main() {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut reader = BufReader::new(stdin.lock());
+    let mut writer = stdout.lock();
+    match LspServer::default().run(&mut reader, &mut writer) {
+        Ok(code) => std::process::exit(code),
+        Err(e) => {
+            eprintln!("wp-lsp: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! LSP 基础协议：`Content-Length` 头 + JSON-RPC 2.0 消息体
+
+use std::io::{self, BufRead, Write};
+
+use serde_json::{Value, json};
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const SERVER_NOT_INITIALIZED: i64 = -32002;
+
+/// 读取一条消息；输入结束时返回 `Ok(None)`
+pub fn read_message<R: BufRead>(reader: &mut R) -> io::Result<Option<Value>> {
+    let mut content_len: Option<usize> = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            if content_len.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            let len = value.trim().parse::<usize>().map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("bad content-length: {e}"),
+                )
+            })?;
+            content_len = Some(len);
+        }
+    }
+    let mut body = vec![0u8; content_len.unwrap_or_default()];
+    reader.read_exact(&mut body)?;
+    match serde_json::from_slice(&body) {
+        Ok(msg) => Ok(Some(msg)),
+        // 消息体不是 JSON：按协议回复 ParseError，由调用方处理
+        Err(e) => Ok(Some(
+            json!({ "jsonrpc": "2.0", "error": { "code": PARSE_ERROR, "message": e.to_string() } }),
+        )),
+    }
+}
+
+pub fn write_message<W: Write>(writer: &mut W, msg: &Value) -> io::Result<()> {
+    let body = serde_json::to_string(msg)?;
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()
+}
+
+pub fn response(id: Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+pub fn error_response(id: Value, code: i64, message: impl Into<String>) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message.into() } })
+}
+
+pub fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_frame_roundtrip() {
+        let mut buf = Vec::new();
+        let msg = notification("initialized", json!({}));
+        write_message(&mut buf, &msg).unwrap();
+        write_message(&mut buf, &response(json!(1), json!(null))).unwrap();
+
+        let mut reader = Cursor::new(buf);
+        assert_eq!(read_message(&mut reader).unwrap(), Some(msg));
+        let second = read_message(&mut reader).unwrap().unwrap();
+        assert_eq!(second["id"], json!(1));
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn test_bad_body() {
+        let mut reader = Cursor::new(b"Content-Length: 3\r\n\r\n{x}".to_vec());
+        let msg = read_message(&mut reader).unwrap().unwrap();
+        assert_eq!(msg["error"]["code"], json!(PARSE_ERROR));
+    }
+}
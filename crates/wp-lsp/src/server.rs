@@ -0,0 +1,386 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+use serde_json::{Value, json};
+
+use crate::catalog;
+use crate::diagnostics;
+use crate::document::{Document, Lang};
+use crate::protocol::{
+    INVALID_REQUEST, METHOD_NOT_FOUND, SERVER_NOT_INITIALIZED, error_response, notification,
+    read_message, response, write_message,
+};
+
+/// 单线程语言服务：每条消息处理完即回写，便于用脚本化会话测试
+#[derive(Debug, Default)]
+pub struct LspServer {
+    docs: HashMap<String, Document>,
+    initialized: bool,
+    shutdown: bool,
+}
+
+impl LspServer {
+    /// 运行到 `exit`；返回进程退出码（收到 `shutdown` 后退出为 0，否则为 1）
+    pub fn run<R: BufRead, W: Write>(&mut self, reader: &mut R, writer: &mut W) -> io::Result<i32> {
+        while let Some(msg) = read_message(reader)? {
+            if msg["method"] == "exit" {
+                return Ok(if self.shutdown { 0 } else { 1 });
+            }
+            for out in self.handle(msg) {
+                write_message(writer, &out)?;
+            }
+        }
+        Ok(1)
+    }
+
+    /// 处理一条消息，返回需要写回客户端的响应与通知
+    pub fn handle(&mut self, msg: Value) -> Vec<Value> {
+        let id = msg.get("id").cloned();
+        let Some(method) = msg["method"].as_str() else {
+            // 解析失败的消息体或客户端发来的响应
+            if msg.get("error").is_some() && id.is_none() {
+                return vec![msg_with_null_id(msg)];
+            }
+            return Vec::new();
+        };
+        let params = &msg["params"];
+
+        if !self.initialized && method != "initialize" {
+            return match id {
+                Some(id) => vec![error_response(
+                    id,
+                    SERVER_NOT_INITIALIZED,
+                    "server not initialized",
+                )],
+                None => Vec::new(),
+            };
+        }
+
+        match (method, id) {
+            ("initialize", Some(id)) => {
+                self.initialized = true;
+                vec![response(id, capabilities())]
+            }
+            ("shutdown", Some(id)) => {
+                self.shutdown = true;
+                vec![response(id, Value::Null)]
+            }
+            ("textDocument/completion", Some(id)) => {
+                vec![response(id, self.completion(params))]
+            }
+            ("textDocument/hover", Some(id)) => vec![response(id, self.hover(params))],
+            (_, Some(id)) if self.shutdown => {
+                vec![error_response(
+                    id,
+                    INVALID_REQUEST,
+                    "server is shutting down",
+                )]
+            }
+            (_, Some(id)) => vec![error_response(
+                id,
+                METHOD_NOT_FOUND,
+                format!("method not found: {}", method),
+            )],
+            ("textDocument/didOpen", None) => self.did_open(params),
+            ("textDocument/didChange", None) => self.did_change(params),
+            ("textDocument/didClose", None) => self.did_close(params),
+            // initialized、$/cancelRequest 等通知无需处理
+            (_, None) => Vec::new(),
+        }
+    }
+
+    fn did_open(&mut self, params: &Value) -> Vec<Value> {
+        let item = &params["textDocument"];
+        let Some(uri) = item["uri"].as_str() else {
+            return Vec::new();
+        };
+        let Some(lang) = Lang::detect(uri, item["languageId"].as_str()) else {
+            return Vec::new();
+        };
+        let doc = Document::new(
+            lang,
+            item["version"].as_i64().unwrap_or_default(),
+            item["text"].as_str().unwrap_or_default().to_string(),
+        );
+        self.docs.insert(uri.to_string(), doc);
+        self.publish(uri)
+    }
+
+    // 仅支持全量同步：取最后一次变更的完整文本
+    fn did_change(&mut self, params: &Value) -> Vec<Value> {
+        let Some(uri) = params["textDocument"]["uri"].as_str() else {
+            return Vec::new();
+        };
+        let Some(doc) = self.docs.get_mut(uri) else {
+            return Vec::new();
+        };
+        if let Some(text) = params["contentChanges"]
+            .as_array()
+            .and_then(|changes| changes.last())
+            .and_then(|change| change["text"].as_str())
+        {
+            doc.text = text.to_string();
+        }
+        if let Some(version) = params["textDocument"]["version"].as_i64() {
+            doc.version = version;
+        }
+        self.publish(uri)
+    }
+
+    fn did_close(&mut self, params: &Value) -> Vec<Value> {
+        let Some(uri) = params["textDocument"]["uri"].as_str() else {
+            return Vec::new();
+        };
+        if self.docs.remove(uri).is_none() {
+            return Vec::new();
+        }
+        vec![notification(
+            "textDocument/publishDiagnostics",
+            json!({ "uri": uri, "diagnostics": [] }),
+        )]
+    }
+
+    fn publish(&self, uri: &str) -> Vec<Value> {
+        let Some(doc) = self.docs.get(uri) else {
+            return Vec::new();
+        };
+        let items: Vec<Value> = diagnostics::check(doc.lang, &doc.text)
+            .iter()
+            .map(|d| d.to_lsp(doc))
+            .collect();
+        vec![notification(
+            "textDocument/publishDiagnostics",
+            json!({ "uri": uri, "version": doc.version, "diagnostics": items }),
+        )]
+    }
+
+    fn completion(&self, params: &Value) -> Value {
+        let Some(doc) = self.doc_of(params) else {
+            return Value::Null;
+        };
+        let offset = doc.offset(&params["position"]);
+        let prefix = doc.prefix_at(offset);
+        let after_pipe = doc.prev_char(offset) == Some('|');
+        let items: Vec<Value> = catalog::candidates(doc.lang, after_pipe)
+            .into_iter()
+            .filter(|b| b.name.starts_with(prefix))
+            .map(|b| {
+                json!({
+                    "label": b.name,
+                    "kind": b.kind.completion_kind(),
+                    "documentation": b.doc,
+                })
+            })
+            .collect();
+        json!({ "isIncomplete": false, "items": items })
+    }
+
+    fn hover(&self, params: &Value) -> Value {
+        let Some(doc) = self.doc_of(params) else {
+            return Value::Null;
+        };
+        let offset = doc.offset(&params["position"]);
+        let Some((start, end, word)) = doc.word_at(offset) else {
+            return Value::Null;
+        };
+        match catalog::lookup(doc.lang, word) {
+            Some(b) => json!({
+                "contents": { "kind": "markdown", "value": format!("**{}**\n\n{}", b.name, b.doc) },
+                "range": { "start": doc.position(start), "end": doc.position(end) },
+            }),
+            None => Value::Null,
+        }
+    }
+
+    fn doc_of(&self, params: &Value) -> Option<&Document> {
+        params["textDocument"]["uri"]
+            .as_str()
+            .and_then(|uri| self.docs.get(uri))
+    }
+}
+
+fn capabilities() -> Value {
+    json!({
+        "capabilities": {
+            "textDocumentSync": { "openClose": true, "change": 1 },
+            "completionProvider": { "triggerCharacters": ["|", "(", ",", "/", ":"] },
+            "hoverProvider": true,
+        },
+        "serverInfo": { "name": "wp-lsp", "version": env!("CARGO_PKG_VERSION") },
+    })
+}
+
+fn msg_with_null_id(mut msg: Value) -> Value {
+    msg["id"] = Value::Null;
+    msg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn frame(msgs: &[Value]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        for msg in msgs {
+            write_message(&mut buf, msg).unwrap();
+        }
+        buf
+    }
+
+    fn session(msgs: &[Value]) -> (i32, Vec<Value>) {
+        let mut reader = Cursor::new(frame(msgs));
+        let mut out = Vec::new();
+        let code = LspServer::default().run(&mut reader, &mut out).unwrap();
+        let mut replies = Vec::new();
+        let mut reader = Cursor::new(out);
+        while let Some(msg) = read_message(&mut reader).unwrap() {
+            replies.push(msg);
+        }
+        (code, replies)
+    }
+
+    fn request(id: i64, method: &str, params: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+    }
+
+    fn notify(method: &str, params: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "method": method, "params": params })
+    }
+
+    fn open(uri: &str, text: &str) -> Value {
+        notify(
+            "textDocument/didOpen",
+            json!({ "textDocument": { "uri": uri, "languageId": "", "version": 1, "text": text } }),
+        )
+    }
+
+    fn at(uri: &str, line: u32, character: u32) -> Value {
+        json!({ "textDocument": { "uri": uri }, "position": { "line": line, "character": character } })
+    }
+
+    fn reply(replies: &[Value], id: i64) -> &Value {
+        replies.iter().find(|r| r["id"] == json!(id)).unwrap()
+    }
+
+    fn labels(result: &Value) -> Vec<&str> {
+        result["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| i["label"].as_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_wpl_session() {
+        let uri = "file:///rules/nginx.wpl";
+        let bad = "package /nginx {\n    rule access {\n        (ip:sip, http/req\n    }\n}";
+        let good = "package /nginx {\n    rule access {\n        | decode/gzip | decode/ | (ip:sip, http/)\n    }\n}";
+        let (code, replies) = session(&[
+            request(1, "initialize", json!({ "capabilities": {} })),
+            notify("initialized", json!({})),
+            open(uri, bad),
+            notify(
+                "textDocument/didChange",
+                json!({
+                    "textDocument": { "uri": uri, "version": 2 },
+                    "contentChanges": [{ "text": good }],
+                }),
+            ),
+            request(2, "textDocument/completion", at(uri, 2, 48)),
+            request(3, "textDocument/completion", at(uri, 2, 31)),
+            request(4, "textDocument/hover", at(uri, 2, 12)),
+            request(5, "textDocument/hover", at(uri, 1, 10)),
+            request(6, "shutdown", json!(null)),
+            notify("exit", json!(null)),
+        ]);
+        assert_eq!(code, 0);
+        assert_eq!(
+            reply(&replies, 1)["result"]["capabilities"]["hoverProvider"],
+            json!(true)
+        );
+
+        let diags: Vec<&Value> = replies
+            .iter()
+            .filter(|r| r["method"] == "textDocument/publishDiagnostics")
+            .collect();
+        assert_eq!(diags.len(), 2);
+        let first = diags[0]["params"]["diagnostics"].as_array().unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0]["severity"], json!(1));
+        assert!(first[0]["range"]["start"]["line"].as_u64().unwrap() >= 1);
+        // decode/ 本身会被当作未注册的预处理单元
+        assert_eq!(diags[1]["params"]["version"], json!(2));
+
+        let types = labels(&reply(&replies, 2)["result"]);
+        assert!(types.contains(&"http/request") && types.contains(&"http/status"));
+        assert!(types.iter().all(|l| l.starts_with("http/")));
+
+        let units = labels(&reply(&replies, 3)["result"]);
+        assert!(units.contains(&"decode/gzip") && units.contains(&"decode/charset"));
+        assert!(!units.contains(&"chars"));
+
+        let hover = &reply(&replies, 4)["result"];
+        assert!(
+            hover["contents"]["value"]
+                .as_str()
+                .unwrap()
+                .contains("decode/gzip")
+        );
+        assert_eq!(reply(&replies, 5)["result"], Value::Null);
+    }
+
+    #[test]
+    fn test_oml_session() {
+        let uri = "file:///models/a.oml";
+        let text = "name : a\n---\nts = pipe take(t) | Time::to_ts_ms ;\nx = Now:: ;\n";
+        let (_, replies) = session(&[
+            request(1, "initialize", json!({})),
+            open(uri, text),
+            request(2, "textDocument/completion", at(uri, 2, 20)),
+            request(3, "textDocument/completion", at(uri, 3, 9)),
+            request(4, "textDocument/hover", at(uri, 2, 25)),
+            notify("exit", json!(null)),
+        ]);
+
+        let diags = replies
+            .iter()
+            .find(|r| r["method"] == "textDocument/publishDiagnostics")
+            .unwrap();
+        let items = diags["params"]["diagnostics"].as_array().unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0]["range"]["start"]["line"], json!(3));
+
+        let after_pipe = labels(&reply(&replies, 2)["result"]);
+        assert!(after_pipe.contains(&"Time::to_ts_ms") && !after_pipe.contains(&"take"));
+
+        let now = labels(&reply(&replies, 3)["result"]);
+        assert_eq!(now, vec!["Now::time", "Now::date", "Now::hour"]);
+
+        let hover = &reply(&replies, 4)["result"];
+        assert!(
+            hover["contents"]["value"]
+                .as_str()
+                .unwrap()
+                .contains("Time::to_ts_ms")
+        );
+    }
+
+    #[test]
+    fn test_protocol_errors() {
+        let mut server = LspServer::default();
+        let out = server.handle(request(1, "textDocument/hover", json!({})));
+        assert_eq!(out[0]["error"]["code"], json!(SERVER_NOT_INITIALIZED));
+
+        server.handle(request(2, "initialize", json!({})));
+        let out = server.handle(request(3, "workspace/symbol", json!({})));
+        assert_eq!(out[0]["error"]["code"], json!(METHOD_NOT_FOUND));
+
+        // 未知扩展名的文档不做诊断
+        assert!(server.handle(open("file:///a.txt", "x")).is_empty());
+
+        let (code, _) = session(&[notify("exit", json!(null))]);
+        assert_eq!(code, 1);
+    }
+}
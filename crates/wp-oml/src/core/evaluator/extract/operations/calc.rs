@@ -0,0 +1,381 @@
+use crate::core::prelude::*;
+use crate::language::{CalcExpr, CalcOp, CalcOperation, CalcUnary};
+use std::cmp::Ordering;
+use wp_data_fmt::{DataFormat, Raw};
+use wp_model_core::model::{DataField, DataRecord};
+
+use crate::core::FieldExtractor;
+
+// 表达式求值：任一操作数缺失或为 null 时结果为 null（不输出目标字段），
+// 仅 `false && x`、`true || x` 可短路得到确定结果；`??` 用于为缺失值兜底。
+impl FieldExtractor for CalcOperation {
+    fn extract_one(
+        &self,
+        target: &EvaluationTarget,
+        src: &mut DataRecordRef<'_>,
+        dst: &DataRecord,
+    ) -> Option<DataField> {
+        let expr = self.expr();
+        let mut field = match expr {
+            CalcExpr::Binary(CalcOp::Coalesce, _, _) => coalesce(expr, target, src, dst)?,
+            _ => value_field(target.safe_name(), eval_expr(expr, target, src, dst)?),
+        };
+        field.set_name(target.safe_name());
+        Some(field)
+    }
+}
+
+// `??`：依次取首个非空（非 null/ignore、非空串、非空数组）操作数，并保留其原有类型
+fn coalesce(
+    expr: &CalcExpr,
+    target: &EvaluationTarget,
+    src: &mut DataRecordRef<'_>,
+    dst: &DataRecord,
+) -> Option<DataField> {
+    let field = match expr {
+        CalcExpr::Binary(CalcOp::Coalesce, l, r) => {
+            return coalesce(l, target, src, dst).or_else(|| coalesce(r, target, src, dst));
+        }
+        CalcExpr::Get(x) => x.extract_one(target, src, dst),
+        CalcExpr::Fun(x) => x.extract_one(target, src, dst),
+        CalcExpr::Val(x) => Some(x.clone()),
+        _ => eval_expr(expr, target, src, dst).map(|x| value_field(target.safe_name(), x)),
+    };
+    field.filter(|x| match x.get_value() {
+        Value::Null | Value::Ignore(_) => false,
+        Value::Chars(x) => !x.is_empty(),
+        Value::Array(x) => !x.is_empty(),
+        _ => true,
+    })
+}
+
+pub(crate) fn eval_expr(
+    expr: &CalcExpr,
+    target: &EvaluationTarget,
+    src: &mut DataRecordRef<'_>,
+    dst: &DataRecord,
+) -> Option<Value> {
+    match expr {
+        CalcExpr::Get(x) => operand(x.extract_one(target, src, dst)),
+        CalcExpr::Fun(x) => operand(x.extract_one(target, src, dst)),
+        CalcExpr::Val(x) => operand(Some(x.clone())),
+        CalcExpr::Unary(op, x) => eval_unary(*op, eval_expr(x, target, src, dst)?),
+        CalcExpr::Binary(CalcOp::Coalesce, _, _) => operand(coalesce(expr, target, src, dst)),
+        CalcExpr::Binary(CalcOp::And, l, r) => {
+            let l = eval_expr(l, target, src, dst);
+            if l == Some(Value::Bool(false)) {
+                return l;
+            }
+            match (l, eval_expr(r, target, src, dst)) {
+                (_, Some(Value::Bool(false))) => Some(Value::Bool(false)),
+                (Some(Value::Bool(true)), Some(Value::Bool(true))) => Some(Value::Bool(true)),
+                _ => None,
+            }
+        }
+        CalcExpr::Binary(CalcOp::Or, l, r) => {
+            let l = eval_expr(l, target, src, dst);
+            if l == Some(Value::Bool(true)) {
+                return l;
+            }
+            match (l, eval_expr(r, target, src, dst)) {
+                (_, Some(Value::Bool(true))) => Some(Value::Bool(true)),
+                (Some(Value::Bool(false)), Some(Value::Bool(false))) => Some(Value::Bool(false)),
+                _ => None,
+            }
+        }
+        CalcExpr::Binary(op, l, r) => {
+            let l = eval_expr(l, target, src, dst)?;
+            let r = eval_expr(r, target, src, dst)?;
+            eval_binary(*op, l, r)
+        }
+    }
+}
+
+fn operand(field: Option<DataField>) -> Option<Value> {
+    match field?.get_value() {
+        Value::Null => None,
+        x => Some(x.clone()),
+    }
+}
+
+pub(crate) fn value_field(name: String, value: Value) -> DataField {
+    match value {
+        Value::Digit(x) => DataField::from_digit(name, x),
+        Value::Float(x) => DataField::from_float(name, x),
+        Value::Chars(x) => DataField::from_chars(name, x.to_string()),
+        Value::Bool(x) => DataField::from_bool(name, x),
+        Value::Time(x) => DataField::from_time(name, x),
+        x => DataField::new(DataType::default(), name, x),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Num {
+    Int(i64),
+    Float(f64),
+}
+
+impl Num {
+    fn as_f64(self) -> f64 {
+        match self {
+            Num::Int(x) => x as f64,
+            Num::Float(x) => x,
+        }
+    }
+    fn into_value(self) -> Option<Value> {
+        match self {
+            Num::Int(x) => Some(Value::Digit(x)),
+            Num::Float(x) if x.is_finite() => Some(Value::Float(x)),
+            Num::Float(_) => None,
+        }
+    }
+}
+
+// 数值转换：chars 按数字文本解析
+fn to_num(value: &Value) -> Option<Num> {
+    match value {
+        Value::Digit(x) => Some(Num::Int(*x)),
+        Value::Float(x) => Some(Num::Float(*x)),
+        Value::Chars(x) => {
+            let x = x.trim();
+            x.parse::<i64>()
+                .map(Num::Int)
+                .ok()
+                .or_else(|| x.parse::<f64>().ok().map(Num::Float))
+        }
+        _ => None,
+    }
+}
+
+fn is_num(value: &Value) -> bool {
+    matches!(value, Value::Digit(_) | Value::Float(_))
+}
+
+fn eval_unary(op: CalcUnary, value: Value) -> Option<Value> {
+    match (op, value) {
+        (CalcUnary::Not, Value::Bool(x)) => Some(Value::Bool(!x)),
+        (CalcUnary::Not, _) => None,
+        (CalcUnary::Neg, x) => match to_num(&x)? {
+            Num::Int(x) => x.checked_neg().map(Value::Digit),
+            Num::Float(x) => Some(Value::Float(-x)),
+        },
+    }
+}
+
+fn eval_binary(op: CalcOp, l: Value, r: Value) -> Option<Value> {
+    match op {
+        CalcOp::Add => match (&l, &r) {
+            (Value::Chars(_), _) | (_, Value::Chars(_)) => {
+                let raw = Raw;
+                Some(Value::Chars(
+                    format!("{}{}", raw.fmt_value(&l), raw.fmt_value(&r)).into(),
+                ))
+            }
+            (Value::Time(t), x) | (x, Value::Time(t)) => shift_time(t, to_int(x)?),
+            _ => arith(op, to_num(&l)?, to_num(&r)?),
+        },
+        CalcOp::Sub => match (&l, &r) {
+            (Value::Time(a), Value::Time(b)) => {
+                Some(Value::Digit(a.signed_duration_since(*b).num_seconds()))
+            }
+            (Value::Time(t), x) => shift_time(t, to_int(x)?.checked_neg()?),
+            _ => arith(op, to_num(&l)?, to_num(&r)?),
+        },
+        CalcOp::Mul | CalcOp::Div | CalcOp::Rem => arith(op, to_num(&l)?, to_num(&r)?),
+        CalcOp::Eq | CalcOp::Ne | CalcOp::Lt | CalcOp::Le | CalcOp::Gt | CalcOp::Ge => {
+            let ord = compare(&l, &r)?;
+            let hit = match op {
+                CalcOp::Eq => ord == Ordering::Equal,
+                CalcOp::Ne => ord != Ordering::Equal,
+                CalcOp::Lt => ord == Ordering::Less,
+                CalcOp::Le => ord != Ordering::Greater,
+                CalcOp::Gt => ord == Ordering::Greater,
+                _ => ord != Ordering::Less,
+            };
+            Some(Value::Bool(hit))
+        }
+        CalcOp::And | CalcOp::Or | CalcOp::Coalesce => None,
+    }
+}
+
+fn to_int(value: &Value) -> Option<i64> {
+    match to_num(value)? {
+        Num::Int(x) => Some(x),
+        Num::Float(_) => None,
+    }
+}
+
+// 时间 ± 秒数
+fn shift_time(time: &chrono::NaiveDateTime, secs: i64) -> Option<Value> {
+    let delta = chrono::Duration::try_seconds(secs)?;
+    time.checked_add_signed(delta).map(Value::Time)
+}
+
+// 整数运算保持整数（溢出、除零为 null），含浮点时按浮点计算
+fn arith(op: CalcOp, l: Num, r: Num) -> Option<Value> {
+    let num = match (l, r) {
+        (Num::Int(a), Num::Int(b)) => Num::Int(match op {
+            CalcOp::Add => a.checked_add(b)?,
+            CalcOp::Sub => a.checked_sub(b)?,
+            CalcOp::Mul => a.checked_mul(b)?,
+            CalcOp::Div => a.checked_div(b)?,
+            CalcOp::Rem => a.checked_rem(b)?,
+            _ => return None,
+        }),
+        _ => {
+            let (a, b) = (l.as_f64(), r.as_f64());
+            Num::Float(match op {
+                CalcOp::Add => a + b,
+                CalcOp::Sub => a - b,
+                CalcOp::Mul => a * b,
+                CalcOp::Div if b != 0.0 => a / b,
+                CalcOp::Rem if b != 0.0 => a % b,
+                _ => return None,
+            })
+        }
+    };
+    num.into_value()
+}
+
+// 数值按大小比较；chars 与数值比较时能解析则按数值，否则按文本
+pub(crate) fn compare(l: &Value, r: &Value) -> Option<Ordering> {
+    match (l, r) {
+        (Value::Chars(a), Value::Chars(b)) => {
+            let (a, b): (&str, &str) = (a, b);
+            Some(a.cmp(b))
+        }
+        (Value::Time(a), Value::Time(b)) => Some(a.cmp(b)),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        (Value::IpAddr(a), Value::IpAddr(b)) => Some(a.cmp(b)),
+        (Value::Chars(_), x) | (x, Value::Chars(_)) if is_num(x) => match (to_num(l), to_num(r)) {
+            (Some(a), Some(b)) => a.as_f64().partial_cmp(&b.as_f64()),
+            _ => {
+                let raw = Raw;
+                let (a, b) = (raw.fmt_value(l).to_string(), raw.fmt_value(r).to_string());
+                Some(a.cmp(&b))
+            }
+        },
+        _ if is_num(l) && is_num(r) => match (to_num(l)?, to_num(r)?) {
+            (Num::Int(a), Num::Int(b)) => Some(a.cmp(&b)),
+            (a, b) => a.as_f64().partial_cmp(&b.as_f64()),
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::DataTransformer;
+    use crate::parser::oml_parse_raw;
+    use chrono::NaiveDate;
+    use orion_error::TestAssert;
+    use wp_data_model::cache::FieldQueryCache;
+    use wp_model_core::model::{DataField, DataRecord};
+
+    #[test]
+    fn test_calc_arith_and_concat() {
+        let cache = &mut FieldQueryCache::default();
+        let src = DataRecord {
+            items: vec![
+                DataField::from_digit("bytes", 4096),
+                DataField::from_digit("code", 503),
+                DataField::from_chars("a", "host"),
+                DataField::from_digit("b", 8080),
+                DataField::from_chars("n", "2.5"),
+                DataField::from_float("ratio", 0.5),
+            ],
+        };
+        let mut conf = r#"
+        name : test
+        ---
+        kb = read(bytes) / 1024 ;
+        full = read(a) + ":" + read(b) ;
+        mix = (read(b) + 20) * 2 % 7 ;
+        half = read(bytes) * read(ratio) ;
+        txt = read(n) * 2 ;
+        neg = -read(code) + 3 ;
+        err = read(code) >= 500 && read(code) < 600 ;
+        bad = read(code) == "503" || read(a) > "hz" ;
+        "#;
+        let model = oml_parse_raw(&mut conf).assert();
+        let target = model.transform(src, cache);
+        assert_eq!(target.field("kb"), Some(&DataField::from_digit("kb", 4)));
+        assert_eq!(
+            target.field("full"),
+            Some(&DataField::from_chars("full", "host:8080"))
+        );
+        assert_eq!(target.field("mix"), Some(&DataField::from_digit("mix", 2)));
+        assert_eq!(
+            target.field("half"),
+            Some(&DataField::from_float("half", 2048.0))
+        );
+        assert_eq!(
+            target.field("txt"),
+            Some(&DataField::from_float("txt", 5.0))
+        );
+        assert_eq!(
+            target.field("neg"),
+            Some(&DataField::from_digit("neg", -500))
+        );
+        assert_eq!(
+            target.field("err"),
+            Some(&DataField::from_bool("err", true))
+        );
+        assert_eq!(
+            target.field("bad"),
+            Some(&DataField::from_bool("bad", true))
+        );
+    }
+
+    #[test]
+    fn test_calc_null_and_time() {
+        let cache = &mut FieldQueryCache::default();
+        let begin = NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let src = DataRecord {
+            items: vec![
+                DataField::from_digit("x", 10),
+                DataField::from_digit("zero", 0),
+                DataField::from_chars("word", "abc"),
+                DataField::from_time("begin", begin),
+                DataField::from_time("end", begin + chrono::Duration::seconds(90)),
+            ],
+        };
+        let mut conf = r#"
+        name : test
+        ---
+        miss = read(x) + read(nothing) ;
+        div0 = read(x) / read(zero) ;
+        nan = read(word) * 2 ;
+        dft = read(nothing) { _ : digit(5) } + read(x) ;
+        short = read(nothing) > 1 || read(x) > 1 ;
+        unknown = read(nothing) > 1 && read(x) > 1 ;
+        cost = read(end) - read(begin) ;
+        next = read(begin) + 3600 ;
+        "#;
+        let model = oml_parse_raw(&mut conf).assert();
+        let target = model.transform(src, cache);
+        assert_eq!(target.field("miss"), None);
+        assert_eq!(target.field("div0"), None);
+        assert_eq!(target.field("nan"), None);
+        assert_eq!(target.field("dft"), Some(&DataField::from_digit("dft", 15)));
+        assert_eq!(
+            target.field("short"),
+            Some(&DataField::from_bool("short", true))
+        );
+        assert_eq!(target.field("unknown"), None);
+        assert_eq!(
+            target.field("cost"),
+            Some(&DataField::from_digit("cost", 90))
+        );
+        assert_eq!(
+            target.field("next"),
+            Some(&DataField::from_time(
+                "next",
+                begin + chrono::Duration::seconds(3600)
+            ))
+        );
+    }
+}
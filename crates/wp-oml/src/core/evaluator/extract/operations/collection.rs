@@ -0,0 +1,416 @@
+use super::calc::{compare, eval_expr, value_field};
+use crate::core::prelude::*;
+use crate::language::{
+    ArrContains, ArrDistinct, ArrFilter, ArrFirst, ArrLambda, ArrLast, ArrMap, ArrSort, ObjFlatten,
+    ObjKeys, ObjMerge, ObjUnflatten, ObjValues,
+};
+use std::cmp::Ordering;
+use wp_data_fmt::{DataFormat, Raw};
+use wp_model_core::model::types::value::ObjectValue;
+
+impl ArrLambda {
+    // 以元素为源记录求值：元素绑定为 `var`，对象成员展开为 `var/key`
+    fn apply(&self, item: &DataField) -> Option<Value> {
+        let mut bound = Vec::with_capacity(4);
+        if let Value::Obj(obj) = item.get_value() {
+            flatten_into(&mut bound, &self.var, "/", obj);
+        }
+        let mut elem = item.clone();
+        elem.set_name(self.var.clone());
+        bound.push(elem);
+        let mut src = DataRecordRef::from(&bound);
+        let target = EvaluationTarget::new(self.var.clone(), DataType::Auto);
+        eval_expr(&self.body, &target, &mut src, &DataRecord::default())
+    }
+}
+
+impl ValueProcessor for ArrMap {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Array(arr) => {
+                let name = in_val.get_name().to_string();
+                let items = arr
+                    .iter()
+                    .filter_map(|x| self.lambda.apply(x))
+                    .map(|x| value_field(name.clone(), x))
+                    .collect();
+                DataField::from_arr(name, items)
+            }
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for ArrFilter {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Array(arr) => {
+                let items = arr
+                    .iter()
+                    .filter(|x| self.lambda.apply(x) == Some(Value::Bool(true)))
+                    .cloned()
+                    .collect();
+                DataField::from_arr(in_val.get_name().to_string(), items)
+            }
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for ArrDistinct {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Array(arr) => {
+                let mut items: Vec<DataField> = Vec::with_capacity(arr.len());
+                for x in arr {
+                    if !items.iter().any(|o| o.get_value() == x.get_value()) {
+                        items.push(x.clone());
+                    }
+                }
+                DataField::from_arr(in_val.get_name().to_string(), items)
+            }
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for ArrSort {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Array(arr) => {
+                let mut items = arr.clone();
+                items.sort_by(|a, b| sort_order(a.get_value(), b.get_value()));
+                DataField::from_arr(in_val.get_name().to_string(), items)
+            }
+            _ => in_val,
+        }
+    }
+}
+
+// 可比较时按值比较，否则按 raw 文本比较
+fn sort_order(l: &Value, r: &Value) -> Ordering {
+    compare(l, r).unwrap_or_else(|| {
+        let raw = Raw;
+        raw.fmt_value(l)
+            .to_string()
+            .cmp(&raw.fmt_value(r).to_string())
+    })
+}
+
+impl ValueProcessor for ArrFirst {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Array(arr) => match arr.first() {
+                Some(x) => x.clone(),
+                None => DataField::from_ignore(in_val.get_name()),
+            },
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for ArrLast {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Array(arr) => match arr.last() {
+                Some(x) => x.clone(),
+                None => DataField::from_ignore(in_val.get_name()),
+            },
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for ArrContains {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        let expect = self.value.get_value();
+        let hit = match in_val.get_value() {
+            Value::Array(arr) => arr
+                .iter()
+                .any(|x| compare(x.get_value(), expect) == Some(Ordering::Equal)),
+            Value::Chars(x) => x.contains(Raw.fmt_value(expect).to_string().as_str()),
+            Value::Obj(obj) => obj.contains_key(Raw.fmt_value(expect).to_string().as_str()),
+            _ => return in_val,
+        };
+        DataField::from_bool(in_val.get_name().to_string(), hit)
+    }
+}
+
+impl ValueProcessor for ObjKeys {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Obj(obj) => {
+                let name = in_val.get_name().to_string();
+                let items = obj
+                    .keys()
+                    .map(|k| DataField::from_chars(name.clone(), k.clone()))
+                    .collect();
+                DataField::from_arr(name, items)
+            }
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for ObjValues {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Obj(obj) => {
+                let items = obj.values().cloned().collect();
+                DataField::from_arr(in_val.get_name().to_string(), items)
+            }
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for ObjMerge {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Array(arr) => {
+                let mut merged = ObjectValue::default();
+                for x in arr {
+                    if let Value::Obj(obj) = x.get_value() {
+                        for (k, v) in obj.iter() {
+                            merged.insert(k.clone(), v.clone());
+                        }
+                    }
+                }
+                DataField::from_obj(in_val.get_name().to_string(), merged)
+            }
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for ObjFlatten {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Obj(obj) => {
+                let mut items = Vec::with_capacity(obj.len());
+                for (k, v) in obj.iter() {
+                    match v.get_value() {
+                        Value::Obj(sub) => flatten_into(&mut items, k, &self.sep, sub),
+                        _ => {
+                            let mut x = v.clone();
+                            x.set_name(k.clone());
+                            items.push(x);
+                        }
+                    }
+                }
+                let mut flat = ObjectValue::default();
+                for x in items {
+                    flat.insert(x.get_name().to_string(), x);
+                }
+                DataField::from_obj(in_val.get_name().to_string(), flat)
+            }
+            _ => in_val,
+        }
+    }
+}
+
+// 递归展开对象成员，字段名为 `prefix<sep>key`
+fn flatten_into(out: &mut Vec<DataField>, prefix: &str, sep: &str, obj: &ObjectValue) {
+    for (k, v) in obj.iter() {
+        let path = format!("{}{}{}", prefix, sep, k);
+        match v.get_value() {
+            Value::Obj(sub) => flatten_into(out, &path, sep, sub),
+            _ => {
+                let mut x = v.clone();
+                x.set_name(path);
+                out.push(x);
+            }
+        }
+    }
+}
+
+impl ValueProcessor for ObjUnflatten {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Obj(obj) if !self.sep.is_empty() => {
+                let mut nested = ObjectValue::default();
+                for (k, v) in obj.iter() {
+                    let path: Vec<&str> = k.split(self.sep.as_str()).collect();
+                    insert_path(&mut nested, &path, v.clone());
+                }
+                DataField::from_obj(in_val.get_name().to_string(), nested)
+            }
+            _ => in_val,
+        }
+    }
+}
+
+// 按路径插入，中间层缺失或非对象时以新对象替换
+fn insert_path(obj: &mut ObjectValue, path: &[&str], mut val: DataField) {
+    let Some((key, rest)) = path.split_first() else {
+        return;
+    };
+    if rest.is_empty() {
+        val.set_name(key.to_string());
+        obj.insert(key.to_string(), val);
+        return;
+    }
+    let mut sub = match obj.get(*key).map(|x| x.get_value()) {
+        Some(Value::Obj(x)) => x.clone(),
+        _ => ObjectValue::default(),
+    };
+    insert_path(&mut sub, rest, val);
+    obj.insert(key.to_string(), DataField::from_obj(key.to_string(), sub));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::DataTransformer;
+    use crate::parser::oml_parse_raw;
+    use orion_error::TestAssert;
+    use wp_data_model::cache::FieldQueryCache;
+    use wp_model_core::model::types::value::ObjectValue;
+    use wp_model_core::model::{DataField, DataRecord, Value};
+
+    fn arr_of(target: &DataRecord, name: &str) -> Vec<Value> {
+        match target.field(name).map(|x| x.get_value()) {
+            Some(Value::Array(x)) => x.iter().map(|x| x.get_value().clone()).collect(),
+            other => panic!("{} is not array: {:?}", name, other),
+        }
+    }
+
+    fn obj_of(target: &DataRecord, name: &str) -> ObjectValue {
+        match target.field(name).map(|x| x.get_value()) {
+            Some(Value::Obj(x)) => x.clone(),
+            other => panic!("{} is not obj: {:?}", name, other),
+        }
+    }
+
+    fn digits(xs: &[i64]) -> Vec<Value> {
+        xs.iter().map(|x| Value::Digit(*x)).collect()
+    }
+
+    #[test]
+    fn test_array_funs() {
+        let cache = &mut FieldQueryCache::default();
+        let ports: Vec<DataField> = [443, 80, 8080, 80, 22]
+            .iter()
+            .map(|x| DataField::from_digit("port", *x))
+            .collect();
+        let mut svc_a = ObjectValue::default();
+        svc_a.insert("name".into(), DataField::from_chars("name", "web"));
+        svc_a.insert("port".into(), DataField::from_digit("port", 443));
+        let mut svc_b = ObjectValue::default();
+        svc_b.insert("name".into(), DataField::from_chars("name", "ssh"));
+        svc_b.insert("port".into(), DataField::from_digit("port", 22));
+        let src = DataRecord {
+            items: vec![
+                DataField::from_arr("ports", ports),
+                DataField::from_arr(
+                    "svcs",
+                    vec![
+                        DataField::from_obj("svc", svc_a),
+                        DataField::from_obj("svc", svc_b),
+                    ],
+                ),
+                DataField::from_arr("empty", vec![]),
+            ],
+        };
+        let mut conf = r#"
+        name : test
+        ---
+        A = pipe read(ports) | map(x => read(x) + 1) ;
+        B = pipe read(ports) | filter(p => read(p) >= 443) ;
+        C = pipe read(ports) | distinct | sort ;
+        D = pipe read(ports) | len ;
+        E = pipe read(ports) | sort | first ;
+        F = pipe read(ports) | sort | last ;
+        G = pipe read(ports) | contains(8080) ;
+        H = pipe read(ports) | contains(3306) ;
+        I = pipe read(svcs) | filter(s => read(s/port) < 100) | map(s => read(s/name)) ;
+        J = pipe read(empty) | first ;
+        "#;
+        let model = oml_parse_raw(&mut conf).assert();
+        let target = model.transform(src, cache);
+
+        assert_eq!(arr_of(&target, "A"), digits(&[444, 81, 8081, 81, 23]));
+        assert_eq!(arr_of(&target, "B"), digits(&[443, 8080]));
+        assert_eq!(arr_of(&target, "C"), digits(&[22, 80, 443, 8080]));
+        assert_eq!(target.field("D"), Some(&DataField::from_digit("D", 5)));
+        assert_eq!(target.field("E"), Some(&DataField::from_digit("E", 22)));
+        assert_eq!(target.field("F"), Some(&DataField::from_digit("F", 8080)));
+        assert_eq!(target.field("G"), Some(&DataField::from_bool("G", true)));
+        assert_eq!(target.field("H"), Some(&DataField::from_bool("H", false)));
+        assert_eq!(arr_of(&target, "I"), vec![Value::from("ssh")]);
+        assert!(!matches!(
+            target.field("J").map(|x| x.get_value()),
+            Some(Value::Digit(_))
+        ));
+    }
+
+    #[test]
+    fn test_object_funs() {
+        let cache = &mut FieldQueryCache::default();
+        let mut geo = ObjectValue::default();
+        geo.insert("city".into(), DataField::from_chars("city", "bj"));
+        geo.insert("isp".into(), DataField::from_chars("isp", "cu"));
+        let mut ext = ObjectValue::default();
+        ext.insert("geo".into(), DataField::from_obj("geo", geo));
+        ext.insert("level".into(), DataField::from_digit("level", 3));
+        let mut over = ObjectValue::default();
+        over.insert("level".into(), DataField::from_digit("level", 5));
+        over.insert("tag".into(), DataField::from_chars("tag", "vip"));
+        let src = DataRecord {
+            items: vec![
+                DataField::from_obj("ext", ext),
+                DataField::from_obj("over", over),
+            ],
+        };
+        let mut conf = r#"
+        name : test
+        ---
+        K = pipe read(ext) | keys ;
+        V = pipe read(over) | values ;
+        N = pipe read(ext) | len ;
+        H = pipe read(ext) | contains("geo") ;
+        F = pipe read(ext) | flatten ;
+        S = pipe read(ext) | flatten("_") ;
+        U = pipe read(ext) | flatten | unflatten ;
+        both = collect read(keys : [ext, over]) ;
+        M = pipe read(both) | merge ;
+        "#;
+        let model = oml_parse_raw(&mut conf).assert();
+        let target = model.transform(src, cache);
+
+        assert_eq!(
+            arr_of(&target, "K"),
+            vec![Value::from("geo"), Value::from("level")]
+        );
+        assert_eq!(
+            arr_of(&target, "V"),
+            vec![Value::Digit(5), Value::from("vip")]
+        );
+        assert_eq!(target.field("N"), Some(&DataField::from_digit("N", 2)));
+        assert_eq!(target.field("H"), Some(&DataField::from_bool("H", true)));
+
+        let flat = obj_of(&target, "F");
+        assert_eq!(flat.len(), 3);
+        assert_eq!(
+            flat.get("geo.city").map(|x| x.get_value()),
+            Some(&Value::from("bj"))
+        );
+        assert!(obj_of(&target, "S").contains_key("geo_isp"));
+
+        let nested = obj_of(&target, "U");
+        match nested.get("geo").map(|x| x.get_value()) {
+            Some(Value::Obj(geo)) => assert_eq!(
+                geo.get("isp").map(|x| x.get_value()),
+                Some(&Value::from("cu"))
+            ),
+            other => panic!("geo is not obj: {:?}", other),
+        }
+
+        let merged = obj_of(&target, "M");
+        assert_eq!(
+            merged.get("level").map(|x| x.get_value()),
+            Some(&Value::Digit(5))
+        );
+        assert!(merged.contains_key("geo"));
+        assert!(merged.contains_key("tag"));
+    }
+}
@@ -0,0 +1,110 @@
+use super::ifelse::cond_is_true;
+use crate::core::prelude::*;
+use crate::language::{FieldRemove, RecordFilter};
+
+impl RecordFilter {
+    /// 当前记录是否应被丢弃：`drop` 条件成立或 `keep` 条件不成立
+    pub(crate) fn rejects(&self, src: &mut DataRecordRef<'_>, dst: &DataRecord) -> bool {
+        match self {
+            RecordFilter::Drop(x) => cond_is_true(x, src, dst),
+            RecordFilter::Keep(x) => !cond_is_true(x, src, dst),
+        }
+    }
+}
+
+impl ExpEvaluator for FieldRemove {
+    fn eval_proc(
+        &self,
+        _src: &mut DataRecordRef<'_>,
+        dst: &mut DataRecord,
+        _cache: &mut FieldQueryCache,
+    ) {
+        dst.items.retain(|x| !self.matches(x.get_name().trim()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::DataTransformer;
+    use crate::parser::oml_parse_raw;
+    use orion_error::TestAssert;
+    use wp_data_model::cache::FieldQueryCache;
+    use wp_model_core::model::{DataField, DataRecord};
+
+    fn access_log(uri: &str, level: &str) -> DataRecord {
+        DataRecord {
+            items: vec![
+                DataField::from_chars("uri", uri),
+                DataField::from_chars("level", level),
+                DataField::from_digit("status", 200),
+                DataField::from_chars("debug_id", "x1"),
+                DataField::from_chars("debug_span", "x2"),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_drop_keep_remove() {
+        let cache = &mut FieldQueryCache::default();
+        let mut conf = r#"
+        name : test
+        ---
+        drop when uri = '/healthz' or uri = '/ping' ;
+        keep when not level = 'debug' ;
+        * = take() ;
+        code : chars = read(status) ;
+        remove(status, debug_*) ;
+        "#;
+        let model = oml_parse_raw(&mut conf).assert();
+
+        assert!(
+            model
+                .transform_filter(&access_log("/healthz", "info"), cache)
+                .is_none()
+        );
+        assert!(
+            model
+                .transform_filter(&access_log("/api", "debug"), cache)
+                .is_none()
+        );
+        assert!(
+            model
+                .transform_ref(&access_log("/ping", "info"), cache)
+                .items
+                .is_empty()
+        );
+
+        let target = model
+            .transform_filter(&access_log("/api", "info"), cache)
+            .expect("kept");
+        assert_eq!(
+            target.field("uri"),
+            Some(&DataField::from_chars("uri", "/api"))
+        );
+        assert_eq!(
+            target.field("code"),
+            Some(&DataField::from_chars("code", "200"))
+        );
+        assert_eq!(target.field("status"), None);
+        assert_eq!(target.field("debug_id"), None);
+        assert_eq!(target.field("debug_span"), None);
+    }
+
+    #[test]
+    fn test_drop_on_generated_field() {
+        let cache = &mut FieldQueryCache::default();
+        let mut conf = r#"
+        name : test
+        ---
+        kind = if status >= 500 { chars(error) } else { chars(ok) } ;
+        drop when kind = 'ok' ;
+        uri = read(uri) ;
+        "#;
+        let model = oml_parse_raw(&mut conf).assert();
+        assert!(
+            model
+                .transform_filter(&access_log("/api", "info"), cache)
+                .is_none()
+        );
+    }
+}
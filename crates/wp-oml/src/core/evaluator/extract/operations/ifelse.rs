@@ -0,0 +1,123 @@
+use crate::core::prelude::*;
+use crate::language::{CompareExpress, IfOperation, LogicalExpression};
+use orion_exp::LogicOperator;
+use wp_data_model::compare::compare_datafield;
+use wp_model_core::model::{DataField, DataRecord, DataType};
+
+use crate::core::FieldExtractor;
+
+impl FieldExtractor for IfOperation {
+    fn extract_one(
+        &self,
+        target: &EvaluationTarget,
+        src: &mut DataRecordRef<'_>,
+        dst: &DataRecord,
+    ) -> Option<DataField> {
+        if cond_is_true(self.cond(), src, dst) {
+            self.then().extract_one(target, src, dst)
+        } else {
+            self.other().as_ref()?.extract_one(target, src, dst)
+        }
+    }
+}
+
+pub(crate) fn cond_is_true(
+    expr: &LogicalExpression,
+    src: &mut DataRecordRef<'_>,
+    dst: &DataRecord,
+) -> bool {
+    match expr {
+        LogicalExpression::Compare(x) => cmp_is_true(x, src, dst),
+        LogicalExpression::Logic(x) => {
+            let left = x.left().as_ref();
+            match x.op() {
+                LogicOperator::And => {
+                    left.is_none_or(|l| cond_is_true(l, src, dst))
+                        && cond_is_true(x.right(), src, dst)
+                }
+                LogicOperator::Or => {
+                    left.is_some_and(|l| cond_is_true(l, src, dst))
+                        || cond_is_true(x.right(), src, dst)
+                }
+                LogicOperator::Not => !cond_is_true(x.right(), src, dst),
+            }
+        }
+    }
+}
+
+// 左侧字段先取源记录、再取已生成的目标字段；任一侧缺失或类型不一致视为不成立
+fn cmp_is_true(cmp: &CompareExpress, src: &mut DataRecordRef<'_>, dst: &DataRecord) -> bool {
+    let name = cmp.var_name();
+    let Some(left) = src.get(name).or_else(|| dst.field(name)).cloned() else {
+        return false;
+    };
+    let cur = EvaluationTarget::new(name.to_string(), DataType::Auto);
+    match cmp.right_const().extract_one(&cur, src, dst) {
+        Some(right) => compare_datafield(&left, &right, *cmp.compare_op()),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::DataTransformer;
+    use crate::parser::oml_parse_raw;
+    use orion_error::TestAssert;
+    use std::net::{IpAddr, Ipv4Addr};
+    use wp_data_model::cache::FieldQueryCache;
+    use wp_model_core::model::{DataField, DataRecord, DataType};
+
+    #[test]
+    fn test_if_else_and_coalesce() {
+        let cache = &mut FieldQueryCache::default();
+        let src = DataRecord {
+            items: vec![
+                DataField::from_digit("status", 503),
+                DataField::from_chars("method", "POST"),
+                DataField::from_chars("xff", ""),
+                DataField::from_ip("sip", IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))),
+            ],
+        };
+        let mut conf = r#"
+        name : test
+        ---
+        level = if status >= 500 { chars(error) } else if status >= 400 { chars(warn) } else { chars(info) } ;
+        write = if method = 'POST' or method = 'PUT' { bool(true) } else { bool(false) } ;
+        none = if status < 100 { chars(x) } ;
+        code = if not status = 200 { read(status) ; } ;
+        client = read(xff) ?? read(sip) ;
+        client_str : chars = coalesce(read(xff), read(nothing), read(sip)) ;
+        fallback = read(nothing) ?? "-" ;
+        nested = if status > 0 { read(xff) ?? read(method) } ;
+        "#;
+        let model = oml_parse_raw(&mut conf).assert();
+        let target = model.transform(src, cache);
+        assert_eq!(
+            target.field("level"),
+            Some(&DataField::from_chars("level", "error"))
+        );
+        assert_eq!(
+            target.field("write"),
+            Some(&DataField::from_bool("write", true))
+        );
+        assert_eq!(target.field("none"), None);
+        assert_eq!(
+            target.field("code"),
+            Some(&DataField::from_digit("code", 503))
+        );
+        let client = target.field("client").expect("client");
+        assert_eq!(client.get_meta(), &DataType::IP);
+        assert_eq!(
+            target.field("client_str"),
+            Some(&DataField::from_chars("client_str", "10.0.0.1"))
+        );
+        assert_eq!(
+            target.field("fallback"),
+            Some(&DataField::from_chars("fallback", "-"))
+        );
+        assert_eq!(
+            target.field("nested"),
+            Some(&DataField::from_chars("nested", "POST"))
+        );
+    }
+}
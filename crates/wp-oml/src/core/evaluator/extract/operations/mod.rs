@@ -1,4 +1,8 @@
 mod array;
+mod calc;
+mod collection;
+mod directive;
+mod ifelse;
 mod map;
 mod matchs;
 mod other;
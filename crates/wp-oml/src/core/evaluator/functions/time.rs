@@ -1,7 +1,18 @@
 use crate::core::FieldExtractor;
 use crate::core::prelude::*;
-use crate::language::{BuiltinFunction, FunOperation, NowDate, NowHour, NowTime};
-use chrono::{Datelike, Local, Timelike};
+use crate::language::{
+    BuiltinFunction, FunOperation, NowDate, NowHour, NowTime, TimeAdd, TimeDiff, TimeFormat,
+    TimeHour, TimeParse, TimeToZone, TimeTrunc, TimeTruncUnit, TimeWeekday,
+};
+use chrono::{
+    DateTime, Datelike, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike,
+};
+use std::fmt::Write;
+
+// 未携带时区的时间值按 UTC+8 本地时间解释，与 `Time::to_ts` 一致
+fn naive_zone() -> FixedOffset {
+    FixedOffset::east_opt(8 * 3600).expect("utc+8 offset")
+}
 impl FieldExtractor for NowTime {
     fn extract_one(
         &self,
@@ -37,6 +48,7 @@ impl FieldExtractor for BuiltinFunction {
             BuiltinFunction::NowTime(x) => x.extract_one(target, src, dst),
             BuiltinFunction::NowDate(x) => x.extract_one(target, src, dst),
             BuiltinFunction::NowHour(x) => x.extract_one(target, src, dst),
+            BuiltinFunction::TimeDiff(x) => x.extract_one(target, src, dst),
         }
     }
 }
@@ -77,13 +89,172 @@ impl FieldExtractor for NowHour {
     }
 }
 
+impl FieldExtractor for TimeDiff {
+    fn extract_one(
+        &self,
+        target: &EvaluationTarget,
+        src: &mut DataRecordRef<'_>,
+        dst: &DataRecord,
+    ) -> Option<DataField> {
+        let end = time_of(&self.end, src, dst)?;
+        let start = time_of(&self.start, src, dst)?;
+        Some(DataField::from_digit(
+            target.safe_name(),
+            end.signed_duration_since(start).num_seconds(),
+        ))
+    }
+}
+
+fn time_of(name: &str, src: &mut DataRecordRef<'_>, dst: &DataRecord) -> Option<NaiveDateTime> {
+    match src.get(name).or_else(|| dst.field(name))?.get_value() {
+        Value::Time(x) => Some(*x),
+        _ => None,
+    }
+}
+
+impl ValueProcessor for TimeFormat {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Time(x) => {
+                let name = in_val.get_name().to_string();
+                let mut out = String::new();
+                // 格式与值不匹配（如无时区时使用 %z）时 chrono 返回 fmt::Error
+                let local = x.and_local_timezone(naive_zone()).single();
+                let done = match (local, &self.zone) {
+                    (Some(t), Some(tz)) => {
+                        write!(out, "{}", t.with_timezone(tz).format(&self.pattern))
+                    }
+                    (Some(t), None) => write!(out, "{}", t.format(&self.pattern)),
+                    (None, _) => Err(std::fmt::Error),
+                };
+                match done {
+                    Ok(_) => DataField::from_chars(name, out),
+                    Err(_) => DataField::from_ignore(name),
+                }
+            }
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for TimeParse {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Chars(x) => {
+                let x = x.trim();
+                let parsed = DateTime::parse_from_str(x, &self.pattern)
+                    .map(|t| t.with_timezone(&naive_zone()).naive_local())
+                    .or_else(|_| NaiveDateTime::parse_from_str(x, &self.pattern))
+                    .or_else(|_| {
+                        NaiveDate::parse_from_str(x, &self.pattern)
+                            .map(|d| d.and_time(NaiveTime::MIN))
+                    });
+                match parsed {
+                    Ok(t) => DataField::from_time(in_val.get_name().to_string(), t),
+                    Err(_) => DataField::from_ignore(in_val.get_name()),
+                }
+            }
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for TimeTrunc {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Time(x) => {
+                let out = match self.unit {
+                    TimeTruncUnit::Minute => x.date().and_hms_opt(x.hour(), x.minute(), 0),
+                    TimeTruncUnit::Hour => x.date().and_hms_opt(x.hour(), 0, 0),
+                    TimeTruncUnit::Day => Some(x.date().and_time(NaiveTime::MIN)),
+                };
+                match out {
+                    Some(t) => DataField::from_time(in_val.get_name().to_string(), t),
+                    None => in_val,
+                }
+            }
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for TimeAdd {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Time(x) => {
+                let out = chrono::Duration::try_milliseconds(self.millis)
+                    .and_then(|d| x.checked_add_signed(d));
+                match out {
+                    Some(t) => DataField::from_time(in_val.get_name().to_string(), t),
+                    None => DataField::from_ignore(in_val.get_name()),
+                }
+            }
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for TimeToZone {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Time(x) => {
+                // 夏令时重叠取较早时刻，跳变缺口内的本地时间无法换算
+                let local = match self.from {
+                    Some(from) => from
+                        .from_local_datetime(x)
+                        .earliest()
+                        .map(|t| t.with_timezone(&self.zone)),
+                    None => x
+                        .and_local_timezone(naive_zone())
+                        .single()
+                        .map(|t| t.with_timezone(&self.zone)),
+                };
+                match local {
+                    Some(t) => DataField::from_time(in_val.get_name().to_string(), t.naive_local()),
+                    None => DataField::from_ignore(in_val.get_name()),
+                }
+            }
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for TimeWeekday {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Time(x) => DataField::from_digit(
+                in_val.get_name().to_string(),
+                x.weekday().number_from_monday() as i64,
+            ),
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for TimeHour {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Time(x) => DataField::from_digit(in_val.get_name().to_string(), x.hour() as i64),
+            _ => in_val,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::core::DataTransformer;
     use crate::parser::oml_parse_raw;
+    use chrono::{NaiveDate, NaiveDateTime};
     use orion_error::TestAssertWithMsg;
     use wp_data_model::cache::FieldQueryCache;
-    use wp_model_core::model::{DataField, DataRecord};
+    use wp_model_core::model::{DataField, DataRecord, Value};
+
+    fn at(d: u32, h: u32, m: u32, s: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 3, d)
+            .unwrap()
+            .and_hms_opt(h, m, s)
+            .unwrap()
+    }
 
     #[test]
     fn test_pipe() {
@@ -119,4 +290,115 @@ mod tests {
         assert!(target.field("X3").is_some());
         println!("{}", target);
     }
+
+    #[test]
+    fn test_time_funs() {
+        let cache = &mut FieldQueryCache::default();
+        let data = vec![
+            DataField::from_chars("raw", "10/Mar/2024:23:45:30 +0800"),
+            DataField::from_chars("raw_ny", "10/Mar/2024:11:45:30 -0400"),
+            DataField::from_chars("day", "2024-03-10"),
+        ];
+        let src = DataRecord { items: data };
+
+        let mut conf = r#"
+        name : test
+        ---
+        T  = time(2024-03-10 23:45:30) ;
+        P  = pipe read(raw) | Time::parse("%d/%b/%Y:%H:%M:%S %z") ;
+        P2 = pipe read(raw_ny) | Time::parse("%d/%b/%Y:%H:%M:%S %z") ;
+        D  = pipe read(day) | Time::parse("%Y-%m-%d") ;
+        X  = pipe read(day) | Time::parse("%H:%M") ;
+        F  = pipe read(T) | Time::format("%Y-%m-%d %H:%M:%S", "Asia/Shanghai") ;
+        F2 = pipe read(T) | Time::format("%Y%m%d") ;
+        F3 = pipe read(T) | Time::format("%H:%M %z") ;
+        TH = pipe read(T) | Time::trunc(hour) ;
+        TD = pipe read(T) | Time::trunc(day) ;
+        A  = pipe read(T) | Time::add(1h30m) ;
+        Z  = pipe read(T) | Time::to_zone("Asia/Tokyo") ;
+        Z2 = pipe read(T) | Time::to_zone("UTC", "America/New_York") ;
+        W  = pipe read(T) | Time::weekday ;
+        H  = pipe read(T) | Time::to_zone("Asia/Shanghai") | Time::hour ;
+        S  = Time::diff(A, T) ;
+         "#;
+        let model = oml_parse_raw(&mut conf).assert("oml_conf");
+        let target = model.transform(src, cache);
+
+        let time = |name: &str, t: NaiveDateTime| Some(DataField::from_time(name, t));
+        // 未带时区的时间按 UTC+8 解释
+        assert_eq!(target.field("P").cloned(), time("P", at(10, 23, 45, 30)));
+        assert_eq!(target.field("P2").cloned(), time("P2", at(10, 23, 45, 30)));
+        assert_eq!(target.field("D").cloned(), time("D", at(10, 0, 0, 0)));
+        assert!(!matches!(
+            target.field("X").map(|x| x.get_value()),
+            Some(Value::Time(_))
+        ));
+        assert_eq!(
+            target.field("F").cloned(),
+            Some(DataField::from_chars("F", "2024-03-10 23:45:30"))
+        );
+        assert_eq!(
+            target.field("F2").cloned(),
+            Some(DataField::from_chars("F2", "20240310"))
+        );
+        assert_eq!(
+            target.field("F3").cloned(),
+            Some(DataField::from_chars("F3", "23:45 +0800"))
+        );
+        assert_eq!(target.field("TH").cloned(), time("TH", at(10, 23, 0, 0)));
+        assert_eq!(target.field("TD").cloned(), time("TD", at(10, 0, 0, 0)));
+        assert_eq!(target.field("A").cloned(), time("A", at(11, 1, 15, 30)));
+        assert_eq!(target.field("Z").cloned(), time("Z", at(11, 0, 45, 30)));
+        // 纽约当日已进入夏令时（UTC-4）
+        assert_eq!(target.field("Z2").cloned(), time("Z2", at(11, 3, 45, 30)));
+        assert_eq!(
+            target.field("W").cloned(),
+            Some(DataField::from_digit("W", 7))
+        );
+        assert_eq!(
+            target.field("H").cloned(),
+            Some(DataField::from_digit("H", 23))
+        );
+        assert_eq!(
+            target.field("S").cloned(),
+            Some(DataField::from_digit("S", 5400))
+        );
+    }
+
+    #[test]
+    fn test_time_parse_to_ts() {
+        let cache = &mut FieldQueryCache::default();
+        let src = DataRecord {
+            items: vec![
+                DataField::from_chars("cn", "10/Mar/2024:23:45:30 +0800"),
+                DataField::from_chars("ny", "10/Mar/2024:11:45:30 -0400"),
+                DataField::from_chars("utc", "2024-03-10T15:45:30+00:00"),
+            ],
+        };
+        let mut conf = r#"
+        name : test
+        ---
+        A = pipe read(cn) | Time::parse("%d/%b/%Y:%H:%M:%S %z") | Time::to_ts ;
+        B = pipe read(ny) | Time::parse("%d/%b/%Y:%H:%M:%S %z") | Time::to_ts ;
+        C = pipe read(utc) | Time::parse("%Y-%m-%dT%H:%M:%S%:z") | Time::to_ts ;
+        D = pipe read(utc) | Time::parse("%Y-%m-%dT%H:%M:%S%:z") | Time::format("%Y-%m-%dT%H:%M:%S%:z", "UTC") ;
+         "#;
+        let model = oml_parse_raw(&mut conf).assert("oml_conf");
+        let target = model.transform(src, cache);
+
+        // 2024-03-10T15:45:30Z
+        let ts = 1710085530;
+        for name in ["A", "B", "C"] {
+            assert_eq!(
+                target.field(name).cloned(),
+                Some(DataField::from_digit(name, ts)),
+                "{}",
+                name
+            );
+        }
+        assert_eq!(
+            target.field("D").cloned(),
+            Some(DataField::from_chars("D", "2024-03-10T15:45:30+00:00"))
+        );
+    }
 }
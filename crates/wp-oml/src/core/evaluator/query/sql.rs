@@ -22,139 +22,72 @@ impl FieldExtractor for SqlQuery {
         dst: &DataRecord,
         cache: &mut FieldQueryCache,
     ) -> Vec<DataField> {
-        let mut params = Vec::with_capacity(5);
         let target = EvaluationTarget::auto_default();
+        let mut binds = Vec::with_capacity(self.vars().len());
         for (v, acq) in self.vars() {
-            if let Some(mut tdo) = acq.extract_one(&target, src, dst) {
-                tdo.set_name(format!(":{}", v));
-                params.push(tdo);
+            if let Some(tdo) = acq.extract_one(&target, src, dst) {
+                binds.push((v.as_str(), tdo));
             }
         }
-        debug_kdb!("pararms:{:#?}", params);
-        let sql = self.oml_sql();
-        debug_kdb!("[sql] {}", sql);
-        for (v, acq) in self.vars() {
-            let preview = acq.diy_fmt(&wp_data_fmt::SqlInsert::new_with_json("_"));
-            debug_kdb!("[param] :{} = {}", v, preview);
-        }
-        let md5 = DataField::from_chars("sql".to_string(), self.sql_md5().clone());
-        // 规范化缓存键：仅保留 Value/名称，Meta 统一为 Auto，减少“同值不同 meta”导致的缓存碎片
-        fn norm(f: &DataField) -> DataField {
-            use wp_model_core::model::DataType;
-            DataField::new(DataType::default(), f.clone_name(), f.get_value().clone())
-        }
+        // 按参数名排序，缓存键与 HashMap 的遍历顺序无关
+        binds.sort_by(|a, b| a.0.cmp(b.0));
+        let query = BoundSql::new(self, binds);
+        debug_kdb!("[sql] {}", query.sql);
+        debug_kdb!("[sql] params: {:?}", query.params);
+        let named = query.params.to_params();
+        let out = kdb::cache_query(&query.sql, &query.keys, &named, cache);
+        debug_kdb!("[sql] got {} cols", out.len());
+        out
+    }
+    fn support_batch(&self) -> bool {
+        true
+    }
+}
 
-        match params.len() {
-            0 => {
-                let c_params: [DataField; 1] = [norm(&md5)];
-                // 无命名参数：传递空切片
-                let out = kdb::cache_query(sql, &c_params, &[], cache);
-                debug_kdb!("[sql] got {} cols", out.len());
-                out
-            }
+/// 一次求值的绑定结果，参数个数不限；
+/// `in (:x)` 绑定数组时按长度展开为 `in (:x_0, :x_1, ...)`，空数组展开为 `in ()`。
+struct BoundSql {
+    sql: String,
+    // 缓存键：sql 摘要与各参数值，数组参数前追加长度以区分不同的展开
+    keys: Vec<DataField>,
+    params: Vec<SqlNamedParam>,
+}
 
-            1 => {
-                let c_params: [DataField; 2] = [norm(&md5), norm(&params[0])];
-                let _p0 = params.remove(0);
-                let q_params: [SqlNamedParam; 1] = [SqlNamedParam(c_params[1].clone())];
-                let q_p = &q_params.to_params();
-                let out = kdb::cache_query(sql, &c_params, q_p, cache);
-                debug_kdb!("[sql] got {} cols", out.len());
-                out
-            }
-            2 => {
-                let c_params: [DataField; 3] = [norm(&md5), norm(&params[1]), norm(&params[0])];
-                let _ = (params.remove(1), params.remove(0));
-                let q_params: [SqlNamedParam; 2] = [
-                    SqlNamedParam(c_params[1].clone()),
-                    SqlNamedParam(c_params[2].clone()),
-                ];
-                let q_p = &q_params.to_params();
-                let out = kdb::cache_query(sql, &c_params, q_p, cache);
-                debug_kdb!("[sql] got {} cols", out.len());
-                out
-            }
-            3 => {
-                let c_params: [DataField; 4] = [
-                    norm(&md5),
-                    norm(&params[2]),
-                    norm(&params[1]),
-                    norm(&params[0]),
-                ];
-                let _ = (params.remove(2), params.remove(1), params.remove(0));
-                let q_params: [SqlNamedParam; 3] = [
-                    SqlNamedParam(c_params[1].clone()),
-                    SqlNamedParam(c_params[2].clone()),
-                    SqlNamedParam(c_params[3].clone()),
-                ];
-                let q_p = &q_params.to_params();
-                let out = kdb::cache_query(sql, &c_params, q_p, cache);
-                println!("[sql] got {} cols", out.len());
-                out
-            }
-            4 => {
-                // 显式构造，避免 try_into().unwrap() 带来的运行期 panic 风险
-                let c_params: [DataField; 5] = [
-                    norm(&md5),
-                    norm(&params[3]),
-                    norm(&params[2]),
-                    norm(&params[1]),
-                    norm(&params[0]),
-                ];
-                let _ = (
-                    params.remove(3),
-                    params.remove(2),
-                    params.remove(1),
-                    params.remove(0),
-                );
-                let q_params: [SqlNamedParam; 4] = [
-                    SqlNamedParam(c_params[1].clone()),
-                    SqlNamedParam(c_params[2].clone()),
-                    SqlNamedParam(c_params[3].clone()),
-                    SqlNamedParam(c_params[4].clone()),
-                ];
-                let q_p = &q_params.to_params();
-                let out = kdb::cache_query(self.oml_sql(), &c_params, q_p, cache);
-                debug_kdb!("[sql] got {} cols", out.len());
-                out
-            }
-            5 => {
-                let c_params: [DataField; 6] = [
-                    norm(&md5),
-                    norm(&params[4]),
-                    norm(&params[3]),
-                    norm(&params[2]),
-                    norm(&params[1]),
-                    norm(&params[0]),
-                ];
-                let _ = (
-                    params.remove(4),
-                    params.remove(3),
-                    params.remove(2),
-                    params.remove(1),
-                    params.remove(0),
-                );
-                let q_params: [SqlNamedParam; 5] = [
-                    SqlNamedParam(c_params[1].clone()),
-                    SqlNamedParam(c_params[2].clone()),
-                    SqlNamedParam(c_params[3].clone()),
-                    SqlNamedParam(c_params[4].clone()),
-                    SqlNamedParam(c_params[5].clone()),
-                ];
-                let q_p = &q_params.to_params();
-                let out = kdb::cache_query(self.oml_sql(), &c_params, q_p, cache);
-                debug_kdb!("[sql] got {} cols", out.len());
-                out
-            }
-            _ => {
-                error_data!("not support more 9 params in sql eval: {}", params.len());
-                //unimplemented!("not support more 9 params len ")
-                Vec::new()
+impl BoundSql {
+    fn new(query: &SqlQuery, binds: Vec<(&str, DataField)>) -> Self {
+        let mut bound = Self {
+            sql: query.oml_sql().clone(),
+            keys: vec![DataField::from_chars("sql", query.sql_md5().as_str())],
+            params: Vec::with_capacity(binds.len()),
+        };
+        for (name, field) in binds {
+            let in_list = format!("in (:{})", name);
+            match field.get_value() {
+                Value::Array(items) if bound.sql.contains(&in_list) => {
+                    let holders: Vec<String> = (0..items.len())
+                        .map(|i| format!(":{}_{}", name, i))
+                        .collect();
+                    bound.sql = bound
+                        .sql
+                        .replace(&in_list, &format!("in ({})", holders.join(", ")));
+                    bound
+                        .keys
+                        .push(DataField::from_digit(name, items.len() as i64));
+                    for (holder, item) in holders.into_iter().zip(items) {
+                        bound.bind(holder, item);
+                    }
+                }
+                _ => bound.bind(format!(":{}", name), &field),
             }
         }
+        bound
     }
-    fn support_batch(&self) -> bool {
-        true
+
+    // 缓存键只取值，meta 统一为 auto，减少“同值不同 meta”导致的缓存碎片
+    fn bind(&mut self, holder: String, field: &DataField) {
+        let param = DataField::new(DataType::default(), holder, field.get_value().clone());
+        self.keys.push(param.clone());
+        self.params.push(SqlNamedParam(param));
     }
 }
 
@@ -288,22 +221,29 @@ mod tests {
     }
 
     #[test]
-    fn test_too_many_params_query() {
+    fn test_many_params_query() {
         ensure_provider();
         let cache = &mut FieldQueryCache::default();
 
-        let params = vec![
-            ("p1", DataField::from_digit("p1".to_string(), 1)),
-            ("p2", DataField::from_digit("p2".to_string(), 2)),
-            ("p3", DataField::from_digit("p3".to_string(), 3)),
-            ("p4", DataField::from_digit("p4".to_string(), 4)),
-            ("p5", DataField::from_digit("p5".to_string(), 5)),
-            ("p6", DataField::from_digit("p6".to_string(), 6)),
-        ];
-
+        let params: Vec<(String, DataField)> = (1..=9)
+            .map(|i| {
+                (
+                    format!("p{}", i),
+                    DataField::from_digit(format!("p{}", i), i),
+                )
+            })
+            .collect();
+        let holders: Vec<String> = params
+            .iter()
+            .map(|(name, _)| format!(":{}", name))
+            .collect();
+        let sql = format!("SELECT * FROM test WHERE id IN ({})", holders.join(", "));
         let query = create_test_query(
-            "SELECT * FROM test WHERE id IN (:p1, :p2, :p3, :p4, :p5, :p6)",
-            params,
+            sql.as_str(),
+            params
+                .iter()
+                .map(|(n, f)| (n.as_str(), f.clone()))
+                .collect(),
         );
 
         let result = query.extract_more(
@@ -311,7 +251,43 @@ mod tests {
             &DataRecord::default(),
             cache,
         );
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].get_value(), &Value::Digit(1));
+    }
+
+    #[test]
+    fn test_in_list_query() {
+        ensure_provider();
+        let mut cache = FieldQueryCache::default();
+        let ids = |items: Vec<i64>| {
+            DataField::from_arr(
+                "id".to_string(),
+                items
+                    .into_iter()
+                    .map(|x| DataField::from_digit("id".to_string(), x))
+                    .collect(),
+            )
+        };
+        let run = |field: DataField, cache: &mut FieldQueryCache| {
+            create_test_query(
+                "SELECT name FROM test WHERE id in (:id) ORDER BY id DESC",
+                vec![("id", field)],
+            )
+            .extract_more(
+                &mut DataRecordRef::from(&DataRecord::default()),
+                &DataRecord::default(),
+                cache,
+            )
+        };
 
-        assert!(result.is_empty());
+        let result = run(ids(vec![2, 9]), &mut cache);
+        assert_eq!(result, vec![DataField::from_chars("name", "test2")]);
+        let result = run(ids(vec![1, 2]), &mut cache);
+        assert_eq!(result, vec![DataField::from_chars("name", "test2")]);
+        // 标量按单值绑定
+        let result = run(DataField::from_digit("id".to_string(), 1), &mut cache);
+        assert_eq!(result, vec![DataField::from_chars("name", "test1")]);
+        // 空数组不匹配任何行
+        assert!(run(ids(vec![]), &mut cache).is_empty());
     }
 }
@@ -44,6 +44,13 @@ impl ExpEvaluator for EvalExp {
             EvalExp::Batch(x) => {
                 x.eval_proc(src, dst, cache);
             }
+            // 记录过滤由模型在遍历条目时处理（见 `DataTransformer::transform_filter`）
+            EvalExp::Filter(_) => {}
+            EvalExp::Remove(x) => {
+                x.eval_proc(src, dst, cache);
+            }
+            // 绑定块在加载期展开（见 `ObjModel::resolve_imports`），未展开时不产生字段
+            EvalExp::Use(_) => {}
         }
     }
 }
@@ -62,5 +69,13 @@ pub trait DataTransformer {
     fn transform_ref(&self, data: &DataRecord, cache: &mut FieldQueryCache) -> DataRecord {
         self.transform(data.clone(), cache)
     }
+    /// 带过滤语义的变换：记录被 `drop when` / `keep when` 丢弃时返回 `None`
+    fn transform_filter(
+        &self,
+        data: &DataRecord,
+        cache: &mut FieldQueryCache,
+    ) -> Option<DataRecord> {
+        Some(self.transform_ref(data, cache))
+    }
     fn append(&self, data: &mut DataRecord);
 }
@@ -6,6 +6,8 @@ mod base64;
 mod escape;
 mod net;
 pub mod other;
+mod privacy;
+mod string;
 mod time;
 
 impl ValueProcessor for PipeFun {
@@ -22,6 +24,13 @@ impl ValueProcessor for PipeFun {
             PipeFun::TimeToTsMs(o) => o.value_cacu(in_val),
             PipeFun::TimeToTsUs(o) => o.value_cacu(in_val),
             PipeFun::TimeToTsZone(o) => o.value_cacu(in_val),
+            PipeFun::TimeFormat(o) => o.value_cacu(in_val),
+            PipeFun::TimeParse(o) => o.value_cacu(in_val),
+            PipeFun::TimeTrunc(o) => o.value_cacu(in_val),
+            PipeFun::TimeAdd(o) => o.value_cacu(in_val),
+            PipeFun::TimeToZone(o) => o.value_cacu(in_val),
+            PipeFun::TimeWeekday(o) => o.value_cacu(in_val),
+            PipeFun::TimeHour(o) => o.value_cacu(in_val),
             PipeFun::Nth(o) => o.value_cacu(in_val),
             PipeFun::Get(o) => o.value_cacu(in_val),
             PipeFun::ToStr(o) => o.value_cacu(in_val),
@@ -31,6 +40,36 @@ impl ValueProcessor for PipeFun {
             PipeFun::PathGet(o) => o.value_cacu(in_val),
             PipeFun::UrlGet(o) => o.value_cacu(in_val),
             PipeFun::Ip4ToInt(o) => o.value_cacu(in_val),
+            PipeFun::Lower(o) => o.value_cacu(in_val),
+            PipeFun::Upper(o) => o.value_cacu(in_val),
+            PipeFun::Trim(o) => o.value_cacu(in_val),
+            PipeFun::StrLen(o) => o.value_cacu(in_val),
+            PipeFun::Substr(o) => o.value_cacu(in_val),
+            PipeFun::Replace(o) => o.value_cacu(in_val),
+            PipeFun::RegexReplace(o) => o.value_cacu(in_val),
+            PipeFun::RegexExtract(o) => o.value_cacu(in_val),
+            PipeFun::Split(o) => o.value_cacu(in_val),
+            PipeFun::Join(o) => o.value_cacu(in_val),
+            PipeFun::Pad(o) => o.value_cacu(in_val),
+            PipeFun::Md5Hash(o) => o.value_cacu(in_val),
+            PipeFun::Sha1Hash(o) => o.value_cacu(in_val),
+            PipeFun::Sha256Hash(o) => o.value_cacu(in_val),
+            PipeFun::HmacSha256(o) => o.value_cacu(in_val),
+            PipeFun::Mask(o) => o.value_cacu(in_val),
+            PipeFun::Redact(o) => o.value_cacu(in_val),
+            PipeFun::Pseudonymize(o) => o.value_cacu(in_val),
+            PipeFun::ArrMap(o) => o.value_cacu(in_val),
+            PipeFun::ArrFilter(o) => o.value_cacu(in_val),
+            PipeFun::ArrDistinct(o) => o.value_cacu(in_val),
+            PipeFun::ArrSort(o) => o.value_cacu(in_val),
+            PipeFun::ArrFirst(o) => o.value_cacu(in_val),
+            PipeFun::ArrLast(o) => o.value_cacu(in_val),
+            PipeFun::ArrContains(o) => o.value_cacu(in_val),
+            PipeFun::ObjKeys(o) => o.value_cacu(in_val),
+            PipeFun::ObjValues(o) => o.value_cacu(in_val),
+            PipeFun::ObjMerge(o) => o.value_cacu(in_val),
+            PipeFun::ObjFlatten(o) => o.value_cacu(in_val),
+            PipeFun::ObjUnflatten(o) => o.value_cacu(in_val),
         }
     }
 }
@@ -0,0 +1,347 @@
+use crate::core::diagnostics::{self, OmlIssue, OmlIssueKind};
+use crate::core::prelude::*;
+use crate::core::secret;
+use crate::language::{
+    HmacSha256, Mask, Md5Hash, Pseudonymize, REDACTED_TEXT, Redact, Sha1Hash, Sha256Hash,
+};
+use crypto::digest::Digest;
+use crypto::hmac::Hmac;
+use crypto::mac::Mac;
+use crypto::sha1::Sha1;
+use crypto::sha2::Sha256;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use wp_data_fmt::{DataFormat, Json, Raw};
+
+// 摘要/脱敏的输入文本：字符串取原文，数组/对象取 JSON 序列化，其它标量取 raw 格式
+fn plain_text(val: &Value) -> Option<String> {
+    match val {
+        Value::Chars(x) => Some(x.to_string()),
+        Value::Null => None,
+        Value::Array(_) | Value::Obj(_) => Some(Json.fmt_value(val).to_string()),
+        other => Some(Raw.fmt_value(other).to_string()),
+    }
+}
+
+fn secret_key(name: &str) -> Option<std::sync::Arc<[u8]>> {
+    let key = secret::lookup(name);
+    if key.is_none() {
+        diagnostics::push(OmlIssue::new(
+            OmlIssueKind::SecretMissing,
+            format!("secret '{}' not found", name),
+        ));
+    }
+    key
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::new(Sha256::new(), key);
+    mac.input(data);
+    mac.result().code().to_vec()
+}
+
+impl ValueProcessor for Md5Hash {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match plain_text(in_val.get_value()) {
+            Some(text) => DataField::from_chars(
+                in_val.get_name().to_string(),
+                format!("{:x}", md5::compute(text.as_bytes())),
+            ),
+            None => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for Sha1Hash {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match plain_text(in_val.get_value()) {
+            Some(text) => {
+                let mut hasher = Sha1::new();
+                hasher.input_str(&text);
+                DataField::from_chars(in_val.get_name().to_string(), hasher.result_str())
+            }
+            None => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for Sha256Hash {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match plain_text(in_val.get_value()) {
+            Some(text) => {
+                let mut hasher = Sha256::new();
+                hasher.input_str(&text);
+                DataField::from_chars(in_val.get_name().to_string(), hasher.result_str())
+            }
+            None => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for HmacSha256 {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        let Some(text) = plain_text(in_val.get_value()) else {
+            return in_val;
+        };
+        match secret_key(&self.key_env) {
+            Some(key) => DataField::from_chars(
+                in_val.get_name().to_string(),
+                hex::encode(hmac_sha256(&key, text.as_bytes())),
+            ),
+            // 密钥缺失时不输出明文
+            None => DataField::from_ignore(in_val.get_name()),
+        }
+    }
+}
+
+impl ValueProcessor for Mask {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        // 数组/对象无法按位置保留前后缀：整体替换
+        if matches!(in_val.get_value(), Value::Array(_) | Value::Obj(_)) {
+            return DataField::from_chars(in_val.get_name().to_string(), REDACTED_TEXT);
+        }
+        let Some(text) = plain_text(in_val.get_value()) else {
+            return in_val;
+        };
+        let chars: Vec<char> = text.chars().collect();
+        let count = chars.len();
+        // 长度不足以保留前后缀时整体掩码
+        let masked: String = if count <= self.keep_prefix + self.keep_suffix {
+            std::iter::repeat_n(self.mask, count).collect()
+        } else {
+            chars
+                .iter()
+                .enumerate()
+                .map(|(i, c)| {
+                    if i < self.keep_prefix || i >= count - self.keep_suffix {
+                        *c
+                    } else {
+                        self.mask
+                    }
+                })
+                .collect()
+        };
+        DataField::from_chars(in_val.get_name().to_string(), masked)
+    }
+}
+
+impl ValueProcessor for Redact {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        DataField::from_chars(in_val.get_name().to_string(), REDACTED_TEXT)
+    }
+}
+
+// 以 HMAC-SHA256(key, input || counter) 生成确定性的伪随机字节流
+struct KeyStream<'a> {
+    key: &'a [u8],
+    input: &'a [u8],
+    counter: u32,
+    block: Vec<u8>,
+    pos: usize,
+}
+
+impl<'a> KeyStream<'a> {
+    fn new(key: &'a [u8], input: &'a [u8]) -> Self {
+        Self {
+            key,
+            input,
+            counter: 0,
+            block: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    fn next_byte(&mut self) -> u8 {
+        if self.pos >= self.block.len() {
+            let mut data = self.input.to_vec();
+            data.extend_from_slice(&self.counter.to_be_bytes());
+            self.block = hmac_sha256(self.key, &data);
+            self.counter += 1;
+            self.pos = 0;
+        }
+        self.pos += 1;
+        self.block[self.pos - 1]
+    }
+
+    fn next_u16(&mut self) -> u16 {
+        u16::from_be_bytes([self.next_byte(), self.next_byte()])
+    }
+}
+
+const CJK_START: u32 = 0x4E00;
+const CJK_END: u32 = 0x9FA5;
+
+fn pseudo_ip(key: &[u8], ip: &IpAddr) -> IpAddr {
+    let input = ip.to_string();
+    let mut stream = KeyStream::new(key, input.as_bytes());
+    match ip {
+        IpAddr::V4(_) => {
+            let mut octets = [0u8; 4];
+            octets.iter_mut().for_each(|x| *x = stream.next_byte());
+            IpAddr::V4(Ipv4Addr::from(octets))
+        }
+        IpAddr::V6(_) => {
+            let mut octets = [0u8; 16];
+            octets.iter_mut().for_each(|x| *x = stream.next_byte());
+            IpAddr::V6(Ipv6Addr::from(octets))
+        }
+    }
+}
+
+// 逐字符替换并保持类别：数字、小写、大写、常用汉字；其余字符原样保留
+fn pseudo_text(key: &[u8], text: &str) -> String {
+    let mut stream = KeyStream::new(key, text.as_bytes());
+    text.chars()
+        .map(|c| match c {
+            '0'..='9' => (b'0' + stream.next_byte() % 10) as char,
+            'a'..='z' => (b'a' + stream.next_byte() % 26) as char,
+            'A'..='Z' => (b'A' + stream.next_byte() % 26) as char,
+            c if (CJK_START..=CJK_END).contains(&(c as u32)) => {
+                let offset = stream.next_u16() as u32 % (CJK_END - CJK_START + 1);
+                char::from_u32(CJK_START + offset).unwrap_or(c)
+            }
+            c => c,
+        })
+        .collect()
+}
+
+impl ValueProcessor for Pseudonymize {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        let name = in_val.get_name().to_string();
+        match in_val.get_value() {
+            Value::IpAddr(ip) => match secret_key(&self.key_env) {
+                Some(key) => DataField::from_ip(name, pseudo_ip(&key, ip)),
+                None => DataField::from_ignore(name),
+            },
+            Value::Chars(x) => match secret_key(&self.key_env) {
+                Some(key) => {
+                    let out = match x.parse::<IpAddr>() {
+                        Ok(ip) => pseudo_ip(&key, &ip).to_string(),
+                        Err(_) => pseudo_text(&key, x),
+                    };
+                    DataField::from_chars(name, out)
+                }
+                None => DataField::from_ignore(name),
+            },
+            Value::Null => in_val,
+            // 其余类型（数字、数组、对象等）按序列化文本替换，不原样输出
+            other => match (secret_key(&self.key_env), plain_text(other)) {
+                (Some(key), Some(text)) => DataField::from_chars(name, pseudo_text(&key, &text)),
+                _ => DataField::from_ignore(name),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::DataTransformer;
+    use crate::core::secret::set_env_dict;
+    use crate::parser::oml_parse_raw;
+    use orion_error::TestAssert;
+    use orion_variate::{EnvDict, ValueType};
+    use std::net::{IpAddr, Ipv4Addr};
+    use wp_data_model::cache::FieldQueryCache;
+    use wp_model_core::model::{DataField, DataRecord, Value};
+
+    fn chars_of(target: &DataRecord, name: &str) -> String {
+        match target.field(name).map(|x| x.get_value()) {
+            Some(Value::Chars(x)) => x.to_string(),
+            other => panic!("{} is not chars: {:?}", name, other),
+        }
+    }
+
+    #[test]
+    fn test_pipe_privacy_funs() {
+        let mut dict = EnvDict::new();
+        dict.insert("OML_TEST_PII_KEY", ValueType::from("k-001"));
+        set_env_dict(dict);
+
+        let cache = &mut FieldQueryCache::default();
+        let src = DataRecord {
+            items: vec![
+                DataField::from_chars("phone", "13812345678"),
+                DataField::from_chars("user", "Alice_42"),
+                DataField::from_chars("name", "张三"),
+                DataField::from_ip("src_ip", IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3))),
+                DataField::from_chars("dst_ip", "192.168.1.1"),
+                DataField::from_arr(
+                    "phones".to_string(),
+                    vec![DataField::from_chars("phones", "13812345678")],
+                ),
+            ],
+        };
+        let mut conf = r##"
+        name : test
+        ---
+        A = pipe read(phone) | md5 ;
+        B = pipe read(phone) | sha1 ;
+        C = pipe read(phone) | sha256 ;
+        D = pipe read(phone) | hmac_sha256(OML_TEST_PII_KEY) ;
+        E = pipe read(phone) | hmac_sha256(OML_TEST_PII_KEY) ;
+        F = pipe read(phone) | mask(3, 4) ;
+        G = pipe read(user) | mask(1, 0, "#") ;
+        H = pipe read(phone) | redact ;
+        I = pipe read(user) | pseudonymize(OML_TEST_PII_KEY) ;
+        J = pipe read(user) | pseudonymize(OML_TEST_PII_KEY) ;
+        K = pipe read(name) | pseudonymize(OML_TEST_PII_KEY) ;
+        L = pipe read(src_ip) | pseudonymize(OML_TEST_PII_KEY) ;
+        M = pipe read(dst_ip) | pseudonymize(OML_TEST_PII_KEY) ;
+        N = pipe read(phone) | hmac_sha256(OML_TEST_PII_MISSING) ;
+        O = pipe read(phones) | md5 ;
+        P = pipe read(phones) | hmac_sha256(OML_TEST_PII_KEY) ;
+        Q = pipe read(phones) | mask(3, 4) ;
+        R = pipe read(phones) | pseudonymize(OML_TEST_PII_KEY) ;
+        "##;
+        let model = oml_parse_raw(&mut conf).assert();
+        let target = model.transform(src, cache);
+
+        assert_eq!(
+            chars_of(&target, "A"),
+            format!("{:x}", md5::compute(b"13812345678"))
+        );
+        assert_eq!(chars_of(&target, "B").len(), 40);
+        assert_eq!(chars_of(&target, "C").len(), 64);
+        assert_eq!(chars_of(&target, "D").len(), 64);
+        assert_eq!(chars_of(&target, "D"), chars_of(&target, "E"));
+        assert_eq!(chars_of(&target, "F"), "138****5678");
+        assert_eq!(chars_of(&target, "G"), "A#######");
+        assert_eq!(chars_of(&target, "H"), "***");
+
+        let user = chars_of(&target, "I");
+        assert_eq!(user, chars_of(&target, "J"));
+        assert_ne!(user, "Alice_42");
+        let kinds = |s: &str| {
+            s.chars()
+                .map(|c| {
+                    (
+                        c.is_ascii_uppercase(),
+                        c.is_ascii_lowercase(),
+                        c.is_ascii_digit(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(kinds(&user), kinds("Alice_42"));
+        assert_eq!(user.chars().nth(5), Some('_'));
+
+        let name = chars_of(&target, "K");
+        assert_eq!(name.chars().count(), 2);
+        assert!(name.chars().all(|c| ('\u{4E00}'..='\u{9FA5}').contains(&c)));
+
+        assert!(matches!(
+            target.field("L").map(|x| x.get_value()),
+            Some(Value::IpAddr(IpAddr::V4(_)))
+        ));
+        assert!(chars_of(&target, "M").parse::<Ipv4Addr>().is_ok());
+        assert!(!matches!(
+            target.field("N").map(|x| x.get_value()),
+            Some(Value::Chars(_))
+        ));
+
+        // 数组按序列化文本摘要/脱敏，不原样透传
+        assert_eq!(chars_of(&target, "O").len(), 32);
+        assert_eq!(chars_of(&target, "P").len(), 64);
+        assert_eq!(chars_of(&target, "Q"), "***");
+        assert!(!chars_of(&target, "R").contains("13812345678"));
+    }
+}
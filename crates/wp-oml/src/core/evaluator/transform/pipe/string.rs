@@ -0,0 +1,242 @@
+use crate::core::prelude::*;
+use crate::language::{
+    Join, Lower, Pad, PadSide, RegexExtract, RegexReplace, Replace, Split, StrLen, Substr, Trim,
+    Upper,
+};
+use wp_data_fmt::{DataFormat, Raw};
+
+impl ValueProcessor for Lower {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Chars(x) => {
+                DataField::from_chars(in_val.get_name().to_string(), x.to_lowercase())
+            }
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for Upper {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Chars(x) => {
+                DataField::from_chars(in_val.get_name().to_string(), x.to_uppercase())
+            }
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for Trim {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Chars(x) => DataField::from_chars(in_val.get_name().to_string(), x.trim()),
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for StrLen {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        let len = match in_val.get_value() {
+            Value::Chars(x) => x.chars().count(),
+            Value::Array(x) => x.len(),
+            Value::Obj(x) => x.len(),
+            _ => return in_val,
+        };
+        DataField::from_digit(in_val.get_name().to_string(), len as i64)
+    }
+}
+
+impl ValueProcessor for Substr {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Chars(x) => {
+                let count = x.chars().count() as i64;
+                let start = if self.start < 0 {
+                    (count + self.start).max(0)
+                } else {
+                    self.start.min(count)
+                } as usize;
+                let sub: String = match self.len {
+                    Some(len) => x.chars().skip(start).take(len).collect(),
+                    None => x.chars().skip(start).collect(),
+                };
+                DataField::from_chars(in_val.get_name().to_string(), sub)
+            }
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for Replace {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Chars(x) if !self.from.is_empty() => DataField::from_chars(
+                in_val.get_name().to_string(),
+                x.replace(self.from.as_str(), self.to.as_str()),
+            ),
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for RegexReplace {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Chars(x) => DataField::from_chars(
+                in_val.get_name().to_string(),
+                self.pattern.replace_all(x, self.to.as_str()).to_string(),
+            ),
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for RegexExtract {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Chars(x) => {
+                let found = self
+                    .pattern
+                    .captures(x)
+                    .and_then(|cap| cap.get(self.group))
+                    .map(|m| m.as_str().to_string());
+                match found {
+                    Some(v) => DataField::from_chars(in_val.get_name().to_string(), v),
+                    None => DataField::from_ignore(in_val.get_name()),
+                }
+            }
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for Split {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Chars(x) => {
+                let name = in_val.get_name().to_string();
+                // 空分隔符按字符拆分
+                let items: Vec<DataField> = if self.sep.is_empty() {
+                    x.chars()
+                        .map(|c| DataField::from_chars(name.clone(), c.to_string()))
+                        .collect()
+                } else {
+                    x.split(self.sep.as_str())
+                        .map(|s| DataField::from_chars(name.clone(), s))
+                        .collect()
+                };
+                DataField::from_arr(name, items)
+            }
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for Join {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        match in_val.get_value() {
+            Value::Array(arr) => {
+                let raw = Raw;
+                let parts: Vec<String> = arr
+                    .iter()
+                    .map(|x| raw.fmt_value(x.get_value()).to_string())
+                    .collect();
+                DataField::from_chars(in_val.get_name().to_string(), parts.join(&self.sep))
+            }
+            _ => in_val,
+        }
+    }
+}
+
+impl ValueProcessor for Pad {
+    fn value_cacu(&self, in_val: DataField) -> DataField {
+        let text = match in_val.get_value() {
+            Value::Chars(x) => x.to_string(),
+            Value::Digit(x) => x.to_string(),
+            _ => return in_val,
+        };
+        let count = text.chars().count();
+        if count >= self.width {
+            return DataField::from_chars(in_val.get_name().to_string(), text);
+        }
+        let fill: String = std::iter::repeat_n(self.fill, self.width - count).collect();
+        let padded = match self.side {
+            PadSide::Left => format!("{}{}", fill, text),
+            PadSide::Right => format!("{}{}", text, fill),
+        };
+        DataField::from_chars(in_val.get_name().to_string(), padded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::DataTransformer;
+    use crate::parser::oml_parse_raw;
+    use orion_error::TestAssert;
+    use wp_data_model::cache::FieldQueryCache;
+    use wp_model_core::model::{DataField, DataRecord, Value};
+
+    #[test]
+    fn test_pipe_string_funs() {
+        let cache = &mut FieldQueryCache::default();
+        let src = DataRecord {
+            items: vec![
+                DataField::from_chars("ua", "  Mozilla/5.0 (X11; Linux)  "),
+                DataField::from_chars("path", "/api/v1/users/42"),
+                DataField::from_chars("tags", "a,b,,c"),
+                DataField::from_digit("code", 7),
+                DataField::from_chars("host", "中文主机"),
+            ],
+        };
+        let mut conf = r##"
+        name : test
+        ---
+        A = pipe read(ua) | trim | lower ;
+        B = pipe read(ua) | trim | upper | substr(0, 7) ;
+        C = pipe read(path) | substr(-2) ;
+        D = pipe read(path) | replace("/", ".") ;
+        E = pipe read(path) | regex_replace(r#"\d+"#, "{id}") ;
+        F = pipe read(ua) | regex_extract(r#"Mozilla/(\d+\.\d+)"#, 1) ;
+        G = pipe read(path) | regex_extract("nomatch", 0) ;
+        H = pipe read(tags) | split(",") | len ;
+        I = pipe read(tags) | split(",") | join("|") ;
+        J = pipe read(code) | pad(3, "0") ;
+        K = pipe read(code) | pad(3, "*", right) ;
+        L = pipe read(host) | len ;
+        M = pipe read(tags) | split(",") | nth(3) ;
+        "##;
+        let model = oml_parse_raw(&mut conf).assert();
+        let target = model.transform(src, cache);
+        let chars = |name: &str, val: &str| Some(DataField::from_chars(name, val));
+        assert_eq!(
+            target.field("A").cloned(),
+            chars("A", "mozilla/5.0 (x11; linux)")
+        );
+        assert_eq!(target.field("B").cloned(), chars("B", "MOZILLA"));
+        assert_eq!(target.field("C").cloned(), chars("C", "42"));
+        assert_eq!(target.field("D").cloned(), chars("D", ".api.v1.users.42"));
+        assert_eq!(
+            target.field("E").cloned(),
+            chars("E", "/api/v{id}/users/{id}")
+        );
+        assert_eq!(target.field("F").cloned(), chars("F", "5.0"));
+        assert!(!matches!(
+            target.field("G").map(|x| x.get_value()),
+            Some(Value::Chars(_))
+        ));
+        assert_eq!(
+            target.field("H").cloned(),
+            Some(DataField::from_digit("H", 4))
+        );
+        assert_eq!(target.field("I").cloned(), chars("I", "a|b||c"));
+        assert_eq!(target.field("J").cloned(), chars("J", "007"));
+        assert_eq!(target.field("K").cloned(), chars("K", "7**"));
+        assert_eq!(
+            target.field("L").cloned(),
+            Some(DataField::from_digit("L", 4))
+        );
+        assert_eq!(target.field("M").cloned(), chars("M", "c"));
+    }
+}
@@ -3,6 +3,8 @@ mod error;
 mod evaluator;
 mod model;
 mod prelude;
+pub mod schema;
+pub mod secret;
 pub use error::OMLRunError;
 pub use error::OMLRunReason;
 pub use error::OMLRunResult;
@@ -58,6 +60,8 @@ impl FieldExtractor for PreciseEvaluator {
             PreciseEvaluator::Fun(o) => o.extract_one(target, src, dst),
             PreciseEvaluator::Fmt(o) => o.extract_one(target, src, dst),
             PreciseEvaluator::Collect(o) => o.extract_one(target, src, dst),
+            PreciseEvaluator::Calc(o) => o.extract_one(target, src, dst),
+            PreciseEvaluator::If(o) => o.extract_one(target, src, dst),
             PreciseEvaluator::Val(o) => o.extract_one(target, src, dst),
         }
     }
@@ -78,6 +82,8 @@ impl FieldExtractor for PreciseEvaluator {
             PreciseEvaluator::Fun(o) => o.extract_more(src, dst, cache),
             PreciseEvaluator::Fmt(o) => o.extract_more(src, dst, cache),
             PreciseEvaluator::Collect(o) => o.extract_more(src, dst, cache),
+            PreciseEvaluator::Calc(o) => o.extract_more(src, dst, cache),
+            PreciseEvaluator::If(o) => o.extract_more(src, dst, cache),
             PreciseEvaluator::Val(o) => o.extract_more(src, dst, cache),
         }
     }
@@ -93,6 +99,8 @@ impl FieldExtractor for PreciseEvaluator {
             PreciseEvaluator::Fun(o) => o.support_batch(),
             PreciseEvaluator::Fmt(o) => o.support_batch(),
             PreciseEvaluator::Collect(o) => o.support_batch(),
+            PreciseEvaluator::Calc(o) => o.support_batch(),
+            PreciseEvaluator::If(o) => o.support_batch(),
             PreciseEvaluator::Val(o) => o.support_batch(),
         }
     }
@@ -0,0 +1,301 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::super::ConfADMExt;
+use crate::core::prelude::*;
+use crate::language::{ObjModel, OmlBlock, OmlLibrary};
+use crate::parser::error::OMLCodeErrorTait;
+use crate::parser::oml_parse_lib_raw;
+use orion_error::{ContextRecord, ErrorOwe, ErrorWith, WithContext};
+use wp_error::parse_error::{OMLCodeError, OMLCodeReason, OMLCodeResult};
+use wp_parser::comment::CommentParser;
+
+impl ConfADMExt for OmlLibrary {
+    fn load(path: &str) -> OMLCodeResult<Self>
+    where
+        Self: Sized,
+    {
+        let mut ctx = WithContext::want("load oml library");
+        ctx.record("path", path);
+        let content = std::fs::read_to_string(path)
+            .owe(OMLCodeReason::NotFound("oml library load fail".into()))
+            .with(&ctx)?;
+        let mut raw_code = content.as_str();
+        let code = CommentParser::ignore_comment(&mut raw_code)
+            .map_err(|e| OMLCodeError::from_syntax(e, raw_code, path))?;
+        let mut pure_code = code.as_str();
+        match oml_parse_lib_raw(&mut pure_code) {
+            Ok(res) => Ok(res),
+            // 错误位置指向库文件本身
+            Err(e) => Err(OMLCodeError::from_syntax(e, pure_code, path)).with(&ctx),
+        }
+    }
+}
+
+impl ObjModel {
+    /// 加载 `import` 引入的库并展开 `use <block> ;`
+    ///
+    /// `path` 为模型文件路径，相对路径按引用方所在目录解析；导入链或块引用成环时报错。
+    pub fn resolve_imports(&mut self, path: &str) -> OMLCodeResult<()> {
+        let mut resolver = ImportResolver::default();
+        let origin = file_key(Path::new(path));
+        resolver.stack.push(origin.clone());
+        for target in self.imports().clone() {
+            resolver.import(&origin, &target)?;
+        }
+        let items = std::mem::take(&mut self.items);
+        self.items = resolver.expand(items, &mut Vec::new())?;
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct ImportResolver {
+    // 当前导入链，用于环检测
+    stack: Vec<PathBuf>,
+    // 已加载的库，菱形依赖只加载一次
+    loaded: HashSet<PathBuf>,
+    blocks: HashMap<String, (OmlBlock, PathBuf)>,
+}
+
+impl ImportResolver {
+    fn import(&mut self, from: &Path, target: &str) -> OMLCodeResult<()> {
+        let mut ctx = WithContext::want("import oml library");
+        ctx.record("from", from.display().to_string());
+        ctx.record("import", target);
+        let dir = from.parent().unwrap_or(Path::new("."));
+        let path = file_key(&dir.join(target));
+        if self.stack.contains(&path) {
+            let chain = self
+                .stack
+                .iter()
+                .chain(std::iter::once(&path))
+                .map(|x| x.display().to_string())
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            return Err(OMLCodeError::from(OMLCodeReason::Syntax(format!(
+                "import cycle: {}",
+                chain
+            ))))
+            .with(&ctx);
+        }
+        if !self.loaded.insert(path.clone()) {
+            return Ok(());
+        }
+        let lib = OmlLibrary::load(&path.display().to_string()).with(&ctx)?;
+
+        self.stack.push(path.clone());
+        for sub in lib.imports() {
+            self.import(&path, sub)?;
+        }
+        self.stack.pop();
+
+        for block in lib.into_blocks() {
+            if let Some((_, first)) = self.blocks.get(block.name()) {
+                return Err(OMLCodeError::from(OMLCodeReason::Syntax(format!(
+                    "duplicate block '{}': {} and {}",
+                    block.name(),
+                    first.display(),
+                    path.display()
+                ))))
+                .with(&ctx);
+            }
+            self.blocks
+                .insert(block.name().clone(), (block, path.clone()));
+        }
+        Ok(())
+    }
+
+    fn expand(&self, items: Vec<EvalExp>, using: &mut Vec<String>) -> OMLCodeResult<Vec<EvalExp>> {
+        let mut out = Vec::with_capacity(items.len());
+        for item in items {
+            let EvalExp::Use(x) = item else {
+                out.push(item);
+                continue;
+            };
+            let Some((block, path)) = self.blocks.get(x.name()) else {
+                return Err(OMLCodeError::from(OMLCodeReason::NotFound(format!(
+                    "block '{}' not found in imports",
+                    x.name()
+                ))));
+            };
+            if using.iter().any(|u| u == x.name()) {
+                return Err(OMLCodeError::from(OMLCodeReason::Syntax(format!(
+                    "block cycle: {} -> {} ({})",
+                    using.join(" -> "),
+                    x.name(),
+                    path.display()
+                ))));
+            }
+            using.push(x.name().to_string());
+            out.extend(self.expand(block.items().clone(), using)?);
+            using.pop();
+        }
+        Ok(out)
+    }
+}
+
+// 规范化路径用于环检测与去重，文件不存在时保留原路径，由加载报错
+fn file_key(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{ConfADMExt, DataTransformer};
+    use crate::language::ObjModel;
+    use std::path::PathBuf;
+    use wp_data_model::cache::FieldQueryCache;
+    use wp_model_core::model::{DataField, DataRecord};
+
+    fn work_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("oml_import_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        for (path, code) in files {
+            let file = root.join(path);
+            std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+            std::fs::write(file, code).unwrap();
+        }
+        root
+    }
+
+    fn load(root: &PathBuf) -> Result<ObjModel, String> {
+        ObjModel::load(root.join("main.oml").to_str().unwrap()).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_import_blocks() {
+        let root = work_dir(
+            "blocks",
+            &[
+                (
+                    "main.oml",
+                    r#"
+name : main
+import "lib/ecs.oml" ;
+import "lib/geo.oml" ;
+---
+use ecs_http ;
+host : chars = take(host) ;
+"#,
+                ),
+                (
+                    "lib/ecs.oml",
+                    r#"
+import "base.oml" ;
+block ecs_http {
+    http.method : chars = take(method) ;
+    use ecs_base ;
+}
+"#,
+                ),
+                (
+                    "lib/geo.oml",
+                    r#"
+import "base.oml" ;
+block geo { city : chars = chars(bj) ; }
+"#,
+                ),
+                (
+                    "lib/base.oml",
+                    r#"
+// 公共字段
+block ecs_base {
+    ecs.version : chars = chars(8.11) ;
+    drop when method = "OPTIONS" ;
+}
+"#,
+                ),
+            ],
+        );
+        let model = load(&root).expect("load model");
+        assert_eq!(model.items.len(), 4);
+
+        let cache = &mut FieldQueryCache::default();
+        let src = DataRecord {
+            items: vec![
+                DataField::from_chars("method", "GET"),
+                DataField::from_chars("host", "a.com"),
+            ],
+        };
+        let target = model.transform_filter(&src, cache).expect("kept");
+        assert_eq!(
+            target.field("http.method"),
+            Some(&DataField::from_chars("http.method", "GET"))
+        );
+        assert_eq!(
+            target.field("ecs.version"),
+            Some(&DataField::from_chars("ecs.version", "8.11"))
+        );
+        assert_eq!(
+            target.field("host"),
+            Some(&DataField::from_chars("host", "a.com"))
+        );
+        assert_eq!(target.field("city"), None);
+
+        let src = DataRecord {
+            items: vec![DataField::from_chars("method", "OPTIONS")],
+        };
+        assert!(model.transform_filter(&src, cache).is_none());
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn test_import_errors() {
+        let root = work_dir(
+            "cycle",
+            &[
+                (
+                    "main.oml",
+                    "name : main\nimport \"a.oml\" ;\n---\nuse a ;\n",
+                ),
+                (
+                    "a.oml",
+                    "import \"b.oml\" ;\nblock a { x : chars = take(x) ; }\n",
+                ),
+                ("b.oml", "import \"a.oml\" ;\n"),
+            ],
+        );
+        let e = load(&root).unwrap_err();
+        assert!(e.contains("import cycle"), "{}", e);
+        let _ = std::fs::remove_dir_all(root);
+
+        let root = work_dir(
+            "use_cycle",
+            &[
+                (
+                    "main.oml",
+                    "name : main\nimport \"a.oml\" ;\n---\nuse a ;\n",
+                ),
+                ("a.oml", "block a { use b ; }\nblock b { use a ; }\n"),
+            ],
+        );
+        let e = load(&root).unwrap_err();
+        assert!(e.contains("block cycle: a -> b -> a"), "{}", e);
+        let _ = std::fs::remove_dir_all(root);
+
+        let root = work_dir(
+            "missing",
+            &[("main.oml", "name : main\n---\nuse nothing ;\n")],
+        );
+        let e = load(&root).unwrap_err();
+        assert!(e.contains("block 'nothing' not found"), "{}", e);
+        let _ = std::fs::remove_dir_all(root);
+
+        // 语法错误定位到被引入的库文件
+        let root = work_dir(
+            "syntax",
+            &[
+                (
+                    "main.oml",
+                    "name : main\nimport \"lib/bad.oml\" ;\n---\nuse a ;\n",
+                ),
+                ("lib/bad.oml", "block a { x : chars = take(x) ;\n"),
+            ],
+        );
+        let e = load(&root).unwrap_err();
+        assert!(e.contains("bad.oml"), "{}", e);
+        assert!(e.contains("block <name> { <bindings> }"), "{}", e);
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
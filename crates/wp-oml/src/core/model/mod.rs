@@ -1,3 +1,4 @@
+mod import;
 mod object;
 mod record;
 mod types;
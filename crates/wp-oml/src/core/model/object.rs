@@ -17,14 +17,28 @@ impl DataTransformer for ObjModel {
     }
 
     fn transform_ref(&self, data: &DataRecord, cache: &mut FieldQueryCache) -> DataRecord {
+        self.transform_filter(data, cache).unwrap_or_default()
+    }
+
+    fn transform_filter(
+        &self,
+        data: &DataRecord,
+        cache: &mut FieldQueryCache,
+    ) -> Option<DataRecord> {
         diagnostics::reset();
         let mut out = DataRecord::default();
         let mut tdo_ref = DataRecordRef::from(data);
         for ado in &self.items {
+            if let EvalExp::Filter(x) = ado
+                && x.rejects(&mut tdo_ref, &out)
+            {
+                debug_data!("{} filter out record", self.name());
+                return None;
+            }
             ado.eval_proc(&mut tdo_ref, &mut out, cache);
         }
         debug_data!("{} convert crate item : {}", self.name(), self.items.len());
-        out
+        Some(out)
     }
 
     fn append(&self, data: &mut DataRecord) {
@@ -52,9 +66,11 @@ impl ConfADMExt for ObjModel {
         let code = CommentParser::ignore_comment(&mut raw_code)
             .map_err(|e| OMLCodeError::from_syntax(e, raw_code, path))?;
         let mut pure_code = code.as_str();
-        match oml_parse_raw(&mut pure_code) {
-            Ok(res) => Ok(res),
-            Err(e) => Err(OMLCodeError::from_syntax(e, pure_code, path)).with(&ctx),
-        }
+        let mut model = match oml_parse_raw(&mut pure_code) {
+            Ok(res) => res,
+            Err(e) => return Err(OMLCodeError::from_syntax(e, pure_code, path)).with(&ctx),
+        };
+        model.resolve_imports(path).with(&ctx)?;
+        Ok(model)
     }
 }
@@ -28,6 +28,17 @@ impl DataTransformer for DataModel {
         }
     }
 
+    fn transform_filter(
+        &self,
+        data: &DataRecord,
+        cache: &mut FieldQueryCache,
+    ) -> Option<DataRecord> {
+        match self {
+            DataModel::Stub(null_model) => null_model.transform_filter(data, cache),
+            DataModel::Object(obj_model) => obj_model.transform_filter(data, cache),
+        }
+    }
+
     fn append(&self, data: &mut DataRecord) {
         match self {
             DataModel::Stub(null_model) => null_model.append(data),
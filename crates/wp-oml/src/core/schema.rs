@@ -0,0 +1,463 @@
+//! OML 模型与 WPL 规则输出字段的静态校验。
+//! - 未知字段：`take`/`read`/条件引用的字段既不由规则输出、也不由前面的绑定产生；
+//! - 类型不符：源字段类型无法转换为绑定声明的类型；
+//! - 不可达分支：`match` 分支常量与源字段类型不同，或与前面的分支重复。
+
+use std::collections::BTreeMap;
+
+use crate::language::{
+    CalcExpr, CondAccessor, DirectAccessor, EvalExp, LogicalExpression, MatchCond, MatchCondition,
+    MatchOperation, MatchSource, NestedAccessor, ObjModel, PreciseEvaluator, RecordFilter,
+    RecordOperation, SingleEvalExp,
+};
+use wp_model_core::model::DataType;
+use wpl::WplSchema;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaIssueKind {
+    UnknownField,   // 引用的字段不存在
+    TypeMismatch,   // 源字段类型无法转换为目标类型
+    UnreachableArm, // match 分支永远不会命中
+}
+
+#[derive(Debug, Clone)]
+pub struct SchemaIssue {
+    pub kind: SchemaIssueKind,
+    /// 出问题的绑定目标，如 `sip`、`drop`
+    pub target: String,
+    pub detail: String,
+}
+
+impl SchemaIssue {
+    pub fn new(
+        kind: SchemaIssueKind,
+        target: impl Into<String>,
+        detail: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind,
+            target: target.into(),
+            detail: detail.into(),
+        }
+    }
+    pub fn to_brief(&self) -> String {
+        match self.kind {
+            SchemaIssueKind::UnknownField => {
+                format!("unknown_field: {}: {}", self.target, self.detail)
+            }
+            SchemaIssueKind::TypeMismatch => {
+                format!("type_mismatch: {}: {}", self.target, self.detail)
+            }
+            SchemaIssueKind::UnreachableArm => {
+                format!("unreachable_arm: {}: {}", self.target, self.detail)
+            }
+        }
+    }
+}
+
+/// 按 WPL 规则的输出字段校验模型（`use` 已在加载时展开）
+pub fn check_model(model: &ObjModel, schema: &WplSchema) -> Vec<SchemaIssue> {
+    let mut checker = SchemaChecker {
+        schema,
+        produced: BTreeMap::new(),
+        issues: Vec::new(),
+    };
+    for item in &model.items {
+        checker.check_item(item);
+    }
+    checker.issues
+}
+
+// 源字段：名称与类型，类型在运行期才确定时为 None
+type SrcField = (String, Option<DataType>);
+
+struct SchemaChecker<'a> {
+    schema: &'a WplSchema,
+    // 前面绑定已产生的目标字段，`read` 与条件可读取
+    produced: BTreeMap<String, DataType>,
+    issues: Vec<SchemaIssue>,
+}
+
+impl SchemaChecker<'_> {
+    fn check_item(&mut self, item: &EvalExp) {
+        match item {
+            EvalExp::Single(x) => self.check_single(x),
+            EvalExp::Filter(x) => {
+                let label = match x {
+                    RecordFilter::Drop(_) => "drop",
+                    RecordFilter::Keep(_) => "keep",
+                };
+                self.check_cond(label, x.cond());
+            }
+            EvalExp::Remove(x) => self.produced.retain(|name, _| !x.matches(name)),
+            // 批量绑定按通配名取字段；`use` 在加载时已展开
+            EvalExp::Batch(_) | EvalExp::Use(_) => {}
+        }
+    }
+
+    fn check_single(&mut self, exp: &SingleEvalExp) {
+        let targets = exp.target();
+        let label = targets
+            .iter()
+            .map(|x| x.safe_name())
+            .collect::<Vec<_>>()
+            .join(",");
+        let src = self.check_eval(&label, exp.eval_way());
+        // 多目标（SQL 查询）不推导类型
+        let src_type = match (targets.len(), src) {
+            (1, Some((name, Some(meta)))) => Some((name, meta)),
+            _ => None,
+        };
+        for target in targets {
+            let mut meta = target.data_type().clone();
+            if let Some((name, src_meta)) = &src_type {
+                if !convertible(src_meta, &meta) {
+                    self.push(
+                        SchemaIssueKind::TypeMismatch,
+                        target.safe_name(),
+                        format!("'{}' is {}, can not convert to {}", name, src_meta, meta),
+                    );
+                }
+                if meta == DataType::Auto {
+                    meta = src_meta.clone();
+                }
+            }
+            self.produced.insert(target.safe_name(), meta);
+        }
+    }
+
+    fn check_eval(&mut self, label: &str, eval: &PreciseEvaluator) -> Option<SrcField> {
+        match eval {
+            PreciseEvaluator::Tdc(x) => self.check_record(label, x),
+            // 以下取值会改变类型，只校验引用的字段
+            PreciseEvaluator::Pipe(x) => {
+                self.check_direct(label, x.from(), false);
+                None
+            }
+            PreciseEvaluator::Collect(x) => {
+                self.check_direct(label, x.dat_crate(), false);
+                None
+            }
+            PreciseEvaluator::Match(x) => {
+                self.check_match(label, x);
+                None
+            }
+            PreciseEvaluator::Map(x) => {
+                for sub in x.subs() {
+                    self.check_nested(&sub.target().safe_name(), sub.acquirer());
+                }
+                None
+            }
+            PreciseEvaluator::Fmt(x) => {
+                for sub in x.subs() {
+                    self.check_record(label, sub);
+                }
+                None
+            }
+            PreciseEvaluator::Calc(x) => {
+                self.check_calc(label, x.expr());
+                None
+            }
+            PreciseEvaluator::If(x) => {
+                self.check_cond(label, x.cond());
+                self.check_eval(label, x.then());
+                if let Some(other) = x.other() {
+                    self.check_eval(label, other);
+                }
+                None
+            }
+            PreciseEvaluator::Sql(x) => {
+                for acc in x.vars().values() {
+                    self.check_cond_acc(label, acc);
+                }
+                None
+            }
+            PreciseEvaluator::Obj(_) | PreciseEvaluator::Fun(_) | PreciseEvaluator::Val(_) => None,
+        }
+    }
+
+    fn check_record(&mut self, label: &str, op: &RecordOperation) -> Option<SrcField> {
+        self.check_direct(label, op.dat_get(), op.default_val().is_some())
+    }
+
+    // 未指定字段名时按目标名取值；有缺省值或规则输出不确定时不报未知字段
+    fn check_direct(
+        &mut self,
+        label: &str,
+        acc: &DirectAccessor,
+        has_default: bool,
+    ) -> Option<SrcField> {
+        let (get, option, collect, default_acq, read) = match acc {
+            DirectAccessor::Take(x) => (x.get(), x.option(), x.collect(), x.default_acq(), false),
+            DirectAccessor::Read(x) => (x.get(), x.option(), x.collect(), x.default_acq(), true),
+        };
+        // 通配收集在运行期按名匹配
+        if !collect.is_empty() {
+            return None;
+        }
+        let name = get
+            .clone()
+            .unwrap_or_else(|| label.split(',').next().unwrap_or_default().to_string());
+        if name.contains('*') {
+            return None;
+        }
+        for key in std::iter::once(&name).chain(option.iter()) {
+            if let Some(meta) = self.lookup(key, read) {
+                let meta = (meta != DataType::Auto).then_some(meta);
+                return Some((key.clone(), meta));
+            }
+        }
+        if !has_default && default_acq.is_none() {
+            self.unknown(label, &name);
+        }
+        None
+    }
+
+    // read 先取已生成的目标字段，take 只取源记录
+    fn lookup(&self, name: &str, read: bool) -> Option<DataType> {
+        if read && let Some(meta) = self.produced.get(name) {
+            return Some(meta.clone());
+        }
+        self.schema.field_type(name).cloned()
+    }
+
+    fn unknown(&mut self, label: &str, name: &str) {
+        if !self.schema.is_open() {
+            self.push(
+                SchemaIssueKind::UnknownField,
+                label,
+                format!("field '{}' is not produced by the wpl rule", name),
+            );
+        }
+    }
+
+    fn check_nested(&mut self, label: &str, acc: &NestedAccessor) {
+        match acc {
+            NestedAccessor::Direct(x) => {
+                self.check_record(label, x);
+            }
+            NestedAccessor::Collect(x) => {
+                self.check_direct(label, x.dat_crate(), false);
+            }
+            NestedAccessor::Field(_) | NestedAccessor::Fun(_) => {}
+        }
+    }
+
+    fn check_calc(&mut self, label: &str, expr: &CalcExpr) {
+        match expr {
+            CalcExpr::Get(x) => {
+                self.check_record(label, x);
+            }
+            CalcExpr::Unary(_, x) => self.check_calc(label, x),
+            CalcExpr::Binary(_, l, r) => {
+                self.check_calc(label, l);
+                self.check_calc(label, r);
+            }
+            CalcExpr::Fun(_) | CalcExpr::Val(_) => {}
+        }
+    }
+
+    // 条件左侧字段先取源记录、再取已生成的目标字段
+    fn check_cond(&mut self, label: &str, cond: &LogicalExpression) {
+        match cond {
+            LogicalExpression::Compare(x) => {
+                let name = x.var_name();
+                if self.lookup(name, true).is_none() {
+                    self.unknown(label, name);
+                }
+                self.check_cond_acc(label, x.right_const());
+            }
+            LogicalExpression::Logic(x) => {
+                if let Some(left) = x.left() {
+                    self.check_cond(label, left);
+                }
+                self.check_cond(label, x.right());
+            }
+        }
+    }
+
+    fn check_cond_acc(&mut self, label: &str, acc: &CondAccessor) {
+        if let CondAccessor::Tdc(x) = acc {
+            self.check_record(label, x);
+        }
+    }
+
+    fn check_match(&mut self, label: &str, op: &MatchOperation) {
+        let sources = match op.dat_crate() {
+            MatchSource::Single(x) => vec![self.check_direct(label, x, false)],
+            MatchSource::Double(fst, sec) => vec![
+                self.check_direct(label, fst, false),
+                self.check_direct(label, sec, false),
+            ],
+        };
+        let mut seen: Vec<&MatchCondition> = Vec::new();
+        for case in op.items() {
+            let conds = match case.cond() {
+                MatchCondition::Single(x) => vec![x],
+                MatchCondition::Double(fst, sec) => vec![fst, sec],
+                MatchCondition::Default => Vec::new(),
+            };
+            let arm = case.cond().to_string();
+            let arm = arm.trim();
+            let mismatch = conds.iter().zip(&sources).find_map(|(cond, src)| {
+                let (name, Some(src_meta)) = src.as_ref()? else {
+                    return None;
+                };
+                let meta = cond_meta(cond)?;
+                (!comparable(src_meta, meta)).then(|| (name.clone(), src_meta.clone()))
+            });
+            if let Some((name, src_meta)) = mismatch {
+                self.push(
+                    SchemaIssueKind::UnreachableArm,
+                    label,
+                    format!("arm {} never matches '{}' of type {}", arm, name, src_meta),
+                );
+            } else if seen.contains(&&MatchCondition::Default) || seen.contains(&case.cond()) {
+                self.push(
+                    SchemaIssueKind::UnreachableArm,
+                    label,
+                    format!("arm {} is shadowed by an earlier arm", arm),
+                );
+            }
+            seen.push(case.cond());
+            self.check_nested(label, case.result());
+        }
+        if let Some(default) = op.default() {
+            self.check_nested(label, default.result());
+        }
+    }
+
+    fn push(&mut self, kind: SchemaIssueKind, target: impl Into<String>, detail: String) {
+        self.issues.push(SchemaIssue::new(kind, target, detail));
+    }
+}
+
+// 分支常量的类型；`_` 与存在性判断（ignore）不参与类型比较
+fn cond_meta(cond: &MatchCond) -> Option<&DataType> {
+    let meta = match cond {
+        MatchCond::Eq(x) | MatchCond::Neq(x) | MatchCond::In(x, _) => x.get_meta(),
+        MatchCond::Default => return None,
+    };
+    (*meta != DataType::Ignore).then_some(meta)
+}
+
+// 运行期按值的种类比较：时间族为 time，http 状态码与端口为 digit，http 文本为 chars
+fn value_kind(meta: &DataType) -> DataType {
+    match meta {
+        DataType::TimeCLF
+        | DataType::TimeISO
+        | DataType::TimeRFC3339
+        | DataType::TimeRFC2822
+        | DataType::TimeTIMESTAMP => DataType::Time,
+        DataType::HttpStatus | DataType::Port => DataType::Digit,
+        DataType::HttpRequest | DataType::HttpAgent | DataType::HttpMethod => DataType::Chars,
+        other => other.clone(),
+    }
+}
+
+fn comparable(src: &DataType, cond: &DataType) -> bool {
+    *cond == DataType::Auto || value_kind(src) == value_kind(cond)
+}
+
+// 与 omlobj_meta_conv 一致：目标为 chars/auto 时原样输出，chars 按目标类型解析
+fn convertible(src: &DataType, dst: &DataType) -> bool {
+    matches!(dst, DataType::Auto | DataType::Chars)
+        || value_kind(src) == DataType::Chars
+        || value_kind(src) == value_kind(dst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SchemaIssueKind, check_model};
+    use crate::parser::oml_parse_raw;
+    use orion_error::TestAssert;
+    use wpl::{WplPackage, WplSchema};
+
+    fn schemas() -> Vec<WplSchema> {
+        let mut code = r#"package /nginx {
+   rule access { (ip:sip,_^2,time/clf:recv_time<[,]>,chars:uri,http/status:status,digit:size) }
+   rule api { (json(digit@size, chars@uri)) }
+}"#;
+        let pkg = WplPackage::parse(&mut code, "nginx.wpl").assert();
+        pkg.rules.iter().map(|x| x.output_schema()).collect()
+    }
+
+    fn issues(code: &str, schema: &WplSchema) -> Vec<(SchemaIssueKind, String)> {
+        let mut code = code;
+        let model = oml_parse_raw(&mut code).assert();
+        check_model(&model, schema)
+            .into_iter()
+            .map(|x| (x.kind.clone(), x.to_brief()))
+            .collect()
+    }
+
+    #[test]
+    fn test_check_model() {
+        let schemas = schemas();
+        let code = r#"
+name : nginx
+rule : /nginx/*
+---
+src_ip : ip = take(sip) ;
+code : digit = read(status) ;
+url = read(uri) ;
+level = match read(status) {
+    digit(500) => chars(error),
+    _ => chars(ok),
+};
+ts : time = read(recv_time) ;
+copy : ip = read(src_ip) ;
+host : chars = read(host) { _ : chars(unknown) } ;
+drop when size = 0 ;
+"#;
+        assert_eq!(issues(code, &schemas[0]), vec![]);
+
+        let code = r#"
+name : nginx
+rule : /nginx/*
+---
+agent = take(agent) ;
+addr : ip = read(size) ;
+zone = match read(sip) {
+    chars(a) => chars(x),
+    ip(10.0.0.1) => chars(y),
+    ip(10.0.0.1) => chars(z),
+};
+url = pipe take(path) | to_str ;
+keep when method = "GET" ;
+"#;
+        let found = issues(code, &schemas[0]);
+        let kinds: Vec<_> = found.iter().map(|x| x.0.clone()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                SchemaIssueKind::UnknownField,
+                SchemaIssueKind::TypeMismatch,
+                SchemaIssueKind::UnreachableArm,
+                SchemaIssueKind::UnreachableArm,
+                SchemaIssueKind::UnknownField,
+                SchemaIssueKind::UnknownField,
+            ],
+            "{:?}",
+            found
+        );
+        assert!(found[0].1.contains("'agent'"), "{:?}", found);
+        assert!(found[1].1.contains("'size' is digit"), "{:?}", found);
+        assert!(found[2].1.contains("never matches 'sip'"), "{:?}", found);
+        assert!(found[3].1.contains("shadowed"), "{:?}", found);
+        assert!(found[5].1.starts_with("unknown_field: keep"), "{:?}", found);
+
+        // json 未声明的键在运行期才确定，只校验声明的子字段与分支
+        let found = issues(code, &schemas[1]);
+        let kinds: Vec<_> = found.iter().map(|x| x.0.clone()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                SchemaIssueKind::TypeMismatch,
+                SchemaIssueKind::UnreachableArm
+            ],
+            "{:?}",
+            found
+        );
+    }
+}
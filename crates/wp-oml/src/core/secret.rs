@@ -0,0 +1,70 @@
+//! 脱敏函数使用的密钥
+//!
+//! OML 模型中只写密钥名（如 `hmac_sha256(PII_HMAC_KEY)`），取值在运行期从环境字典解析；
+//! 字典中缺失时再查进程环境变量。引擎加载模型时以其环境字典调用 `set_env_dict`。
+//! 已解析的密钥按名缓存（`set_env_dict` 会清空缓存）；缺失的密钥不缓存，之后补上即可生效。
+
+use orion_variate::{EnvDict, EnvEvaluable};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, RwLock};
+
+static DICT: RwLock<Option<EnvDict>> = RwLock::new(None);
+static CACHE: LazyLock<RwLock<HashMap<String, Arc<[u8]>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// 设置密钥解析所用的环境字典（通常在加载模型时调用一次）
+pub fn set_env_dict(dict: EnvDict) {
+    *DICT.write().expect("secret dict lock") = Some(dict);
+    CACHE.write().expect("secret cache lock").clear();
+}
+
+pub(crate) fn lookup(name: &str) -> Option<Arc<[u8]>> {
+    if let Some(hit) = CACHE.read().expect("secret cache lock").get(name) {
+        return Some(hit.clone());
+    }
+    let value: Arc<[u8]> = Arc::from(resolve(name)?.into_bytes());
+    CACHE
+        .write()
+        .expect("secret cache lock")
+        .insert(name.to_string(), value.clone());
+    Some(value)
+}
+
+fn resolve(name: &str) -> Option<String> {
+    let placeholder = format!("${{{}}}", name);
+    if let Some(dict) = DICT.read().expect("secret dict lock").as_ref() {
+        let value = placeholder.clone().env_eval(dict);
+        if value != placeholder && !value.is_empty() {
+            return Some(value);
+        }
+    }
+    std::env::var(name).ok().filter(|x| !x.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orion_variate::ValueType;
+
+    #[test]
+    fn test_secret_lookup() {
+        let mut dict = EnvDict::new();
+        dict.insert("OML_TEST_SECRET_A", ValueType::from("abc"));
+        set_env_dict(dict);
+        assert_eq!(lookup("OML_TEST_SECRET_A").as_deref(), Some(&b"abc"[..]));
+        assert!(lookup("OML_TEST_SECRET_NONE").is_none());
+
+        // 首次缺失不缓存：之后设置的环境变量可被查到
+        assert!(lookup("OML_TEST_SECRET_LATE").is_none());
+        unsafe {
+            std::env::set_var("OML_TEST_SECRET_LATE", "late");
+        }
+        assert_eq!(
+            lookup("OML_TEST_SECRET_LATE").as_deref(),
+            Some(&b"late"[..])
+        );
+        unsafe {
+            std::env::remove_var("OML_TEST_SECRET_LATE");
+        }
+    }
+}
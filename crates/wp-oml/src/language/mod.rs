@@ -9,26 +9,39 @@ pub use syntax::{
     bindings::GenericBinding,
     conditions::{ArgsTakeAble, CompareExpress, LogicalExpression},
     evaluators::{
-        BatchEvalExp, BatchEvalExpBuilder, BatchEvaluation, EvalExp, PreciseEvaluator,
-        SingleEvalExp, SingleEvalExpBuilder,
+        BatchEvalExp, BatchEvalExpBuilder, BatchEvaluation, BlockUse, EvalExp, FieldRemove,
+        PreciseEvaluator, RecordFilter, SingleEvalExp, SingleEvalExpBuilder,
     },
     functions::{
+        ArrContains, ArrDistinct, ArrFilter, ArrFirst, ArrLambda, ArrLast, ArrMap, ArrSort,
         Base64Decode, Base64Encode, BuiltinFunction, Dumb, EncodeType, FUN_NOW_DATE, FUN_NOW_HOUR,
-        FUN_NOW_TIME, FunOperation, Get, HtmlEscape, HtmlUnescape, Ip4ToInt, JsonEscape,
-        JsonUnescape, NowDate, NowHour, NowTime, Nth, PIPE_BASE64_DECODE, PIPE_BASE64_ENCODE,
-        PIPE_GET, PIPE_HTML_ESCAPE, PIPE_HTML_UNESCAPE, PIPE_IP4_TO_INT, PIPE_JSON_ESCAPE,
-        PIPE_JSON_UNESCAPE, PIPE_NTH, PIPE_PATH, PIPE_SKIP_EMPTY, PIPE_STR_ESCAPE, PIPE_TIME_TO_TS,
-        PIPE_TIME_TO_TS_MS, PIPE_TIME_TO_TS_US, PIPE_TIME_TO_TS_ZONE, PIPE_TO_JSON, PIPE_TO_STR,
-        PIPE_URL, PathGet, PathType, PipeFun, SkipEmpty, StrEscape, TimeStampUnit, TimeToTs,
-        TimeToTsMs, TimeToTsUs, TimeToTsZone, ToJson, ToStr, UrlGet, UrlType,
+        FUN_NOW_TIME, FUN_TIME_DIFF, FunOperation, Get, HmacSha256, HtmlEscape, HtmlUnescape,
+        Ip4ToInt, Join, JsonEscape, JsonUnescape, Lower, Mask, Md5Hash, NowDate, NowHour, NowTime,
+        Nth, OBJ_PATH_SEP, ObjFlatten, ObjKeys, ObjMerge, ObjUnflatten, ObjValues,
+        PIPE_BASE64_DECODE, PIPE_BASE64_ENCODE, PIPE_CONTAINS, PIPE_DISTINCT, PIPE_FILTER,
+        PIPE_FIRST, PIPE_FLATTEN, PIPE_GET, PIPE_HMAC_SHA256, PIPE_HTML_ESCAPE, PIPE_HTML_UNESCAPE,
+        PIPE_IP4_TO_INT, PIPE_JOIN, PIPE_JSON_ESCAPE, PIPE_JSON_UNESCAPE, PIPE_KEYS, PIPE_LAST,
+        PIPE_LEN, PIPE_LOWER, PIPE_MAP, PIPE_MASK, PIPE_MD5, PIPE_MERGE, PIPE_NTH, PIPE_PAD,
+        PIPE_PATH, PIPE_PSEUDONYMIZE, PIPE_REDACT, PIPE_REGEX_EXTRACT, PIPE_REGEX_REPLACE,
+        PIPE_REPLACE, PIPE_SHA1, PIPE_SHA256, PIPE_SKIP_EMPTY, PIPE_SORT, PIPE_SPLIT,
+        PIPE_STR_ESCAPE, PIPE_SUBSTR, PIPE_TIME_ADD, PIPE_TIME_FORMAT, PIPE_TIME_HOUR,
+        PIPE_TIME_PARSE, PIPE_TIME_TO_TS, PIPE_TIME_TO_TS_MS, PIPE_TIME_TO_TS_US,
+        PIPE_TIME_TO_TS_ZONE, PIPE_TIME_TO_ZONE, PIPE_TIME_TRUNC, PIPE_TIME_WEEKDAY, PIPE_TO_JSON,
+        PIPE_TO_STR, PIPE_TRIM, PIPE_UNFLATTEN, PIPE_UPPER, PIPE_URL, PIPE_VALUES, Pad, PadSide,
+        PathGet, PathType, PipeFun, Pseudonymize, REDACTED_TEXT, Redact, RegexExtract,
+        RegexReplace, Replace, Sha1Hash, Sha256Hash, SkipEmpty, Split, StrEscape, StrLen, Substr,
+        TimeAdd, TimeDiff, TimeFormat, TimeHour, TimeParse, TimeStampUnit, TimeToTs, TimeToTsMs,
+        TimeToTsUs, TimeToTsZone, TimeToZone, TimeTrunc, TimeTruncUnit, TimeWeekday, ToJson, ToStr,
+        Trim, Upper, UrlGet, UrlType,
     },
     //lib_prm::LookupQuery,
     operations::{
-        FmtOperation, MapOperation, MatchAble, MatchCase, MatchCond, MatchCondition,
-        MatchOperation, MatchSource, PiPeOperation, RecordOperation, RecordOperationBuilder,
-        SqlQuery,
+        CalcExpr, CalcOp, CalcOperation, CalcUnary, FmtOperation, IfOperation, MapOperation,
+        MatchAble, MatchCase, MatchCond, MatchCondition, MatchOperation, MatchSource,
+        PiPeOperation, RecordOperation, RecordOperationBuilder, SqlQuery,
     },
 };
+pub use types::library::{OmlBlock, OmlLibrary};
 pub use types::model::DataModel;
 pub use types::model::ObjModel;
 pub use types::model::StubModel;
@@ -0,0 +1,77 @@
+use crate::language::prelude::*;
+use crate::language::syntax::conditions::LogicalExpression;
+use wildmatch::WildMatch;
+
+/// 记录过滤指令：`drop when <cond> ;` / `keep when <cond> ;`
+///
+/// 按在模型中的位置求值，条件左侧字段先取源记录、再取已生成的目标字段；
+/// 记录被丢弃后不再执行后续条目，由 `DataTransformer::transform_filter` 返回 `None`。
+#[derive(Debug, Clone)]
+pub enum RecordFilter {
+    Drop(LogicalExpression),
+    Keep(LogicalExpression),
+}
+
+impl RecordFilter {
+    pub fn cond(&self) -> &LogicalExpression {
+        match self {
+            RecordFilter::Drop(x) | RecordFilter::Keep(x) => x,
+        }
+    }
+}
+
+impl Display for RecordFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecordFilter::Drop(x) => write!(f, "drop when {} ;", x),
+            RecordFilter::Keep(x) => write!(f, "keep when {} ;", x),
+        }
+    }
+}
+
+/// 字段移除指令：`remove(debug, trace_*) ;`，从已生成的目标记录中移除匹配的字段
+#[derive(Debug, Clone)]
+pub struct FieldRemove {
+    patterns: Vec<String>,
+    matchers: Vec<WildMatch>,
+}
+
+impl FieldRemove {
+    pub fn new(patterns: Vec<String>) -> Self {
+        let matchers = patterns.iter().map(|x| WildMatch::new(x)).collect();
+        Self { patterns, matchers }
+    }
+    pub fn patterns(&self) -> &Vec<String> {
+        &self.patterns
+    }
+    pub fn matches(&self, name: &str) -> bool {
+        self.matchers.iter().any(|x| x.matches(name))
+    }
+}
+
+impl Display for FieldRemove {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "remove({}) ;", self.patterns.join(", "))
+    }
+}
+
+/// 引用库中的绑定块：`use <block> ;`，加载模型时展开为块内条目
+#[derive(Debug, Clone)]
+pub struct BlockUse {
+    name: String,
+}
+
+impl BlockUse {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Display for BlockUse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "use {} ;", self.name)
+    }
+}
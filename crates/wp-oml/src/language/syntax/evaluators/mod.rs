@@ -1,8 +1,10 @@
 use std::fmt::{Display, Formatter};
 
+pub use directive::{BlockUse, FieldRemove, RecordFilter};
 pub use pattern::{BatchEvalExp, BatchEvalExpBuilder, BatchEvaluation};
 pub use precise::{PreciseEvaluator, SingleEvalExp, SingleEvalExpBuilder};
 
+pub mod directive;
 pub mod pattern;
 pub mod precise;
 #[allow(clippy::large_enum_variant)]
@@ -10,12 +12,18 @@ pub mod precise;
 pub enum EvalExp {
     Single(SingleEvalExp),
     Batch(BatchEvalExp),
+    Filter(RecordFilter),
+    Remove(FieldRemove),
+    Use(BlockUse),
 }
 impl Display for EvalExp {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             EvalExp::Single(x) => Display::fmt(x, f),
             EvalExp::Batch(x) => Display::fmt(x, f),
+            EvalExp::Filter(x) => Display::fmt(x, f),
+            EvalExp::Remove(x) => Display::fmt(x, f),
+            EvalExp::Use(x) => Display::fmt(x, f),
         }
     }
 }
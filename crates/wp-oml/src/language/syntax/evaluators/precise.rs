@@ -1,7 +1,9 @@
 use crate::language::prelude::*;
 use crate::language::syntax::accessors::nested::arr::ArrOperation;
 use crate::language::syntax::functions::FunOperation;
+use crate::language::syntax::operations::calc::CalcOperation;
 use crate::language::syntax::operations::fmt::FmtOperation;
+use crate::language::syntax::operations::ifelse::IfOperation;
 use crate::language::syntax::operations::map::MapOperation;
 use crate::language::syntax::operations::matchs::MatchOperation;
 use crate::language::syntax::operations::pipe::PiPeOperation;
@@ -43,6 +45,8 @@ pub enum PreciseEvaluator {
     Fun(FunOperation),
     Fmt(FmtOperation),
     Collect(ArrOperation),
+    Calc(CalcOperation),
+    If(IfOperation),
     Val(Value),
 }
 
@@ -64,6 +68,8 @@ impl Display for PreciseEvaluator {
             PreciseEvaluator::Fun(x) => Display::fmt(x, f),
             PreciseEvaluator::Fmt(x) => Display::fmt(x, f),
             PreciseEvaluator::Collect(x) => Display::fmt(x, f),
+            PreciseEvaluator::Calc(x) => Display::fmt(x, f),
+            PreciseEvaluator::If(x) => Display::fmt(x, f),
             PreciseEvaluator::Val(x) => Display::fmt(x, f),
         }
     }
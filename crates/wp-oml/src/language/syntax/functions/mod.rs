@@ -11,6 +11,8 @@ pub enum BuiltinFunction {
     NowDate(NowDate),
     #[strum(to_string = "Now::hour")]
     NowHour(NowHour),
+    #[strum(to_string = "Time::diff")]
+    TimeDiff(TimeDiff),
 }
 
 #[derive(Debug, Clone, Getters, Serialize, Deserialize, PartialEq)]
@@ -24,17 +26,28 @@ impl FunOperation {
 }
 impl Display for FunOperation {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}() ", self.fun)
+        match &self.fun {
+            BuiltinFunction::TimeDiff(x) => write!(f, "{} ", x),
+            x => write!(f, "{}() ", x),
+        }
     }
 }
 
 pub use pipe::{
-    Base64Decode, Base64Encode, Dumb, EncodeType, Get, HtmlEscape, HtmlUnescape, Ip4ToInt,
-    JsonEscape, JsonUnescape, Nth, PIPE_BASE64_DECODE, PIPE_BASE64_ENCODE, PIPE_GET,
-    PIPE_HTML_ESCAPE, PIPE_HTML_UNESCAPE, PIPE_IP4_TO_INT, PIPE_JSON_ESCAPE, PIPE_JSON_UNESCAPE,
-    PIPE_NTH, PIPE_PATH, PIPE_SKIP_EMPTY, PIPE_STR_ESCAPE, PIPE_TIME_TO_TS, PIPE_TIME_TO_TS_MS,
-    PIPE_TIME_TO_TS_US, PIPE_TIME_TO_TS_ZONE, PIPE_TO_JSON, PIPE_TO_STR, PIPE_URL, PathGet,
-    PathType, PipeFun, SkipEmpty, StrEscape, TimeStampUnit, TimeToTs, TimeToTsMs, TimeToTsUs,
-    TimeToTsZone, ToJson, ToStr, UrlGet, UrlType,
+    ArrContains, ArrDistinct, ArrFilter, ArrFirst, ArrLambda, ArrLast, ArrMap, ArrSort,
+    Base64Decode, Base64Encode, Dumb, EncodeType, Get, HmacSha256, HtmlEscape, HtmlUnescape,
+    Ip4ToInt, Join, JsonEscape, JsonUnescape, Lower, Mask, Md5Hash, Nth, OBJ_PATH_SEP, ObjFlatten,
+    ObjKeys, ObjMerge, ObjUnflatten, ObjValues, PIPE_BASE64_DECODE, PIPE_BASE64_ENCODE,
+    PIPE_CONTAINS, PIPE_DISTINCT, PIPE_FILTER, PIPE_FIRST, PIPE_FLATTEN, PIPE_GET,
+    PIPE_HMAC_SHA256, PIPE_HTML_ESCAPE, PIPE_HTML_UNESCAPE, PIPE_IP4_TO_INT, PIPE_JOIN,
+    PIPE_JSON_ESCAPE, PIPE_JSON_UNESCAPE, PIPE_KEYS, PIPE_LAST, PIPE_LEN, PIPE_LOWER, PIPE_MAP,
+    PIPE_MASK, PIPE_MD5, PIPE_MERGE, PIPE_NTH, PIPE_PAD, PIPE_PATH, PIPE_PSEUDONYMIZE, PIPE_REDACT,
+    PIPE_REGEX_EXTRACT, PIPE_REGEX_REPLACE, PIPE_REPLACE, PIPE_SHA1, PIPE_SHA256, PIPE_SKIP_EMPTY,
+    PIPE_SORT, PIPE_SPLIT, PIPE_STR_ESCAPE, PIPE_SUBSTR, PIPE_TIME_TO_TS, PIPE_TIME_TO_TS_MS,
+    PIPE_TIME_TO_TS_US, PIPE_TIME_TO_TS_ZONE, PIPE_TO_JSON, PIPE_TO_STR, PIPE_TRIM, PIPE_UNFLATTEN,
+    PIPE_UPPER, PIPE_URL, PIPE_VALUES, Pad, PadSide, PathGet, PathType, PipeFun, Pseudonymize,
+    REDACTED_TEXT, Redact, RegexExtract, RegexReplace, Replace, Sha1Hash, Sha256Hash, SkipEmpty,
+    Split, StrEscape, StrLen, Substr, TimeStampUnit, TimeToTs, TimeToTsMs, TimeToTsUs,
+    TimeToTsZone, ToJson, ToStr, Trim, Upper, UrlGet, UrlType,
 };
 pub use time::*;
@@ -0,0 +1,137 @@
+use crate::language::prelude::*;
+use crate::language::syntax::operations::CalcExpr;
+use wp_data_fmt::Raw;
+
+use super::string::fmt_str_arg;
+
+/// 数组元素上的单参 lambda：`x => <expr>`
+///
+/// 表达式沿用 OML 表达式语法，以 `read(x)` 引用当前元素；元素为对象时可用
+/// `read(x/key)` 引用其成员。
+#[derive(Clone, Debug)]
+pub struct ArrLambda {
+    pub(crate) var: String,
+    pub(crate) body: CalcExpr,
+}
+
+/// 对数组每个元素求值：`map(x => read(x) * 2)`，结果为 null 的元素被丢弃
+pub const PIPE_MAP: &str = "map";
+#[derive(Clone, Debug)]
+pub struct ArrMap {
+    pub(crate) lambda: ArrLambda,
+}
+
+/// 保留表达式为 true 的元素：`filter(x => read(x) > 10)`
+pub const PIPE_FILTER: &str = "filter";
+#[derive(Clone, Debug)]
+pub struct ArrFilter {
+    pub(crate) lambda: ArrLambda,
+}
+
+pub const PIPE_DISTINCT: &str = "distinct";
+#[derive(Clone, Debug, Default)]
+pub struct ArrDistinct {}
+
+/// 升序排序：数值按大小，其余按文本；排序稳定
+pub const PIPE_SORT: &str = "sort";
+#[derive(Clone, Debug, Default)]
+pub struct ArrSort {}
+
+pub const PIPE_FIRST: &str = "first";
+#[derive(Clone, Debug, Default)]
+pub struct ArrFirst {}
+
+pub const PIPE_LAST: &str = "last";
+#[derive(Clone, Debug, Default)]
+pub struct ArrLast {}
+
+/// 包含判断，输出 bool：数组按元素值、字符串按子串、对象按键
+pub const PIPE_CONTAINS: &str = "contains";
+#[derive(Clone, Debug)]
+pub struct ArrContains {
+    pub(crate) value: DataField,
+}
+
+pub const PIPE_KEYS: &str = "keys";
+#[derive(Clone, Debug, Default)]
+pub struct ObjKeys {}
+
+pub const PIPE_VALUES: &str = "values";
+#[derive(Clone, Debug, Default)]
+pub struct ObjValues {}
+
+/// 合并对象数组为一个对象，同名键后者覆盖前者
+pub const PIPE_MERGE: &str = "merge";
+#[derive(Clone, Debug, Default)]
+pub struct ObjMerge {}
+
+pub const OBJ_PATH_SEP: &str = ".";
+
+/// 嵌套对象展开为单层：`{a:{b:1}}` => `{"a.b":1}`
+pub const PIPE_FLATTEN: &str = "flatten";
+#[derive(Clone, Debug)]
+pub struct ObjFlatten {
+    pub(crate) sep: String,
+}
+
+/// `flatten` 的逆操作：按分隔符拆分键并还原嵌套对象
+pub const PIPE_UNFLATTEN: &str = "unflatten";
+#[derive(Clone, Debug)]
+pub struct ObjUnflatten {
+    pub(crate) sep: String,
+}
+
+impl Default for ObjFlatten {
+    fn default() -> Self {
+        Self {
+            sep: OBJ_PATH_SEP.to_string(),
+        }
+    }
+}
+
+impl Default for ObjUnflatten {
+    fn default() -> Self {
+        Self {
+            sep: OBJ_PATH_SEP.to_string(),
+        }
+    }
+}
+
+impl Display for ArrLambda {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} => {}", self.var, self.body)
+    }
+}
+
+impl Display for ArrMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({})", PIPE_MAP, self.lambda)
+    }
+}
+
+impl Display for ArrFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({})", PIPE_FILTER, self.lambda)
+    }
+}
+
+impl Display for ArrContains {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.value.get_value() {
+            Value::Chars(x) => write!(f, "{}({})", PIPE_CONTAINS, fmt_str_arg(x)),
+            x => write!(f, "{}({})", PIPE_CONTAINS, Raw.fmt_value(x)),
+        }
+    }
+}
+
+impl Display for ObjFlatten {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({})", PIPE_FLATTEN, fmt_str_arg(&self.sep))
+    }
+}
+
+impl Display for ObjUnflatten {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({})", PIPE_UNFLATTEN, fmt_str_arg(&self.sep))
+    }
+}
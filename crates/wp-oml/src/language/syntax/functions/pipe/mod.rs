@@ -1,18 +1,29 @@
 use crate::language::prelude::*;
 
 pub mod base64;
+pub mod collection;
 pub mod escape;
 pub mod fmt;
 pub mod net;
 pub mod other;
+pub mod privacy;
+pub mod string;
 pub mod time;
 pub use base64::*;
+pub use collection::*;
 pub use escape::*;
 pub use fmt::*;
 pub use net::*;
 pub use other::*;
+pub use privacy::*;
+pub use string::*;
 pub use time::*;
 
+use crate::language::syntax::functions::time::{
+    PIPE_TIME_HOUR, PIPE_TIME_WEEKDAY, TimeAdd, TimeFormat, TimeHour, TimeParse, TimeToZone,
+    TimeTrunc, TimeWeekday,
+};
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
 pub enum PipeFun {
@@ -27,6 +38,13 @@ pub enum PipeFun {
     TimeToTsMs(TimeToTsMs),
     TimeToTsUs(TimeToTsUs),
     TimeToTsZone(TimeToTsZone),
+    TimeFormat(TimeFormat),
+    TimeParse(TimeParse),
+    TimeTrunc(TimeTrunc),
+    TimeAdd(TimeAdd),
+    TimeToZone(TimeToZone),
+    TimeWeekday(TimeWeekday),
+    TimeHour(TimeHour),
     Nth(Nth),
     Get(Get),
     ToStr(ToStr),
@@ -36,6 +54,36 @@ pub enum PipeFun {
     PathGet(PathGet),
     UrlGet(UrlGet),
     Ip4ToInt(Ip4ToInt),
+    Lower(Lower),
+    Upper(Upper),
+    Trim(Trim),
+    StrLen(StrLen),
+    Substr(Substr),
+    Replace(Replace),
+    RegexReplace(RegexReplace),
+    RegexExtract(RegexExtract),
+    Split(Split),
+    Join(Join),
+    Pad(Pad),
+    Md5Hash(Md5Hash),
+    Sha1Hash(Sha1Hash),
+    Sha256Hash(Sha256Hash),
+    HmacSha256(HmacSha256),
+    Mask(Mask),
+    Redact(Redact),
+    Pseudonymize(Pseudonymize),
+    ArrMap(ArrMap),
+    ArrFilter(ArrFilter),
+    ArrDistinct(ArrDistinct),
+    ArrSort(ArrSort),
+    ArrFirst(ArrFirst),
+    ArrLast(ArrLast),
+    ArrContains(ArrContains),
+    ObjKeys(ObjKeys),
+    ObjValues(ObjValues),
+    ObjMerge(ObjMerge),
+    ObjFlatten(ObjFlatten),
+    ObjUnflatten(ObjUnflatten),
 }
 
 impl Display for PipeFun {
@@ -52,6 +100,13 @@ impl Display for PipeFun {
             PipeFun::TimeToTsMs(_) => write!(f, "{}", PIPE_TIME_TO_TS_MS),
             PipeFun::TimeToTsUs(_) => write!(f, "{}", PIPE_TIME_TO_TS_US),
             PipeFun::TimeToTsZone(v) => write!(f, "{}", v),
+            PipeFun::TimeFormat(v) => write!(f, "{}", v),
+            PipeFun::TimeParse(v) => write!(f, "{}", v),
+            PipeFun::TimeTrunc(v) => write!(f, "{}", v),
+            PipeFun::TimeAdd(v) => write!(f, "{}", v),
+            PipeFun::TimeToZone(v) => write!(f, "{}", v),
+            PipeFun::TimeWeekday(_) => write!(f, "{}", PIPE_TIME_WEEKDAY),
+            PipeFun::TimeHour(_) => write!(f, "{}", PIPE_TIME_HOUR),
             PipeFun::Nth(v) => write!(f, "{}", v),
             PipeFun::Get(v) => write!(f, "{}", v),
             PipeFun::ToJson(_) => write!(f, "{}", PIPE_TO_JSON),
@@ -61,6 +116,36 @@ impl Display for PipeFun {
             PipeFun::PathGet(v) => write!(f, "{}", v),
             PipeFun::UrlGet(v) => write!(f, "{}", v),
             PipeFun::Ip4ToInt(v) => write!(f, "{}", v),
+            PipeFun::Lower(_) => write!(f, "{}", PIPE_LOWER),
+            PipeFun::Upper(_) => write!(f, "{}", PIPE_UPPER),
+            PipeFun::Trim(_) => write!(f, "{}", PIPE_TRIM),
+            PipeFun::StrLen(_) => write!(f, "{}", PIPE_LEN),
+            PipeFun::Substr(v) => write!(f, "{}", v),
+            PipeFun::Replace(v) => write!(f, "{}", v),
+            PipeFun::RegexReplace(v) => write!(f, "{}", v),
+            PipeFun::RegexExtract(v) => write!(f, "{}", v),
+            PipeFun::Split(v) => write!(f, "{}", v),
+            PipeFun::Join(v) => write!(f, "{}", v),
+            PipeFun::Pad(v) => write!(f, "{}", v),
+            PipeFun::Md5Hash(_) => write!(f, "{}", PIPE_MD5),
+            PipeFun::Sha1Hash(_) => write!(f, "{}", PIPE_SHA1),
+            PipeFun::Sha256Hash(_) => write!(f, "{}", PIPE_SHA256),
+            PipeFun::HmacSha256(v) => write!(f, "{}", v),
+            PipeFun::Mask(v) => write!(f, "{}", v),
+            PipeFun::Redact(_) => write!(f, "{}", PIPE_REDACT),
+            PipeFun::Pseudonymize(v) => write!(f, "{}", v),
+            PipeFun::ArrMap(v) => write!(f, "{}", v),
+            PipeFun::ArrFilter(v) => write!(f, "{}", v),
+            PipeFun::ArrDistinct(_) => write!(f, "{}", PIPE_DISTINCT),
+            PipeFun::ArrSort(_) => write!(f, "{}", PIPE_SORT),
+            PipeFun::ArrFirst(_) => write!(f, "{}", PIPE_FIRST),
+            PipeFun::ArrLast(_) => write!(f, "{}", PIPE_LAST),
+            PipeFun::ArrContains(v) => write!(f, "{}", v),
+            PipeFun::ObjKeys(_) => write!(f, "{}", PIPE_KEYS),
+            PipeFun::ObjValues(_) => write!(f, "{}", PIPE_VALUES),
+            PipeFun::ObjMerge(_) => write!(f, "{}", PIPE_MERGE),
+            PipeFun::ObjFlatten(v) => write!(f, "{}", v),
+            PipeFun::ObjUnflatten(v) => write!(f, "{}", v),
         }
     }
 }
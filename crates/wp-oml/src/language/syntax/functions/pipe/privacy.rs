@@ -0,0 +1,78 @@
+use crate::language::prelude::*;
+
+use super::string::fmt_str_arg;
+
+pub const PIPE_MD5: &str = "md5";
+#[derive(Clone, Debug, Default)]
+pub struct Md5Hash {}
+
+pub const PIPE_SHA1: &str = "sha1";
+#[derive(Clone, Debug, Default)]
+pub struct Sha1Hash {}
+
+pub const PIPE_SHA256: &str = "sha256";
+#[derive(Clone, Debug, Default)]
+pub struct Sha256Hash {}
+
+/// HMAC-SHA256 摘要；参数为密钥名，取值见 `core::secret`
+pub const PIPE_HMAC_SHA256: &str = "hmac_sha256";
+#[derive(Clone, Debug, Default)]
+pub struct HmacSha256 {
+    pub(crate) key_env: String,
+}
+
+/// 保留前后若干字符，其余以掩码字符替换：`mask(3, 4, "*")`
+pub const PIPE_MASK: &str = "mask";
+#[derive(Clone, Debug)]
+pub struct Mask {
+    pub(crate) keep_prefix: usize,
+    pub(crate) keep_suffix: usize,
+    pub(crate) mask: char,
+}
+
+impl Default for Mask {
+    fn default() -> Self {
+        Self {
+            keep_prefix: 0,
+            keep_suffix: 0,
+            mask: '*',
+        }
+    }
+}
+
+pub const PIPE_REDACT: &str = "redact";
+pub const REDACTED_TEXT: &str = "***";
+#[derive(Clone, Debug, Default)]
+pub struct Redact {}
+
+/// 保持格式的假名化：IP 映射为同族 IP，字符串逐字符保持数字/字母/汉字类别与长度
+pub const PIPE_PSEUDONYMIZE: &str = "pseudonymize";
+#[derive(Clone, Debug, Default)]
+pub struct Pseudonymize {
+    pub(crate) key_env: String,
+}
+
+impl Display for HmacSha256 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({})", PIPE_HMAC_SHA256, self.key_env)
+    }
+}
+
+impl Display for Mask {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}({},{},{})",
+            PIPE_MASK,
+            self.keep_prefix,
+            self.keep_suffix,
+            fmt_str_arg(&self.mask.to_string())
+        )
+    }
+}
+
+impl Display for Pseudonymize {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({})", PIPE_PSEUDONYMIZE, self.key_env)
+    }
+}
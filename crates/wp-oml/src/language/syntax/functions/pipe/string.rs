@@ -0,0 +1,169 @@
+use crate::language::prelude::*;
+use regex::Regex;
+use strum_macros::EnumString;
+
+pub const PIPE_LOWER: &str = "lower";
+#[derive(Clone, Debug, Default)]
+pub struct Lower {}
+
+pub const PIPE_UPPER: &str = "upper";
+#[derive(Clone, Debug, Default)]
+pub struct Upper {}
+
+pub const PIPE_TRIM: &str = "trim";
+#[derive(Clone, Debug, Default)]
+pub struct Trim {}
+
+pub const PIPE_LEN: &str = "len";
+#[derive(Clone, Debug, Default)]
+pub struct StrLen {}
+
+/// 按字符截取；`start` 为负时从末尾计数，省略 `len` 时取到结尾
+pub const PIPE_SUBSTR: &str = "substr";
+#[derive(Clone, Debug, Default)]
+pub struct Substr {
+    pub(crate) start: i64,
+    pub(crate) len: Option<usize>,
+}
+
+pub const PIPE_REPLACE: &str = "replace";
+#[derive(Clone, Debug, Default)]
+pub struct Replace {
+    pub(crate) from: String,
+    pub(crate) to: String,
+}
+
+/// 正则替换，`to` 中可用 `$1`/`${name}` 引用分组
+pub const PIPE_REGEX_REPLACE: &str = "regex_replace";
+#[derive(Clone, Debug)]
+pub struct RegexReplace {
+    pub(crate) pattern: Regex,
+    pub(crate) to: String,
+}
+
+/// 正则提取指定分组（0 为整体匹配），未匹配时输出 ignore
+pub const PIPE_REGEX_EXTRACT: &str = "regex_extract";
+#[derive(Clone, Debug)]
+pub struct RegexExtract {
+    pub(crate) pattern: Regex,
+    pub(crate) group: usize,
+}
+
+pub const PIPE_SPLIT: &str = "split";
+#[derive(Clone, Debug, Default)]
+pub struct Split {
+    pub(crate) sep: String,
+}
+
+pub const PIPE_JOIN: &str = "join";
+#[derive(Clone, Debug, Default)]
+pub struct Join {
+    pub(crate) sep: String,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, EnumString, strum_macros::Display)]
+pub enum PadSide {
+    #[default]
+    #[strum(serialize = "left")]
+    Left,
+    #[strum(serialize = "right")]
+    Right,
+}
+
+/// 填充到指定宽度（按字符计）：`pad(8)`、`pad(8, "0")`、`pad(8, " ", right)`
+pub const PIPE_PAD: &str = "pad";
+#[derive(Clone, Debug)]
+pub struct Pad {
+    pub(crate) width: usize,
+    pub(crate) fill: char,
+    pub(crate) side: PadSide,
+}
+
+impl Default for Pad {
+    fn default() -> Self {
+        Self {
+            width: 0,
+            fill: ' ',
+            side: PadSide::Left,
+        }
+    }
+}
+
+// 字符串参数输出：含引号或反斜杠时使用 r#"..."#，保证可再次解析
+pub(crate) fn fmt_str_arg(s: &str) -> String {
+    if (s.contains('\\') || s.contains('"')) && !s.contains("\"#") {
+        format!("r#\"{}\"#", s)
+    } else {
+        format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+impl Display for Substr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.len {
+            Some(len) => write!(f, "{}({},{})", PIPE_SUBSTR, self.start, len),
+            None => write!(f, "{}({})", PIPE_SUBSTR, self.start),
+        }
+    }
+}
+
+impl Display for Replace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}({},{})",
+            PIPE_REPLACE,
+            fmt_str_arg(&self.from),
+            fmt_str_arg(&self.to)
+        )
+    }
+}
+
+impl Display for RegexReplace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}({},{})",
+            PIPE_REGEX_REPLACE,
+            fmt_str_arg(self.pattern.as_str()),
+            fmt_str_arg(&self.to)
+        )
+    }
+}
+
+impl Display for RegexExtract {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}({},{})",
+            PIPE_REGEX_EXTRACT,
+            fmt_str_arg(self.pattern.as_str()),
+            self.group
+        )
+    }
+}
+
+impl Display for Split {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({})", PIPE_SPLIT, fmt_str_arg(&self.sep))
+    }
+}
+
+impl Display for Join {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({})", PIPE_JOIN, fmt_str_arg(&self.sep))
+    }
+}
+
+impl Display for Pad {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}({},{},{})",
+            PIPE_PAD,
+            self.width,
+            fmt_str_arg(&self.fill.to_string()),
+            self.side
+        )
+    }
+}
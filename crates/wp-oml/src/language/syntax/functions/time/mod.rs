@@ -1,6 +1,12 @@
+use crate::language::prelude::*;
+use chrono_tz::Tz;
+
+use super::pipe::string::fmt_str_arg;
+
 pub const FUN_NOW_TIME: &str = "Now::time";
 pub const FUN_NOW_DATE: &str = "Now::date";
 pub const FUN_NOW_HOUR: &str = "Now::hour";
+pub const FUN_TIME_DIFF: &str = "Time::diff";
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct NowTime {}
@@ -8,3 +14,166 @@ pub struct NowTime {}
 pub struct NowDate {}
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct NowHour {}
+
+/// 两个时间字段之差（秒）：`Time::diff(end, start)` = end - start
+///
+/// 字段先取源记录、再取已生成的目标字段；任一侧缺失或不是时间时不产生结果。
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TimeDiff {
+    pub(crate) end: String,
+    pub(crate) start: String,
+}
+
+impl TimeDiff {
+    pub fn new<S: Into<String>>(end: S, start: S) -> Self {
+        Self {
+            end: end.into(),
+            start: start.into(),
+        }
+    }
+}
+
+impl Display for TimeDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({},{})", FUN_TIME_DIFF, self.end, self.start)
+    }
+}
+
+// 以下时区相关管道函数约定：未携带时区的时间值按 UTC+8 本地时间解释，与 `Time::to_ts` 一致。
+
+/// 按 strftime 格式输出字符串，可选转换到 IANA 时区：
+/// `Time::format("%Y-%m-%d %H:%M:%S", "Asia/Shanghai")`
+pub const PIPE_TIME_FORMAT: &str = "Time::format";
+#[derive(Clone, Debug)]
+pub struct TimeFormat {
+    pub(crate) pattern: String,
+    pub(crate) zone: Option<Tz>,
+}
+
+/// 按 strftime 格式解析字符串为时间：`Time::parse("%d/%b/%Y:%H:%M:%S %z")`
+///
+/// 格式含时区偏移时结果换算为 UTC+8 本地时间；仅含日期时取当日零点；解析失败输出 ignore。
+pub const PIPE_TIME_PARSE: &str = "Time::parse";
+#[derive(Clone, Debug)]
+pub struct TimeParse {
+    pub(crate) pattern: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, Display, PartialEq)]
+#[display(style = "snake_case")]
+pub enum TimeTruncUnit {
+    Minute,
+    #[default]
+    Hour,
+    Day,
+}
+
+/// 时间截断到整分/整点/当日零点：`Time::trunc(hour)`
+pub const PIPE_TIME_TRUNC: &str = "Time::trunc";
+#[derive(Clone, Debug, Default)]
+pub struct TimeTrunc {
+    pub(crate) unit: TimeTruncUnit,
+}
+
+/// 时间偏移：`Time::add(1h30m)`、`Time::add(-7d)`，单位 d/h/m/s/ms
+pub const PIPE_TIME_ADD: &str = "Time::add";
+#[derive(Clone, Debug, Default)]
+pub struct TimeAdd {
+    pub(crate) millis: i64,
+}
+
+/// 时区换算：`Time::to_zone("Asia/Tokyo")` 将（UTC+8）时间换算为目标时区的本地时间；
+/// `Time::to_zone("UTC", "Asia/Shanghai")` 指定源时区。
+pub const PIPE_TIME_TO_ZONE: &str = "Time::to_zone";
+#[derive(Clone, Debug)]
+pub struct TimeToZone {
+    pub(crate) zone: Tz,
+    pub(crate) from: Option<Tz>,
+}
+
+/// ISO 星期：周一为 1，周日为 7
+pub const PIPE_TIME_WEEKDAY: &str = "Time::weekday";
+#[derive(Clone, Debug, Default)]
+pub struct TimeWeekday {}
+
+/// 小时：0 - 23
+pub const PIPE_TIME_HOUR: &str = "Time::hour";
+#[derive(Clone, Debug, Default)]
+pub struct TimeHour {}
+
+impl Display for TimeFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.zone {
+            Some(tz) => write!(
+                f,
+                "{}({},{})",
+                PIPE_TIME_FORMAT,
+                fmt_str_arg(&self.pattern),
+                fmt_str_arg(tz.name())
+            ),
+            None => write!(f, "{}({})", PIPE_TIME_FORMAT, fmt_str_arg(&self.pattern)),
+        }
+    }
+}
+
+impl Display for TimeParse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({})", PIPE_TIME_PARSE, fmt_str_arg(&self.pattern))
+    }
+}
+
+impl Display for TimeTrunc {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}({})", PIPE_TIME_TRUNC, self.unit)
+    }
+}
+
+const DURATION_UNITS: [(&str, u64); 4] = [
+    ("d", 86_400_000),
+    ("h", 3_600_000),
+    ("m", 60_000),
+    ("s", 1_000),
+];
+
+impl Display for TimeAdd {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}(", PIPE_TIME_ADD)?;
+        if self.millis < 0 {
+            write!(f, "-")?;
+        }
+        let mut rest = self.millis.unsigned_abs();
+        if rest == 0 {
+            write!(f, "0s")?;
+        }
+        for (unit, size) in DURATION_UNITS {
+            if rest >= size {
+                write!(f, "{}{}", rest / size, unit)?;
+                rest %= size;
+            }
+        }
+        if rest > 0 {
+            write!(f, "{}ms", rest)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl Display for TimeToZone {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.from {
+            Some(from) => write!(
+                f,
+                "{}({},{})",
+                PIPE_TIME_TO_ZONE,
+                fmt_str_arg(self.zone.name()),
+                fmt_str_arg(from.name())
+            ),
+            None => write!(
+                f,
+                "{}({})",
+                PIPE_TIME_TO_ZONE,
+                fmt_str_arg(self.zone.name())
+            ),
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use crate::language::prelude::*;
+use crate::language::syntax::functions::FunOperation;
+use crate::language::syntax::operations::record::RecordOperation;
+
+/// 表达式二元运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalcOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+    Coalesce,
+}
+
+impl CalcOp {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            CalcOp::Add => "+",
+            CalcOp::Sub => "-",
+            CalcOp::Mul => "*",
+            CalcOp::Div => "/",
+            CalcOp::Rem => "%",
+            CalcOp::Eq => "==",
+            CalcOp::Ne => "!=",
+            CalcOp::Lt => "<",
+            CalcOp::Le => "<=",
+            CalcOp::Gt => ">",
+            CalcOp::Ge => ">=",
+            CalcOp::And => "&&",
+            CalcOp::Or => "||",
+            CalcOp::Coalesce => "??",
+        }
+    }
+    /// 优先级：数值越大结合越紧
+    pub fn priority(&self) -> u8 {
+        match self {
+            CalcOp::Coalesce => 0,
+            CalcOp::Or => 1,
+            CalcOp::And => 2,
+            CalcOp::Eq | CalcOp::Ne | CalcOp::Lt | CalcOp::Le | CalcOp::Gt | CalcOp::Ge => 3,
+            CalcOp::Add | CalcOp::Sub => 4,
+            CalcOp::Mul | CalcOp::Div | CalcOp::Rem => 5,
+        }
+    }
+}
+
+/// 表达式一元运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalcUnary {
+    Neg,
+    Not,
+}
+
+#[derive(Debug, Clone)]
+pub enum CalcExpr {
+    Get(RecordOperation),
+    Fun(FunOperation),
+    Val(DataField),
+    Unary(CalcUnary, Box<CalcExpr>),
+    Binary(CalcOp, Box<CalcExpr>, Box<CalcExpr>),
+}
+
+impl CalcExpr {
+    pub fn binary(op: CalcOp, left: CalcExpr, right: CalcExpr) -> Self {
+        CalcExpr::Binary(op, Box::new(left), Box::new(right))
+    }
+    pub fn unary(op: CalcUnary, expr: CalcExpr) -> Self {
+        CalcExpr::Unary(op, Box::new(expr))
+    }
+    fn priority(&self) -> u8 {
+        match self {
+            CalcExpr::Binary(op, _, _) => op.priority(),
+            _ => u8::MAX,
+        }
+    }
+    fn fmt_operand(&self, f: &mut Formatter<'_>, paren: bool) -> std::fmt::Result {
+        if paren {
+            write!(f, "({})", self)
+        } else {
+            write!(f, "{}", self)
+        }
+    }
+}
+
+impl Display for CalcExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CalcExpr::Get(x) => write!(f, "{}", x.to_string().trim()),
+            CalcExpr::Fun(x) => Display::fmt(x, f),
+            CalcExpr::Val(x) => fmt_literal(x, f),
+            CalcExpr::Unary(op, x) => {
+                match op {
+                    CalcUnary::Neg => write!(f, "-")?,
+                    CalcUnary::Not => write!(f, "!")?,
+                }
+                x.fmt_operand(f, matches!(**x, CalcExpr::Unary(..) | CalcExpr::Binary(..)))
+            }
+            CalcExpr::Binary(op, l, r) => {
+                // 左结合：右侧同级也需要括号
+                l.fmt_operand(f, l.priority() < op.priority())?;
+                write!(f, " {} ", op.symbol())?;
+                r.fmt_operand(f, r.priority() <= op.priority())
+            }
+        }
+    }
+}
+
+fn fmt_literal(field: &DataField, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match field.get_value() {
+        Value::Digit(x) => write!(f, "{}", x),
+        Value::Float(x) => write!(f, "{:?}", x),
+        Value::Bool(x) => write!(f, "{}", x),
+        Value::Chars(x) => {
+            write!(f, "\"")?;
+            for c in x.chars() {
+                match c {
+                    '"' => write!(f, "\\\"")?,
+                    '\\' => write!(f, "\\\\")?,
+                    _ => write!(f, "{}", c)?,
+                }
+            }
+            write!(f, "\"")
+        }
+        _ => Display::fmt(field, f),
+    }
+}
+
+/// 表达式求值：算术、字符串拼接、比较与布尔运算
+#[derive(Debug, Clone, Getters)]
+pub struct CalcOperation {
+    expr: CalcExpr,
+}
+
+impl CalcOperation {
+    pub fn new(expr: CalcExpr) -> Self {
+        Self { expr }
+    }
+}
+
+impl Display for CalcOperation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.expr, f)
+    }
+}
@@ -0,0 +1,39 @@
+use crate::language::prelude::*;
+use crate::language::syntax::conditions::LogicalExpression;
+use crate::language::syntax::evaluators::PreciseEvaluator;
+
+/// 条件取值：`if <cond> { <expr> } else { <expr> }`
+///
+/// 条件沿用 SQL 风格条件语法（`status >= 500 and uri = read(path)`），左侧为记录中的字段名；
+/// 省略 `else` 且条件不成立时不输出目标字段。
+#[derive(Debug, Clone, Getters)]
+pub struct IfOperation {
+    cond: LogicalExpression,
+    then: Box<PreciseEvaluator>,
+    other: Option<Box<PreciseEvaluator>>,
+}
+
+impl IfOperation {
+    pub fn new(
+        cond: LogicalExpression,
+        then: PreciseEvaluator,
+        other: Option<PreciseEvaluator>,
+    ) -> Self {
+        Self {
+            cond,
+            then: Box::new(then),
+            other: other.map(Box::new),
+        }
+    }
+}
+
+impl Display for IfOperation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "if {} {{ {} }}", self.cond, self.then)?;
+        match self.other.as_deref() {
+            Some(x @ PreciseEvaluator::If(_)) => write!(f, " else {}", x),
+            Some(x) => write!(f, " else {{ {} }}", x),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,10 +1,14 @@
+pub mod calc;
 pub mod fmt;
+pub mod ifelse;
 pub mod map;
 pub mod matchs;
 pub mod pipe;
 pub mod record;
 pub mod sql;
+pub use calc::*;
 pub use fmt::*;
+pub use ifelse::*;
 pub use map::*;
 pub use matchs::*;
 pub use pipe::*;
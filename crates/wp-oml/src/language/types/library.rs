@@ -0,0 +1,62 @@
+use std::fmt::{Display, Formatter};
+
+use crate::language::EvalExp;
+use derive_getters::Getters;
+
+/// 可复用绑定块：`block <name> { <bindings> }`，由模型以 `use <name> ;` 引用
+#[derive(Getters, Debug, Clone)]
+pub struct OmlBlock {
+    name: String,
+    items: Vec<EvalExp>,
+}
+
+impl OmlBlock {
+    pub fn new(name: String, items: Vec<EvalExp>) -> Self {
+        Self { name, items }
+    }
+}
+
+/// OML 库文件：只包含 `import` 与 `block` 定义，加载模型时按需引入
+#[derive(Getters, Debug, Clone, Default)]
+pub struct OmlLibrary {
+    imports: Vec<String>,
+    blocks: Vec<OmlBlock>,
+}
+
+impl OmlLibrary {
+    pub(crate) fn add_import(&mut self, path: String) {
+        self.imports.push(path);
+    }
+    pub(crate) fn add_block(&mut self, block: OmlBlock) {
+        self.blocks.push(block);
+    }
+    pub fn into_blocks(self) -> Vec<OmlBlock> {
+        self.blocks
+    }
+}
+
+pub(crate) fn fmt_import(f: &mut Formatter<'_>, path: &str) -> std::fmt::Result {
+    writeln!(f, "import \"{}\" ;", path)
+}
+
+impl Display for OmlBlock {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "block {} {{", self.name)?;
+        for i in &self.items {
+            writeln!(f, "    {}", i)?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+impl Display for OmlLibrary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for path in &self.imports {
+            fmt_import(f, path)?;
+        }
+        for block in &self.blocks {
+            Display::fmt(block, f)?;
+        }
+        Ok(())
+    }
+}
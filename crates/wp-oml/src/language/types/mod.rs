@@ -1,2 +1,3 @@
+pub mod library;
 pub mod model;
 pub mod target;
@@ -1,5 +1,6 @@
 use std::fmt::{Display, Formatter};
 
+use super::library::fmt_import;
 use crate::language::EvalExp;
 use derive_getters::Getters;
 use enum_dispatch::enum_dispatch;
@@ -9,6 +10,7 @@ use wp_specs::WildArray;
 pub struct ObjModel {
     name: String,
     rules: WildArray,
+    imports: Vec<String>,
     pub items: Vec<EvalExp>,
 }
 
@@ -18,6 +20,9 @@ impl ObjModel {
             self.rules = WildArray::new1(rules);
         }
     }
+    pub(crate) fn bind_imports(&mut self, imports: Vec<String>) {
+        self.imports = imports;
+    }
 }
 
 impl ObjModel {
@@ -25,6 +30,7 @@ impl ObjModel {
         Self {
             name,
             rules: WildArray::default(),
+            imports: Vec::new(),
             items: Vec::new(),
         }
     }
@@ -38,6 +44,9 @@ impl Display for ObjModel {
                 writeln!(f, "\t{}", rule)?;
             }
         }
+        for path in &self.imports {
+            fmt_import(f, path)?;
+        }
         writeln!(f, "---")?;
         for i in &self.items {
             writeln!(f, "{}", i)?;
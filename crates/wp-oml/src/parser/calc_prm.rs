@@ -0,0 +1,232 @@
+use crate::language::{CalcExpr, CalcOp, CalcOperation, CalcUnary, FunOperation, PreciseEvaluator};
+use crate::parser::fun_prm::oml_fun_item;
+use crate::parser::oml_aggregate::oml_tdo_get;
+use crate::parser::syntax::oml_value;
+use winnow::ascii::{digit1, multispace0};
+use winnow::combinator::{alt, cut_err, fail, not, opt, peek, separated};
+use winnow::stream::Stream;
+use winnow::token::{any, literal, one_of};
+use wp_model_core::model::DataField;
+use wp_parser::Parser;
+use wp_parser::WResult;
+use wp_parser::symbol::{ctx_desc, symbol_comma};
+
+/// 表达式：`read(bytes) / 1024`、`read(a) + ":" + read(b)`、`read(code) >= 500 && read(ok)`、
+/// `read(xff) ?? read(sip)`
+///
+/// 仅在出现运算符（或括号）时成立；单一取值交由原有语法处理。
+pub fn oml_aga_calc(data: &mut &str) -> WResult<PreciseEvaluator> {
+    multispace0.parse_next(data)?;
+    let cp = data.checkpoint();
+    let paren = data.starts_with('(');
+    let expr = oml_calc_expr.parse_next(data)?;
+    if !paren && matches!(expr, CalcExpr::Get(_) | CalcExpr::Fun(_) | CalcExpr::Val(_)) {
+        data.reset(&cp);
+        return fail.context(ctx_desc("not a expression")).parse_next(data);
+    }
+    Ok(PreciseEvaluator::Calc(CalcOperation::new(expr)))
+}
+
+pub fn oml_calc_expr(data: &mut &str) -> WResult<CalcExpr> {
+    calc_binary(data, 0)
+}
+
+// 按优先级爬升解析二元运算，同级左结合
+fn calc_binary(data: &mut &str, min_priority: u8) -> WResult<CalcExpr> {
+    let mut left = calc_unary.parse_next(data)?;
+    loop {
+        let cp = data.checkpoint();
+        let op = match opt(calc_op).parse_next(data)? {
+            Some(op) if op.priority() >= min_priority => op,
+            _ => {
+                data.reset(&cp);
+                break;
+            }
+        };
+        let right = cut_err(|d: &mut &str| calc_binary(d, op.priority() + 1))
+            .context(ctx_desc(">> <expr> <op> <expr>"))
+            .parse_next(data)?;
+        left = CalcExpr::binary(op, left, right);
+    }
+    Ok(left)
+}
+
+fn calc_op(data: &mut &str) -> WResult<CalcOp> {
+    multispace0.parse_next(data)?;
+    alt((
+        "??".value(CalcOp::Coalesce),
+        "||".value(CalcOp::Or),
+        "&&".value(CalcOp::And),
+        calc_word("or").value(CalcOp::Or),
+        calc_word("and").value(CalcOp::And),
+        "==".value(CalcOp::Eq),
+        "!=".value(CalcOp::Ne),
+        "<=".value(CalcOp::Le),
+        ">=".value(CalcOp::Ge),
+        "<".value(CalcOp::Lt),
+        ">".value(CalcOp::Gt),
+        "+".value(CalcOp::Add),
+        "-".value(CalcOp::Sub),
+        "*".value(CalcOp::Mul),
+        "/".value(CalcOp::Div),
+        "%".value(CalcOp::Rem),
+    ))
+    .parse_next(data)
+}
+
+fn calc_unary(data: &mut &str) -> WResult<CalcExpr> {
+    multispace0.parse_next(data)?;
+    let op = opt(alt((
+        "-".value(CalcUnary::Neg),
+        "!".value(CalcUnary::Not),
+        calc_word("not").value(CalcUnary::Not),
+    )))
+    .parse_next(data)?;
+    match op {
+        Some(op) => {
+            let expr = cut_err(calc_unary)
+                .context(ctx_desc(">> -<expr> | !<expr>"))
+                .parse_next(data)?;
+            Ok(CalcExpr::unary(op, expr))
+        }
+        None => calc_primary.parse_next(data),
+    }
+}
+
+fn calc_primary(data: &mut &str) -> WResult<CalcExpr> {
+    multispace0.parse_next(data)?;
+    alt((
+        calc_paren,
+        calc_string.map(|x| CalcExpr::Val(DataField::from_chars("", x))),
+        calc_number.map(CalcExpr::Val),
+        calc_word("true").map(|_| CalcExpr::Val(DataField::from_bool("", true))),
+        calc_word("false").map(|_| CalcExpr::Val(DataField::from_bool("", false))),
+        calc_coalesce,
+        oml_tdo_get.map(CalcExpr::Get),
+        oml_fun_item.map(|x| CalcExpr::Fun(FunOperation::new(x))),
+        oml_value.map(CalcExpr::Val),
+    ))
+    .parse_next(data)
+}
+
+fn calc_paren(data: &mut &str) -> WResult<CalcExpr> {
+    "(".parse_next(data)?;
+    let expr = cut_err(oml_calc_expr)
+        .context(ctx_desc(">> ( <expr> )"))
+        .parse_next(data)?;
+    multispace0.parse_next(data)?;
+    cut_err(")").context(ctx_desc(">> )")).parse_next(data)?;
+    Ok(expr)
+}
+
+// coalesce(a, b, ...) 等价于 a ?? b ?? ...
+fn calc_coalesce(data: &mut &str) -> WResult<CalcExpr> {
+    (calc_word("coalesce"), multispace0, "(").parse_next(data)?;
+    let args: Vec<CalcExpr> = cut_err(separated(2.., oml_calc_expr, symbol_comma))
+        .context(ctx_desc(">> coalesce(<expr>, <expr>, ...)"))
+        .parse_next(data)?;
+    multispace0.parse_next(data)?;
+    cut_err(")").context(ctx_desc(">> )")).parse_next(data)?;
+    match args
+        .into_iter()
+        .reduce(|l, r| CalcExpr::binary(CalcOp::Coalesce, l, r))
+    {
+        Some(expr) => Ok(expr),
+        None => fail.parse_next(data),
+    }
+}
+
+fn calc_number(data: &mut &str) -> WResult<DataField> {
+    let int = digit1.parse_next(data)?;
+    let frac = opt(('.', digit1).take()).parse_next(data)?;
+    not(one_of(|c: char| c.is_alphanumeric() || c == '_')).parse_next(data)?;
+    match frac {
+        None => match int.parse::<i64>() {
+            Ok(x) => Ok(DataField::from_digit("", x)),
+            Err(_) => fail.context(ctx_desc("digit overflow")).parse_next(data),
+        },
+        Some(frac) => {
+            let x = format!("{}{}", int, frac)
+                .parse::<f64>()
+                .unwrap_or_default();
+            Ok(DataField::from_float("", x))
+        }
+    }
+}
+
+// 双引号字符串，支持 \" 与 \\ 转义
+fn calc_string(data: &mut &str) -> WResult<String> {
+    '"'.parse_next(data)?;
+    let mut out = String::new();
+    loop {
+        match cut_err(any)
+            .context(ctx_desc(">> \"...\""))
+            .parse_next(data)?
+        {
+            '"' => return Ok(out),
+            '\\' => out.push(cut_err(any).parse_next(data)?),
+            c => out.push(c),
+        }
+    }
+}
+
+// 关键字运算符（and/or/not/true/false），要求词边界
+fn calc_word<'a>(word: &'static str) -> impl FnMut(&mut &'a str) -> WResult<&'a str> {
+    move |data: &mut &'a str| {
+        let x = literal(word).parse_next(data)?;
+        peek(not(one_of(|c: char| c.is_alphanumeric() || c == '_'))).parse_next(data)?;
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::calc_prm::oml_aga_calc;
+    use crate::parser::utils::for_test::{assert_oml_parse, assert_oml_parse_ext};
+    use wp_parser::Parser;
+    use wp_parser::WResult as ModalResult;
+
+    #[test]
+    fn test_oml_calc() -> ModalResult<()> {
+        let mut code = r#"read(bytes) / 1024"#;
+        assert_oml_parse(&mut code, oml_aga_calc);
+        let mut code = r#"read(a) + ":" + read(b)"#;
+        assert_oml_parse(&mut code, oml_aga_calc);
+        let mut code = r#"(read(a) + 1) * 2 - -3 % 2"#;
+        assert_oml_parse(&mut code, oml_aga_calc);
+        let mut code = r#"read(x) { _ : digit(0) } >= 1.5 && !(read(ok) == true)"#;
+        assert_oml_parse(&mut code, oml_aga_calc);
+        let mut code = r#"read(a) - (read(b) - read(c))"#;
+        assert_oml_parse(&mut code, oml_aga_calc);
+        let mut code = r#"Now::time() - read(begin) > 3600"#;
+        assert_oml_parse(&mut code, oml_aga_calc);
+        let mut code = r#""a\"b" + "c""#;
+        assert_oml_parse(&mut code, oml_aga_calc);
+
+        // 多余括号在输出时去除
+        let mut code = r#"((read(a) * 2)) + (read(b))"#;
+        assert_oml_parse_ext(&mut code, oml_aga_calc, "read(a) * 2 + read(b)");
+        let mut code = r#"@a or @b and not @c"#;
+        assert_oml_parse_ext(&mut code, oml_aga_calc, "read(a) || read(b) && !read(c)");
+
+        let mut code = r#"read(xff) ?? read(sip) ?? "0.0.0.0""#;
+        assert_oml_parse(&mut code, oml_aga_calc);
+        let mut code = r#"coalesce(read(a), read(b) + 1, 0)"#;
+        assert_oml_parse_ext(&mut code, oml_aga_calc, "read(a) ?? read(b) + 1 ?? 0");
+        let mut code = r#"(read(a) ?? 1) * 2"#;
+        assert_oml_parse(&mut code, oml_aga_calc);
+        Ok(())
+    }
+
+    #[test]
+    fn test_oml_calc_not_expr() {
+        // 无运算符时回退，不消费输入
+        for code in ["read(a) ;", "read(a) | base64_encode ;", "chars(x) ;"] {
+            let mut input = code;
+            assert!(oml_aga_calc.parse_next(&mut input).is_err());
+            assert_eq!(input, code);
+        }
+        let mut code = "read(a) + ;";
+        assert!(oml_aga_calc.parse_next(&mut code).is_err());
+    }
+}
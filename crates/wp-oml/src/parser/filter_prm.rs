@@ -0,0 +1,98 @@
+use crate::language::{EvalExp, FieldRemove, RecordFilter};
+use crate::parser::cond::SCondParser;
+use crate::parser::if_prm::take_cond_until;
+use crate::parser::import_prm::oml_use;
+use winnow::ascii::{multispace0, multispace1};
+use winnow::combinator::{alt, cut_err, separated};
+use wp_parser::Parser;
+use wp_parser::WResult;
+use wp_parser::atom::take_wild_key;
+use wp_parser::symbol::{
+    ctx_desc, symbol_bracket_beg, symbol_bracket_end, symbol_comma, symbol_semicolon,
+};
+
+/// 模型级指令：`drop when <cond> ;`、`keep when <cond> ;`、`remove(<field|pattern>, ...) ;`、
+/// `use <block> ;`
+///
+/// 关键字后未跟 `when` / `(` 时回溯，`drop = read(x) ;` 等同名目标仍按普通绑定解析。
+pub fn oml_directive(data: &mut &str) -> WResult<EvalExp> {
+    alt((oml_filter, oml_remove, oml_use)).parse_next(data)
+}
+
+fn oml_filter(data: &mut &str) -> WResult<EvalExp> {
+    multispace0.parse_next(data)?;
+    let (kw, _, _) = (alt(("drop", "keep")), multispace1, "when").parse_next(data)?;
+    let mut cond_text = cut_err(|d: &mut &str| take_cond_until(d, ';'))
+        .context(ctx_desc(">> drop|keep when <cond> ;"))
+        .parse_next(data)?;
+    let cond = cut_err(|d: &mut &str| SCondParser::exp(d))
+        .context(ctx_desc(">> drop|keep when <cond> ;"))
+        .parse_next(&mut cond_text)?;
+    let filter = if kw == "drop" {
+        RecordFilter::Drop(cond)
+    } else {
+        RecordFilter::Keep(cond)
+    };
+    Ok(EvalExp::Filter(filter))
+}
+
+fn oml_remove(data: &mut &str) -> WResult<EvalExp> {
+    multispace0.parse_next(data)?;
+    ("remove", symbol_bracket_beg).parse_next(data)?;
+    let names: Vec<&str> = cut_err(separated(1.., take_wild_key, symbol_comma))
+        .context(ctx_desc(">> remove(<field|pattern>, ...) ;"))
+        .parse_next(data)?;
+    cut_err((symbol_bracket_end, symbol_semicolon))
+        .context(ctx_desc(">> remove(<field|pattern>, ...) ;"))
+        .parse_next(data)?;
+    Ok(EvalExp::Remove(FieldRemove::new(
+        names.into_iter().map(|x| x.to_string()).collect(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::language::EvalExp;
+    use crate::parser::filter_prm::oml_directive;
+    use crate::parser::oml_parse_raw;
+    use crate::parser::utils::for_test::{assert_oml_parse, err_of_oml};
+    use wp_parser::WResult as ModalResult;
+
+    #[test]
+    fn test_oml_directive() -> ModalResult<()> {
+        let mut code = r#"drop when uri = "/healthz" or uri = "/ping" ;"#;
+        assert_oml_parse(&mut code, oml_directive);
+
+        let mut code = r#"keep when level != "debug" and not status < 400 ;"#;
+        assert_oml_parse(&mut code, oml_directive);
+
+        let mut code = r#"remove(debug, trace_*, details[0]) ;"#;
+        assert_oml_parse(&mut code, oml_directive);
+
+        // 同名目标仍按普通绑定解析
+        let mut code = r#"
+name : test
+---
+drop = read(drop) ;
+keep_x = read(keep) ;
+removed = read(remove) ;
+drop when drop = 'y' ;
+"#;
+        let model = oml_parse_raw(&mut code)?;
+        assert_eq!(model.items.len(), 4);
+        assert!(matches!(model.items[0], EvalExp::Single(_)));
+        assert!(matches!(model.items[3], EvalExp::Filter(_)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_oml_directive_err() {
+        let mut code = r#"remove(a, b"#;
+        let e = err_of_oml(&mut code, oml_directive);
+        assert!(e.to_string().contains("remove(<field|pattern>, ...)"));
+
+        let mut code = r#"drop when a = 1"#;
+        let e = err_of_oml(&mut code, oml_directive);
+        assert!(e.to_string().contains("drop|keep when <cond>"));
+    }
+}
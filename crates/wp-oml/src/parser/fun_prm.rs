@@ -1,12 +1,14 @@
 use crate::language::{
-    BuiltinFunction, FUN_NOW_DATE, FUN_NOW_HOUR, FUN_NOW_TIME, FunOperation, NowDate, NowHour,
-    NowTime, PreciseEvaluator,
+    BuiltinFunction, FUN_NOW_DATE, FUN_NOW_HOUR, FUN_NOW_TIME, FUN_TIME_DIFF, FunOperation,
+    NowDate, NowHour, NowTime, PreciseEvaluator, TimeDiff,
 };
 use winnow::ascii::multispace0;
-use winnow::combinator::alt;
+use winnow::combinator::{alt, cut_err};
 use wp_parser::Parser;
 use wp_parser::WResult;
+use wp_parser::symbol::{ctx_desc, symbol_bracket_beg, symbol_bracket_end, symbol_comma};
 use wp_parser::utils::get_scope;
+use wpl::parser::utils::take_key;
 
 pub fn oml_gw_fun(data: &mut &str) -> WResult<PreciseEvaluator> {
     let fun = oml_fun_item.parse_next(data)?;
@@ -15,6 +17,10 @@ pub fn oml_gw_fun(data: &mut &str) -> WResult<PreciseEvaluator> {
 
 pub fn oml_fun_item(data: &mut &str) -> WResult<BuiltinFunction> {
     multispace0.parse_next(data)?;
+    alt((oml_time_diff.map(BuiltinFunction::TimeDiff), oml_now_fun)).parse_next(data)
+}
+
+fn oml_now_fun(data: &mut &str) -> WResult<BuiltinFunction> {
     let fun = alt((
         FUN_NOW_DATE.map(|_| BuiltinFunction::NowDate(NowDate::default())),
         FUN_NOW_HOUR.map(|_| BuiltinFunction::NowHour(NowHour::default())),
@@ -25,10 +31,25 @@ pub fn oml_fun_item(data: &mut &str) -> WResult<BuiltinFunction> {
     Ok(fun)
 }
 
+// Time::diff(end, start)
+fn oml_time_diff(data: &mut &str) -> WResult<TimeDiff> {
+    FUN_TIME_DIFF.parse_next(data)?;
+    let (_, end, _, start, _) = cut_err((
+        symbol_bracket_beg,
+        (multispace0, take_key).map(|(_, x)| x),
+        symbol_comma,
+        (multispace0, take_key).map(|(_, x)| x),
+        symbol_bracket_end,
+    ))
+    .context(ctx_desc(">> Time::diff(<end>, <start>)"))
+    .parse_next(data)?;
+    Ok(TimeDiff::new(end, start))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::parser::fun_prm::oml_gw_fun;
-    use crate::parser::utils::for_test::assert_oml_parse;
+    use crate::parser::utils::for_test::{assert_oml_parse, err_of_oml};
     use wp_parser::WResult as ModalResult;
 
     #[test]
@@ -45,6 +66,16 @@ mod tests {
      "#;
         assert_oml_parse(&mut code, oml_gw_fun);
 
+        let mut code = r#" Time::diff(end_at, start_at)"#;
+        assert_oml_parse(&mut code, oml_gw_fun);
+
         Ok(())
     }
+
+    #[test]
+    fn test_oml_fun_err() {
+        let mut code = r#" Time::diff(end_at)"#;
+        let e = err_of_oml(&mut code, oml_gw_fun);
+        assert!(e.to_string().contains("Time::diff(<end>, <start>)"));
+    }
 }
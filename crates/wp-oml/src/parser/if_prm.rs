@@ -0,0 +1,108 @@
+use crate::language::{IfOperation, PreciseEvaluator};
+use crate::parser::cond::SCondParser;
+use crate::parser::keyword::{kw_else, kw_gw_if};
+use crate::parser::oml_aggregate::oml_eval_way;
+use winnow::ascii::multispace0;
+use winnow::combinator::{cut_err, fail, opt};
+use wp_parser::Parser;
+use wp_parser::WResult;
+use wp_parser::symbol::{ctx_desc, symbol_brace_beg, symbol_brace_end, symbol_semicolon};
+use wpl::parser::utils::peek_str;
+
+/// `if <cond> { <expr> } [else if <cond> { <expr> }] [else { <expr> }]`
+pub fn oml_aga_if(data: &mut &str) -> WResult<PreciseEvaluator> {
+    kw_gw_if.parse_next(data)?;
+    let mut cond_text = cut_err(|d: &mut &str| take_cond_until(d, '{'))
+        .context(ctx_desc(">> if <cond> { <expr> }"))
+        .parse_next(data)?;
+    let cond = cut_err(|d: &mut &str| SCondParser::exp(d))
+        .context(ctx_desc(">> if <cond> { <expr> }"))
+        .parse_next(&mut cond_text)?;
+    let then = oml_if_body.parse_next(data)?;
+    let other = if opt(kw_else).parse_next(data)?.is_some() {
+        multispace0.parse_next(data)?;
+        if peek_str("{", data).is_ok() {
+            symbol_brace_beg.parse_next(data)?;
+            Some(oml_if_body.parse_next(data)?)
+        } else {
+            Some(
+                cut_err(oml_aga_if)
+                    .context(ctx_desc(">> else { <expr> } | else if ..."))
+                    .parse_next(data)?,
+            )
+        }
+    } else {
+        None
+    };
+    Ok(PreciseEvaluator::If(IfOperation::new(cond, then, other)))
+}
+
+// 条件文本截止到括号与引号之外的第一个 `stop`（并消费该字符），
+// 避免 `read(x) {` 被当作缺省值块；条件内需要缺省值时用括号包裹
+pub(crate) fn take_cond_until<'a>(data: &mut &'a str, stop: char) -> WResult<&'a str> {
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    for (i, c) in data.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, c) if c == stop && depth == 0 => {
+                let cond = &data[..i];
+                *data = &data[i + 1..];
+                return Ok(cond);
+            }
+            _ => {}
+        }
+    }
+    fail.parse_next(data)
+}
+
+// 分支体：`{` 之后的取值表达式，可带 `;`
+fn oml_if_body(data: &mut &str) -> WResult<PreciseEvaluator> {
+    let gw = cut_err(oml_eval_way)
+        .context(ctx_desc(">> { <expr> }"))
+        .parse_next(data)?;
+    opt(symbol_semicolon).parse_next(data)?;
+    cut_err(symbol_brace_end)
+        .context(ctx_desc(">> }"))
+        .parse_next(data)?;
+    Ok(gw)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::if_prm::oml_aga_if;
+    use crate::parser::utils::for_test::{assert_oml_parse, assert_oml_parse_ext, err_of_oml};
+    use wp_parser::WResult as ModalResult;
+
+    #[test]
+    fn test_oml_if() -> ModalResult<()> {
+        let mut code = r#"if status >= 500 { read(a) } else { read(b) }"#;
+        assert_oml_parse(&mut code, oml_aga_if);
+
+        let mut code = r#"if xff != '' and not src = read(dst) { read(xff) ; } else if code = 1 { read(sip) } else { read(a) ?? read(b) }"#;
+        assert_oml_parse_ext(
+            &mut code,
+            oml_aga_if,
+            r#"if xff != "" and not src = read(dst) { read(xff) } else if code = 1 { read(sip) } else { read(a) ?? read(b) }"#,
+        );
+
+        let mut code = r#"if (a = 1 or b = 2) { pipe read(c) | to_str }"#;
+        assert_oml_parse_ext(
+            &mut code,
+            oml_aga_if,
+            r#"if a = 1 or b = 2 { pipe read(c) | to_str }"#,
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_oml_if_err() {
+        let mut code = r#"if a = 1 { read(b) "#;
+        let e = err_of_oml(&mut code, oml_aga_if);
+        assert!(e.to_string().contains(">> }"));
+    }
+}
@@ -0,0 +1,170 @@
+use crate::language::{BlockUse, EvalExp, OmlBlock, OmlLibrary};
+use crate::parser::error::OMLCodeErrorTait;
+use crate::parser::filter_prm::oml_directive;
+use crate::parser::oml_aggregate::oml_aggregate;
+use winnow::ascii::{multispace0, multispace1};
+use winnow::combinator::{alt, cut_err, opt, peek, repeat};
+use wp_error::{OMLCodeError, OMLCodeResult};
+use wp_parser::Parser;
+use wp_parser::WResult;
+use wp_parser::atom::take_var_name;
+use wp_parser::comment::CommentParser;
+use wp_parser::symbol::{ctx_desc, symbol_brace_beg, symbol_brace_end, symbol_semicolon};
+use wpl::parser::utils::quot_str;
+
+pub fn oml_parse_lib_raw(data: &mut &str) -> WResult<OmlLibrary> {
+    oml_lib_code.parse_next(data)
+}
+
+pub fn oml_parse_lib(data: &mut &str, tag: &str) -> OMLCodeResult<OmlLibrary> {
+    match oml_lib_code.parse_next(data) {
+        Ok(o) => Ok(o),
+        Err(e) => Err(OMLCodeError::from_syntax(e, data, tag)),
+    }
+}
+
+/// 是否为库文件：去掉注释后首个记号为 `import` 或 `block`（模型文件以 `name` 开头）
+pub fn is_oml_library(code: &str) -> bool {
+    let pure = CommentParser::ignore_comment(&mut &*code).unwrap_or_else(|_| code.to_string());
+    let mut data = pure.as_str();
+    peek(oml_lib_head).parse_next(&mut data).is_ok()
+}
+
+fn oml_lib_head(data: &mut &str) -> WResult<()> {
+    multispace0.parse_next(data)?;
+    (alt(("import", "block")), multispace1).parse_next(data)?;
+    Ok(())
+}
+
+// 库文件：`import "<path>" ;` 与 `block <name> { ... }` 任意交替
+pub fn oml_lib_code(data: &mut &str) -> WResult<OmlLibrary> {
+    let mut lib = OmlLibrary::default();
+    loop {
+        multispace0.parse_next(data)?;
+        if data.is_empty() {
+            break;
+        }
+        if let Some(path) = opt(oml_import).parse_next(data)? {
+            lib.add_import(path);
+            continue;
+        }
+        let block = cut_err(oml_block)
+            .context(ctx_desc(">> import \"<path>\" ; | block <name> { ... }"))
+            .parse_next(data)?;
+        lib.add_block(block);
+    }
+    Ok(lib)
+}
+
+/// `import "<path>" ;`，路径相对于当前文件所在目录
+pub fn oml_import(data: &mut &str) -> WResult<String> {
+    oml_import_head.parse_next(data)?;
+    let path = cut_err(quot_str)
+        .context(ctx_desc(">> import \"<path>\" ;"))
+        .parse_next(data)?;
+    cut_err(symbol_semicolon)
+        .context(ctx_desc(">> import \"<path>\" ;"))
+        .parse_next(data)?;
+    Ok(path.to_string())
+}
+
+// `import` 后须紧跟引号路径，rule 列表据此判断结束
+pub(crate) fn oml_import_head(data: &mut &str) -> WResult<()> {
+    multispace0.parse_next(data)?;
+    ("import", multispace0, peek("\"")).parse_next(data)?;
+    Ok(())
+}
+
+fn oml_block(data: &mut &str) -> WResult<OmlBlock> {
+    multispace0.parse_next(data)?;
+    ("block", multispace1).parse_next(data)?;
+    let name = cut_err((take_var_name, symbol_brace_beg))
+        .context(ctx_desc(">> block <name> { <bindings> }"))
+        .map(|(x, _)| x.to_string())
+        .parse_next(data)?;
+    let items: Vec<EvalExp> = repeat(1.., alt((oml_directive, oml_aggregate))).parse_next(data)?;
+    cut_err(symbol_brace_end)
+        .context(ctx_desc(">> block <name> { <bindings> }"))
+        .parse_next(data)?;
+    Ok(OmlBlock::new(name, items))
+}
+
+/// `use <block> ;`，`use` 后未跟块名时回溯，`use = read(x) ;` 仍按普通绑定解析
+pub fn oml_use(data: &mut &str) -> WResult<EvalExp> {
+    multispace0.parse_next(data)?;
+    ("use", multispace1).parse_next(data)?;
+    let name = take_var_name.parse_next(data)?;
+    cut_err(symbol_semicolon)
+        .context(ctx_desc(">> use <block> ;"))
+        .parse_next(data)?;
+    Ok(EvalExp::Use(BlockUse::new(name.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::language::EvalExp;
+    use crate::parser::import_prm::{is_oml_library, oml_lib_code};
+    use crate::parser::oml_parse_raw;
+    use crate::parser::utils::for_test::{assert_oml_parse, err_of_oml};
+    use wp_parser::WResult as ModalResult;
+
+    #[test]
+    fn test_oml_lib() -> ModalResult<()> {
+        let mut code = r#"
+import "common.oml" ;
+block ecs_http {
+    method : chars = take(method) ;
+    status : digit = take(status) ;
+    use ecs_base ;
+}
+block severity {
+    drop when level = "trace" ;
+    version : chars = chars(1.0.0) ;
+}
+"#;
+        assert!(is_oml_library(code));
+        assert_oml_parse(&mut code, oml_lib_code);
+
+        let mut code = r#"
+name : nginx
+rule : /nginx/* /apache/*
+import "lib/ecs.oml" ;
+import "lib/geo.oml" ;
+---
+use ecs_http ;
+use = read(use) ;
+"#;
+        assert!(!is_oml_library(code));
+        let model = oml_parse_raw(&mut code)?;
+        assert_eq!(model.rules().as_ref().len(), 2);
+        assert_eq!(model.imports(), &vec!["lib/ecs.oml", "lib/geo.oml"]);
+        assert!(matches!(model.items[0], EvalExp::Use(_)));
+        assert!(matches!(model.items[1], EvalExp::Single(_)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_oml_lib_leading_comment() {
+        let code = "// 公共字段\n\nblock ecs_base {\n    host = take(host) ;\n}\n";
+        assert!(is_oml_library(code));
+        let code = "// 公共依赖\nimport \"common.oml\" ;\n";
+        assert!(is_oml_library(code));
+        let code = "// block 与 import 只出现在注释里\nname : nginx\nrule : /nginx/*\n---\nhost = take(host) ;\n";
+        assert!(!is_oml_library(code));
+    }
+
+    #[test]
+    fn test_oml_lib_err() {
+        let mut code = r#"import "a.oml""#;
+        let e = err_of_oml(&mut code, oml_lib_code);
+        assert!(e.to_string().contains("import \"<path>\" ;"));
+
+        let mut code = r#"block geo { x = read(x) ; "#;
+        let e = err_of_oml(&mut code, oml_lib_code);
+        assert!(e.to_string().contains("block <name> { <bindings> }"));
+
+        let mut code = r#"x = read(x) ;"#;
+        let e = err_of_oml(&mut code, oml_lib_code);
+        assert!(e.to_string().contains("block <name> { ... }"));
+    }
+}
@@ -220,3 +220,21 @@ pub fn kw_where(data: &mut &str) -> WResult<()> {
         .parse_next(data)?;
     Ok(())
 }
+
+pub fn kw_gw_if(data: &mut &str) -> WResult<()> {
+    let _ = multispace0.parse_next(data)?;
+    literal("if")
+        .context(StrContext::Label("oml keyword"))
+        .context(ctx_desc("need 'if' keyword"))
+        .parse_next(data)?;
+    Ok(())
+}
+
+pub fn kw_else(data: &mut &str) -> WResult<()> {
+    let _ = multispace0.parse_next(data)?;
+    literal("else")
+        .context(StrContext::Label("oml keyword"))
+        .context(ctx_desc("need 'else' keyword"))
+        .parse_next(data)?;
+    Ok(())
+}
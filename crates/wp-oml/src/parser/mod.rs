@@ -1,9 +1,13 @@
+mod calc_prm;
 pub mod code;
 mod collect_prm;
 mod cond;
 pub mod error;
+mod filter_prm;
 mod fmt_prm;
 mod fun_prm;
+mod if_prm;
+mod import_prm;
 pub mod keyword;
 mod map_prm;
 mod match_prm;
@@ -18,4 +22,5 @@ mod syntax;
 mod tdc_prm;
 mod utils;
 
+pub use import_prm::{is_oml_library, oml_parse_lib, oml_parse_lib_raw};
 pub use oml_conf::{oml_parse, oml_parse_raw};
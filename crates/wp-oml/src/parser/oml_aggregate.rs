@@ -8,13 +8,15 @@ use crate::language::NestedBinding;
 use crate::language::ReadOptionBuilder;
 use crate::language::RecordOperationBuilder;
 use crate::language::SingleEvalExpBuilder;
-use crate::language::{MatchSource, RecordOperation};
+use crate::language::{MatchSource, PreciseEvaluator, RecordOperation};
 
 use crate::language::DirectAccessor;
 use crate::language::{BatchEvalTarget, EvaluationTarget};
+use crate::parser::calc_prm::oml_aga_calc;
 use crate::parser::collect_prm::oml_aga_collect;
 use crate::parser::fmt_prm::oml_aga_fmt;
 use crate::parser::fun_prm::oml_gw_fun;
+use crate::parser::if_prm::oml_aga_if;
 use crate::parser::keyword::{kw_crate_symbol, kw_in, kw_keys, kw_option, kw_read, kw_take};
 use crate::parser::map_prm::oml_aga_map;
 use crate::parser::match_prm::oml_aga_match;
@@ -25,7 +27,7 @@ use crate::parser::syntax::oml_default;
 use crate::parser::tdc_prm::{oml_aga_tdc, oml_aga_value, oml_batch_gw_get};
 use crate::parser::{oml_acq, syntax};
 use winnow::ascii::multispace0;
-use winnow::combinator::{alt, fail, peek, repeat, separated, trace};
+use winnow::combinator::{alt, fail, opt, peek, repeat, separated, trace};
 use winnow::error::StrContext;
 use winnow::error::StrContextValue;
 use winnow::stream::Stream;
@@ -105,10 +107,11 @@ pub fn oml_aggregate(data: &mut &str) -> WResult<EvalExp> {
         .parse_next(data)?;
     symbol_assign.parse_next(data)?;
     multispace0.parse_next(data)?;
-    let key = peek(take_key).parse_next(data)?;
 
     let first_target = target_vec.first().expect("no target define");
-    let unit = if first_target.safe_name().contains('*') {
+    let batch = first_target.safe_name().contains('*');
+    let unit = if batch {
+        let key = peek(take_key).parse_next(data)?;
         let gw = match key {
             "take" => oml_batch_gw_get
                 .context(ctx_label("take"))
@@ -127,29 +130,7 @@ pub fn oml_aggregate(data: &mut &str) -> WResult<EvalExp> {
         builder.eval_way(gw);
         EvalExp::Batch(err_convert(builder.build(), "BatchEvalExp Build failed")?)
     } else {
-        let gw = match key {
-            "match" => oml_aga_match.parse_next(data)?,
-            "object" => oml_aga_map.parse_next(data)?,
-            "pipe" => oml_aga_pipe.parse_next(data)?,
-            "collect" => oml_aga_collect.parse_next(data)?,
-            //"query" => oml_aga_shmlib.parse_next(data)?,
-            "select" => oml_aga_sql.parse_next(data)?,
-            "fmt" => oml_aga_fmt.parse_next(data)?,
-            "take" => alt((pipe_prm::oml_aga_pipe_noprefix, oml_aga_tdc)).parse_next(data)?,
-            "read" => alt((pipe_prm::oml_aga_pipe_noprefix, oml_aga_tdc)).parse_next(data)?,
-            _ => alt((
-                trace("get value:", oml_aga_value),
-                trace("fun  struct:", oml_gw_fun),
-                fail.context(StrContext::Label("method"))
-                    .context(StrContext::Expected(StrContextValue::StringLiteral(
-                        "<meta>(...)",
-                    )))
-                    .context(StrContext::Expected(StrContextValue::StringLiteral(
-                        "inner fun",
-                    ))),
-            ))
-            .parse_next(data)?,
-        };
+        let gw = oml_eval_way.parse_next(data)?;
         let mut builder = SingleEvalExpBuilder::default();
         builder.target(target_vec);
         builder.eval_way(gw);
@@ -165,6 +146,41 @@ pub fn oml_aggregate(data: &mut &str) -> WResult<EvalExp> {
     Ok(unit)
 }
 
+/// 单目标的取值方式（`=` 右侧）；`if` 分支体复用
+pub fn oml_eval_way(data: &mut &str) -> WResult<PreciseEvaluator> {
+    multispace0.parse_next(data)?;
+    // 表达式（含运算符）优先；单一取值仍走下方的关键字分派
+    if let Some(gw) = opt(oml_aga_calc).parse_next(data)? {
+        return Ok(gw);
+    }
+    let key = peek(take_key).parse_next(data)?;
+    let gw = match key {
+        "match" => oml_aga_match.parse_next(data)?,
+        "object" => oml_aga_map.parse_next(data)?,
+        "pipe" => oml_aga_pipe.parse_next(data)?,
+        "collect" => oml_aga_collect.parse_next(data)?,
+        //"query" => oml_aga_shmlib.parse_next(data)?,
+        "select" => oml_aga_sql.parse_next(data)?,
+        "fmt" => oml_aga_fmt.parse_next(data)?,
+        "if" => oml_aga_if.parse_next(data)?,
+        "take" => alt((pipe_prm::oml_aga_pipe_noprefix, oml_aga_tdc)).parse_next(data)?,
+        "read" => alt((pipe_prm::oml_aga_pipe_noprefix, oml_aga_tdc)).parse_next(data)?,
+        _ => alt((
+            trace("get value:", oml_aga_value),
+            trace("fun  struct:", oml_gw_fun),
+            fail.context(StrContext::Label("method"))
+                .context(StrContext::Expected(StrContextValue::StringLiteral(
+                    "<meta>(...)",
+                )))
+                .context(StrContext::Expected(StrContextValue::StringLiteral(
+                    "inner fun",
+                ))),
+        ))
+        .parse_next(data)?,
+    };
+    Ok(gw)
+}
+
 pub fn oml_aggregate_sub(data: &mut &str) -> WResult<Vec<NestedBinding>> {
     let targets = oml_target_vec_same_meta.parse_next(data)?;
 
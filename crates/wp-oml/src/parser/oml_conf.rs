@@ -1,9 +1,11 @@
 use crate::language::{EvalExp, ObjModel};
 use crate::parser::error::OMLCodeErrorTait;
+use crate::parser::filter_prm::oml_directive;
+use crate::parser::import_prm::{oml_import, oml_import_head};
 use crate::parser::keyword::{kw_head_sep_line, kw_oml_name};
 use crate::parser::oml_aggregate::oml_aggregate;
 use winnow::ascii::multispace0;
-use winnow::combinator::{opt, repeat};
+use winnow::combinator::{alt, not, opt, preceded, repeat};
 use winnow::error::StrContext;
 use wp_error::{OMLCodeError, OMLCodeResult};
 use wp_parser::Parser;
@@ -31,8 +33,11 @@ pub fn oml_conf_code(data: &mut &str) -> WResult<ObjModel> {
     let rules = opt(oml_conf_rules).parse_next(data)?;
     debug_data!("obj model: rules loaded!");
     a_items.bind_rules(rules);
+    let imports = repeat(0.., oml_import).parse_next(data)?;
+    a_items.bind_imports(imports);
     kw_head_sep_line.parse_next(data)?;
-    let mut items: Vec<EvalExp> = repeat(1.., oml_aggregate).parse_next(data)?;
+    let mut items: Vec<EvalExp> =
+        repeat(1.., alt((oml_directive, oml_aggregate))).parse_next(data)?;
     debug_data!("obj model: aggregate item  loaded!");
     //repeat(1.., terminated(oml_aggregate, symbol_semicolon)).parse_next(data)?;
     a_items.items.append(&mut items);
@@ -61,7 +66,8 @@ pub fn oml_conf_head(data: &mut &str) -> WResult<String> {
 pub fn oml_conf_rules(data: &mut &str) -> WResult<Vec<String>> {
     multispace0.parse_next(data)?;
     let (_, _) = (kw_oml_rule, symbol_colon).parse_next(data)?;
-    let rules: Vec<&str> = repeat(0.., take_obj_wild_path).parse_next(data)?;
+    let rules: Vec<&str> =
+        repeat(0.., preceded(not(oml_import_head), take_obj_wild_path)).parse_next(data)?;
     Ok(rules.into_iter().map(|s| s.to_string()).collect())
 }
 
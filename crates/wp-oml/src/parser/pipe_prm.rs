@@ -1,5 +1,11 @@
 use std::str::FromStr;
 
+use crate::language::{
+    ArrContains, ArrDistinct, ArrFilter, ArrFirst, ArrLambda, ArrLast, ArrMap, ArrSort, ObjFlatten,
+    ObjKeys, ObjMerge, ObjUnflatten, ObjValues, PIPE_CONTAINS, PIPE_DISTINCT, PIPE_FILTER,
+    PIPE_FIRST, PIPE_FLATTEN, PIPE_KEYS, PIPE_LAST, PIPE_MAP, PIPE_MERGE, PIPE_SORT,
+    PIPE_UNFLATTEN, PIPE_VALUES,
+};
 use crate::language::{
     Base64Decode, EncodeType, Get, HtmlEscape, HtmlUnescape, JsonEscape, JsonUnescape, Nth,
     PIPE_BASE64_DECODE, PIPE_GET, PIPE_HTML_ESCAPE, PIPE_HTML_UNESCAPE, PIPE_JSON_ESCAPE,
@@ -9,20 +15,40 @@ use crate::language::{
     TimeToTsUs, TimeToTsZone, ToJson, UrlGet, UrlType,
 };
 use crate::language::{Base64Encode, PIPE_BASE64_ENCODE, PIPE_TO_STR, ToStr};
+use crate::language::{
+    HmacSha256, Mask, Md5Hash, PIPE_HMAC_SHA256, PIPE_MASK, PIPE_MD5, PIPE_PSEUDONYMIZE,
+    PIPE_REDACT, PIPE_SHA1, PIPE_SHA256, Pseudonymize, Redact, Sha1Hash, Sha256Hash,
+};
 use crate::language::{Ip4ToInt, PIPE_IP4_TO_INT, PiPeOperation, PipeFun};
+use crate::language::{
+    Join, Lower, PIPE_JOIN, PIPE_LEN, PIPE_LOWER, PIPE_PAD, PIPE_REGEX_EXTRACT, PIPE_REGEX_REPLACE,
+    PIPE_REPLACE, PIPE_SPLIT, PIPE_SUBSTR, PIPE_TRIM, PIPE_UPPER, Pad, PadSide, RegexExtract,
+    RegexReplace, Replace, Split, StrLen, Substr, Trim, Upper,
+};
+use crate::language::{
+    PIPE_TIME_ADD, PIPE_TIME_FORMAT, PIPE_TIME_HOUR, PIPE_TIME_PARSE, PIPE_TIME_TO_ZONE,
+    PIPE_TIME_TRUNC, PIPE_TIME_WEEKDAY, TimeAdd, TimeFormat, TimeHour, TimeParse, TimeToZone,
+    TimeTrunc, TimeTruncUnit, TimeWeekday,
+};
+use crate::parser::calc_prm::oml_calc_expr;
 use crate::parser::keyword::kw_gw_pipe;
 use crate::parser::oml_aggregate::oml_var_get;
 use crate::winnow::error::ParserError;
+use chrono::format::{Item, StrftimeItems};
+use chrono_tz::Tz;
+use regex::Regex;
 use winnow::ascii::{alphanumeric0, digit1, multispace0};
-use winnow::combinator::{alt, fail, opt, repeat};
+use winnow::combinator::{alt, cut_err, fail, opt, repeat};
 use winnow::error::{ContextError, ErrMode, StrContext};
 use winnow::stream::Stream; // for checkpoint/reset on &str
+use wp_model_core::model::DataField;
 use wp_parser::Parser;
 use wp_parser::WResult;
+use wp_parser::atom::take_var_name;
 use wp_parser::fun::fun_trait::{Fun1Builder, Fun2Builder};
 use wp_parser::fun::parser;
-use wp_parser::symbol::{ctx_desc, symbol_pipe};
-use wpl::parser::utils::take_key;
+use wp_parser::symbol::{ctx_desc, symbol_comma, symbol_pipe};
+use wpl::parser::utils::{decode_escapes, quot_r_str, quot_str, take_key};
 
 impl Fun1Builder for Nth {
     type ARG1 = usize;
@@ -165,6 +191,578 @@ impl Fun1Builder for UrlGet {
         UrlGet { key: args }
     }
 }
+// 字符串参数："..."（支持转义）或 r#"..."#（原样）
+fn pipe_str_arg(data: &mut &str) -> WResult<String> {
+    multispace0.parse_next(data)?;
+    alt((quot_r_str.map(String::from), quot_str.map(decode_escapes))).parse_next(data)
+}
+
+fn pipe_regex_arg(data: &mut &str) -> WResult<Regex> {
+    let pattern = pipe_str_arg.parse_next(data)?;
+    match Regex::new(&pattern) {
+        Ok(re) => Ok(re),
+        Err(e) => {
+            warn_data!("invalid regex '{}': {}", pattern, e);
+            cut_err(fail)
+                .context(ctx_desc("invalid regex pattern"))
+                .parse_next(data)
+        }
+    }
+}
+
+fn pipe_usize_arg(data: &mut &str) -> WResult<usize> {
+    multispace0.parse_next(data)?;
+    let val = digit1.parse_next(data)?;
+    Ok(val.parse::<usize>().unwrap_or(0))
+}
+
+fn pipe_next_arg(data: &mut &str) -> WResult<()> {
+    (multispace0, symbol_comma, multispace0).parse_next(data)?;
+    Ok(())
+}
+
+impl Fun1Builder for Substr {
+    type ARG1 = (i64, Option<usize>);
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        let sign = opt("-").parse_next(data)?;
+        let start = pipe_usize_arg.parse_next(data)? as i64;
+        let start = if sign.is_some() { -start } else { start };
+        let len = opt((pipe_next_arg, pipe_usize_arg).map(|(_, x)| x)).parse_next(data)?;
+        Ok((start, len))
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_SUBSTR
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        Substr {
+            start: args.0,
+            len: args.1,
+        }
+    }
+}
+
+impl Fun2Builder for Replace {
+    type ARG1 = String;
+    type ARG2 = String;
+    fn fun_name() -> &'static str {
+        PIPE_REPLACE
+    }
+    fn args1(data: &mut &str) -> WResult<String> {
+        pipe_str_arg.parse_next(data)
+    }
+    fn args2(data: &mut &str) -> WResult<String> {
+        pipe_str_arg.parse_next(data)
+    }
+    fn build(args: (String, String)) -> Self {
+        Replace {
+            from: args.0,
+            to: args.1,
+        }
+    }
+}
+
+impl Fun2Builder for RegexReplace {
+    type ARG1 = Regex;
+    type ARG2 = String;
+    fn fun_name() -> &'static str {
+        PIPE_REGEX_REPLACE
+    }
+    fn args1(data: &mut &str) -> WResult<Regex> {
+        pipe_regex_arg.parse_next(data)
+    }
+    fn args2(data: &mut &str) -> WResult<String> {
+        pipe_str_arg.parse_next(data)
+    }
+    fn build(args: (Regex, String)) -> Self {
+        RegexReplace {
+            pattern: args.0,
+            to: args.1,
+        }
+    }
+}
+
+impl Fun2Builder for RegexExtract {
+    type ARG1 = Regex;
+    type ARG2 = usize;
+    fn fun_name() -> &'static str {
+        PIPE_REGEX_EXTRACT
+    }
+    fn args1(data: &mut &str) -> WResult<Regex> {
+        pipe_regex_arg.parse_next(data)
+    }
+    fn args2(data: &mut &str) -> WResult<usize> {
+        pipe_usize_arg.parse_next(data)
+    }
+    fn build(args: (Regex, usize)) -> Self {
+        RegexExtract {
+            pattern: args.0,
+            group: args.1,
+        }
+    }
+}
+
+impl Fun1Builder for Split {
+    type ARG1 = String;
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        pipe_str_arg.parse_next(data)
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_SPLIT
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        Split { sep: args }
+    }
+}
+
+impl Fun1Builder for Join {
+    type ARG1 = String;
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        pipe_str_arg.parse_next(data)
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_JOIN
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        Join { sep: args }
+    }
+}
+
+impl Fun1Builder for Pad {
+    type ARG1 = (usize, char, PadSide);
+    // pad(width [, "fill" [, left|right]])
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        let width = pipe_usize_arg.parse_next(data)?;
+        let fill = opt((pipe_next_arg, pipe_str_arg).map(|(_, x)| x)).parse_next(data)?;
+        let fill = match fill {
+            None => ' ',
+            Some(x) if x.chars().count() == 1 => x.chars().next().unwrap_or(' '),
+            Some(_) => {
+                return cut_err(fail)
+                    .context(ctx_desc("pad fill must be one char"))
+                    .parse_next(data);
+            }
+        };
+        let side = opt((
+            pipe_next_arg,
+            alt((
+                "left".map(|_| PadSide::Left),
+                "right".map(|_| PadSide::Right),
+            )),
+        ))
+        .parse_next(data)?;
+        Ok((width, fill, side.map(|(_, x)| x).unwrap_or_default()))
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_PAD
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        Pad {
+            width: args.0,
+            fill: args.1,
+            side: args.2,
+        }
+    }
+}
+
+// 密钥名参数：只允许标识符，不接受字面量密钥
+fn pipe_secret_arg(data: &mut &str) -> WResult<String> {
+    multispace0.parse_next(data)?;
+    if opt(alt(("\"", "r#\""))).parse_next(data)?.is_some() {
+        return cut_err(fail)
+            .context(ctx_desc("secret must be a key name, not a literal"))
+            .parse_next(data);
+    }
+    Ok(take_key(data)?.to_string())
+}
+
+impl Fun1Builder for HmacSha256 {
+    type ARG1 = String;
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        pipe_secret_arg.parse_next(data)
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_HMAC_SHA256
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        HmacSha256 { key_env: args }
+    }
+}
+
+impl Fun1Builder for Pseudonymize {
+    type ARG1 = String;
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        pipe_secret_arg.parse_next(data)
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_PSEUDONYMIZE
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        Pseudonymize { key_env: args }
+    }
+}
+
+impl Fun1Builder for Mask {
+    type ARG1 = (usize, usize, char);
+    // mask(keep_prefix, keep_suffix [, "c"])
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        let prefix = pipe_usize_arg.parse_next(data)?;
+        pipe_next_arg.parse_next(data)?;
+        let suffix = pipe_usize_arg.parse_next(data)?;
+        let mask = opt((pipe_next_arg, pipe_str_arg).map(|(_, x)| x)).parse_next(data)?;
+        let mask = match mask {
+            None => '*',
+            Some(x) if x.chars().count() == 1 => x.chars().next().unwrap_or('*'),
+            Some(_) => {
+                return cut_err(fail)
+                    .context(ctx_desc("mask char must be one char"))
+                    .parse_next(data);
+            }
+        };
+        Ok((prefix, suffix, mask))
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_MASK
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        Mask {
+            keep_prefix: args.0,
+            keep_suffix: args.1,
+            mask: args.2,
+        }
+    }
+}
+
+// 单参 lambda：`x => <expr>`
+fn pipe_lambda_arg(data: &mut &str) -> WResult<ArrLambda> {
+    multispace0.parse_next(data)?;
+    let var = take_var_name.parse_next(data)?;
+    (multispace0, "=>").parse_next(data)?;
+    let body = cut_err(oml_calc_expr)
+        .context(ctx_desc(">> <var> => <expr>"))
+        .parse_next(data)?;
+    Ok(ArrLambda {
+        var: var.to_string(),
+        body,
+    })
+}
+
+// 字面量参数：字符串、整数或 true/false
+fn pipe_literal_arg(data: &mut &str) -> WResult<DataField> {
+    multispace0.parse_next(data)?;
+    alt((
+        pipe_str_arg.map(|x| DataField::from_chars("", x)),
+        "true".map(|_| DataField::from_bool("", true)),
+        "false".map(|_| DataField::from_bool("", false)),
+        (opt("-"), digit1)
+            .take()
+            .map(|x: &str| DataField::from_digit("", x.parse::<i64>().unwrap_or(0))),
+    ))
+    .parse_next(data)
+}
+
+impl Fun1Builder for ArrMap {
+    type ARG1 = ArrLambda;
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        pipe_lambda_arg.parse_next(data)
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_MAP
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        ArrMap { lambda: args }
+    }
+}
+
+impl Fun1Builder for ArrFilter {
+    type ARG1 = ArrLambda;
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        pipe_lambda_arg.parse_next(data)
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_FILTER
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        ArrFilter { lambda: args }
+    }
+}
+
+impl Fun1Builder for ArrContains {
+    type ARG1 = DataField;
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        pipe_literal_arg.parse_next(data)
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_CONTAINS
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        ArrContains { value: args }
+    }
+}
+
+impl Fun1Builder for ObjFlatten {
+    type ARG1 = String;
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        pipe_str_arg.parse_next(data)
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_FLATTEN
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        ObjFlatten { sep: args }
+    }
+}
+
+impl Fun1Builder for ObjUnflatten {
+    type ARG1 = String;
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        pipe_str_arg.parse_next(data)
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_UNFLATTEN
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        ObjUnflatten { sep: args }
+    }
+}
+
+// 时区参数：IANA 名称，如 "Asia/Shanghai"、"UTC"
+fn pipe_zone_arg(data: &mut &str) -> WResult<Tz> {
+    let name = pipe_str_arg.parse_next(data)?;
+    match Tz::from_str(&name) {
+        Ok(tz) => Ok(tz),
+        Err(e) => {
+            warn_data!("invalid time zone '{}': {}", name, e);
+            cut_err(fail)
+                .context(ctx_desc("invalid IANA time zone"))
+                .parse_next(data)
+        }
+    }
+}
+
+// strftime 格式参数，解析期校验占位符
+fn pipe_strftime_arg(data: &mut &str) -> WResult<String> {
+    let pattern = pipe_str_arg.parse_next(data)?;
+    if StrftimeItems::new(&pattern).any(|x| matches!(x, Item::Error)) {
+        warn_data!("invalid time format '{}'", pattern);
+        return cut_err(fail)
+            .context(ctx_desc("invalid time format"))
+            .parse_next(data);
+    }
+    Ok(pattern)
+}
+
+// 时长参数：`1h30m`、`-7d`、`500ms`，换算为毫秒
+fn pipe_duration_arg(data: &mut &str) -> WResult<i64> {
+    multispace0.parse_next(data)?;
+    let sign = opt("-").parse_next(data)?;
+    let parts: Vec<(&str, &str)> =
+        repeat(1.., (digit1, alt(("ms", "d", "h", "m", "s")))).parse_next(data)?;
+    let millis = parts.into_iter().try_fold(0_i64, |acc, (num, unit)| {
+        let size = match unit {
+            "d" => 86_400_000,
+            "h" => 3_600_000,
+            "m" => 60_000,
+            "s" => 1_000,
+            _ => 1,
+        };
+        num.parse::<i64>()
+            .ok()
+            .and_then(|x| x.checked_mul(size))
+            .and_then(|x| acc.checked_add(x))
+    });
+    match millis {
+        Some(x) if sign.is_some() => Ok(-x),
+        Some(x) => Ok(x),
+        None => cut_err(fail)
+            .context(ctx_desc("duration out of range"))
+            .parse_next(data),
+    }
+}
+
+impl Fun1Builder for TimeFormat {
+    type ARG1 = (String, Option<Tz>);
+    // Time::format("pattern" [, "zone"])
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        let pattern = pipe_strftime_arg.parse_next(data)?;
+        let zone = opt((pipe_next_arg, pipe_zone_arg).map(|(_, x)| x)).parse_next(data)?;
+        Ok((pattern, zone))
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_TIME_FORMAT
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        TimeFormat {
+            pattern: args.0,
+            zone: args.1,
+        }
+    }
+}
+
+impl Fun1Builder for TimeParse {
+    type ARG1 = String;
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        pipe_strftime_arg.parse_next(data)
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_TIME_PARSE
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        TimeParse { pattern: args }
+    }
+}
+
+impl Fun1Builder for TimeTrunc {
+    type ARG1 = TimeTruncUnit;
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        multispace0.parse_next(data)?;
+        alt((
+            "minute".map(|_| TimeTruncUnit::Minute),
+            "hour".map(|_| TimeTruncUnit::Hour),
+            "day".map(|_| TimeTruncUnit::Day),
+        ))
+        .parse_next(data)
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_TIME_TRUNC
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        TimeTrunc { unit: args }
+    }
+}
+
+impl Fun1Builder for TimeAdd {
+    type ARG1 = i64;
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        pipe_duration_arg.parse_next(data)
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_TIME_ADD
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        TimeAdd { millis: args }
+    }
+}
+
+impl Fun1Builder for TimeToZone {
+    type ARG1 = (Tz, Option<Tz>);
+    // Time::to_zone("zone" [, "from"])
+    fn args1(data: &mut &str) -> WResult<Self::ARG1> {
+        let zone = pipe_zone_arg.parse_next(data)?;
+        let from = opt((pipe_next_arg, pipe_zone_arg).map(|(_, x)| x)).parse_next(data)?;
+        Ok((zone, from))
+    }
+
+    fn fun_name() -> &'static str {
+        PIPE_TIME_TO_ZONE
+    }
+
+    fn build(args: Self::ARG1) -> Self {
+        TimeToZone {
+            zone: args.0,
+            from: args.1,
+        }
+    }
+}
+
+// 时间函数
+fn oml_pipe_time(data: &mut &str) -> WResult<PipeFun> {
+    alt((
+        parser::call_fun_args1::<TimeFormat>.map(PipeFun::TimeFormat),
+        parser::call_fun_args1::<TimeParse>.map(PipeFun::TimeParse),
+        parser::call_fun_args1::<TimeTrunc>.map(PipeFun::TimeTrunc),
+        parser::call_fun_args1::<TimeAdd>.map(PipeFun::TimeAdd),
+        parser::call_fun_args1::<TimeToZone>.map(PipeFun::TimeToZone),
+        PIPE_TIME_WEEKDAY.map(|_| PipeFun::TimeWeekday(TimeWeekday::default())),
+        PIPE_TIME_HOUR.map(|_| PipeFun::TimeHour(TimeHour::default())),
+    ))
+    .parse_next(data)
+}
+
+// 数组与对象函数
+fn oml_pipe_collection(data: &mut &str) -> WResult<PipeFun> {
+    alt((
+        parser::call_fun_args1::<ArrMap>.map(PipeFun::ArrMap),
+        parser::call_fun_args1::<ArrFilter>.map(PipeFun::ArrFilter),
+        parser::call_fun_args1::<ArrContains>.map(PipeFun::ArrContains),
+        parser::call_fun_args1::<ObjFlatten>.map(PipeFun::ObjFlatten),
+        parser::call_fun_args1::<ObjUnflatten>.map(PipeFun::ObjUnflatten),
+        PIPE_FLATTEN.map(|_| PipeFun::ObjFlatten(ObjFlatten::default())),
+        PIPE_UNFLATTEN.map(|_| PipeFun::ObjUnflatten(ObjUnflatten::default())),
+        PIPE_DISTINCT.map(|_| PipeFun::ArrDistinct(ArrDistinct::default())),
+        PIPE_SORT.map(|_| PipeFun::ArrSort(ArrSort::default())),
+        PIPE_FIRST.map(|_| PipeFun::ArrFirst(ArrFirst::default())),
+        PIPE_LAST.map(|_| PipeFun::ArrLast(ArrLast::default())),
+        PIPE_KEYS.map(|_| PipeFun::ObjKeys(ObjKeys::default())),
+        PIPE_VALUES.map(|_| PipeFun::ObjValues(ObjValues::default())),
+        PIPE_MERGE.map(|_| PipeFun::ObjMerge(ObjMerge::default())),
+    ))
+    .parse_next(data)
+}
+
+// 摘要与脱敏函数
+fn oml_pipe_privacy(data: &mut &str) -> WResult<PipeFun> {
+    alt((
+        parser::call_fun_args1::<HmacSha256>.map(PipeFun::HmacSha256),
+        parser::call_fun_args1::<Pseudonymize>.map(PipeFun::Pseudonymize),
+        parser::call_fun_args1::<Mask>.map(PipeFun::Mask),
+        PIPE_MD5.map(|_| PipeFun::Md5Hash(Md5Hash::default())),
+        PIPE_SHA1.map(|_| PipeFun::Sha1Hash(Sha1Hash::default())),
+        PIPE_SHA256.map(|_| PipeFun::Sha256Hash(Sha256Hash::default())),
+        PIPE_REDACT.map(|_| PipeFun::Redact(Redact::default())),
+    ))
+    .parse_next(data)
+}
+
+// 字符串处理函数
+fn oml_pipe_str(data: &mut &str) -> WResult<PipeFun> {
+    alt((
+        parser::call_fun_args1::<Substr>.map(PipeFun::Substr),
+        parser::call_fun_args2::<Replace>.map(PipeFun::Replace),
+        parser::call_fun_args2::<RegexReplace>.map(PipeFun::RegexReplace),
+        parser::call_fun_args2::<RegexExtract>.map(PipeFun::RegexExtract),
+        parser::call_fun_args1::<Split>.map(PipeFun::Split),
+        parser::call_fun_args1::<Join>.map(PipeFun::Join),
+        parser::call_fun_args1::<Pad>.map(PipeFun::Pad),
+        PIPE_LOWER.map(|_| PipeFun::Lower(Lower::default())),
+        PIPE_UPPER.map(|_| PipeFun::Upper(Upper::default())),
+        PIPE_TRIM.map(|_| PipeFun::Trim(Trim::default())),
+        PIPE_LEN.map(|_| PipeFun::StrLen(StrLen::default())),
+    ))
+    .parse_next(data)
+}
+
 pub fn oml_aga_pipe(data: &mut &str) -> WResult<PreciseEvaluator> {
     kw_gw_pipe.parse_next(data)?;
     let from = oml_var_get.parse_next(data)?;
@@ -208,6 +806,12 @@ pub fn oml_pipe(data: &mut &str) -> WResult<PipeFun> {
         PIPE_TO_STR.map(|_| PipeFun::ToStr(ToStr::default())),
         PIPE_SKIP_EMPTY.map(|_| PipeFun::SkipEmpty(SkipEmpty::default())),
         PIPE_IP4_TO_INT.map(|_| PipeFun::Ip4ToInt(Ip4ToInt::default())),
+        alt((
+            oml_pipe_time,
+            oml_pipe_str,
+            oml_pipe_privacy,
+            oml_pipe_collection,
+        )),
     ))
     .context(StrContext::Label("pipe fun"))
     .context(ctx_desc("fun not found!"))
@@ -218,7 +822,7 @@ pub fn oml_pipe(data: &mut &str) -> WResult<PipeFun> {
 #[cfg(test)]
 mod tests {
     use crate::parser::pipe_prm::oml_aga_pipe;
-    use crate::parser::utils::for_test::{assert_oml_parse, err_of_oml};
+    use crate::parser::utils::for_test::{assert_oml_parse, assert_oml_parse_ext, err_of_oml};
     use wp_parser::WResult;
 
     #[test]
@@ -246,6 +850,71 @@ mod tests {
 
         let mut code = r#" pipe take(ip) | url(host)"#;
         assert_oml_parse(&mut code, oml_aga_pipe);
+
+        let mut code = r#" pipe take(ua) | trim | lower | upper | len"#;
+        assert_oml_parse(&mut code, oml_aga_pipe);
+
+        let mut code = r#" pipe take(path) | substr(-3) | substr(1,2) | replace("a\"b", "c")"#;
+        assert_oml_parse_ext(
+            &mut code,
+            oml_aga_pipe,
+            r##"pipe take(path) | substr(-3) | substr(1,2) | replace(r#"a"b"#, "c")"##,
+        );
+
+        let mut code =
+            r##" pipe take(path) | regex_replace(r#"\d+"#, "$1") | regex_extract(r#"v(\d)"#, 1)"##;
+        assert_oml_parse(&mut code, oml_aga_pipe);
+
+        let mut code =
+            r#" pipe take(tags) | split(",") | join(";") | pad(8," ",left) | pad(4,"0",right)"#;
+        assert_oml_parse(&mut code, oml_aga_pipe);
+
+        let mut code = r#" pipe take(tags) | pad(8)"#;
+        assert_oml_parse_ext(
+            &mut code,
+            oml_aga_pipe,
+            r#"pipe take(tags) | pad(8," ",left)"#,
+        );
+
+        let mut code = r#" pipe take(phone) | md5 | sha1 | sha256 | hmac_sha256(PII_KEY) | redact"#;
+        assert_oml_parse(&mut code, oml_aga_pipe);
+
+        let mut code =
+            r##" pipe take(phone) | mask(3, 4) | mask(1,0,"#") | pseudonymize(PII_KEY)"##;
+        assert_oml_parse_ext(
+            &mut code,
+            oml_aga_pipe,
+            r##"pipe take(phone) | mask(3,4,"*") | mask(1,0,"#") | pseudonymize(PII_KEY)"##,
+        );
+
+        let mut code = r#" pipe take(ports) | map(x => read(x) + 1) | filter(p => read(p) >= 443 && read(p) != 8080) | distinct | sort | first"#;
+        assert_oml_parse(&mut code, oml_aga_pipe);
+
+        let mut code = r#" pipe take(tags) | last | contains("a") | contains(-1)"#;
+        assert_oml_parse(&mut code, oml_aga_pipe);
+
+        let mut code = r#" pipe take(ext) | keys | values | merge | flatten | unflatten("/")"#;
+        assert_oml_parse_ext(
+            &mut code,
+            oml_aga_pipe,
+            r#"pipe take(ext) | keys | values | merge | flatten(".") | unflatten("/")"#,
+        );
+
+        let mut code = r#" pipe take(ts) | Time::parse("%d/%b/%Y:%H:%M:%S %z") | Time::to_zone("Asia/Shanghai") | Time::format("%Y-%m-%d")"#;
+        assert_oml_parse(&mut code, oml_aga_pipe);
+
+        let mut code = r#" pipe take(ts) | Time::to_zone("UTC", "America/New_York") | Time::format("%H:%M %Z", "Europe/Berlin")"#;
+        assert_oml_parse(&mut code, oml_aga_pipe);
+
+        let mut code = r#" pipe take(ts) | Time::trunc(day) | Time::add(90m) | Time::add(-1d0h500ms) | Time::weekday"#;
+        assert_oml_parse_ext(
+            &mut code,
+            oml_aga_pipe,
+            r#"pipe take(ts) | Time::trunc(day) | Time::add(1h30m) | Time::add(-1d500ms) | Time::weekday"#,
+        );
+
+        let mut code = r#" pipe take(ts) | Time::trunc(minute) | Time::hour"#;
+        assert_oml_parse(&mut code, oml_aga_pipe);
         Ok(())
     }
     #[test]
@@ -259,5 +928,25 @@ mod tests {
         let e = err_of_oml(&mut code, oml_aga_pipe);
         println!("err:{}, \nwhere:{}", e, code);
         assert!(e.to_string().contains("need 'pipe' keyword"));
+
+        let mut code = r#" pipe take(ip) | regex_extract("(", 0)"#;
+        let e = err_of_oml(&mut code, oml_aga_pipe);
+        assert!(e.to_string().contains("invalid regex"));
+
+        let mut code = r#" pipe take(ip) | hmac_sha256("abc")"#;
+        let e = err_of_oml(&mut code, oml_aga_pipe);
+        assert!(e.to_string().contains("key name"));
+
+        let mut code = r#" pipe take(ports) | map(x => )"#;
+        let e = err_of_oml(&mut code, oml_aga_pipe);
+        assert!(e.to_string().contains("<var> => <expr>"));
+
+        let mut code = r#" pipe take(ts) | Time::to_zone("Mars/Olympus")"#;
+        let e = err_of_oml(&mut code, oml_aga_pipe);
+        assert!(e.to_string().contains("invalid IANA time zone"));
+
+        let mut code = r#" pipe take(ts) | Time::format("%Y-%Q")"#;
+        let e = err_of_oml(&mut code, oml_aga_pipe);
+        assert!(e.to_string().contains("invalid time format"));
     }
 }
@@ -158,6 +158,115 @@ fn rewrite_lhs_fn_eq_literal(s: &str) -> Option<String> {
     Some(format!("{} = {}", rhs, lhs))
 }
 
+/// Find the next standalone `in` keyword (case-insensitive), skipping quoted literals.
+fn find_in_kw(s: &str) -> Option<usize> {
+    let bytes = s.as_bytes();
+    let mut quote: Option<u8> = None;
+    for (pos, c) in bytes.iter().enumerate() {
+        match quote {
+            Some(q) if *c == q => quote = None,
+            Some(_) => {}
+            None if *c == b'\'' || *c == b'"' => quote = Some(*c),
+            None => {
+                let before = pos > 0 && bytes[pos - 1].is_ascii_whitespace();
+                let after = bytes
+                    .get(pos + 2)
+                    .is_some_and(|c| c.is_ascii_whitespace() || *c == b'(');
+                if before && after && bytes[pos..pos + 2].eq_ignore_ascii_case(b"in") {
+                    return Some(pos);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Length of a leading `read(..)` / `take(..)` accessor, including the closing ')'.
+fn in_acq_len(s: &str) -> Option<usize> {
+    let rest = s.strip_prefix("read").or_else(|| s.strip_prefix("take"))?;
+    let open = s.len() - rest.trim_start().len();
+    if !s[open..].starts_with('(') {
+        return None;
+    }
+    let close = s[open..].find(')')?;
+    Some(open + close + 1)
+}
+
+/// Placeholder column for the n-th IN list; unique, so only that comparison is restored.
+fn in_marker(n: usize) -> String {
+    format!("__in{}__", n)
+}
+
+/// Rewrite `<col> in read(x)` and `<col> in (read(x))` to `<marker> = read(x)` so the generic
+/// cond parser accepts them; returns the IN columns in order, the n-th one standing behind
+/// `in_marker(n)`. An array value is expanded into a placeholder list at runtime.
+fn rewrite_in_lists(s: &str) -> (String, Vec<String>) {
+    let mut out = String::with_capacity(s.len());
+    let mut cols = Vec::new();
+    let mut rest = s;
+    while let Some(pos) = find_in_kw(rest) {
+        let head = rest[..pos].trim_end();
+        let col = head
+            .rsplit(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+            .next()
+            .unwrap_or_default();
+        let after = rest[pos + 2..].trim_start();
+        let acq = match after.strip_prefix('(') {
+            Some(inner) => {
+                let inner = inner.trim_start();
+                in_acq_len(inner).and_then(|n| {
+                    let tail = inner[n..].trim_start().strip_prefix(')')?;
+                    Some((&inner[..n], tail))
+                })
+            }
+            None => in_acq_len(after).map(|n| (&after[..n], &after[n..])),
+        };
+        match acq {
+            Some((acq, tail)) if !col.is_empty() => {
+                out.push_str(&head[..head.len() - col.len()]);
+                out.push_str(&in_marker(cols.len()));
+                out.push_str(" = ");
+                out.push_str(acq);
+                cols.push(col.to_string());
+                rest = tail;
+            }
+            _ => {
+                out.push_str(&rest[..pos + 2]);
+                rest = &rest[pos + 2..];
+            }
+        }
+    }
+    out.push_str(rest);
+    (out, cols)
+}
+
+/// Turn each `<marker> = :<marker>` back into `<col> in (:<param>)`; the param keeps the column
+/// name unless another condition already binds it.
+fn restore_in_lists(
+    mut w_sql: String,
+    vars: &mut HashMap<String, CondAccessor>,
+    in_cols: &[String],
+) -> String {
+    for (n, col) in in_cols.iter().enumerate() {
+        let marker = in_marker(n);
+        let Some(acc) = vars.remove(&marker) else {
+            continue;
+        };
+        let param = if vars.contains_key(col) {
+            format!("{}_in{}", col, n)
+        } else {
+            col.clone()
+        };
+        w_sql = w_sql.replacen(
+            &format!("{} = :{}", marker, marker),
+            &format!("{} in (:{})", col, param),
+            1,
+        );
+        vars.insert(param, acc);
+    }
+    w_sql
+}
+
 /// Convert a SQL piece, mapping `read(arg)` to `:arg` and collecting params.
 fn to_sql_piece(s: &str, params: &mut HashMap<String, CondAccessor>) -> String {
     let st = s.trim();
@@ -223,10 +332,12 @@ pub fn oml_sql(data: &mut &str) -> WResult<SqlQuery> {
         return Ok(SqlQuery::new(sql, vars));
     }
 
-    // Generic path
+    // Generic path; IN lists are parsed as `=` and restored afterwards
+    let (sql_cond_buf, in_cols) = rewrite_in_lists(&sql_cond_buf);
     let mut sql_cond = sql_cond_buf.as_str();
     let cond = SCondParser::end_exp(&mut sql_cond, ";")?;
-    let (w_sql, vars) = cond.args_take();
+    let (w_sql, mut vars) = cond.args_take();
+    let w_sql = restore_in_lists(w_sql, &mut vars, &in_cols);
 
     // Strict mode: reject invalid body; compat mode: fallback to original
     let strict = is_sql_strict();
@@ -291,6 +402,47 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_oml_sql_in_list() -> ModalResult<()> {
+        super::set_sql_strict_for_test(Some(true));
+        let mut code = r#" select a, b from table_1 where x = read(src) and id in (read(ids)) ;"#;
+        assert_oml_parse(&mut code, oml_sql);
+
+        let mut code = r#" select a from table_1 where id in take(ids) or login = read(user) ;"#;
+        let query = oml_sql.parse_next(&mut code)?;
+        assert_eq!(
+            query.oml_sql(),
+            "select a from table_1 where id in (:id) or login = :login "
+        );
+        assert_eq!(query.vars().len(), 2);
+
+        // 同一列既有等值条件又有 IN 列表：只还原 IN 那一处，参数名互不冲突
+        let mut code = r#" select a from table_1 where id = read(id) and id in (read(ids)) ;"#;
+        let query = oml_sql.parse_next(&mut code)?;
+        assert_eq!(
+            query.oml_sql(),
+            "select a from table_1 where id = :id and id in (:id_in0) "
+        );
+        assert_eq!(query.vars().len(), 2);
+
+        // 引号内的 in 不是关键字
+        let mut code =
+            r#" select a from table_1 where note = 'sign in (read(x))' and id in read(ids) ;"#;
+        let query = oml_sql.parse_next(&mut code)?;
+        assert!(query.oml_sql().contains("note = :note"));
+        assert!(query.oml_sql().ends_with("id in (:id) "));
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_in_kw() {
+        use super::find_in_kw;
+        assert_eq!(find_in_kw("id IN (read(x))"), Some(3));
+        assert_eq!(find_in_kw("x = 'a in b' and y in (1)"), Some(19));
+        assert_eq!(find_in_kw("x = \"join (a)\""), None);
+        assert_eq!(find_in_kw("login = read(x)"), None);
+    }
+
     #[test]
     fn test_oml_sql_strict_err() {
         super::set_sql_strict_for_test(Some(true));
@@ -362,6 +514,68 @@ zone : chars = select zone from zone where ip_start_int <= ip4_int(read(src_ip))
         assert_eq!(zone, Some("A"));
         Ok(())
     }
+    #[test]
+    fn test_sql_multi_column_exec() {
+        let db = MemDB::global();
+        db.table_create(
+            "CREATE TABLE IF NOT EXISTS net_zone (region TEXT, env TEXT, tier TEXT, rack TEXT, level INTEGER, site TEXT, vendor TEXT, zone TEXT, owner TEXT)",
+        )
+        .assert();
+        for row in [
+            "'cn', 'prod', 'web', 'r1', 3, 'bj', 'dell', 'z-web-1', 'ops'",
+            "'cn', 'prod', 'web', 'r2', 1, 'bj', 'dell', 'z-web-2', 'dev'",
+            "'cn', 'test', 'web', 'r1', 3, 'bj', 'dell', 'z-test', 'qa'",
+        ] {
+            db.execute(&format!("INSERT INTO net_zone VALUES ({})", row))
+                .assert();
+        }
+        let _ = kdb::init_mem_provider(db);
+        use wp_model_core::model::Value;
+
+        // 7 个参数，其中 rack 由数组字段绑定为 IN 列表
+        let mut conf = r#"
+name : test
+---
+zone, owner = select zone, owner from net_zone where region = read(region) and env = read(env) and tier = read(tier) and rack in (read(racks)) and level >= read(level) and site = read(site) and vendor = read(vendor) ;
+        "#;
+        let model = oml_parse_raw(&mut conf).assert();
+
+        let record = |racks: &[&str], level: i64| DataRecord {
+            items: vec![
+                DataField::from_chars("region", "cn"),
+                DataField::from_chars("env", "prod"),
+                DataField::from_chars("tier", "web"),
+                DataField::from_arr(
+                    "racks".to_string(),
+                    racks
+                        .iter()
+                        .map(|x| DataField::from_chars("racks", *x))
+                        .collect(),
+                ),
+                DataField::from_digit("level", level),
+                DataField::from_chars("site", "bj"),
+                DataField::from_chars("vendor", "dell"),
+            ],
+        };
+        let cache = &mut FieldQueryCache::default();
+        for (racks, level, expect) in [
+            (vec!["r2", "r1"], 2, Some(("z-web-1", "ops"))),
+            (vec!["r2"], 1, Some(("z-web-2", "dev"))),
+            // 命中缓存的同一组参数
+            (vec!["r2"], 1, Some(("z-web-2", "dev"))),
+            (vec![], 1, None),
+        ] {
+            let out = model.transform(record(&racks, level), cache);
+            let got = out.get2("zone").zip(out.get2("owner"));
+            assert_eq!(
+                got.map(|(z, o)| (z.get_value().clone(), o.get_value().clone())),
+                expect.map(|(z, o)| (Value::from(z), Value::from(o))),
+                "racks: {:?}",
+                racks
+            );
+        }
+    }
+
     #[test]
     fn test_sql_oml_err() {
         let mut code = r#" selec a, b from table_1 where x = read (src);"#;
@@ -58,6 +58,46 @@ impl CommentParser {
         }
         Ok(out)
     }
+
+    /// 与 `ignore_comment` 识别相同的注释，但以空格替换注释内容并保留换行，
+    /// 输出与输入字节偏移一一对应，便于把解析错误定位回原始代码
+    pub fn mask_comment(input: &mut &str) -> WResult<String> {
+        fn blank(out: &mut String, text: &str) {
+            out.extend(std::iter::repeat_n(' ', text.len()));
+        }
+        let mut status = DslStatus::Code;
+        let mut out = String::with_capacity(input.len());
+        while !input.is_empty() {
+            out += multispace0.parse_next(input)?;
+            match status {
+                DslStatus::Code => {
+                    if let Some(open) = opt(literal("/*")).parse_next(input)? {
+                        blank(&mut out, open);
+                        status = DslStatus::Comment;
+                        continue;
+                    }
+                    if let Some(open) = opt(literal("//")).parse_next(input)? {
+                        blank(&mut out, open);
+                        blank(&mut out, till_line_ending.parse_next(input)?);
+                        continue;
+                    }
+                    out += till_line_ending.parse_next(input)?;
+                }
+                DslStatus::Comment => {
+                    if let Some(close) = opt(literal("*/")).parse_next(input)? {
+                        blank(&mut out, close);
+                        status = DslStatus::Code;
+                        continue;
+                    }
+                    blank(&mut out, till_line_ending.parse_next(input)?);
+                }
+            }
+            if opt(newline).parse_next(input)?.is_some() {
+                out.push('\n');
+            }
+        }
+        Ok(out)
+    }
 }
 #[cfg(test)]
 mod tests {
@@ -78,4 +118,19 @@ mod tests {
         let p_code = CommentParser::ignore_comment(&mut code).expect("ignore comment fail");
         println!("{}", p_code)
     }
+
+    #[test]
+    fn test_mask_comment() {
+        let code = "a=1;\n  //b=1;\n  /*\n c=1;\n */ x=1;\n// 中文\ny=2;";
+        let masked = CommentParser::mask_comment(&mut &*code).expect("mask comment fail");
+        assert_eq!(masked.len(), code.len());
+        assert_eq!(masked.lines().count(), code.lines().count());
+        assert_eq!(&masked[code.find("x=1").unwrap()..][..3], "x=1");
+        assert_eq!(&masked[code.find("y=2").unwrap()..], "y=2");
+        assert!(!masked.contains("b=1") && !masked.contains("c=1"));
+
+        let pure = CommentParser::ignore_comment(&mut &*code).expect("ignore comment fail");
+        let squeeze = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+        assert_eq!(squeeze(&masked), squeeze(&pure));
+    }
 }
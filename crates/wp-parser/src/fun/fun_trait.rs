@@ -21,6 +21,17 @@ pub trait Fun2Builder {
     fn build(args: (Self::ARG1, Self::ARG2)) -> Self;
 }
 
+pub trait Fun3Builder {
+    type ARG1;
+    type ARG2;
+    type ARG3;
+    fn args1(data: &mut &str) -> WResult<Self::ARG1>;
+    fn args2(data: &mut &str) -> WResult<Self::ARG2>;
+    fn args3(data: &mut &str) -> WResult<Self::ARG3>;
+    fn fun_name() -> &'static str;
+    fn build(args: (Self::ARG1, Self::ARG2, Self::ARG3)) -> Self;
+}
+
 pub trait ParseNext<T> {
     fn parse_next(input: &mut &str) -> WResult<T>;
 }
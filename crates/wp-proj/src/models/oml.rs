@@ -1,12 +1,17 @@
+use oml::core::ConfADMExt;
+use oml::core::schema::check_model;
+use oml::language::ObjModel;
+use oml::parser::is_oml_library;
 use orion_error::{ToStructError, UvsConfFrom};
 use orion_variate::EnvDict;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use wp_conf::{engine::EngineConfig, utils::find_conf_files};
-use wp_engine::facade::config::WPARSE_OML_FILE;
+use wp_engine::facade::config::{WPARSE_OML_FILE, source_fields};
 use wp_engine::facade::generator::fetch_oml_data;
 use wp_error::run_error::{RunReason, RunResult};
 
+use crate::models::wpl::Wpl;
 use crate::traits::{Checkable, Component, ComponentBase, ComponentLifecycle, HasExamples};
 use crate::types::CheckStatus;
 use crate::utils::{TemplateInitializer, error_handler::ErrorHandler};
@@ -107,6 +112,47 @@ rule = "/example/*"
             .map_err(|e| RunReason::from_conf(format!("parse oml failed: {}", e)).to_err())?;
         Ok(CheckStatus::Suc)
     }
+
+    /// 按关联 WPL 规则的输出字段校验各模型，返回 `<model> @ <rule>: <issue>` 列表
+    ///
+    /// 库文件与无法加载的模型跳过（加载错误由 `check` 报告）。
+    pub fn check_schema(&self, wpl: &Wpl) -> RunResult<Vec<String>> {
+        let oml_root = self.oml_root();
+        if !oml_root.exists() {
+            return Ok(Vec::new());
+        }
+        let root_str = oml_root
+            .to_str()
+            .ok_or_else(|| RunReason::from_conf("OML文件路径无效").to_err())?;
+        let oml_files = find_conf_files(root_str, WPARSE_OML_FILE)
+            .map_err(|e| RunReason::from_conf(format!("OML 查找失败: {}", e)).to_err())?;
+        let mut schemas = wpl.rule_schemas();
+        for (_, schema) in schemas.iter_mut() {
+            for (name, meta) in source_fields() {
+                schema.add_field(name, meta);
+            }
+        }
+
+        let mut issues = Vec::new();
+        for f in &oml_files {
+            let raw = std::fs::read_to_string(f).unwrap_or_default();
+            if is_oml_library(&raw) {
+                continue;
+            }
+            let Ok(model) = ObjModel::load(&f.to_string_lossy()) else {
+                continue;
+            };
+            for (path, schema) in &schemas {
+                if !model.rules().as_ref().iter().any(|x| x.matches(path)) {
+                    continue;
+                }
+                for issue in check_model(&model, schema) {
+                    issues.push(format!("{} @ {}: {}", model.name(), path, issue.to_brief()));
+                }
+            }
+        }
+        Ok(issues)
+    }
 }
 
 // Trait implementations for unified component interface
@@ -156,4 +202,26 @@ mod tests {
         assert!(example_file.exists());
         assert!(!temp.path().join("models/oml/*.oml").exists());
     }
+
+    #[test]
+    fn check_schema_reports_unknown_fields() {
+        let temp = temp_workdir();
+        let root = temp.path().to_str().unwrap();
+        let eng = Arc::new(EngineConfig::init(root).conf_absolutize(root));
+        let wpl = Wpl::new(root, eng.clone());
+        wpl.init_with_examples().expect("init wpl");
+        let oml = Oml::new(root, eng);
+        oml.init_with_examples().expect("init oml");
+        assert_eq!(oml.check_schema(&wpl).expect("check"), Vec::<String>::new());
+
+        std::fs::write(
+            temp.path().join("models/oml/bad.oml"),
+            "name : bad\nrule : /nginx/*\n---\nsrc : ip = take(sip) ;\nhost = take(host) ;\nsrc_ip : ip = take(wp_src_ip) ;\n",
+        )
+        .unwrap();
+        let issues = oml.check_schema(&wpl).expect("check");
+        assert_eq!(issues.len(), 1, "{:?}", issues);
+        assert!(issues[0].starts_with("bad @ /nginx/"), "{:?}", issues);
+        assert!(issues[0].contains("unknown_field: host"), "{:?}", issues);
+    }
 }
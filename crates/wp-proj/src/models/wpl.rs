@@ -5,7 +5,7 @@ use std::sync::Arc;
 use wp_conf::engine::EngineConfig;
 use wp_engine::facade::config::WPARSE_RULE_FILE;
 use wp_error::run_error::{RunReason, RunResult};
-use wpl::WplCode;
+use wpl::{WplCode, WplSchema};
 
 use crate::traits::{Checkable, Component, ComponentBase, ComponentLifecycle, HasExamples};
 use crate::types::CheckStatus;
@@ -42,6 +42,36 @@ impl Wpl {
         self.resolve_path(self.eng_conf().rule_root())
     }
 
+    /// 规则文件：`parse*.wpl`，未找到时回退为根目录下的 `*.wpl`
+    fn rule_files(&self) -> Vec<PathBuf> {
+        let rule_root = self.rule_root();
+        let rules =
+            wp_conf::utils::find_conf_files(rule_root.to_string_lossy().as_ref(), WPARSE_RULE_FILE)
+                .unwrap_or_default();
+        if !rules.is_empty() {
+            return rules;
+        }
+        let wpl_pattern = format!("{}/*.wpl", rule_root.display());
+        glob::glob(&wpl_pattern)
+            .map(|x| x.filter_map(Result::ok).collect())
+            .unwrap_or_default()
+    }
+
+    /// 各规则路径（如 `/nginx/access`）及其输出字段，无法解析的文件由 `check` 报告，此处跳过
+    pub fn rule_schemas(&self) -> Vec<(String, WplSchema)> {
+        let mut out = Vec::new();
+        for fp in self.rule_files() {
+            let raw = std::fs::read_to_string(&fp).unwrap_or_default();
+            let Ok(pkg) = WplCode::build(fp, raw.as_str()).and_then(|code| code.parse_pkg()) else {
+                continue;
+            };
+            for rule in &pkg.rules {
+                out.push((rule.path(pkg.name.as_str()), rule.output_schema()));
+            }
+        }
+        out
+    }
+
     /// Initialize WPL with example content for the specified project directory
     pub fn init_with_examples(&self) -> RunResult<()> {
         let work_root = self.work_root();
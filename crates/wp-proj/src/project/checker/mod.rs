@@ -39,6 +39,15 @@ pub fn check_with(
     Ok(())
 }
 
+// OML 引用的字段须由匹配的 WPL 规则输出，类型可转换
+fn oml_schema_cell(project: &WarpProject) -> Cell {
+    match project.oml().check_schema(project.wpl()) {
+        Ok(issues) if issues.is_empty() => Cell::success(),
+        Ok(issues) => Cell::failure(issues.join("; ")),
+        Err(e) => Cell::failure(e.reason().to_string()),
+    }
+}
+
 fn component_stat_value(enabled: bool, count: &ComponentCount) -> serde_json::Value {
     use serde_json::json;
     if enabled {
@@ -185,7 +194,7 @@ fn evaluate_target(
     if comps.oml {
         row.oml = match project.oml().check(dict) {
             Ok(check_status) => match check_status {
-                CheckStatus::Suc => Cell::success(),
+                CheckStatus::Suc => oml_schema_cell(project),
                 CheckStatus::Miss => Cell::success_with_message("OML 文件缺失".to_string()),
                 CheckStatus::Error => Cell::failure("OML 检查错误".to_string()),
             },
@@ -1,6 +1,7 @@
 use comfy_table::{Cell as TCell, Table};
 use oml::core::ConfADMExt;
 use oml::language::ObjModel;
+use oml::parser::is_oml_library;
 use orion_error::{ToStructError, UvsConfFrom};
 use orion_variate::EnvDict;
 use serde_json::json;
@@ -197,6 +198,13 @@ pub fn collect_oml_models(work_root: &str, dict: &EnvDict) -> RunResult<Vec<OmlR
     let mut items = Vec::new();
     for path in files {
         let path_str = path.to_string_lossy().to_string();
+        let raw = std::fs::read_to_string(&path).map_err(|e| {
+            RunReason::from_conf(format!("read OML {} failed: {}", path_str, e)).to_err()
+        })?;
+        // 库文件仅供模型 import，没有名称与规则
+        if is_oml_library(&raw) {
+            continue;
+        }
         let model = ObjModel::load(path_str.as_str()).map_err(|e| {
             RunReason::from_conf(format!("parse OML {} failed: {}", path_str, e)).to_err()
         })?;
@@ -254,6 +262,8 @@ fn format_rules(rules: &[String]) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{temp_workdir, write_basic_wparse_config, write_file};
+    use wp_conf::test_support::ForTest;
 
     fn sample_route_row() -> wp_cli_core::business::connectors::sinks::RouteRow {
         wp_cli_core::business::connectors::sinks::RouteRow {
@@ -300,4 +310,25 @@ mod tests {
             DisplayFormat::Table
         ));
     }
+
+    #[test]
+    fn collect_oml_models_skips_libraries() {
+        let temp = temp_workdir();
+        write_basic_wparse_config(temp.path());
+        write_file(
+            temp.path(),
+            "models/oml/nginx.oml",
+            "name : nginx\nrule : /nginx/*\n---\nhost = take(host) ;\n",
+        );
+        write_file(
+            temp.path(),
+            "models/oml/lib/common.oml",
+            "// 公共字段\nblock ecs_base {\n    host = take(host) ;\n}\n",
+        );
+        let models = collect_oml_models(temp.path().to_str().unwrap(), &EnvDict::test_default())
+            .expect("collect oml");
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].name, "nginx");
+        assert_eq!(models[0].rules, vec!["/nginx/*".to_string()]);
+    }
 }
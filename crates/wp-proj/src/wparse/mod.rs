@@ -2,6 +2,10 @@ use orion_variate::EnvDict;
 
 use crate::utils::LogHandler;
 
+pub mod rule_fmt;
+pub mod rule_learn;
+pub mod rule_profile;
+pub mod rule_test;
 pub mod samples;
 
 /// WParse 管理器
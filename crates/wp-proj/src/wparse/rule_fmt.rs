@@ -0,0 +1,163 @@
+use glob::glob;
+use orion_error::{ToStructError, UvsConfFrom, UvsValidationFrom};
+use orion_variate::EnvDict;
+use std::path::Path;
+use wp_cli_core::{WplFmtFile, WplFmtReport, print_wpl_fmt_report};
+use wp_error::run_error::{RunReason, RunResult};
+use wpl::formatter::{WplFormatted, format_wpl};
+
+use super::samples::resolve_wpl_root;
+
+/// 格式化规则目录下所有 WPL 文件并打印结果；`check` 模式只检查不改写，
+/// 存在未格式化或无法格式化的文件时返回错误（用于 CI）；格式化器暂不支持的文件
+/// （含 `type`/`macro` 定义）跳过并原样保留，`check` 模式下同样返回错误
+pub fn run_wpl_fmt(work_root: &str, dict: &EnvDict, check: bool) -> RunResult<WplFmtReport> {
+    let wpl_root = resolve_wpl_root(work_root, dict)?;
+    let report = fmt_wpl_dir(&wpl_root, check)?;
+    print_wpl_fmt_report(&report);
+    if report.errors() > 0 {
+        return Err(RunReason::from_validation(format!(
+            "{} wpl file(s) can not be formatted",
+            report.errors()
+        ))
+        .to_err());
+    }
+    if check && report.changed() > 0 {
+        return Err(RunReason::from_validation(format!(
+            "{} wpl file(s) not formatted",
+            report.changed()
+        ))
+        .to_err());
+    }
+    if check && report.unsupported() > 0 {
+        return Err(RunReason::from_validation(format!(
+            "{} wpl file(s) not supported by formatter, can not check",
+            report.unsupported()
+        ))
+        .to_err());
+    }
+    Ok(report)
+}
+
+/// 格式化目录下的 `**/*.wpl`（不打印）；非 `check` 模式下改写有变化的文件
+pub fn fmt_wpl_dir(wpl_root: &Path, check: bool) -> RunResult<WplFmtReport> {
+    let mut report = WplFmtReport {
+        check,
+        files: Vec::new(),
+    };
+    if !wpl_root.exists() {
+        return Ok(report);
+    }
+    let pattern = format!("{}/**/*.wpl", wpl_root.display());
+    let walker = glob(&pattern)
+        .map_err(|e| RunReason::from_conf(format!("扫描规则失败: {}", e)).to_err())?;
+    for path in walker.filter_map(Result::ok) {
+        let label = path
+            .strip_prefix(wpl_root)
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+        let code = std::fs::read_to_string(&path).map_err(|e| {
+            RunReason::from_conf(format!("read wpl failed: {}: {}", label, e)).to_err()
+        })?;
+        let mut file = WplFmtFile {
+            file: label,
+            status: "ok".to_string(),
+            error: None,
+            note: None,
+        };
+        match format_wpl(&code) {
+            Ok(WplFormatted::Text(formatted)) if formatted == code => {}
+            Ok(WplFormatted::Text(formatted)) => {
+                file.status = "changed".to_string();
+                if !check {
+                    std::fs::write(&path, formatted).map_err(|e| {
+                        RunReason::from_conf(format!("write wpl failed: {}: {}", file.file, e))
+                            .to_err()
+                    })?;
+                }
+            }
+            Ok(WplFormatted::Unsupported(why)) => {
+                file.status = "unsupported".to_string();
+                file.note = Some(why);
+            }
+            Err(e) => {
+                file.status = "error".to_string();
+                file.error = Some(e.to_string());
+            }
+        }
+        report.files.push(file);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{temp_workdir, write_basic_wparse_config, write_file};
+    use wp_conf::test_support::ForTest;
+
+    #[test]
+    fn fmt_check_then_write() {
+        let temp = temp_workdir();
+        let root = temp.path().join("models/wpl");
+        std::fs::create_dir_all(root.join("nginx")).unwrap();
+        let messy = root.join("nginx/parse.wpl");
+        std::fs::write(&messy, "package nginx { rule a { (ip:sip,digit) } }").unwrap();
+        let broken = root.join("broken.wpl");
+        std::fs::write(&broken, "package x { rule a { (ip } }").unwrap();
+        let typed = root.join("typed.wpl");
+        let typed_code = "package t {\n type head = (ip:sip)\n rule a { (head) }\n}";
+        std::fs::write(&typed, typed_code).unwrap();
+
+        let report = fmt_wpl_dir(&root, true).expect("check");
+        assert_eq!(report.files.len(), 3);
+        assert_eq!(report.changed(), 1);
+        assert_eq!(report.errors(), 1);
+        assert_eq!(report.unsupported(), 1);
+        assert!(
+            std::fs::read_to_string(&messy)
+                .unwrap()
+                .starts_with("package nginx { rule")
+        );
+
+        std::fs::remove_file(&broken).unwrap();
+        let report = fmt_wpl_dir(&root, false).expect("write");
+        assert_eq!(report.changed(), 1);
+        let report = fmt_wpl_dir(&root, true).expect("recheck");
+        assert_eq!(report.changed(), 0);
+        assert_eq!(report.errors(), 0);
+        // 含 type 定义的文件跳过，不改写
+        assert_eq!(report.unsupported(), 1);
+        assert_eq!(std::fs::read_to_string(&typed).unwrap(), typed_code);
+        assert!(
+            fmt_wpl_dir(&temp.path().join("missing"), true)
+                .unwrap()
+                .files
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn fmt_check_fails_on_type_definition() {
+        let temp = temp_workdir();
+        write_basic_wparse_config(temp.path());
+        let work = temp.path().to_string_lossy().to_string();
+        let dict = EnvDict::test_default();
+        write_file(
+            temp.path(),
+            "models/wpl/ok.wpl",
+            "package p {\n  rule a {\n    (\n      ip\n    )\n  }\n}\n",
+        );
+        assert!(run_wpl_fmt(&work, &dict, true).is_ok());
+
+        let typed_code = "package t {\n type head = (ip:sip)\n rule a { (head) }\n}";
+        let typed = write_file(temp.path(), "models/wpl/typed.wpl", typed_code);
+        // 含 type 定义的文件无法确认格式，check 不通过
+        assert!(run_wpl_fmt(&work, &dict, true).is_err());
+        // 写入模式仍跳过并原样保留
+        let report = run_wpl_fmt(&work, &dict, false).expect("write");
+        assert_eq!(report.unsupported(), 1);
+        assert_eq!(std::fs::read_to_string(&typed).unwrap(), typed_code);
+    }
+}
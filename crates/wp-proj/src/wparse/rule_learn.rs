@@ -0,0 +1,80 @@
+use orion_error::{ToStructError, UvsConfFrom, UvsValidationFrom};
+use std::path::Path;
+use wp_cli_core::{WplLearnColumn, WplLearnReport, WplLearnSample, print_wpl_learn_report};
+use wp_error::run_error::{RunReason, RunResult};
+use wpl::learn::{LearnReport, SampleStatus, learn_rule};
+
+/// 从样本文件（每行一条日志）推断 WPL 规则，打印规则与覆盖率
+pub fn run_wpl_learn<P: AsRef<Path>>(sample_file: P, rule_name: &str) -> RunResult<WplLearnReport> {
+    let path = sample_file.as_ref();
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        RunReason::from_conf(format!("read samples failed: {}: {}", path.display(), e)).to_err()
+    })?;
+    let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+    let report = learn_samples(rule_name, &lines)?;
+    print_wpl_learn_report(&report);
+    Ok(report)
+}
+
+/// 推断并转换为报告（不打印）
+pub fn learn_samples(rule_name: &str, lines: &[&str]) -> RunResult<WplLearnReport> {
+    let learned = learn_rule(rule_name, lines)
+        .map_err(|e| RunReason::from_validation(format!("wpl learn failed: {}", e)).to_err())?;
+    Ok(to_report(&learned))
+}
+
+fn to_report(learned: &LearnReport) -> WplLearnReport {
+    WplLearnReport {
+        rule: learned.rule.clone(),
+        separator: learned.separator.to_string(),
+        columns: learned
+            .columns
+            .iter()
+            .map(|c| WplLearnColumn {
+                field: c.field.clone(),
+                kind: c.kind.wpl_type().to_string(),
+                example: c.example.clone(),
+            })
+            .collect(),
+        samples: learned
+            .samples
+            .iter()
+            .map(|s| WplLearnSample {
+                line: s.line.clone(),
+                status: match s.status {
+                    SampleStatus::Full => "full",
+                    SampleStatus::Partial => "partial",
+                    SampleStatus::Failed => "failed",
+                }
+                .to_string(),
+                residue: s.residue.clone(),
+                aligned: s.aligned,
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::temp_workdir;
+
+    #[test]
+    fn learn_from_sample_file() {
+        let temp = temp_workdir();
+        let file = temp.path().join("samples.log");
+        std::fs::write(
+            &file,
+            "10.0.0.1|200|login\n\n10.0.0.2|404|logout\n10.0.0.3|500|login\n",
+        )
+        .unwrap();
+        let report = run_wpl_learn(&file, "demo").expect("learn");
+        assert_eq!(report.separator, "|");
+        assert_eq!(report.samples.len(), 3);
+        assert_eq!(report.coverage(), 1.0);
+        assert!(report.rule.starts_with("rule demo {"));
+
+        assert!(learn_samples("demo", &[]).is_err());
+        assert!(run_wpl_learn(temp.path().join("missing.log"), "demo").is_err());
+    }
+}
@@ -0,0 +1,197 @@
+use glob::glob;
+use orion_error::{ToStructError, UvsConfFrom};
+use orion_variate::EnvDict;
+use std::cmp::Reverse;
+use std::path::Path;
+use std::sync::Arc;
+use wp_cli_core::{WplProfileField, WplProfileReport, WplProfileRule, print_wpl_profile_report};
+use wp_error::run_error::{RunReason, RunResult};
+use wpl::{
+    ParseProfiler, ProfileHook, ProfileReport, WplEvaluator, WplMatchConf, WplMatchRank,
+    WplPackage, WplStatementType,
+};
+
+use super::rule_test::load_package;
+use super::samples::resolve_wpl_root;
+
+/// 用样本文件（每行一条日志）剖析规则目录下全部规则的解析耗时，打印排序后的报告；
+/// 规则按优先级、包内顺序依次尝试，并按所在包的匹配策略决定何时停止（同引擎选择逻辑）
+pub fn run_wpl_profile<P: AsRef<Path>>(
+    work_root: &str,
+    dict: &EnvDict,
+    sample_file: P,
+    sample_every: u64,
+    json: bool,
+) -> RunResult<WplProfileReport> {
+    let wpl_root = resolve_wpl_root(work_root, dict)?;
+    let pkgs = load_packages(&wpl_root)?;
+    let path = sample_file.as_ref();
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        RunReason::from_conf(format!("read samples failed: {}: {}", path.display(), e)).to_err()
+    })?;
+    let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+    let report = to_cli_report(profile_lines(&pkgs, &lines, sample_every)?);
+    print_wpl_profile_report(&report, json);
+    Ok(report)
+}
+
+fn to_cli_report(report: ProfileReport) -> WplProfileReport {
+    WplProfileReport {
+        sample_every: report.sample_every,
+        rules: report
+            .rules
+            .into_iter()
+            .map(|rule| WplProfileRule {
+                rule: rule.rule,
+                hits: rule.hits,
+                misses: rule.misses,
+                total_ns: rule.total_ns,
+                miss_ns: rule.miss_ns,
+                avg_hit_ns: rule.avg_hit_ns,
+                avg_miss_ns: rule.avg_miss_ns,
+                fields: rule
+                    .fields
+                    .into_iter()
+                    .map(|f| WplProfileField {
+                        field: f.field,
+                        calls: f.calls,
+                        fails: f.fails,
+                        total_ns: f.total_ns,
+                        avg_ns: f.avg_ns,
+                    })
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+fn load_packages(wpl_root: &Path) -> RunResult<Vec<WplPackage>> {
+    let mut pkgs = Vec::new();
+    if !wpl_root.exists() {
+        return Ok(pkgs);
+    }
+    let pattern = format!("{}/**/*.wpl", wpl_root.display());
+    let walker = glob(&pattern)
+        .map_err(|e| RunReason::from_conf(format!("扫描规则失败: {}", e)).to_err())?;
+    for path in walker.filter_map(Result::ok) {
+        let pkg = load_package(&path).map_err(|e| {
+            RunReason::from_conf(format!("parse wpl failed: {}: {}", path.display(), e)).to_err()
+        })?;
+        pkgs.push(pkg);
+    }
+    Ok(pkgs)
+}
+
+/// 剖析给定的样本（不打印）
+pub fn profile_lines(
+    pkgs: &[WplPackage],
+    lines: &[&str],
+    sample_every: u64,
+) -> RunResult<ProfileReport> {
+    let profiler = Arc::new(ParseProfiler::new(sample_every));
+    let mut rules = Vec::new();
+    for pkg in pkgs {
+        for (index, rule) in pkg.rules.iter().enumerate() {
+            let WplStatementType::Express(express) = &rule.statement;
+            let path = rule.path(pkg.name.as_str());
+            let evaluator = WplEvaluator::from(express, None)
+                .map_err(|e| {
+                    RunReason::from_conf(format!("build {} failed: {}", path, e)).to_err()
+                })?
+                .with_profile(ProfileHook::new(&path, profiler.clone()));
+            let rank = WplMatchRank {
+                priority: rule.priority(),
+                index,
+                residue: 0,
+            };
+            rules.push((rank, rule.match_conf(), evaluator));
+        }
+    }
+    rules.sort_by_key(|(rank, _, _)| (Reverse(rank.priority), rank.index));
+    for line in lines {
+        // 与引擎一致：可接受的结果按首个候选所在包的策略择优，后续规则不可能更优时停止
+        let mut best: Option<(WplMatchRank, WplMatchConf)> = None;
+        for (rank, conf, evaluator) in &rules {
+            if let Some((best_rank, best_conf)) = &best
+                && best_conf.settled(best_rank, rank.priority)
+            {
+                break;
+            }
+            let Ok((_, left)) = evaluator.proc(*line, 0) else {
+                continue;
+            };
+            if !conf.accept(left.len(), line.len()) {
+                continue;
+            }
+            let cand = WplMatchRank {
+                residue: left.len(),
+                ..*rank
+            };
+            best = match best.take() {
+                Some((prev, prev_conf)) if !prev_conf.prefer(&cand, &prev) => {
+                    Some((prev, prev_conf))
+                }
+                _ => Some((cand, *conf)),
+            };
+        }
+    }
+    Ok(profiler.report())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wpl::WplCode;
+
+    #[test]
+    fn profile_reports_hits_and_misses() {
+        let pkg = WplCode::try_from(
+            r#"package demo {
+    rule kv { (ip:sip, digit:port) }
+    rule code { (digit:code) }
+}"#,
+        )
+        .expect("build wpl")
+        .parse_pkg()
+        .expect("parse wpl");
+        let report =
+            profile_lines(&[pkg], &["1.2.3.4 80", "200", "10.0.0.1 443"], 1).expect("profile");
+        let kv = report.rules.iter().find(|r| r.rule == "demo/kv").unwrap();
+        assert_eq!((kv.hits, kv.misses), (2, 1));
+        let code = report.rules.iter().find(|r| r.rule == "demo/code").unwrap();
+        assert_eq!((code.hits, code.misses), (1, 0));
+        assert!(report.to_json().contains("\"rule\": \"demo/kv\""));
+    }
+
+    fn policy_pkg(policy: &str) -> WplPackage {
+        let code = format!(
+            r#"#[match({}, residue: 0.9)]
+package demo {{
+    rule short {{ (digit:id) }}
+    rule long {{ (digit:id, chars:name) }}
+}}"#,
+            policy
+        );
+        WplCode::try_from(code.as_str())
+            .expect("build wpl")
+            .parse_pkg()
+            .expect("parse wpl")
+    }
+
+    #[test]
+    fn profile_follows_match_policy() {
+        // longest：short 留有残留，继续尝试 long
+        let report = profile_lines(&[policy_pkg("longest")], &["12 abc"], 1).expect("profile");
+        let long = report.rules.iter().find(|r| r.rule == "demo/long").unwrap();
+        assert_eq!(long.hits, 1);
+        // first：short 的残留在阈值内即被接受，long 不再尝试
+        let report = profile_lines(&[policy_pkg("first")], &["12 abc"], 1).expect("profile");
+        assert!(report.rules.iter().all(|r| r.rule != "demo/long"));
+        let short = report
+            .rules
+            .iter()
+            .find(|r| r.rule == "demo/short")
+            .unwrap();
+        assert_eq!(short.hits, 1);
+    }
+}
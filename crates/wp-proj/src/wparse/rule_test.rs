@@ -0,0 +1,206 @@
+use glob::glob;
+use orion_error::{ToStructError, UvsConfFrom, UvsValidationFrom};
+use orion_variate::EnvDict;
+use std::path::Path;
+use wp_cli_core::{WplCaseResult, WplFieldDiff, WplTestReport, print_wpl_test_report};
+use wp_error::run_error::{RunReason, RunResult};
+use wpl::parser::datatype::{field_ins, meta_of_name};
+use wpl::{WplCode, WplEvaluator, WplExpectField, WplPackage, WplStatementType, WplTestCase};
+
+use super::samples::resolve_wpl_root;
+
+/// 执行规则目录下所有 WPL 包内的 `test { ... }` 用例，打印结果；存在失败用例时返回错误
+pub fn run_wpl_tests(work_root: &str, dict: &EnvDict) -> RunResult<WplTestReport> {
+    let report = collect_wpl_tests(work_root, dict)?;
+    print_wpl_test_report(&report);
+    if !report.is_ok() {
+        return Err(RunReason::from_validation(format!(
+            "{} wpl test case(s) failed",
+            report.failed()
+        ))
+        .to_err());
+    }
+    Ok(report)
+}
+
+/// 执行用例并汇总结果（不打印）
+pub fn collect_wpl_tests(work_root: &str, dict: &EnvDict) -> RunResult<WplTestReport> {
+    let wpl_root = resolve_wpl_root(work_root, dict)?;
+    let mut report = WplTestReport::default();
+    if !wpl_root.exists() {
+        return Ok(report);
+    }
+    let pattern = format!("{}/**/*.wpl", wpl_root.display());
+    let walker = glob(&pattern)
+        .map_err(|e| RunReason::from_conf(format!("扫描规则失败: {}", e)).to_err())?;
+    for path in walker.filter_map(Result::ok) {
+        let label = path
+            .strip_prefix(&wpl_root)
+            .unwrap_or(&path)
+            .display()
+            .to_string();
+        let pkg = load_package(&path).map_err(|e| {
+            RunReason::from_conf(format!("parse wpl failed: {}: {}", label, e)).to_err()
+        })?;
+        report.cases.extend(test_wpl_package(&label, &pkg));
+    }
+    Ok(report)
+}
+
+pub(super) fn load_package(path: &Path) -> Result<WplPackage, String> {
+    let code = WplCode::load(path).map_err(|e| e.to_string())?;
+    code.parse_pkg().map_err(|e| e.to_string())
+}
+
+/// 执行单个包内的全部用例
+pub fn test_wpl_package(file: &str, pkg: &WplPackage) -> Vec<WplCaseResult> {
+    pkg.tests
+        .iter()
+        .enumerate()
+        .map(|(i, case)| {
+            let mut result = WplCaseResult {
+                file: file.to_string(),
+                package: pkg.name.to_string(),
+                rule: case.rule.as_ref().map(|x| x.to_string()),
+                index: i + 1,
+                error: None,
+                diffs: Vec::new(),
+            };
+            match run_case(pkg, case) {
+                Ok(diffs) => result.diffs = diffs,
+                Err(e) => result.error = Some(e),
+            }
+            result
+        })
+        .collect()
+}
+
+// 未指定规则时按包内顺序取首个解析成功的规则
+fn run_case(pkg: &WplPackage, case: &WplTestCase) -> Result<Vec<WplFieldDiff>, String> {
+    let rules: Vec<_> = match &case.rule {
+        Some(name) => pkg.rules.iter().filter(|r| r.name == *name).collect(),
+        None => pkg.rules.iter().collect(),
+    };
+    if rules.is_empty() {
+        return Err(format!(
+            "rule '{}' not found",
+            case.rule.as_deref().unwrap_or_default()
+        ));
+    }
+    let mut last_err = String::new();
+    for rule in rules {
+        let WplStatementType::Express(express) = &rule.statement;
+        let evaluator = WplEvaluator::from(express, None).map_err(|e| e.to_string())?;
+        match evaluator.proc(case.input.as_str(), 0) {
+            Ok((record, _)) => {
+                let mut diffs = Vec::new();
+                for exp in &case.expect {
+                    // 期望值按声明的类型构造，与类型化字面量（如 `ip(1.2.3.4)`）一致
+                    let (meta, _) = meta_of_name(exp.meta.as_str())
+                        .ok_or_else(|| bad_expect(exp, "unknown type"))?;
+                    let expect =
+                        field_ins(meta, "", exp.value.as_str()).map_err(|e| bad_expect(exp, e))?;
+                    let actual = record.field(exp.name.as_str());
+                    let same = actual.is_some_and(|f| {
+                        f.get_meta() == expect.get_meta() && f.get_value() == expect.get_value()
+                    });
+                    if !same {
+                        diffs.push(WplFieldDiff {
+                            field: exp.name.to_string(),
+                            expect: format!("{}({})", exp.meta, exp.value),
+                            actual: actual.map(|f| format!("{}({})", f.get_meta(), f.get_value())),
+                        });
+                    }
+                }
+                return Ok(diffs);
+            }
+            Err(e) => last_err = format!("rule '{}' not match: {}", rule.name, e),
+        }
+    }
+    Err(last_err)
+}
+
+fn bad_expect(exp: &WplExpectField, e: impl std::fmt::Display) -> String {
+    format!(
+        "expect '{}': bad {} value '{}': {}",
+        exp.name, exp.meta, exp.value, e
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(code: &str) -> WplPackage {
+        WplCode::try_from(code)
+            .expect("build wpl")
+            .parse_pkg()
+            .expect("parse wpl")
+    }
+
+    #[test]
+    fn package_cases_report_field_diffs() {
+        let pkg = package(
+            r#"package demo {
+    rule kv { (ip:sip, digit:port) }
+    test kv { input: "1.2.3.4 80"; expect: { sip: ip(1.2.3.4), port: digit(80) } }
+    test kv { input: "1.2.3.4 81"; expect: { sip: ip(1.2.3.5), port: digit(81), host: chars(x) } }
+    test nope { input: "1.2.3.4 80"; expect: { sip: ip(1.2.3.4) } }
+}"#,
+        );
+        let results = test_wpl_package("demo.wpl", &pkg);
+        assert_eq!(results.len(), 3);
+        assert!(results[0].passed(), "{:?}", results[0]);
+
+        assert_eq!(
+            results[1].diffs,
+            vec![
+                WplFieldDiff {
+                    field: "sip".into(),
+                    expect: "ip(1.2.3.5)".into(),
+                    actual: Some("ip(1.2.3.4)".into()),
+                },
+                WplFieldDiff {
+                    field: "host".into(),
+                    expect: "chars(x)".into(),
+                    actual: None,
+                },
+            ]
+        );
+        assert!(results[2].error.as_deref().unwrap().contains("not found"));
+
+        let report = WplTestReport { cases: results };
+        assert_eq!(report.failed(), 2);
+    }
+
+    #[test]
+    fn package_cases_compare_typed_values() {
+        let pkg = package(
+            r#"package demo {
+    rule kv { (ip:sip, digit:port) }
+    test kv { input: "1.2.3.4 80"; expect: { sip: ip(1.2.3.4), port: digit(080) } }
+    test kv { input: "1.2.3.4 80"; expect: { port: chars(80) } }
+    test kv { input: "1.2.3.4 80"; expect: { port: digit(x) } }
+}"#,
+        );
+        let results = test_wpl_package("demo.wpl", &pkg);
+        // 文本不同但值相同
+        assert!(results[0].passed(), "{:?}", results[0]);
+        // 文本相同但类型不同
+        assert_eq!(
+            results[1].diffs,
+            vec![WplFieldDiff {
+                field: "port".into(),
+                expect: "chars(80)".into(),
+                actual: Some("digit(80)".into()),
+            }]
+        );
+        assert!(
+            results[2]
+                .error
+                .as_deref()
+                .unwrap()
+                .contains("bad digit value 'x'")
+        );
+    }
+}
@@ -39,15 +39,20 @@ fn parse_single_run<P: AsRef<Path> + Clone>(data_path: P, rule_file: P) -> RunRe
     Ok(())
 }
 
-fn discover_sample_jobs(work_root: &str, dict: &EnvDict) -> RunResult<Vec<SampleJob>> {
+/// 按 wparse.toml 的 rule_root 解析 WPL 规则目录
+pub(crate) fn resolve_wpl_root(work_root: &str, dict: &EnvDict) -> RunResult<PathBuf> {
     let (cm, main) = load_warp_engine_confs(work_root, dict)
         .map_err(|e| RunReason::from_conf(format!("加载 wparse.toml 失败: {}", e)).to_err())?;
     let rule_root = Path::new(main.rule_root());
-    let wpl_root = if rule_root.is_absolute() {
+    Ok(if rule_root.is_absolute() {
         rule_root.to_path_buf()
     } else {
         Path::new(&cm.work_root_path()).join(rule_root)
-    };
+    })
+}
+
+fn discover_sample_jobs(work_root: &str, dict: &EnvDict) -> RunResult<Vec<SampleJob>> {
+    let wpl_root = resolve_wpl_root(work_root, dict)?;
     if !wpl_root.exists() {
         return Ok(Vec::new());
     }
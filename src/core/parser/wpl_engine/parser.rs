@@ -5,10 +5,23 @@ use crate::core::parser::wpl_engine::pipeline::WplPipeline;
 use crate::{core::parser::ParseOption, stat::MonSend};
 use orion_conf::ToStructError;
 use orion_error::{UvsDataFrom, UvsReason};
+use std::cmp::Reverse;
 use std::sync::Arc;
 use wp_connector_api::SourceEvent;
 use wp_model_core::model::data::Field;
-use wpl::{WparseError, WparseReason, WparseResult};
+use wp_model_core::model::{DataRecord, DataType};
+use wpl::{WparseError, WparseReason, WparseResult, WplMatchConf, WplMatchRank};
+
+pub const SRC_KEY_FIELD: &str = "wp_src_key";
+pub const SRC_IP_FIELD: &str = "wp_src_ip";
+
+/// 开启 `gen_msg_id` 时引擎向每条解析结果追加的来源字段
+pub fn source_fields() -> Vec<(&'static str, DataType)> {
+    vec![
+        (SRC_KEY_FIELD, DataType::Chars),
+        (SRC_IP_FIELD, DataType::IP),
+    ]
+}
 
 /// 数据包解析器
 #[derive(Clone, getset::Getters)]
@@ -17,9 +30,34 @@ pub struct MultiParser {
     pipelines: Vec<WplPipeline>,
 }
 
+/// 可接受的解析结果，按首个候选所在包的匹配策略择优
+struct Candidate {
+    pos: usize,
+    rank: WplMatchRank,
+    conf: WplMatchConf,
+    record: DataRecord,
+    residue: String,
+}
+
+impl Candidate {
+    // 是否优于 other（以 other 的策略比较）
+    fn better_than(&self, other: &Candidate) -> bool {
+        other.conf.prefer(&self.rank, &other.rank)
+    }
+
+    // 后续规则不可能更优时结束尝试（规则已按优先级降序排列）
+    fn settled(&self, next: &WplPipeline) -> bool {
+        self.conf.settled(&self.rank, *next.priority())
+    }
+}
+
 impl MultiParser {
     pub fn new(pipelines: Vec<WplPipeline>) -> Self {
-        Self { pipelines }
+        let mut parser = Self { pipelines };
+        parser
+            .pipelines
+            .sort_by_key(|p| (Reverse(*p.priority()), *p.index()));
+        parser
     }
 
     /// 处理单个事件
@@ -27,55 +65,53 @@ impl MultiParser {
         let mut max_depth = 0;
         let mut best_wpl = String::new();
         let mut best_error = None;
-        let rule_cnt = self.pipelines.len();
+        let mut found: Option<Candidate> = None;
 
         // 尝试用每个规则处理事件
-        for (idx, wpl_line) in self.pipelines.iter_mut().enumerate() {
-            let is_last = idx == rule_cnt - 1;
+        for pos in 0..self.pipelines.len() {
+            if let Some(cand) = &found
+                && cand.settled(&self.pipelines[pos])
+            {
+                break;
+            }
+            let wpl_line = &mut self.pipelines[pos];
 
             // 调用 WPL 处理
             match wpl_line.proc(event, max_depth) {
-                Ok((mut tdo_crate, un_parsed)) => {
-                    if *setting.gen_msg_id() {
-                        tdo_crate.set_id(event.event_id);
-                        tdo_crate.append(Field::from_chars("wp_src_key", event.src_key.as_str()));
-                        if let Some(ups_ip) = event.ups_ip {
-                            tdo_crate.append(Field::from_ip("wp_src_ip", ups_ip));
-                        }
-                    }
-                    wpl_line.hit_cnt += 1;
-
-                    let wpl_key = wpl_line.wpl_key().to_string();
-
-                    // 根据是否有残留数据返回不同的结果
-                    if un_parsed.is_empty() || un_parsed.is_empty() {
-                        let record = Arc::new(tdo_crate);
-                        return ProcessResult::Success { wpl_key, record };
-                    } else {
+                Ok((record, un_parsed)) => {
+                    let conf = *wpl_line.match_conf();
+                    if !conf.accept(un_parsed.len(), event.payload.len()) {
+                        info_data!(
+                            "wpl parse not complete: {}\n data:{}",
+                            wpl_line.wpl_key(),
+                            event.payload,
+                        );
                         let parsed_len = event.payload.len() - un_parsed.len();
-                        if un_parsed.len() as f64 / event.payload.len() as f64 > 0.2 {
-                            info_data!(
-                                "wpl parse not complete: {}\n data:{}",
-                                wpl_line.wpl_key(),
-                                event.payload,
+                        if parsed_len > max_depth {
+                            max_depth = parsed_len;
+                            best_wpl = wpl_line.wpl_key().clone();
+                            best_error = Some(
+                                WparseReason::from_data("not complete", Some(parsed_len)).to_err(),
                             );
-                            if parsed_len > max_depth {
-                                max_depth = parsed_len;
-                                best_wpl = wpl_line.wpl_key().clone();
-                                best_error = Some(
-                                    WparseReason::from_data("not complete", Some(parsed_len))
-                                        .to_err(),
-                                );
-                            }
-                        } else {
-                            let record = Arc::new(tdo_crate);
-                            return ProcessResult::Partial {
-                                wpl_key,
-                                record,
-                                residue: un_parsed.to_string(),
-                            };
                         }
+                        continue;
                     }
+                    let residue = un_parsed.to_string();
+                    let cand = Candidate {
+                        pos,
+                        rank: WplMatchRank {
+                            priority: *wpl_line.priority(),
+                            index: *wpl_line.index(),
+                            residue: residue.len(),
+                        },
+                        conf,
+                        record,
+                        residue,
+                    };
+                    found = match found.take() {
+                        Some(prev) if !cand.better_than(&prev) => Some(prev),
+                        _ => Some(cand),
+                    };
                 }
                 Err(e) => {
                     info_data!(
@@ -97,14 +133,14 @@ impl MultiParser {
                         best_error = Some(e.clone());
                         break;
                     }
-
-                    if is_last {
-                        break;
-                    }
                 }
             }
         }
 
+        if let Some(cand) = found {
+            return self.accept(event, setting, cand);
+        }
+
         // 所有规则都失败，返回深度最高的失败信息
         let best_error = best_error.unwrap_or_else(|| {
             WparseError::from(WparseReason::Uvs(UvsReason::SystemError(
@@ -115,6 +151,39 @@ impl MultiParser {
             best_wpl, best_error, max_depth,
         ))
     }
+
+    fn accept(
+        &mut self,
+        event: &SourceEvent,
+        setting: &ParseOption,
+        cand: Candidate,
+    ) -> ProcessResult {
+        let wpl_line = &mut self.pipelines[cand.pos];
+        wpl_line.hit_cnt += 1;
+        let wpl_key = wpl_line.wpl_key().to_string();
+
+        let mut tdo_crate = cand.record;
+        if *setting.gen_msg_id() {
+            tdo_crate.set_id(event.event_id);
+            tdo_crate.append(Field::from_chars(SRC_KEY_FIELD, event.src_key.as_str()));
+            if let Some(ups_ip) = event.ups_ip {
+                tdo_crate.append(Field::from_ip(SRC_IP_FIELD, ups_ip));
+            }
+        }
+        let record = Arc::new(tdo_crate);
+
+        // 根据是否有残留数据返回不同的结果
+        if cand.residue.is_empty() {
+            ProcessResult::Success { wpl_key, record }
+        } else {
+            ProcessResult::Partial {
+                wpl_key,
+                record,
+                residue: cand.residue,
+            }
+        }
+    }
+
     pub fn stop(&mut self) {
         self.pipelines.iter_mut().for_each(|i| i.stop());
     }
@@ -124,9 +193,11 @@ impl MultiParser {
             return;
         }
 
+        // 优先级始终优先于命中次数，避免重叠规则的选择随统计窗口变化
         self.pipelines.sort_by(|a, b| {
-            b.hit_cnt
-                .cmp(&a.hit_cnt)
+            b.priority()
+                .cmp(a.priority())
+                .then_with(|| b.hit_cnt.cmp(&a.hit_cnt))
                 .then_with(|| a.index().cmp(b.index()))
         });
 
@@ -155,7 +226,9 @@ mod tests {
     use super::*;
     use crate::core::parser::wpl_engine::pipeline::WplPipeline;
     use crate::sinks::SinkGroupAgent;
-    use wpl::WplEvaluator;
+    use wp_connector_api::Tags;
+    use wp_parse_api::RawData;
+    use wpl::{WplEvaluator, WplMatchPolicy, gen_pkg_id};
 
     fn dummy_pipeline(idx: usize, hit: usize) -> WplPipeline {
         let evaluator = WplEvaluator::from_code("rule dummy { ( _ ) }").expect("build wpl");
@@ -191,6 +264,129 @@ mod tests {
         assert_eq!(order, vec!["rule-1", "rule-2", "rule-0"]);
         assert!(parser.pipelines.iter().all(|p| p.hit_cnt == 0));
     }
+
+    const SHORT_RULE: &str = "rule short { (digit:id) }";
+    const LONG_RULE: &str = "rule long { (digit:id, chars:name) }";
+
+    fn rule_pipeline(idx: usize, code: &str, priority: i64, conf: WplMatchConf) -> WplPipeline {
+        let evaluator = WplEvaluator::from_code(code).expect("build wpl");
+        WplPipeline::new(
+            idx,
+            format!("rule-{}", idx),
+            Vec::new(),
+            evaluator,
+            vec![SinkGroupAgent::null()],
+            Vec::new(),
+        )
+        .with_match(priority, conf)
+    }
+
+    fn policy(policy: WplMatchPolicy, residue: f64) -> WplMatchConf {
+        WplMatchConf { policy, residue }
+    }
+
+    fn matched(parser: &mut MultiParser, payload: &str) -> (String, bool) {
+        let event = SourceEvent::new(
+            gen_pkg_id(),
+            "test-src",
+            RawData::String(payload.to_string()),
+            Arc::new(Tags::new()),
+        );
+        match parser.parse_event(&event, &ParseOption::default()) {
+            ProcessResult::Success { wpl_key, .. } => (wpl_key, true),
+            ProcessResult::Partial { wpl_key, .. } => (wpl_key, false),
+            ProcessResult::Miss(info) => panic!("unexpected miss: {}", info),
+        }
+    }
+
+    #[test]
+    fn match_policy_selects_rule() {
+        let cases = [
+            (WplMatchPolicy::First, "rule-0", false),
+            (WplMatchPolicy::Longest, "rule-1", true),
+            (WplMatchPolicy::Strict, "rule-1", true),
+        ];
+        for (kind, expect, complete) in cases {
+            let conf = policy(kind, 0.8);
+            let mut parser = MultiParser::new(vec![
+                rule_pipeline(0, SHORT_RULE, 0, conf),
+                rule_pipeline(1, LONG_RULE, 0, conf),
+            ]);
+            assert_eq!(
+                matched(&mut parser, "12 abc"),
+                (expect.to_string(), complete),
+                "{:?}",
+                kind
+            );
+        }
+    }
+
+    #[test]
+    fn longest_policy_ranks_residue_before_priority() {
+        let conf = policy(WplMatchPolicy::Longest, 0.8);
+        // 残留少者胜出，即使优先级更低
+        let mut parser = MultiParser::new(vec![
+            rule_pipeline(0, SHORT_RULE, 5, conf),
+            rule_pipeline(1, LONG_RULE, 0, conf),
+            rule_pipeline(2, LONG_RULE, 0, conf),
+        ]);
+        assert_eq!(matched(&mut parser, "12 abc"), ("rule-1".to_string(), true));
+        // 残留相同按优先级，再按声明顺序
+        let mut parser = MultiParser::new(vec![
+            rule_pipeline(0, SHORT_RULE, 0, conf),
+            rule_pipeline(1, SHORT_RULE, 3, conf),
+            rule_pipeline(2, SHORT_RULE, 3, conf),
+        ]);
+        assert_eq!(
+            matched(&mut parser, "12 abc"),
+            ("rule-1".to_string(), false)
+        );
+    }
+
+    #[test]
+    fn residue_threshold_is_configurable() {
+        // 默认阈值 0.2：残留 "abc" 占比 0.5，不接受，落到完整匹配的规则
+        let mut parser = MultiParser::new(vec![
+            rule_pipeline(0, SHORT_RULE, 0, WplMatchConf::default()),
+            rule_pipeline(1, LONG_RULE, 0, WplMatchConf::default()),
+        ]);
+        assert_eq!(matched(&mut parser, "12 abc"), ("rule-1".to_string(), true));
+
+        let loose = policy(WplMatchPolicy::First, 0.7);
+        let mut parser = MultiParser::new(vec![
+            rule_pipeline(0, SHORT_RULE, 0, loose),
+            rule_pipeline(1, LONG_RULE, 0, loose),
+        ]);
+        assert_eq!(
+            matched(&mut parser, "12 abc"),
+            ("rule-0".to_string(), false)
+        );
+    }
+
+    #[test]
+    fn priority_policy_is_stable_across_windows() {
+        let conf = policy(WplMatchPolicy::Priority, 1.0);
+        let mut parser = MultiParser::new(vec![
+            rule_pipeline(0, LONG_RULE, 0, conf),
+            rule_pipeline(1, SHORT_RULE, 5, conf),
+            rule_pipeline(2, SHORT_RULE, 5, conf),
+        ]);
+        for _ in 0..3 {
+            assert_eq!(
+                matched(&mut parser, "12 abc"),
+                ("rule-1".to_string(), false)
+            );
+        }
+        parser.pipelines[1].hit_cnt = 100;
+        parser.optimized(0);
+        let order: Vec<_> = parser.pipelines.iter().map(|p| *p.index()).collect();
+        assert_eq!(order, vec![2, 1, 0]);
+        // 命中次数只影响尝试顺序，同优先级仍按声明顺序选择
+        assert_eq!(
+            matched(&mut parser, "12 abc"),
+            ("rule-1".to_string(), false)
+        );
+    }
 }
 
 // 重新导出主要类型
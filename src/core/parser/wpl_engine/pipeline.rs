@@ -5,12 +5,13 @@ use crate::sinks::SinkGroupAgent;
 use crate::stat::MonSend;
 use crate::stat::metric_collect::MetricCollectors;
 use std::cmp::Ordering;
+use std::sync::Arc;
 use wp_parse_api::{DataResult, RawData};
 use wp_stat::StatRecorder;
 use wp_stat::StatReq;
 use wpl::WparseResult;
 use wpl::{AnnotationFunc, AnnotationType};
-use wpl::{OPTIMIZE_TIMES, WplEvaluator};
+use wpl::{OPTIMIZE_TIMES, ParseProfiler, ProfileHook, WplEvaluator, WplMatchConf};
 
 #[derive(Getters, Clone)]
 pub struct WplPipeline {
@@ -23,6 +24,8 @@ pub struct WplPipeline {
     wpl_key: String,
     s_name: String,
     stat_ext: MetricCollectors,
+    priority: i64,
+    match_conf: WplMatchConf,
 }
 
 impl WplPipeline {
@@ -48,9 +51,26 @@ impl WplPipeline {
             access_cnt: 0,
             s_name,
             stat_ext,
+            priority: 0,
+            match_conf: WplMatchConf::default(),
         }
     }
 
+    /// 设置规则优先级与所在包的匹配策略
+    pub fn with_match(mut self, priority: i64, match_conf: WplMatchConf) -> Self {
+        self.priority = priority;
+        self.match_conf = match_conf;
+        self
+    }
+
+    /// 挂载解析剖析，按 wpl_key 汇总到共享的剖析器
+    pub fn with_profile(mut self, profiler: &Arc<ParseProfiler>) -> Self {
+        self.parser = self
+            .parser
+            .with_profile(ProfileHook::new(&self.wpl_key, profiler.clone()));
+        self
+    }
+
     pub fn short_name(&self) -> &str {
         self.s_name.as_str()
     }
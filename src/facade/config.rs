@@ -1,5 +1,6 @@
 //! Facade: 配置/模型对外入口（稳定 re-export）。
 
+pub use crate::core::parser::wpl_engine::parser::source_fields;
 pub use crate::orchestrator::config::loader::WarpConf;
 pub use crate::orchestrator::config::models::{load_warp_engine_confs, stat_reqs_from};
 pub use wp_conf::constants::{
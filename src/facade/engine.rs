@@ -4,6 +4,7 @@ use futures_lite::StreamExt;
 use orion_variate::EnvDict;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc::{Receiver, Sender};
 use tokio::time::timeout;
@@ -16,11 +17,14 @@ use wp_log::conf::log_init;
 // bring logging macros into scope (Rust 2018+ requires explicit import for macro_rules! macros)
 use wp_log::{info_ctrl, warn_ctrl};
 use wp_stat::{StatRequires, StatStage};
+use wpl::ParseProfiler;
 
 use crate::facade::args::ParseArgs;
 use crate::facade::args::resolve_run_work_root;
 use crate::orchestrator::config::loader::WarpConf;
-use crate::orchestrator::config::models::{load_warp_engine_confs, stat_reqs_from};
+use crate::orchestrator::config::models::{
+    load_warp_engine_confs, register_wasm_plugins, stat_reqs_from,
+};
 use crate::orchestrator::engine::resource::EngineResource;
 use crate::orchestrator::engine::resource::WarpResourceBuilder;
 use crate::orchestrator::engine::service::start_warp_service;
@@ -47,6 +51,7 @@ pub struct WpApp {
     pid_guard: Option<PidRec>,
     bus_enabled: bool,
     env_dict: EnvDict,
+    profiler: Option<Arc<ParseProfiler>>,
 }
 
 impl WpApp {
@@ -63,9 +68,14 @@ impl WpApp {
         let stat_reqs = stat_reqs_from(main_conf.stat_conf());
         log_init(main_conf.log_conf()).err_conv()?;
         info_ctrl!("log conf: {} ", main_conf.log_conf());
+        register_wasm_plugins(&main_conf)?;
         // 初始化引擎侧注册表：注册内置工厂 + 导入 API 已注册工厂 + 打印注册清单
         crate::connectors::startup::init_runtime_registries();
         let (cmd_send, cmd_recv) = tokio::sync::mpsc::channel::<CommandType>(1000);
+        // [performance].profile_every 设置时开启解析剖析
+        let profiler = main_conf
+            .profile_every()
+            .map(|every| Arc::new(ParseProfiler::new(every)));
         Ok(Self {
             main_conf,
             conf_manager,
@@ -76,6 +86,7 @@ impl WpApp {
             pid_guard: None,
             bus_enabled: false,
             env_dict,
+            profiler,
         })
     }
 
@@ -121,6 +132,7 @@ impl WpApp {
             self.stat_reqs.clone(),
             run_mode.clone(),
             env_dict,
+            self.profiler.clone(),
         )
         .await?;
 
@@ -172,6 +184,23 @@ impl WpApp {
         } else {
             task_admin.all_down_wait_signal().await?;
         }
+        self.dump_profile()?;
+        Ok(())
+    }
+
+    /// 输出解析剖析报告至日志与工作目录 .run/parse_profile.json
+    fn dump_profile(&self) -> RunResult<()> {
+        let Some(profiler) = &self.profiler else {
+            return Ok(());
+        };
+        let report = profiler.report();
+        info_ctrl!("parse profile:\n{}", report);
+        let out_path = self.conf_manager.runtime_path("parse_profile.json");
+        if Path::new(&out_path).exists() {
+            std::fs::remove_file(&out_path).owe_res()?;
+        }
+        wp_conf::utils::save_data(Some(report.to_json()), out_path.as_str(), true).owe_res()?;
+        info_ctrl!("parse profile saved: {}", out_path);
         Ok(())
     }
 
@@ -195,6 +224,7 @@ async fn load_engine_res(
     stat_reqs: StatRequires,
     run_mode: RunMode,
     env_dict: &EnvDict,
+    profiler: Option<Arc<ParseProfiler>>,
 ) -> RunResult<EngineResource> {
     let mut ctx = OperationContext::want("load-engine-res").with_auto_log();
     let knowdb_path =
@@ -258,6 +288,9 @@ async fn load_engine_res(
     )
     .await?;
 
+    if let Some(profiler) = profiler {
+        res_center.set_profiler(profiler);
+    }
     res_center.ins_engine_res(
         sink_service.agent(),
         stat_reqs.get_requ_items(StatStage::Parse),
@@ -12,7 +12,9 @@ use wp_stat::{StatRequires, StatStage};
 
 use crate::facade::args::{ParseArgs, resolve_run_work_root};
 use crate::orchestrator::config::loader::WarpConf;
-use crate::orchestrator::config::models::{load_warp_engine_confs, stat_reqs_from};
+use crate::orchestrator::config::models::{
+    load_warp_engine_confs, register_wasm_plugins, stat_reqs_from,
+};
 use crate::orchestrator::engine::recovery::recover_main;
 use crate::resources::core::manager::ResManager;
 use crate::runtime::sink::act_sink::SinkService;
@@ -38,6 +40,7 @@ impl WpRescueApp {
         let run_args = args.completion_from(&main_conf)?;
         let stat_reqs = stat_reqs_from(main_conf.stat_conf());
         log_init(main_conf.log_conf()).err_conv()?;
+        register_wasm_plugins(&main_conf)?;
         Ok(Self {
             main_conf,
             conf_manager,
@@ -5,4 +5,4 @@ pub use wp_conf::sinks::infra;
 
 // Helper functions for warp module
 pub mod warp_helpers;
-pub use warp_helpers::{load_warp_engine_confs, stat_reqs_from};
+pub use warp_helpers::{load_warp_engine_confs, register_wasm_plugins, stat_reqs_from};
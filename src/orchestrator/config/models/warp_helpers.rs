@@ -12,6 +12,7 @@ use wp_error::{RunReason, run_error::RunResult};
 use wp_knowledge::facade;
 use wp_log::conf::LogConf;
 use wp_stat::{StatReq, StatRequires, StatStage, StatTarget};
+use wpl::wasm::{DEFAULT_FUEL, DEFAULT_MEMORY_MB, WasmLimits, load_wasm_plugin};
 
 /// Load configuration using a supplied EnvDict for templating overrides
 pub fn load_warp_engine_confs(
@@ -31,6 +32,20 @@ pub fn load_warp_engine_confs(
     Ok((conf_manager, main_conf))
 }
 
+/// 加载 `[[plugins.wasm]]` 声明的插件，供 WPL `wasm(<name>)` 引用
+pub fn register_wasm_plugins(conf: &EngineConfig) -> RunResult<()> {
+    for plugin in conf.plugins().wasm.iter() {
+        let limits = WasmLimits {
+            fuel: plugin.fuel.unwrap_or(DEFAULT_FUEL),
+            memory_mb: plugin.memory_mb.unwrap_or(DEFAULT_MEMORY_MB),
+        };
+        load_wasm_plugin(plugin.name.as_str(), plugin.path.as_str(), limits)
+            .map_err(|e| RunReason::from_conf(e.to_string()).to_err())?;
+        info_ctrl!("wasm plugin loaded: {} ({})", plugin.name, plugin.path);
+    }
+    Ok(())
+}
+
 const TOP_N: usize = 20;
 pub fn stat_reqs_from(conf: &StatConf) -> StatRequires {
     // 将新结构 [[stat.<stage>]] 映射为运行期 StatReq
@@ -85,6 +85,12 @@ impl ResManager {
         for wpl_pkg in wpl_space.packages.iter() {
             let mut parsers =
                 multi_code_ins_parse_units(self, wpl_pkg, &mut idx_keeper, stat_reqs.clone())?;
+            if let Some(profiler) = &self.profiler {
+                parsers = parsers
+                    .into_iter()
+                    .map(|unit| unit.with_profile(profiler))
+                    .collect();
+            }
             self.parse_units.append(&mut parsers);
         }
         Ok(())
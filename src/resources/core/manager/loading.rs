@@ -6,6 +6,7 @@ use crate::resources::utils::{load_engine_code, load_oml_code};
 use crate::sinks::SinkGroupAgent;
 use oml::core::ConfADMExt;
 use oml::language::{DataModel, ObjModel};
+use oml::parser::is_oml_library;
 use orion_conf::{ErrorWith, UvsConfFrom};
 use orion_error::{ErrorConv, ErrorOwe, OperationContext, ToStructError, UvsLogicFrom};
 use orion_variate::EnvDict;
@@ -33,14 +34,21 @@ impl ResManager {
         Ok(())
     }
 
-    pub async fn load_all_ldm(&mut self, oml_root: &str) -> RunResult<()> {
+    pub async fn load_all_ldm(&mut self, oml_root: &str, dict: &EnvDict) -> RunResult<()> {
         info_ctrl!("load all oml model");
+        // 脱敏函数的密钥名按引擎环境字典解析
+        oml::core::secret::set_env_dict(dict.clone());
         let oml_spc = load_oml_code(oml_root).await?;
         let wpl_index = self
             .wpl_index
             .clone()
             .ok_or(RunReason::from_logic("not init  wpl all rule key"))?;
-        for (path, _code) in oml_spc.items {
+        for (path, code) in oml_spc.items {
+            // 库文件仅供模型 import，不单独加载
+            if is_oml_library(code.code()) {
+                info_data!("oml library skipped, from {} ", path);
+                continue;
+            }
             if std::path::Path::new(path.as_str()).exists() && path.ends_with(".oml") {
                 let mdl = ObjModel::load(path.as_str())
                     .err_conv()
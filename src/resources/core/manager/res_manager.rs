@@ -2,6 +2,7 @@ use derive_getters::Getters;
 use orion_variate::EnvDict;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
+use std::sync::Arc;
 
 use crate::core::parser::{SpaceIndex, WplPipeline, WplRepository};
 use crate::orchestrator::config::build_sinks::SinkRouteTable;
@@ -13,6 +14,7 @@ use orion_error::{ErrorOwe, ToStructError, UvsLogicFrom};
 use wp_conf::engine::EngineConfig;
 use wp_error::RunReason;
 use wp_error::run_error::RunResult;
+use wpl::ParseProfiler;
 
 /// 规则到模型的最佳匹配关系：记录每个 rule_key 匹配到的模型及其匹配表达式长度
 #[derive(Default)]
@@ -56,6 +58,7 @@ pub struct ResManager {
     pub(crate) infra_agent: Option<InfraSinkAgent>,
     pub(crate) parse_units: Vec<WplPipeline>,
     pub(crate) sink_table: Option<SinkRouteTable>,
+    pub(crate) profiler: Option<Arc<ParseProfiler>>,
 }
 
 impl ResManager {
@@ -81,7 +84,7 @@ impl ResManager {
         res_center
             .load_all_wpl_code(main_conf, infra_sinks.agent().error())
             .await?;
-        res_center.load_all_ldm(main_conf.oml_root()).await?;
+        res_center.load_all_ldm(main_conf.oml_root(), dict).await?;
         res_center
             .load_all_sink(main_conf.sinks_root(), dict)
             .owe_conf()?;
@@ -125,4 +128,8 @@ impl ResManager {
     pub fn set_infra_agent(&mut self, agent: InfraSinkAgent) {
         self.infra_agent = Some(agent);
     }
+    /// 开启解析剖析：ins_engine_res 构建的解析单元共享该剖析器
+    pub fn set_profiler(&mut self, profiler: Arc<ParseProfiler>) {
+        self.profiler = Some(profiler);
+    }
 }
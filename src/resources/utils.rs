@@ -38,7 +38,8 @@ pub fn multi_code_ins_parse_units(
             parser,
             agent,
             stat_reqs.clone(),
-        );
+        )
+        .with_match(rule.priority(), rule.match_conf());
         items.push(ppu);
     }
     Ok(items)
@@ -63,7 +64,8 @@ pub fn code_ins_parse_units(
             parser,
             agent,
             Vec::new(),
-        );
+        )
+        .with_match(rule.priority(), rule.match_conf());
         items.push(ppu);
     }
     Ok(items)
@@ -386,7 +386,8 @@ impl SinkService {
             res_center
                 .alloc_sink_res(&SinkID::from(group_conf.name()))
                 .await?,
-        );
+        )
+        .with_stat_reqs(stat_reqs.to_owned());
         for conf in group_conf.sinks() {
             Self::init_sink_group(
                 rescue.clone(),
@@ -5,12 +5,15 @@ use wp_conf::limits::sink_channel_cap;
 use crate::resources::SinkResUnit;
 use crate::sinks::SinkRuntime;
 use crate::sinks::{ASinkSender, SinkDatYReceiver, SinkDatYSender, SinkPackage, SinkRecUnit};
-use crate::stat::MonSend;
+use crate::stat::metric_collect::MetricCollectors;
+use crate::stat::{MonSend, STAT_INTERVAL_MS};
 use derive_getters::Getters;
+use orion_error::{ErrorOwe, ErrorWith};
 use orion_overload::append::Appendable;
 use wp_conf::structure::SinkGroupConf;
 use wp_connector_api::SinkResult;
 use wp_data_model::cache::FieldQueryCache;
+use wp_stat::{StatReq, TimedStat};
 
 // split internal helpers
 
@@ -56,13 +59,21 @@ pub struct SinkDispatcher {
     dat_r: SinkDatYReceiver,
     res: SinkResUnit,
     unit_pool: SinkRecUnitPool,
+    // 被 OML 过滤指令丢弃的记录：以 `<组名>/oml_filtered` 计入 sink 统计（不计入 miss/error）
+    filter_target: String,
+    filter_stat: MetricCollectors,
+    filter_timer: TimedStat,
 }
 
 impl SinkDispatcher {
     pub fn new(conf: SinkGroupConf, res: SinkResUnit) -> Self {
         // 改用 tokio::mpsc 事件化通道，便于与 runtime 协作
         let (dat_s, dat_r) = tokio::sync::mpsc::channel(sink_channel_cap());
+        let filter_target = format!("{}/oml_filtered", conf.name());
         Self {
+            filter_stat: MetricCollectors::new(filter_target.clone(), Vec::new()),
+            filter_target,
+            filter_timer: TimedStat::new(),
             conf,
             sinks: Vec::new(),
             dat_s,
@@ -71,6 +82,11 @@ impl SinkDispatcher {
             unit_pool: SinkRecUnitPool::new(),
         }
     }
+    /// 启用 OML 过滤计数的统计（与组内 sink 使用同一组统计需求）
+    pub fn with_stat_reqs(mut self, stat_reqs: Vec<StatReq>) -> Self {
+        self.filter_stat = MetricCollectors::new(self.filter_target.clone(), stat_reqs);
+        self
+    }
     pub fn get_dat_r_mut(&mut self) -> &mut SinkDatYReceiver {
         &mut self.dat_r
     }
@@ -162,10 +178,39 @@ impl SinkDispatcher {
                 self.unit_pool.recycle(leftover);
             }
         }
+        if let Some(mon) = mon {
+            self.timed_filter_stat(mon).await?;
+        }
 
         Ok(processed_count)
     }
 
+    fn record_filtered(&mut self, count: usize) {
+        self.filter_stat
+            .record_task_batch(self.filter_target.as_str(), count);
+    }
+
+    async fn timed_filter_stat(&mut self, mon: &MonSend) -> SinkResult<()> {
+        if self
+            .filter_timer
+            .over_reset_timed_millis(STAT_INTERVAL_MS as u128)
+        {
+            self.send_filter_stat(mon).await?;
+            self.filter_timer.reset_now();
+        }
+        Ok(())
+    }
+
+    /// 上报 OML 过滤计数，与 sink 运行时统计走同一监控通道
+    pub(crate) async fn send_filter_stat(&mut self, mon: &MonSend) -> SinkResult<()> {
+        self.filter_stat
+            .send_stat(mon)
+            .await
+            .owe_sys()
+            .want("oml filter stat")?;
+        Ok(())
+    }
+
     // heavy OML pipeline helpers are moved to dispatcher::oml
 
     // 直发与原始数据下发在 dispatcher::io
@@ -29,6 +29,8 @@ use wp_model_core::model::{DataField, DataRecord};
 enum OmlOutcome {
     Success(DataRecord),
     Failure(DataRecord),
+    // 被模型 `drop when` / `keep when` 过滤，不视为转换失败
+    Filtered,
 }
 
 struct TransformedRecUnit {
@@ -81,7 +83,9 @@ impl SinkDispatcher {
         };
 
         let original_len = input.items.len();
-        let output = om_ins.transform(input, cache);
+        let Some(output) = om_ins.transform_filter(&input, cache) else {
+            return Ok(OmlOutcome::Filtered);
+        };
         if output.items.is_empty() {
             let mut failed = output.clone();
             Self::annotate_err(
@@ -103,7 +107,7 @@ impl SinkDispatcher {
         wpl_meta: &ProcMeta,
         input: Vec<SinkRecUnit>,
         cache: &mut FieldQueryCache,
-    ) -> SinkResult<(Vec<TransformedRecUnit>, Vec<SinkRecUnit>)> {
+    ) -> SinkResult<(Vec<TransformedRecUnit>, Vec<SinkRecUnit>, usize)> {
         let Some(om_ins) = self.get_match_oml(wpl_meta) else {
             let passthrough = input
                 .into_iter()
@@ -114,15 +118,19 @@ impl SinkDispatcher {
                     TransformedRecUnit::new(pkg_id, meta, record)
                 })
                 .collect();
-            return Ok((passthrough, Vec::new()));
+            return Ok((passthrough, Vec::new(), 0));
         };
 
         let mut successes = Vec::with_capacity(input.len());
         let mut failures = Vec::new();
+        let mut filtered = 0;
         for unit in input {
             let (pkg_id, meta, record_arc) = unit.into_parts();
             let original_len = record_arc.items.len();
-            let output = om_ins.transform_ref(record_arc.as_ref(), cache);
+            let Some(output) = om_ins.transform_filter(record_arc.as_ref(), cache) else {
+                filtered += 1;
+                continue;
+            };
             if output.items.is_empty() {
                 let mut failed = output.clone();
                 Self::annotate_err(
@@ -143,7 +151,7 @@ impl SinkDispatcher {
                 successes.push(TransformedRecUnit::new(pkg_id, meta, output));
             }
         }
-        Ok((successes, failures))
+        Ok((successes, failures, filtered))
     }
 
     // 为错误记录添加标准诊断字段
@@ -216,7 +224,8 @@ impl SinkDispatcher {
             return Ok(self.emit_without_transform_batch(batch));
         }
 
-        let (successes, failures) = self.run_oml_pipeline_vec(rule, batch, cache)?;
+        let (successes, failures, filtered) = self.run_oml_pipeline_vec(rule, batch, cache)?;
+        self.record_filtered(filtered);
         for bad in failures {
             let (pkg_id, _, bad_arc) = bad.into_parts();
             let record = Arc::try_unwrap(bad_arc).unwrap_or_else(|arc| arc.as_ref().clone());
@@ -278,6 +287,10 @@ impl SinkDispatcher {
                 self.emit_oml_failure(pkg_id, infra, rule, bad)?;
                 return Ok(Vec::new());
             }
+            OmlOutcome::Filtered => {
+                self.record_filtered(1);
+                return Ok(Vec::new());
+            }
         };
         let matches = self.evaluate_sink_matches(&base);
         let mut remaining = matches.iter().filter(|&&m| m).count();
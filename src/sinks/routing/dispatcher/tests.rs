@@ -381,3 +381,69 @@ converted : chars = chars(done) ;
 }
 
 // 隐私相关逻辑与字段已移除：对应行为测试一并删除
+
+#[tokio::test]
+async fn oml_filter_directives_count_as_filtered() {
+    use wp_model_core::model::DataField;
+    use wp_stat::{ReportVariant, StatReq, StatTarget};
+
+    let mut sink_res = SinkResUnit::use_null();
+    let mut code = r#"
+name : filter_model
+rule :
+    /filter/oml
+---
+drop when uri = '/healthz' ;
+uri = read(uri) ;
+"#;
+    let model = oml_parse_raw(&mut code).expect("parse oml model");
+    sink_res.push_model(DataModel::Object(model));
+
+    let mut group = FlexGroup::default();
+    group.name = "filter".to_string();
+    let stat_reqs = vec![StatReq::simple_test(StatTarget::All, Vec::new(), 10)];
+    let mut dispatcher =
+        SinkDispatcher::new(SinkGroupConf::Flexi(group), sink_res).with_stat_reqs(stat_reqs);
+    let sink_conf = SinkInstanceConf::null_new("sink_a".to_string(), TextFmt::Json, None);
+    dispatcher.append(SinkRuntime::new(
+        "./rescue".to_string(),
+        "sink_a".to_string(),
+        sink_conf,
+        SinkBackendType::Proxy(crate::sinks::builtin_factories::make_blackhole_sink()),
+        None,
+        Vec::new(),
+    ));
+
+    let rule = crate::sinks::ProcMeta::Rule("/filter/oml".to_string());
+    let record = |uri: &str| {
+        let mut rec = DataRecord::default();
+        rec.append(DataField::from_chars("uri", uri));
+        Arc::new(rec)
+    };
+    let infra = InfraSinkAgent::use_null();
+    let mut cache = FieldQueryCache::default();
+
+    let out = dispatcher
+        .oml_proc(1, &infra, &mut cache, &rule, record("/healthz"))
+        .unwrap();
+    assert!(out.is_empty());
+
+    let batch = vec![
+        SinkRecUnit::with_record(2, rule.clone(), record("/healthz")),
+        SinkRecUnit::with_record(3, rule.clone(), record("/api")),
+    ];
+    let outputs = dispatcher
+        .oml_proc_batch(batch, &infra, &mut cache, &rule)
+        .unwrap();
+    assert_eq!(outputs[0].len(), 1);
+    assert_eq!(outputs[0][0].id(), &3);
+
+    // 过滤计数经监控通道上报，与 sink 统计同一路径
+    let (mon_s, mut mon_r) = tokio::sync::mpsc::channel(8);
+    dispatcher.send_filter_stat(&mon_s).await.unwrap();
+    let ReportVariant::Stat(report) = mon_r.try_recv().expect("filter stat");
+    assert_eq!(report.target_display(), "filter/oml_filtered");
+    let total: usize = report.get_data().iter().map(|x| x.stat.total).sum();
+    let success: usize = report.get_data().iter().map(|x| x.stat.success).sum();
+    assert_eq!((total, success), (2, 2));
+}
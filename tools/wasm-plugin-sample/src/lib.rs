@@ -0,0 +1,89 @@
+//! WPL WASM 插件示例（ABI v1）
+//!
+//! 构建：`cargo build --release --target wasm32-unknown-unknown`，
+//! 在 wparse.toml 中声明：
+//!
+//! ```toml
+//! [[plugins.wasm]]
+//! name = "sample"
+//! path = "./plugins/wasm_plugin_sample.wasm"
+//! ```
+//!
+//! WPL 中使用：
+//! - 预处理 `| wasm(sample) |`：ASCII 转大写
+//! - 字段类型 `wasm(sample):port`：取开头连续数字
+//! - 字段管道 `| wasm(sample)`：掩码为等长 `*`
+
+const ABI_VERSION: i32 = 1;
+const TAG_CHARS: u8 = 0;
+const TAG_DIGIT: u8 = 1;
+
+#[unsafe(no_mangle)]
+pub extern "C" fn wp_abi_version() -> i32 {
+    ABI_VERSION
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn wp_alloc(len: i32) -> i32 {
+    let buf = vec![0u8; len as usize].into_boxed_slice();
+    Box::into_raw(buf) as *mut u8 as i32
+}
+
+/// # Safety
+/// `ptr`/`len` 必须来自 `wp_alloc` 或入口返回值
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wp_dealloc(ptr: i32, len: i32) {
+    let slice = std::ptr::slice_from_raw_parts_mut(ptr as *mut u8, len as usize);
+    drop(unsafe { Box::from_raw(slice) });
+}
+
+fn input<'a>(ptr: i32, len: i32) -> &'a [u8] {
+    unsafe { std::slice::from_raw_parts(ptr as *const u8, len as usize) }
+}
+
+/// 移交输出缓冲给宿主，由宿主调用 `wp_dealloc` 释放
+fn output(buf: Vec<u8>) -> i64 {
+    let len = buf.len() as i64;
+    let ptr = Box::into_raw(buf.into_boxed_slice()) as *mut u8 as u32 as i64;
+    (ptr << 32) | len
+}
+
+fn reject() -> i64 {
+    -1
+}
+
+/// 单字段类型化输出；名称为空时宿主使用 WPL 中的字段名
+fn typed_field(consumed: usize, tag: u8, value: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(17 + value.len());
+    out.extend_from_slice(&(consumed as u32).to_le_bytes());
+    out.extend_from_slice(&1u32.to_le_bytes());
+    out.push(tag);
+    out.extend_from_slice(&0u32.to_le_bytes());
+    out.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    out.extend_from_slice(value);
+    out
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn wp_pipe_unit(ptr: i32, len: i32) -> i64 {
+    output(input(ptr, len).to_ascii_uppercase())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn wp_parse(ptr: i32, len: i32) -> i64 {
+    let data = input(ptr, len);
+    let n = data.iter().take_while(|b| b.is_ascii_digit()).count();
+    if n == 0 {
+        return reject();
+    }
+    output(typed_field(n, TAG_DIGIT, &data[..n]))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn wp_field_pipe(ptr: i32, len: i32) -> i64 {
+    let data = input(ptr, len);
+    if data.is_empty() {
+        return reject();
+    }
+    output(typed_field(0, TAG_CHARS, &vec![b'*'; data.len()]))
+}